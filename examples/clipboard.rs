@@ -0,0 +1,57 @@
+extern crate xrb;
+extern crate tokio_core;
+extern crate futures;
+
+use std::env;
+
+use tokio_core::reactor::Core;
+use xrb::clipboard::Clipboard;
+use xrb::event::Event;
+use xrb::xproto::CURRENT_TIME;
+use xrb::Xauth;
+
+/// Prints the contents of the `CLIPBOARD` selection, or if a text is given
+/// serves it until another client takes the selection.
+fn main() {
+    let path = Xauth::get_path().unwrap();
+    let auth_info = Xauth::read_file(&path).unwrap();
+
+    let mut lp = Core::new().unwrap();
+    let req = xrb::Client::connect(1, &auth_info, lp.handle());
+
+    let client = lp.run(req).unwrap();
+    let (mut client, mut clipboard) = lp.run(Clipboard::new(client)).unwrap();
+    let selection = clipboard.atoms().clipboard;
+
+    let text = match env::args().nth(1) {
+        Some(text) => text,
+        None => {
+            let req = clipboard.get_text(client, selection, CURRENT_TIME);
+            let (_, text) = lp.run(req).unwrap();
+
+            println!("{}", text.unwrap_or_default());
+            return;
+        }
+    };
+
+    let req = clipboard.set_text(client, selection, &text, CURRENT_TIME);
+    let (c, owner) = lp.run(req).unwrap();
+    client = c;
+
+    if !owner {
+        println!("Failed to own the clipboard");
+        return;
+    }
+
+    while clipboard.owns(selection) {
+        let (c, event) = lp.run(client.next_event()).unwrap();
+        let (c, handled) = lp.run(clipboard.handle_event(c, &event)).unwrap();
+        client = c;
+
+        if !handled {
+            if let Event::Error(e) = event {
+                println!("{}", e);
+            }
+        }
+    }
+}
@@ -5,6 +5,8 @@ extern crate futures;
 use tokio_core::reactor::Core;
 use xrb::xproto::CreateWindow;
 use xrb::xproto::MapWindow;
+use xrb::xproto::Visualid;
+use xrb::xproto::Window;
 use xrb::xproto::WindowAttributes;
 use xrb::Xauth;
 use futures::Future;
@@ -21,7 +23,7 @@ fn main() {
 
     let parent = client.get_server_info().roots[0].root;
 
-    let req = client.generate_id().and_then(|(client, id)| {
+    let req = client.next_id().and_then(|(client, id): (_, Window)| {
         let attrs = WindowAttributes::new()
             .background_pixel(0xCCFFCC)
             .event_mask(0x1 | 0x8000)
@@ -32,7 +34,7 @@ fn main() {
                 parent: parent,
                 class: 1,
                 depth: 24,
-                visual: 0,
+                visual: Visualid::COPY_FROM_PARENT,
                 x: 100,
                 y: 100,
                 width: 200,
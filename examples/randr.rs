@@ -0,0 +1,39 @@
+extern crate xrb;
+extern crate futures;
+extern crate tokio_core;
+
+use futures::Future;
+use tokio_core::reactor::Core;
+use xrb::randr::Layout;
+use xrb::randr::RRQueryVersion;
+use xrb::Xauth;
+
+fn main() {
+    let path = Xauth::get_path().unwrap();
+    let auth_info = Xauth::read_file(&path).unwrap();
+
+    let mut lp = Core::new().unwrap();
+    let req = xrb::Client::connect(1, &auth_info, lp.handle());
+
+    let client = lp.run(req).unwrap();
+    let root = client.get_server_info().roots[0].root;
+
+    let req = client.perform_ex(RRQueryVersion {
+            major_version: 1,
+            minor_version: 5,
+        })
+        .and_then(move |(client, _)| Layout::current(client, root));
+
+    let (_, layout) = lp.run(req).unwrap();
+
+    println!("Screen: {}x{}", layout.screen_size().0, layout.screen_size().1);
+    for output in &layout.outputs {
+        print!("{} {:?}", output.name, output.connection);
+        if let Some(crtc) = layout.crtc_of(output.output) {
+            if let Some((width, height)) = layout.crtc_size(crtc) {
+                print!(" {}x{}+{}+{}", width, height, crtc.x, crtc.y);
+            }
+        }
+        println!();
+    }
+}
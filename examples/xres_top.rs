@@ -0,0 +1,92 @@
+extern crate xrb;
+extern crate futures;
+extern crate tokio_core;
+
+use std::cmp::Reverse;
+
+use futures::Future;
+use futures::Stream;
+use futures::stream;
+use tokio_core::reactor::Core;
+use xrb::xres;
+use xrb::xres::XResQueryClientPixmapBytes;
+use xrb::xres::XResQueryClients;
+use xrb::xres::XResQueryVersion;
+use xrb::Xauth;
+
+struct Row {
+    resource_base: u32,
+    pid: Option<u32>,
+    pixmap_bytes: u64,
+    windows: u32,
+    pixmaps: u32,
+    resources: u32,
+}
+
+fn main() {
+    let path = Xauth::get_path().unwrap();
+    let auth_info = Xauth::read_file(&path).unwrap();
+
+    let mut lp = Core::new().unwrap();
+    let req = xrb::Client::connect(1, &auth_info, lp.handle());
+
+    let client = lp.run(req).unwrap();
+
+    let req = client.perform_ex(XResQueryVersion {
+            client_major: 1,
+            client_minor: 2,
+        })
+        .and_then(|(client, _)| client.perform_ex(XResQueryClients))
+        .and_then(|(client, clients)| {
+            xres::client_pids(client, &clients).map(move |(client, pids)| (client, clients, pids))
+        })
+        .and_then(|(client, clients, pids)| {
+            stream::iter_ok(clients.into_iter().zip(pids))
+                .fold((client, Vec::new()), |(client, mut rows), (c, pid)| {
+                    let xid = c.resource_base;
+                    client.perform_ex(XResQueryClientPixmapBytes { xid })
+                        .and_then(move |(client, pixmap_bytes)| {
+                            xres::client_resources(client, xid)
+                                .map(move |(client, types)| (client, pixmap_bytes, types))
+                        })
+                        .map(move |(client, pixmap_bytes, types)| {
+                            let count = |name: &str| {
+                                types.iter()
+                                    .find(|t| t.name == name)
+                                    .map_or(0, |t| t.count)
+                            };
+
+                            rows.push(Row {
+                                resource_base: xid,
+                                pid,
+                                pixmap_bytes,
+                                windows: count("WINDOW"),
+                                pixmaps: count("PIXMAP"),
+                                resources: types.iter().map(|t| t.count).sum(),
+                            });
+                            (client, rows)
+                        })
+                })
+        });
+
+    let (_, mut rows) = lp.run(req).unwrap();
+    rows.sort_by_key(|row| Reverse(row.pixmap_bytes));
+
+    println!("{:>10} {:>8} {:>12} {:>8} {:>8} {:>10}",
+             "BASE",
+             "PID",
+             "PIXMAP KiB",
+             "PIXMAPS",
+             "WINDOWS",
+             "RESOURCES");
+    for row in &rows {
+        let pid = row.pid.map_or_else(|| "-".to_owned(), |pid| pid.to_string());
+        println!("{:>#10x} {:>8} {:>12} {:>8} {:>8} {:>10}",
+                 row.resource_base,
+                 pid,
+                 row.pixmap_bytes / 1024,
+                 row.pixmaps,
+                 row.windows,
+                 row.resources);
+    }
+}
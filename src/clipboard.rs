@@ -0,0 +1,540 @@
+//! High level access to selections like `PRIMARY` and `CLIPBOARD`.
+//!
+//! A `Clipboard` creates an unmapped `InputOnly` window that is used to own
+//! selections and to receive the contents of the selections owned by other
+//! clients. Transfers larger than the maximum request size use the `INCR`
+//! protocol described in the ICCCM.
+
+use ::std::collections::HashMap;
+use ::std::io;
+use ::std::rc::Rc;
+
+use ::futures;
+use ::futures::Future;
+use ::futures::future::Loop;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+
+use ::event::Event;
+use ::event::PropertyState;
+use ::event::SelectionNotifyEvent;
+use ::event::SelectionRequestEvent;
+use ::protocol::ReplyFuture;
+use ::xproto;
+use ::xproto::Atom;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+use ::Client;
+
+/// Atoms used by the selection protocol.
+#[derive(Debug, Clone, Copy)]
+pub struct Atoms {
+    pub primary: Atom,
+    pub clipboard: Atom,
+    pub targets: Atom,
+    pub timestamp: Atom,
+    pub incr: Atom,
+    pub utf8_string: Atom,
+    pub text_plain: Atom,
+    pub text_plain_utf8: Atom,
+    pub image_png: Atom,
+
+    /// Property of the clipboard window where converted selections are
+    /// stored.
+    pub xrb_selection: Atom,
+}
+
+const ATOM_NAMES: [&[u8]; 9] = [b"CLIPBOARD",
+                                b"TARGETS",
+                                b"TIMESTAMP",
+                                b"INCR",
+                                b"UTF8_STRING",
+                                b"text/plain",
+                                b"text/plain;charset=utf-8",
+                                b"image/png",
+                                b"XRB_SELECTION"];
+
+impl Atoms {
+    /// Interns all the atoms.
+    pub fn intern(client: Client) -> ReplyFuture<Atoms> {
+        Box::new(xproto::intern_atoms(client, &ATOM_NAMES)
+            .map(|(client, atoms)| {
+                let atoms = Atoms {
+                    primary: xproto::ATOM_PRIMARY,
+                    clipboard: atoms[0],
+                    targets: atoms[1],
+                    timestamp: atoms[2],
+                    incr: atoms[3],
+                    utf8_string: atoms[4],
+                    text_plain: atoms[5],
+                    text_plain_utf8: atoms[6],
+                    image_png: atoms[7],
+                    xrb_selection: atoms[8],
+                };
+
+                (client, atoms)
+            }))
+    }
+}
+
+/// The contents of an owned selection, a list of targets and their data.
+pub type Contents = Vec<(Atom, Vec<u8>)>;
+
+#[derive(Debug)]
+struct Owned {
+    time: Timestamp,
+    contents: Rc<Contents>,
+}
+
+/// Owns and fetches selections.
+#[derive(Debug)]
+pub struct Clipboard {
+    window: Window,
+    atoms: Atoms,
+    owned: HashMap<Atom, Owned>,
+    max_size: usize,
+}
+
+impl Clipboard {
+    /// Creates the clipboard window on the first screen and interns the
+    /// atoms it needs.
+    pub fn new(client: Client) -> ReplyFuture<Clipboard> {
+        let root = client.get_server_info().roots[0].root;
+        let max_size = (client.get_server_info().maximum_request_length as usize * 4 - 24) & !3;
+
+        Box::new(Atoms::intern(client)
+            .and_then(|(client, atoms)| {
+                client.next_id().map(move |(client, window)| (client, atoms, window))
+            })
+            .and_then(move |(client, atoms, window)| {
+                let attrs = xproto::WindowAttributes::new()
+                    .event_mask(xproto::EVENT_PROPERTY_CHANGE)
+                    .build();
+
+                client.perform(xproto::CreateWindow {
+                        wid: window,
+                        parent: root,
+                        class: xproto::WINDOW_CLASS_INPUT_ONLY,
+                        depth: 0,
+                        visual: xproto::Visualid::COPY_FROM_PARENT,
+                        x: 0,
+                        y: 0,
+                        width: 1,
+                        height: 1,
+                        border_width: 0,
+                        attrs,
+                    })
+                    .map(move |(client, _)| {
+                        let clipboard = Clipboard {
+                            window,
+                            atoms,
+                            owned: HashMap::new(),
+                            max_size,
+                        };
+
+                        (client, clipboard)
+                    })
+            }))
+    }
+
+    /// The window used to own and receive selections.
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    pub fn atoms(&self) -> &Atoms {
+        &self.atoms
+    }
+
+    /// Returns true if the clipboard is serving `selection`.
+    pub fn owns(&self, selection: Atom) -> bool {
+        self.owned.contains_key(&selection)
+    }
+
+    /// Takes ownership of `selection` serving `contents`. Resolves to false
+    /// if the server refused the ownership change (for example because
+    /// `time` is older than the last change), in that case `forget` should
+    /// be called.
+    pub fn set(&mut self,
+               client: Client,
+               selection: Atom,
+               contents: Contents,
+               time: Timestamp)
+               -> ReplyFuture<bool> {
+        self.owned.insert(selection,
+                          Owned {
+                              time,
+                              contents: Rc::new(contents),
+                          });
+
+        let window = self.window;
+        Box::new(client.perform(xproto::SetSelectionOwner {
+                owner: window,
+                selection,
+                time,
+            })
+            .and_then(move |(client, _)| client.perform(xproto::GetSelectionOwner { selection }))
+            .map(move |(client, owner)| (client, owner == window)))
+    }
+
+    /// Takes ownership of `selection` serving `text` as `UTF8_STRING`,
+    /// `text/plain;charset=utf-8`, `text/plain` and `STRING`.
+    pub fn set_text(&mut self,
+                    client: Client,
+                    selection: Atom,
+                    text: &str,
+                    time: Timestamp)
+                    -> ReplyFuture<bool> {
+        let latin1 = text.chars().map(|c| if (c as u32) < 0x100 { c as u8 } else { b'?' }).collect();
+        let contents = vec![(self.atoms.utf8_string, text.as_bytes().to_vec()),
+                            (self.atoms.text_plain_utf8, text.as_bytes().to_vec()),
+                            (self.atoms.text_plain, text.as_bytes().to_vec()),
+                            (xproto::ATOM_STRING, latin1)];
+
+        self.set(client, selection, contents, time)
+    }
+
+    /// Stops serving `selection`, the server ownership isn't changed.
+    pub fn forget(&mut self, selection: Atom) {
+        self.owned.remove(&selection);
+    }
+
+    /// Handles the selection events of the clipboard window. Resolves to
+    /// true if `event` was handled.
+    ///
+    /// Requests needing an `INCR` transfer are served before resolving,
+    /// other events received meanwhile are kept in the client queue.
+    pub fn handle_event(&mut self, client: Client, event: &Event) -> ReplyFuture<bool> {
+        match *event {
+            Event::SelectionClear(ref ev) if ev.owner == self.window => {
+                self.owned.remove(&ev.selection);
+                Box::new(futures::finished((client, true)))
+            }
+            Event::SelectionRequest(ref ev) if ev.owner == self.window => {
+                let owned = self.owned
+                    .get(&ev.selection)
+                    .map(|owned| (owned.time, owned.contents.clone()));
+
+                Box::new(serve(client, self.atoms, self.max_size, *ev, owned)
+                    .map(|(client, _)| (client, true)))
+            }
+            _ => Box::new(futures::finished((client, false))),
+        }
+    }
+
+    /// Fetches `selection` converted to `target`. Resolves to the type and
+    /// data of the selection or `None` if the conversion was refused.
+    pub fn get(&self,
+               client: Client,
+               selection: Atom,
+               target: Atom,
+               time: Timestamp)
+               -> ReplyFuture<Option<(Atom, Vec<u8>)>> {
+        if let Some(owned) = self.owned.get(&selection) {
+            let data = convert(&self.atoms, owned.time, &owned.contents, target);
+            return Box::new(futures::finished((client, data)));
+        }
+
+        fetch(client, self.window, self.atoms, selection, target, time)
+    }
+
+    /// Fetches the list of targets `selection` can be converted to.
+    pub fn get_targets(&self,
+                       client: Client,
+                       selection: Atom,
+                       time: Timestamp)
+                       -> ReplyFuture<Vec<Atom>> {
+        Box::new(self.get(client, selection, self.atoms.targets, time).map(|(client, data)| {
+            let targets = data.map(|(_, data)| atoms_from_bytes(&data)).unwrap_or_default();
+            (client, targets)
+        }))
+    }
+
+    /// Fetches `selection` as text, `UTF8_STRING` is tried first and then
+    /// `STRING`.
+    pub fn get_text(&self,
+                    client: Client,
+                    selection: Atom,
+                    time: Timestamp)
+                    -> ReplyFuture<Option<String>> {
+        if let Some(owned) = self.owned.get(&selection) {
+            let text = convert(&self.atoms, owned.time, &owned.contents, self.atoms.utf8_string)
+                .map(|(_, data)| String::from_utf8_lossy(&data).into_owned())
+                .or_else(|| {
+                    convert(&self.atoms, owned.time, &owned.contents, xproto::ATOM_STRING)
+                        .map(|(_, data)| latin1_to_string(&data))
+                });
+
+            return Box::new(futures::finished((client, text)));
+        }
+
+        let window = self.window;
+        let atoms = self.atoms;
+
+        Box::new(fetch(client, window, atoms, selection, atoms.utf8_string, time)
+            .and_then(move |(client, data)| -> ReplyFuture<Option<String>> {
+                if let Some((_, data)) = data {
+                    let text = String::from_utf8_lossy(&data).into_owned();
+                    return Box::new(futures::finished((client, Some(text))));
+                }
+
+                Box::new(fetch(client, window, atoms, selection, xproto::ATOM_STRING, time)
+                    .map(|(client, data)| (client, data.map(|(_, data)| latin1_to_string(&data)))))
+            }))
+    }
+}
+
+fn latin1_to_string(data: &[u8]) -> String {
+    data.iter().map(|&b| b as char).collect()
+}
+
+/// Asks the owner of `selection` to convert it to `target` and reads the
+/// result from the `xrb_selection` property of `window`.
+fn fetch(client: Client,
+         window: Window,
+         atoms: Atoms,
+         selection: Atom,
+         target: Atom,
+         time: Timestamp)
+         -> ReplyFuture<Option<(Atom, Vec<u8>)>> {
+    let property = atoms.xrb_selection;
+
+    Box::new(client.perform(xproto::ConvertSelection {
+            requestor: window,
+            selection,
+            target,
+            property,
+            time,
+        })
+        .and_then(move |(client, _)| {
+            client.wait_for_event(move |ev| match *ev {
+                Event::SelectionNotify(ref ev) => {
+                    ev.requestor == window && ev.selection == selection
+                }
+                _ => false,
+            })
+        })
+        .and_then(move |(client, ev)| -> ReplyFuture<Option<(Atom, Vec<u8>)>> {
+            match ev {
+                Event::SelectionNotify(ref ev) if ev.property != xproto::ATOM_NONE => {}
+                _ => return Box::new(futures::finished((client, None))),
+            }
+
+            Box::new(client.perform(get_property(window, property))
+                .and_then(move |(client, reply)| -> ReplyFuture<Option<(Atom, Vec<u8>)>> {
+                    if reply.type_ == atoms.incr {
+                        Box::new(receive_incr(client, window, property)
+                            .map(|(client, data)| (client, Some(data))))
+                    } else {
+                        Box::new(futures::finished((client, Some((reply.type_, reply.value)))))
+                    }
+                }))
+        }))
+}
+
+fn get_property(window: Window, property: Atom) -> xproto::GetProperty {
+    xproto::GetProperty {
+        delete: true,
+        window,
+        property,
+        type_: xproto::ATOM_ANY,
+        long_offset: 0,
+        long_length: u32::MAX / 4,
+    }
+}
+
+fn to_bytes<T: Copy + Into<u32>>(values: &[T]) -> Vec<u8> {
+    let mut buf = vec![0u8; values.len() * 4];
+    for (chunk, &value) in buf.chunks_mut(4).zip(values) {
+        NativeEndian::write_u32(chunk, value.into());
+    }
+    buf
+}
+
+fn atoms_from_bytes(buf: &[u8]) -> Vec<Atom> {
+    buf.chunks(4).filter(|c| c.len() == 4).map(|c| Atom(NativeEndian::read_u32(c))).collect()
+}
+
+/// Converts owned contents to `target`, returning the property type and
+/// data.
+fn convert(atoms: &Atoms,
+           time: Timestamp,
+           contents: &Contents,
+           target: Atom)
+           -> Option<(Atom, Vec<u8>)> {
+    if target == atoms.targets {
+        let mut targets = vec![atoms.targets, atoms.timestamp];
+        targets.extend(contents.iter().map(|&(target, _)| target));
+        Some((xproto::ATOM_ATOM, to_bytes(&targets)))
+    } else if target == atoms.timestamp {
+        Some((xproto::ATOM_INTEGER, to_bytes(&[time])))
+    } else {
+        contents.iter()
+            .find(|&&(t, _)| t == target)
+            .map(|&(t, ref data)| (t, data.clone()))
+    }
+}
+
+/// Answers a `SelectionRequest`, refusing it if `owned` is `None` or the
+/// target isn't available.
+fn serve(client: Client,
+         atoms: Atoms,
+         max_size: usize,
+         req: SelectionRequestEvent,
+         owned: Option<(Timestamp, Rc<Contents>)>)
+         -> ReplyFuture<()> {
+    // Obsolete clients use `None` as the property.
+    let property = if req.property == xproto::ATOM_NONE {
+        req.target
+    } else {
+        req.property
+    };
+
+    let converted = owned.and_then(|(time, contents)| convert(&atoms, time, &contents, req.target));
+
+    match converted {
+        None => notify(client, req, xproto::ATOM_NONE),
+        Some((type_, data)) => {
+            if data.len() > max_size {
+                return send_incr(client, atoms, max_size, req, property, type_, data);
+            }
+
+            let format = if type_ == xproto::ATOM_ATOM || type_ == xproto::ATOM_INTEGER {
+                32
+            } else {
+                8
+            };
+
+            Box::new(client.perform(xproto::ChangeProperty {
+                    mode: xproto::PropMode::Replace,
+                    window: req.requestor,
+                    property,
+                    type_,
+                    format,
+                    data,
+                })
+                .and_then(move |(client, _)| notify(client, req, property)))
+        }
+    }
+}
+
+/// Sends the `SelectionNotify` event answering `req`.
+fn notify(client: Client, req: SelectionRequestEvent, property: Atom) -> ReplyFuture<()> {
+    let event = SelectionNotifyEvent {
+        time: req.time,
+        requestor: req.requestor,
+        selection: req.selection,
+        target: req.target,
+        property,
+    };
+
+    let event = match event.encode() {
+        Ok(event) => event,
+        Err(e) => return Box::new(futures::failed(e)),
+    };
+
+    client.perform(xproto::SendEvent {
+        propagate: false,
+        destination: req.requestor,
+        event_mask: 0,
+        event,
+    })
+}
+
+/// Serves `data` in chunks of `max_size` bytes using the `INCR` protocol.
+fn send_incr(client: Client,
+             atoms: Atoms,
+             max_size: usize,
+             req: SelectionRequestEvent,
+             property: Atom,
+             type_: Atom,
+             data: Vec<u8>)
+             -> ReplyFuture<()> {
+    let requestor = req.requestor;
+    let attrs = xproto::WindowAttributes::new()
+        .event_mask(xproto::EVENT_PROPERTY_CHANGE)
+        .build();
+
+    let len = data.len() as u32;
+    let data = Rc::new(data);
+
+    Box::new(client.perform(xproto::ChangeWindowAttributes {
+            window: requestor,
+            attrs,
+        })
+        .and_then(move |(client, _)| {
+            client.perform(xproto::ChangeProperty {
+                mode: xproto::PropMode::Replace,
+                window: requestor,
+                property,
+                type_: atoms.incr,
+                format: 32,
+                data: to_bytes(&[len]),
+            })
+        })
+        .and_then(move |(client, _)| notify(client, req, property))
+        .and_then(move |(client, _)| {
+            futures::future::loop_fn((client, 0), move |(client, offset)| {
+                let data = data.clone();
+                client.wait_for_event(move |ev| match *ev {
+                        Event::PropertyNotify(ref ev) => {
+                            ev.window == requestor && ev.atom == property &&
+                            ev.state == PropertyState::Deleted
+                        }
+                        _ => false,
+                    })
+                    .and_then(move |(client, _)| {
+                        let end = ::std::cmp::min(offset + max_size, data.len());
+                        client.perform(xproto::ChangeProperty {
+                                mode: xproto::PropMode::Replace,
+                                window: requestor,
+                                property,
+                                type_,
+                                format: 8,
+                                data: data[offset..end].to_vec(),
+                            })
+                            .map(move |(client, _)| if offset == end {
+                                Loop::Break(client)
+                            } else {
+                                Loop::Continue((client, end))
+                            })
+                    })
+            })
+        })
+        .and_then(move |client| {
+            let attrs = xproto::WindowAttributes::new().event_mask(0).build();
+            client.perform(xproto::ChangeWindowAttributes {
+                window: requestor,
+                attrs,
+            })
+        }))
+}
+
+/// Receives the chunks of an `INCR` transfer, resolving to the data type
+/// and the whole data.
+fn receive_incr(client: Client, window: Window, property: Atom) -> ReplyFuture<(Atom, Vec<u8>)> {
+    Box::new(futures::future::loop_fn((client, xproto::ATOM_NONE, Vec::new()),
+                                      move |(client, type_, mut data)| {
+        client.wait_for_event(move |ev| match *ev {
+                Event::PropertyNotify(ref ev) => {
+                    ev.window == window && ev.atom == property &&
+                    ev.state == PropertyState::NewValue
+                }
+                _ => false,
+            })
+            .and_then(move |(client, _)| client.perform(get_property(window, property)))
+            .and_then(move |(client, reply)| {
+                // Stale notification, the property was already read.
+                if reply.type_ == xproto::ATOM_NONE {
+                    return Ok(Loop::Continue((client, type_, data)));
+                }
+
+                if reply.value.is_empty() {
+                    return Ok(Loop::Break((client, (reply.type_, data))));
+                }
+
+                data.extend_from_slice(&reply.value);
+                Ok::<_, io::Error>(Loop::Continue((client, reply.type_, data)))
+            })
+    }))
+}
@@ -0,0 +1,47 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::composite::EXTENSION_NAME;
+use ::xfixes::Region;
+use ::xproto::Window;
+use ::Client;
+
+const COMPOSITE_OPCODE: u8 = 5;
+
+/// Creates a region with the visible part of `window` and its border.
+#[derive(Debug, Clone, Copy)]
+pub struct CompositeCreateRegionFromBorderClip {
+    pub region: Region,
+    pub window: Window,
+}
+
+impl ExtensionRequest for CompositeCreateRegionFromBorderClip {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(COMPOSITE_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.region.0)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
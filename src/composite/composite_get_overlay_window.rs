@@ -0,0 +1,59 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::composite::EXTENSION_NAME;
+use ::xproto::Window;
+use ::Client;
+
+const COMPOSITE_OPCODE: u8 = 7;
+
+/// Returns the overlay window of the screen of `window`, drawn above every
+/// other window and below the screen saver. It's mapped until all the
+/// clients using it release it.
+#[derive(Debug, Clone, Copy)]
+pub struct CompositeGetOverlayWindow {
+    pub window: Window,
+}
+
+impl ExtensionRequest for CompositeGetOverlayWindow {
+    type Reply = CompositeGetOverlayWindowReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(COMPOSITE_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = CompositeGetOverlayWindowReply {
+                overlay_window: Window(NativeEndian::read_u32(&buf[8..12])),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `CompositeGetOverlayWindow` request.
+#[derive(Debug, Clone, Copy)]
+pub struct CompositeGetOverlayWindowReply {
+    pub overlay_window: Window,
+}
@@ -0,0 +1,76 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::composite::EXTENSION_NAME;
+use ::Client;
+
+const COMPOSITE_OPCODE: u8 = 0;
+
+/// Negotiates the protocol version, it must be sent before any other
+/// Composite request.
+#[derive(Debug, Clone, Copy)]
+pub struct CompositeQueryVersion {
+    pub major_version: u32,
+    pub minor_version: u32,
+}
+
+impl ExtensionRequest for CompositeQueryVersion {
+    type Reply = CompositeQueryVersionReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(COMPOSITE_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.major_version)?;
+        a.write_u32::<NativeEndian>(self.minor_version)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = CompositeQueryVersionReply {
+                major_version: NativeEndian::read_u32(&buf[8..12]),
+                minor_version: NativeEndian::read_u32(&buf[12..16]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `CompositeQueryVersion` request.
+#[derive(Debug, Clone, Copy)]
+pub struct CompositeQueryVersionReply {
+    pub major_version: u32,
+    pub minor_version: u32,
+}
+
+impl VersionRequest for CompositeQueryVersion {
+    fn latest() -> CompositeQueryVersion {
+        CompositeQueryVersion {
+            major_version: 0,
+            minor_version: 4,
+        }
+    }
+
+    fn version(reply: &CompositeQueryVersionReply) -> Version {
+        Version::new(reply.major_version, reply.minor_version)
+    }
+}
@@ -0,0 +1,50 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::composite::EXTENSION_NAME;
+use ::composite::Update;
+use ::xproto::Window;
+use ::Client;
+
+const COMPOSITE_OPCODE: u8 = 2;
+
+/// Redirects the hierarchies of the current and future children of
+/// `window` to off-screen storage.
+#[derive(Debug, Clone, Copy)]
+pub struct CompositeRedirectSubwindows {
+    pub window: Window,
+    pub update: Update,
+}
+
+impl ExtensionRequest for CompositeRedirectSubwindows {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(COMPOSITE_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u8(self.update as u8)?;
+        a.write_all(&[0; 3])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
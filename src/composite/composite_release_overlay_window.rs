@@ -0,0 +1,45 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::composite::EXTENSION_NAME;
+use ::xproto::Window;
+use ::Client;
+
+const COMPOSITE_OPCODE: u8 = 8;
+
+/// Releases the overlay window got with `CompositeGetOverlayWindow`.
+#[derive(Debug, Clone, Copy)]
+pub struct CompositeReleaseOverlayWindow {
+    /// A window of the screen of the overlay window.
+    pub window: Window,
+}
+
+impl ExtensionRequest for CompositeReleaseOverlayWindow {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(COMPOSITE_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
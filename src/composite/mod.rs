@@ -0,0 +1,37 @@
+//! Composite extension, redirects window hierarchies to off-screen storage
+//! so a compositing manager can draw them.
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    composite_query_version,
+    composite_redirect_window,
+    composite_redirect_subwindows,
+    composite_unredirect_window,
+    composite_unredirect_subwindows,
+    composite_create_region_from_border_clip,
+    composite_name_window_pixmap,
+    composite_get_overlay_window,
+    composite_release_overlay_window
+}
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"Composite";
+
+/// How the screen is updated from redirected windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    /// The server draws the windows on the screen.
+    Automatic = 0,
+
+    /// The windows are drawn by the compositing manager, only one client
+    /// can redirect a window manually.
+    Manual = 1,
+}
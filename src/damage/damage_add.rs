@@ -0,0 +1,47 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::damage::EXTENSION_NAME;
+use ::xfixes::Region;
+use ::xproto::Drawable;
+use ::Client;
+
+const DAMAGE_OPCODE: u8 = 4;
+
+/// Reports damage to a drawable, for damage the server can't see.
+#[derive(Debug, Clone, Copy)]
+pub struct DamageAdd {
+    pub drawable: Drawable,
+    pub region: Region,
+}
+
+impl ExtensionRequest for DamageAdd {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DAMAGE_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.drawable.0)?;
+        a.write_u32::<NativeEndian>(self.region.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,52 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::damage::EXTENSION_NAME;
+use ::damage::Damage;
+use ::damage::ReportLevel;
+use ::xproto::Drawable;
+use ::Client;
+
+const DAMAGE_OPCODE: u8 = 1;
+
+/// Starts tracking the damage of a drawable.
+#[derive(Debug, Clone, Copy)]
+pub struct DamageCreate {
+    pub damage: Damage,
+    pub drawable: Drawable,
+    pub level: ReportLevel,
+}
+
+impl ExtensionRequest for DamageCreate {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DAMAGE_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u32::<NativeEndian>(self.damage.0)?;
+        a.write_u32::<NativeEndian>(self.drawable.0)?;
+        a.write_u8(self.level as u8)?;
+        a.write_all(&[0; 3])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
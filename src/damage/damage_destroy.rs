@@ -0,0 +1,44 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::damage::EXTENSION_NAME;
+use ::damage::Damage;
+use ::Client;
+
+const DAMAGE_OPCODE: u8 = 2;
+
+/// Stops tracking the damage of a drawable.
+#[derive(Debug, Clone, Copy)]
+pub struct DamageDestroy {
+    pub damage: Damage,
+}
+
+impl ExtensionRequest for DamageDestroy {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DAMAGE_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.damage.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
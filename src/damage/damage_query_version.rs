@@ -0,0 +1,76 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::damage::EXTENSION_NAME;
+use ::Client;
+
+const DAMAGE_OPCODE: u8 = 0;
+
+/// Negotiates the protocol version, it must be sent before any other
+/// DAMAGE request.
+#[derive(Debug, Clone, Copy)]
+pub struct DamageQueryVersion {
+    pub major_version: u32,
+    pub minor_version: u32,
+}
+
+impl ExtensionRequest for DamageQueryVersion {
+    type Reply = DamageQueryVersionReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DAMAGE_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.major_version)?;
+        a.write_u32::<NativeEndian>(self.minor_version)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = DamageQueryVersionReply {
+                major_version: NativeEndian::read_u32(&buf[8..12]),
+                minor_version: NativeEndian::read_u32(&buf[12..16]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `DamageQueryVersion` request.
+#[derive(Debug, Clone, Copy)]
+pub struct DamageQueryVersionReply {
+    pub major_version: u32,
+    pub minor_version: u32,
+}
+
+impl VersionRequest for DamageQueryVersion {
+    fn latest() -> DamageQueryVersion {
+        DamageQueryVersion {
+            major_version: 1,
+            minor_version: 1,
+        }
+    }
+
+    fn version(reply: &DamageQueryVersionReply) -> Version {
+        Version::new(reply.major_version, reply.minor_version)
+    }
+}
@@ -0,0 +1,53 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::damage::EXTENSION_NAME;
+use ::damage::Damage;
+use ::xfixes::Region;
+use ::Client;
+
+const DAMAGE_OPCODE: u8 = 3;
+
+/// Removes `repair` from the damage, storing the removed part in `parts`.
+#[derive(Debug, Clone, Copy)]
+pub struct DamageSubtract {
+    pub damage: Damage,
+
+    /// `Region::NONE` repairs all of the damage.
+    pub repair: Region,
+
+    /// `Region::NONE` if the removed part isn't needed.
+    pub parts: Region,
+}
+
+impl ExtensionRequest for DamageSubtract {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DAMAGE_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u32::<NativeEndian>(self.damage.0)?;
+        a.write_u32::<NativeEndian>(self.repair.0)?;
+        a.write_u32::<NativeEndian>(self.parts.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,116 @@
+//! DAMAGE extension, reports the regions of drawables that were modified.
+
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+
+use ::event::Event;
+use ::protocol::ExtensionInfo;
+use ::xproto::Drawable;
+use ::xproto::Rectangle;
+use ::xproto::Timestamp;
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    damage_query_version,
+    damage_create,
+    damage_destroy,
+    damage_subtract,
+    damage_add
+}
+
+mod tracker;
+pub use self::tracker::*;
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"DAMAGE";
+
+declare_ids! {
+    /// Tracks the damage of a drawable.
+    Damage
+}
+
+/// What `DamageNotify` events report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportLevel {
+    /// Every damaged rectangle.
+    RawRectangles = 0,
+
+    /// The rectangles extending the damage not yet repaired.
+    DeltaRectangles = 1,
+
+    /// The bounding box of the damage when it grows.
+    BoundingBox = 2,
+
+    /// Only the first damage after the damage was repaired.
+    NonEmpty = 3,
+}
+
+impl From<u8> for ReportLevel {
+    fn from(level: u8) -> ReportLevel {
+        match level {
+            0 => ReportLevel::RawRectangles,
+            1 => ReportLevel::DeltaRectangles,
+            2 => ReportLevel::BoundingBox,
+            _ => ReportLevel::NonEmpty,
+        }
+    }
+}
+
+/// Sent when a drawable is damaged.
+#[derive(Debug, Clone, Copy)]
+pub struct DamageNotifyEvent {
+    pub level: ReportLevel,
+
+    /// Whether more events follow for the same damage.
+    pub more: bool,
+
+    pub drawable: Drawable,
+    pub damage: Damage,
+    pub timestamp: Timestamp,
+
+    /// The damaged area, relative to the drawable.
+    pub area: Rectangle,
+
+    /// The geometry of the drawable, windows are relative to their parent.
+    pub geometry: Rectangle,
+}
+
+/// Offset of the `DamageNotify` event from the extension `first_event`.
+const DAMAGE_NOTIFY: u8 = 0;
+
+impl DamageNotifyEvent {
+    /// Decodes `event` if it's a `DamageNotify` event, `info` is the
+    /// information of the extension as returned by `Client::extension_info`.
+    pub fn from_event(info: &ExtensionInfo, event: &Event) -> Option<DamageNotifyEvent> {
+        match *event {
+            Event::Unknown(ref buf) => DamageNotifyEvent::read(info, buf),
+            _ => None,
+        }
+    }
+
+    /// Decodes an event packet.
+    pub fn read(info: &ExtensionInfo, buf: &[u8]) -> Option<DamageNotifyEvent> {
+        if !info.present || info.first_event == 0 ||
+           (buf[0] & 0x7f).wrapping_sub(info.first_event) != DAMAGE_NOTIFY {
+            return None;
+        }
+
+        Some(DamageNotifyEvent {
+            level: ReportLevel::from(buf[1] & 0x7f),
+            more: buf[1] & 0x80 != 0,
+            drawable: Drawable(NativeEndian::read_u32(&buf[4..8])),
+            damage: Damage(NativeEndian::read_u32(&buf[8..12])),
+            timestamp: NativeEndian::read_u32(&buf[12..16]),
+            area: Rectangle::read(&mut &buf[16..24]).ok()?,
+            geometry: Rectangle::read(&mut &buf[24..32]).ok()?,
+        })
+    }
+}
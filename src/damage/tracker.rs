@@ -0,0 +1,146 @@
+use ::std::cmp;
+use ::std::collections::HashMap;
+
+use ::damage::Damage;
+use ::damage::DamageNotifyEvent;
+use ::damage::DamageSubtract;
+use ::xfixes::Region;
+use ::xproto::Rectangle;
+use ::xproto::Window;
+
+/// The damage to repaint, collected by `DamageTracker`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepaintBatch {
+    /// The damaged rectangles of each window, relative to its parent.
+    pub windows: Vec<(Window, Vec<Rectangle>)>,
+
+    /// The damages reported, in order.
+    pub damages: Vec<Damage>,
+}
+
+impl RepaintBatch {
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// The bounding box of all the damaged rectangles.
+    pub fn bounds(&self) -> Option<Rectangle> {
+        self.windows
+            .iter()
+            .flat_map(|(_, rects)| rects.iter().cloned())
+            .fold(None, |bounds, rect| Some(bounds.map_or(rect, |bounds| union(bounds, rect))))
+    }
+
+    /// The requests repairing the damages of the batch, to be sent after
+    /// repainting when they don't report raw rectangles.
+    pub fn subtract_requests(&self) -> Vec<DamageSubtract> {
+        self.damages
+            .iter()
+            .map(|&damage| {
+                DamageSubtract {
+                    damage,
+                    repair: Region::NONE,
+                    parts: Region::NONE,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Collects the `DamageNotify` events of several windows into repaint
+/// batches.
+///
+/// A batch is ready when the server reports there aren't more events for a
+/// damage, rectangles contained in others are dropped.
+#[derive(Debug, Clone, Default)]
+pub struct DamageTracker {
+    windows: HashMap<Damage, Window>,
+    pending: RepaintBatch,
+}
+
+impl DamageTracker {
+    pub fn new() -> DamageTracker {
+        DamageTracker::default()
+    }
+
+    /// Tracks the events of `damage`, created for `window`.
+    pub fn watch(&mut self, damage: Damage, window: Window) {
+        self.windows.insert(damage, window);
+    }
+
+    /// Stops tracking `window`, returning its damage to be destroyed. The
+    /// pending damage of the window is dropped.
+    pub fn unwatch(&mut self, window: Window) -> Option<Damage> {
+        let damage = self.windows.iter().find(|&(_, &w)| w == window).map(|(&damage, _)| damage);
+        if let Some(damage) = damage {
+            self.windows.remove(&damage);
+            self.pending.windows.retain(|&(w, _)| w != window);
+            self.pending.damages.retain(|&d| d != damage);
+        }
+
+        damage
+    }
+
+    /// Adds the damage of `event`, returns the batch to repaint if no more
+    /// events follow. Events of damages not watched are ignored.
+    pub fn handle(&mut self, event: &DamageNotifyEvent) -> Option<RepaintBatch> {
+        let window = *self.windows.get(&event.damage)?;
+
+        if !self.pending.damages.contains(&event.damage) {
+            self.pending.damages.push(event.damage);
+        }
+
+        let rect = Rectangle {
+            x: event.geometry.x.saturating_add(event.area.x),
+            y: event.geometry.y.saturating_add(event.area.y),
+            width: event.area.width,
+            height: event.area.height,
+        };
+        if rect.width > 0 && rect.height > 0 {
+            let position = self.pending.windows.iter().position(|&(w, _)| w == window);
+            let rects = match position {
+                Some(i) => &mut self.pending.windows[i].1,
+                None => {
+                    self.pending.windows.push((window, Vec::new()));
+                    &mut self.pending.windows.last_mut().unwrap().1
+                }
+            };
+
+            if !rects.iter().any(|&r| contains(r, rect)) {
+                rects.retain(|&r| !contains(rect, r));
+                rects.push(rect);
+            }
+        }
+
+        if event.more {
+            None
+        } else {
+            Some(self.take_batch())
+        }
+    }
+
+    /// Returns the damage collected so far.
+    pub fn take_batch(&mut self) -> RepaintBatch {
+        ::std::mem::take(&mut self.pending)
+    }
+}
+
+fn contains(outer: Rectangle, inner: Rectangle) -> bool {
+    outer.x <= inner.x && outer.y <= inner.y &&
+    outer.x as i32 + outer.width as i32 >= inner.x as i32 + inner.width as i32 &&
+    outer.y as i32 + outer.height as i32 >= inner.y as i32 + inner.height as i32
+}
+
+fn union(a: Rectangle, b: Rectangle) -> Rectangle {
+    let x = cmp::min(a.x, b.x);
+    let y = cmp::min(a.y, b.y);
+    let right = cmp::max(a.x as i32 + a.width as i32, b.x as i32 + b.width as i32);
+    let bottom = cmp::max(a.y as i32 + a.height as i32, b.y as i32 + b.height as i32);
+
+    Rectangle {
+        x,
+        y,
+        width: (right - x as i32) as u16,
+        height: (bottom - y as i32) as u16,
+    }
+}
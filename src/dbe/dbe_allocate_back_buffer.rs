@@ -0,0 +1,54 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::dbe::EXTENSION_NAME;
+use ::dbe::BackBuffer;
+use ::dbe::SwapAction;
+use ::xproto::Window;
+use ::Client;
+
+const DBE_OPCODE: u8 = 1;
+
+/// Allocates the back buffer of a window, `swap_action` is only a hint of
+/// the action used by the swaps. The window must have a visual supporting
+/// back buffers, see `DbeGetVisualInfo`.
+#[derive(Debug, Clone, Copy)]
+pub struct DbeAllocateBackBuffer {
+    pub window: Window,
+    pub buffer: BackBuffer,
+    pub swap_action: SwapAction,
+}
+
+impl ExtensionRequest for DbeAllocateBackBuffer {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DBE_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u32::<NativeEndian>(self.buffer.0)?;
+        a.write_u8(self.swap_action as u8)?;
+        a.write_all(&[0; 3])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
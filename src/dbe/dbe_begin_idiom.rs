@@ -0,0 +1,40 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::dbe::EXTENSION_NAME;
+use ::Client;
+
+const DBE_OPCODE: u8 = 4;
+
+/// Starts a group of requests the server may optimize as a whole, such asn/// a swap followed by drawing in the back buffer.
+#[derive(Debug, Clone, Copy)]
+pub struct DbeBeginIdiom;
+
+impl ExtensionRequest for DbeBeginIdiom {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DBE_OPCODE)?;
+        a.write_u16::<NativeEndian>(1)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
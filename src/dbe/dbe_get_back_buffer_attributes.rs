@@ -0,0 +1,48 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::dbe::EXTENSION_NAME;
+use ::dbe::BackBuffer;
+use ::xproto::Window;
+use ::Client;
+
+const DBE_OPCODE: u8 = 7;
+
+/// Returns the window of a back buffer, `Window::NONE` if the window was
+/// destroyed.
+#[derive(Debug, Clone, Copy)]
+pub struct DbeGetBackBufferAttributes {
+    pub buffer: BackBuffer,
+}
+
+impl ExtensionRequest for DbeGetBackBufferAttributes {
+    type Reply = Window;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DBE_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.buffer.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client)
+            .map(|(client, buf)| (client, Window(NativeEndian::read_u32(&buf[8..12])))))
+    }
+}
@@ -0,0 +1,76 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::futures::Future;
+use ::byteorder::WriteBytesExt;
+use ::byteorder::NativeEndian;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::dbe::EXTENSION_NAME;
+use ::Client;
+
+const DBE_OPCODE: u8 = 0;
+
+/// Returns the version of the extension.
+#[derive(Debug, Clone, Copy)]
+pub struct DbeGetVersion {
+    pub major_version: u8,
+    pub minor_version: u8,
+}
+
+impl ExtensionRequest for DbeGetVersion {
+    type Reply = DbeGetVersionReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DBE_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u8(self.major_version)?;
+        a.write_u8(self.minor_version)?;
+        a.write_all(&[0; 2])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = DbeGetVersionReply {
+                major_version: buf[8],
+                minor_version: buf[9],
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `DbeGetVersion` request.
+#[derive(Debug, Clone, Copy)]
+pub struct DbeGetVersionReply {
+    pub major_version: u8,
+    pub minor_version: u8,
+}
+
+impl VersionRequest for DbeGetVersion {
+    fn latest() -> DbeGetVersion {
+        DbeGetVersion {
+            major_version: 1,
+            minor_version: 0,
+        }
+    }
+
+    fn version(reply: &DbeGetVersionReply) -> Version {
+        Version::new(u32::from(reply.major_version), u32::from(reply.minor_version))
+    }
+}
@@ -0,0 +1,79 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::dbe::EXTENSION_NAME;
+use ::dbe::ScreenVisualInfo;
+use ::dbe::VisualInfo;
+use ::xproto::Drawable;
+use ::xproto::Visualid;
+use ::Client;
+
+const DBE_OPCODE: u8 = 6;
+
+/// Returns the visuals supporting back buffers of the screens of
+/// `drawables`, or of all the screens if it's empty.
+#[derive(Debug, Clone)]
+pub struct DbeGetVisualInfo {
+    pub drawables: Vec<Drawable>,
+}
+
+impl ExtensionRequest for DbeGetVisualInfo {
+    type Reply = Vec<ScreenVisualInfo>;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DBE_OPCODE)?;
+        a.write_u16::<NativeEndian>(2 + self.drawables.len() as u16)?;
+        a.write_u32::<NativeEndian>(self.drawables.len() as u32)?;
+        for drawable in &self.drawables {
+            a.write_u32::<NativeEndian>(drawable.0)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let number = NativeEndian::read_u32(&buf[8..12]) as usize;
+
+            let mut a = &buf[32..];
+            let mut screens = Vec::with_capacity(number);
+            for _ in 0..number {
+                let count = a.read_u32::<NativeEndian>()? as usize;
+
+                let mut infos = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let visual = Visualid(a.read_u32::<NativeEndian>()?);
+                    let depth = a.read_u8()?;
+                    let perf_level = a.read_u8()?;
+                    a.read_u16::<NativeEndian>()?;
+
+                    infos.push(VisualInfo {
+                        visual,
+                        depth,
+                        perf_level,
+                    });
+                }
+
+                screens.push(ScreenVisualInfo { infos });
+            }
+
+            Ok((client, screens))
+        }))
+    }
+}
@@ -0,0 +1,50 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::dbe::EXTENSION_NAME;
+use ::dbe::SwapInfo;
+use ::Client;
+
+const DBE_OPCODE: u8 = 3;
+
+/// Swaps the back and front buffers of the windows at once.
+#[derive(Debug, Clone)]
+pub struct DbeSwapBuffers {
+    pub actions: Vec<SwapInfo>,
+}
+
+impl ExtensionRequest for DbeSwapBuffers {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DBE_OPCODE)?;
+        a.write_u16::<NativeEndian>(2 + 2 * self.actions.len() as u16)?;
+        a.write_u32::<NativeEndian>(self.actions.len() as u32)?;
+        for action in &self.actions {
+            a.write_u32::<NativeEndian>(action.window.0)?;
+            a.write_u8(action.swap_action as u8)?;
+            a.write_all(&[0; 3])?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
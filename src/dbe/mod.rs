@@ -0,0 +1,108 @@
+//! DOUBLE-BUFFER extension requests, back buffers of windows drawn with
+//! core requests and swapped to the screen at once.
+
+use ::std::cmp::Reverse;
+
+use ::xproto::Drawable;
+use ::xproto::Visualid;
+use ::xproto::Window;
+use ::Screen;
+use ::Visual;
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    dbe_get_version,
+    dbe_allocate_back_buffer,
+    dbe_deallocate_back_buffer,
+    dbe_swap_buffers,
+    dbe_begin_idiom,
+    dbe_end_idiom,
+    dbe_get_visual_info,
+    dbe_get_back_buffer_attributes
+}
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"DOUBLE-BUFFER";
+
+declare_ids! {
+    /// The back buffer of a window.
+    BackBuffer
+}
+
+/// A back buffer is drawn with the core requests like a window.
+impl From<BackBuffer> for Drawable {
+    fn from(buffer: BackBuffer) -> Drawable {
+        Drawable(buffer.0)
+    }
+}
+
+/// What the back buffer contains after a swap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapAction {
+    /// Undefined contents, the fastest.
+    Undefined = 0,
+
+    /// Cleared to the window background.
+    Background = 1,
+
+    /// The contents of the front buffer before the swap.
+    Untouched = 2,
+
+    /// The contents of the back buffer before the swap.
+    Copied = 3,
+}
+
+/// A window with the action of its swap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapInfo {
+    pub window: Window,
+    pub swap_action: SwapAction,
+}
+
+/// A visual supporting back buffers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VisualInfo {
+    pub visual: Visualid,
+    pub depth: u8,
+
+    /// Higher is faster, only comparable on the same screen.
+    pub perf_level: u8,
+}
+
+/// The visuals of a screen supporting back buffers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenVisualInfo {
+    pub infos: Vec<VisualInfo>,
+}
+
+impl ScreenVisualInfo {
+    /// Returns the information of a visual, `None` if it doesn't support
+    /// back buffers.
+    pub fn find(&self, visual: Visualid) -> Option<&VisualInfo> {
+        self.infos.iter().find(|info| info.visual == visual)
+    }
+
+    /// Returns the `Visual`s of `screen` supporting back buffers with their
+    /// information, the fastest first.
+    pub fn visuals<'a>(&self, screen: &'a Screen) -> Vec<(&'a Visual, VisualInfo)> {
+        let mut visuals: Vec<(&Visual, VisualInfo)> = screen.allowed_depths
+            .iter()
+            .flat_map(|depth| depth.visuals.iter().map(move |visual| (depth.depth, visual)))
+            .filter_map(|(depth, visual)| {
+                self.find(visual.id)
+                    .filter(|info| info.depth == depth)
+                    .map(|&info| (visual, info))
+            })
+            .collect();
+        visuals.sort_by_key(|&(_, info)| Reverse(info.perf_level));
+        visuals
+    }
+}
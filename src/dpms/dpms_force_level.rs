@@ -0,0 +1,46 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::dpms::EXTENSION_NAME;
+use ::dpms::DPMSMode;
+use ::Client;
+
+const DPMS_OPCODE: u8 = 6;
+
+/// Sets the power level of the monitor, DPMS must be enabled.
+#[derive(Debug, Clone, Copy)]
+pub struct DPMSForceLevel {
+    pub power_level: DPMSMode,
+}
+
+impl ExtensionRequest for DPMSForceLevel {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DPMS_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u16::<NativeEndian>(self.power_level as u16)?;
+        a.write_all(&[0; 2])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,58 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::dpms::EXTENSION_NAME;
+use ::Client;
+
+const DPMS_OPCODE: u8 = 2;
+
+/// Returns the seconds of inactivity before each power saving level.
+#[derive(Debug, Clone, Copy)]
+pub struct DPMSGetTimeouts;
+
+impl ExtensionRequest for DPMSGetTimeouts {
+    type Reply = DPMSGetTimeoutsReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DPMS_OPCODE)?;
+        a.write_u16::<NativeEndian>(1)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = DPMSGetTimeoutsReply {
+                standby_timeout: NativeEndian::read_u16(&buf[8..10]),
+                suspend_timeout: NativeEndian::read_u16(&buf[10..12]),
+                off_timeout: NativeEndian::read_u16(&buf[12..14]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `DPMSGetTimeouts` request.
+#[derive(Debug, Clone, Copy)]
+pub struct DPMSGetTimeoutsReply {
+    /// Zero when the level is disabled.
+    pub standby_timeout: u16,
+    pub suspend_timeout: u16,
+    pub off_timeout: u16,
+}
@@ -0,0 +1,75 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::dpms::EXTENSION_NAME;
+use ::Client;
+
+const DPMS_OPCODE: u8 = 0;
+
+/// Returns the version of the extension supported by the server.
+#[derive(Debug, Clone, Copy)]
+pub struct DPMSGetVersion {
+    pub client_major_version: u16,
+    pub client_minor_version: u16,
+}
+
+impl ExtensionRequest for DPMSGetVersion {
+    type Reply = DPMSGetVersionReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DPMS_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u16::<NativeEndian>(self.client_major_version)?;
+        a.write_u16::<NativeEndian>(self.client_minor_version)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = DPMSGetVersionReply {
+                server_major_version: NativeEndian::read_u16(&buf[8..10]),
+                server_minor_version: NativeEndian::read_u16(&buf[10..12]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `DPMSGetVersion` request.
+#[derive(Debug, Clone, Copy)]
+pub struct DPMSGetVersionReply {
+    pub server_major_version: u16,
+    pub server_minor_version: u16,
+}
+
+impl VersionRequest for DPMSGetVersion {
+    fn latest() -> DPMSGetVersion {
+        DPMSGetVersion {
+            client_major_version: 1,
+            client_minor_version: 1,
+        }
+    }
+
+    fn version(reply: &DPMSGetVersionReply) -> Version {
+        Version::new(u32::from(reply.server_major_version), u32::from(reply.server_minor_version))
+    }
+}
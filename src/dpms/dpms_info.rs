@@ -0,0 +1,58 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::dpms::EXTENSION_NAME;
+use ::dpms::DPMSMode;
+use ::Client;
+
+const DPMS_OPCODE: u8 = 7;
+
+/// Returns the power level of the monitor and whether DPMS is enabled.
+#[derive(Debug, Clone, Copy)]
+pub struct DPMSInfo;
+
+impl ExtensionRequest for DPMSInfo {
+    type Reply = DPMSInfoReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DPMS_OPCODE)?;
+        a.write_u16::<NativeEndian>(1)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = DPMSInfoReply {
+                power_level: DPMSMode::from(NativeEndian::read_u16(&buf[8..10])),
+                state: buf[10] != 0,
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `DPMSInfo` request.
+#[derive(Debug, Clone, Copy)]
+pub struct DPMSInfoReply {
+    pub power_level: DPMSMode,
+
+    /// Whether DPMS is enabled.
+    pub state: bool,
+}
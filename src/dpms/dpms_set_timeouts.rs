@@ -0,0 +1,50 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::dpms::EXTENSION_NAME;
+use ::Client;
+
+const DPMS_OPCODE: u8 = 3;
+
+/// Sets the seconds of inactivity before each power saving level, zero
+/// disables a level.
+#[derive(Debug, Clone, Copy)]
+pub struct DPMSSetTimeouts {
+    pub standby_timeout: u16,
+    pub suspend_timeout: u16,
+    pub off_timeout: u16,
+}
+
+impl ExtensionRequest for DPMSSetTimeouts {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(DPMS_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u16::<NativeEndian>(self.standby_timeout)?;
+        a.write_u16::<NativeEndian>(self.suspend_timeout)?;
+        a.write_u16::<NativeEndian>(self.off_timeout)?;
+        a.write_all(&[0; 2])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
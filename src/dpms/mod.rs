@@ -0,0 +1,45 @@
+//! DPMS extension requests, controlling the power saving levels of the
+//! monitor.
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    dpms_get_version,
+    dpms_capable,
+    dpms_get_timeouts,
+    dpms_set_timeouts,
+    dpms_enable,
+    dpms_disable,
+    dpms_force_level,
+    dpms_info
+}
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"DPMS";
+
+/// Power levels of the monitor, from the highest to the lowest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DPMSMode {
+    On = 0,
+    Standby = 1,
+    Suspend = 2,
+    Off = 3,
+}
+
+impl From<u16> for DPMSMode {
+    fn from(mode: u16) -> DPMSMode {
+        match mode {
+            1 => DPMSMode::Standby,
+            2 => DPMSMode::Suspend,
+            3 => DPMSMode::Off,
+            _ => DPMSMode::On,
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Events sent by the X server.
+
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol;
+use ::xproto::Atom;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+
+pub const KEY_PRESS: u8 = 2;
+pub const KEY_RELEASE: u8 = 3;
+pub const BUTTON_PRESS: u8 = 4;
+pub const BUTTON_RELEASE: u8 = 5;
+pub const MOTION_NOTIFY: u8 = 6;
+pub const PROPERTY_NOTIFY: u8 = 28;
+pub const SELECTION_CLEAR: u8 = 29;
+pub const SELECTION_REQUEST: u8 = 30;
+pub const SELECTION_NOTIFY: u8 = 31;
+pub const CLIENT_MESSAGE: u8 = 33;
+
+/// An event received from the server.
+#[derive(Debug)]
+pub enum Event {
+    KeyPress(InputEvent),
+    KeyRelease(InputEvent),
+    ButtonPress(InputEvent),
+    ButtonRelease(InputEvent),
+    MotionNotify(InputEvent),
+    PropertyNotify(PropertyNotifyEvent),
+    SelectionClear(SelectionClearEvent),
+    SelectionRequest(SelectionRequestEvent),
+    SelectionNotify(SelectionNotifyEvent),
+    ClientMessage(ClientMessageEvent),
+
+    /// An error generated by a request whose reply was not waited for.
+    Error(protocol::Error),
+
+    /// An event this crate doesn't know how to decode, the raw packet is
+    /// kept.
+    Unknown(Vec<u8>),
+}
+
+impl Event {
+    /// Decodes an event (or an error) packet.
+    pub fn read(buf: &[u8]) -> Event {
+        match buf[0] & 0x7f {
+            protocol::ERROR => Event::Error(protocol::Error::read(buf)),
+            KEY_PRESS => Event::KeyPress(InputEvent::read(buf)),
+            KEY_RELEASE => Event::KeyRelease(InputEvent::read(buf)),
+            BUTTON_PRESS => Event::ButtonPress(InputEvent::read(buf)),
+            BUTTON_RELEASE => Event::ButtonRelease(InputEvent::read(buf)),
+            MOTION_NOTIFY => Event::MotionNotify(InputEvent::read(buf)),
+            PROPERTY_NOTIFY => Event::PropertyNotify(PropertyNotifyEvent::read(buf)),
+            SELECTION_CLEAR => Event::SelectionClear(SelectionClearEvent::read(buf)),
+            SELECTION_REQUEST => Event::SelectionRequest(SelectionRequestEvent::read(buf)),
+            SELECTION_NOTIFY => Event::SelectionNotify(SelectionNotifyEvent::read(buf)),
+            CLIENT_MESSAGE => Event::ClientMessage(ClientMessageEvent::read(buf)),
+            _ => Event::Unknown(buf.to_vec()),
+        }
+    }
+}
+
+/// A key, button or pointer motion event.
+#[derive(Debug, Clone, Copy)]
+pub struct InputEvent {
+    /// The keycode or the button, for motion events whether it's a hint.
+    pub detail: u8,
+
+    pub time: Timestamp,
+    pub root: Window,
+
+    /// The window the event is reported relative to.
+    pub event: Window,
+
+    /// The child of `event` containing the pointer, if any.
+    pub child: Window,
+
+    pub root_x: i16,
+    pub root_y: i16,
+    pub event_x: i16,
+    pub event_y: i16,
+
+    /// The modifiers and buttons held before the event.
+    pub state: u16,
+
+    /// Whether `event` is on the screen of the pointer.
+    pub same_screen: bool,
+}
+
+impl InputEvent {
+    fn read(buf: &[u8]) -> InputEvent {
+        InputEvent {
+            detail: buf[1],
+            time: NativeEndian::read_u32(&buf[4..8]),
+            root: Window(NativeEndian::read_u32(&buf[8..12])),
+            event: Window(NativeEndian::read_u32(&buf[12..16])),
+            child: Window(NativeEndian::read_u32(&buf[16..20])),
+            root_x: NativeEndian::read_i16(&buf[20..22]),
+            root_y: NativeEndian::read_i16(&buf[22..24]),
+            event_x: NativeEndian::read_i16(&buf[24..26]),
+            event_y: NativeEndian::read_i16(&buf[26..28]),
+            state: NativeEndian::read_u16(&buf[28..30]),
+            same_screen: buf[30] != 0,
+        }
+    }
+}
+
+/// State of a property after a `PropertyNotify` event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyState {
+    NewValue,
+    Deleted,
+}
+
+/// Reports a change on a property of a window that selected
+/// `EVENT_PROPERTY_CHANGE`.
+#[derive(Debug, Clone, Copy)]
+pub struct PropertyNotifyEvent {
+    pub window: Window,
+    pub atom: Atom,
+    pub time: Timestamp,
+    pub state: PropertyState,
+}
+
+impl PropertyNotifyEvent {
+    fn read(buf: &[u8]) -> PropertyNotifyEvent {
+        PropertyNotifyEvent {
+            window: Window(NativeEndian::read_u32(&buf[4..8])),
+            atom: Atom(NativeEndian::read_u32(&buf[8..12])),
+            time: NativeEndian::read_u32(&buf[12..16]),
+            state: if buf[16] == 0 {
+                PropertyState::NewValue
+            } else {
+                PropertyState::Deleted
+            },
+        }
+    }
+}
+
+/// Sent to the previous owner of a selection when it loses it.
+#[derive(Debug, Clone, Copy)]
+pub struct SelectionClearEvent {
+    pub time: Timestamp,
+    pub owner: Window,
+    pub selection: Atom,
+}
+
+impl SelectionClearEvent {
+    fn read(buf: &[u8]) -> SelectionClearEvent {
+        SelectionClearEvent {
+            time: NativeEndian::read_u32(&buf[4..8]),
+            owner: Window(NativeEndian::read_u32(&buf[8..12])),
+            selection: Atom(NativeEndian::read_u32(&buf[12..16])),
+        }
+    }
+}
+
+/// Sent to the owner of a selection when a client issues a
+/// `ConvertSelection` request.
+#[derive(Debug, Clone, Copy)]
+pub struct SelectionRequestEvent {
+    pub time: Timestamp,
+    pub owner: Window,
+    pub requestor: Window,
+    pub selection: Atom,
+    pub target: Atom,
+    pub property: Atom,
+}
+
+impl SelectionRequestEvent {
+    fn read(buf: &[u8]) -> SelectionRequestEvent {
+        SelectionRequestEvent {
+            time: NativeEndian::read_u32(&buf[4..8]),
+            owner: Window(NativeEndian::read_u32(&buf[8..12])),
+            requestor: Window(NativeEndian::read_u32(&buf[12..16])),
+            selection: Atom(NativeEndian::read_u32(&buf[16..20])),
+            target: Atom(NativeEndian::read_u32(&buf[20..24])),
+            property: Atom(NativeEndian::read_u32(&buf[24..28])),
+        }
+    }
+}
+
+/// Answer to a `ConvertSelection` request, `property` is zero (`None`) if
+/// the conversion was refused.
+#[derive(Debug, Clone, Copy)]
+pub struct SelectionNotifyEvent {
+    pub time: Timestamp,
+    pub requestor: Window,
+    pub selection: Atom,
+    pub target: Atom,
+    pub property: Atom,
+}
+
+impl SelectionNotifyEvent {
+    fn read(buf: &[u8]) -> SelectionNotifyEvent {
+        SelectionNotifyEvent {
+            time: NativeEndian::read_u32(&buf[4..8]),
+            requestor: Window(NativeEndian::read_u32(&buf[8..12])),
+            selection: Atom(NativeEndian::read_u32(&buf[12..16])),
+            target: Atom(NativeEndian::read_u32(&buf[16..20])),
+            property: Atom(NativeEndian::read_u32(&buf[20..24])),
+        }
+    }
+
+    /// Encodes the event to be sent with `SendEvent`.
+    pub fn encode(&self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(SELECTION_NOTIFY)?;
+        a.write_u8(0)?;
+        a.write_u16::<NativeEndian>(0)?;
+        a.write_u32::<NativeEndian>(self.time)?;
+        a.write_u32::<NativeEndian>(self.requestor.0)?;
+        a.write_u32::<NativeEndian>(self.selection.0)?;
+        a.write_u32::<NativeEndian>(self.target.0)?;
+        a.write_u32::<NativeEndian>(self.property.0)?;
+        a.write_all(&[0; 8])?;
+
+        Ok(a.into_inner())
+    }
+}
+
+/// A message sent by a client with `SendEvent`, the contents of `data` are
+/// interpreted according to `type_`.
+#[derive(Debug, Clone, Copy)]
+pub struct ClientMessageEvent {
+    /// Whether `data` is a list of 8, 16 or 32 bit quantities.
+    pub format: u8,
+
+    pub window: Window,
+    pub type_: Atom,
+    pub data: [u8; 20],
+}
+
+impl ClientMessageEvent {
+    /// Creates a message of format 32.
+    pub fn new32(window: Window, type_: Atom, data: [u32; 5]) -> ClientMessageEvent {
+        let mut buf = [0u8; 20];
+        for (chunk, &value) in buf.chunks_mut(4).zip(data.iter()) {
+            NativeEndian::write_u32(chunk, value);
+        }
+
+        ClientMessageEvent {
+            format: 32,
+            window,
+            type_,
+            data: buf,
+        }
+    }
+
+    fn read(buf: &[u8]) -> ClientMessageEvent {
+        let mut data = [0u8; 20];
+        data.copy_from_slice(&buf[12..32]);
+
+        ClientMessageEvent {
+            format: buf[1],
+            window: Window(NativeEndian::read_u32(&buf[4..8])),
+            type_: Atom(NativeEndian::read_u32(&buf[8..12])),
+            data,
+        }
+    }
+
+    /// Interprets `data` as five 32 bit quantities.
+    pub fn data32(&self) -> [u32; 5] {
+        let mut data = [0u32; 5];
+        for (value, chunk) in data.iter_mut().zip(self.data.chunks(4)) {
+            *value = NativeEndian::read_u32(chunk);
+        }
+
+        data
+    }
+
+    /// Encodes the event to be sent with `SendEvent`.
+    pub fn encode(&self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(CLIENT_MESSAGE)?;
+        a.write_u8(self.format)?;
+        a.write_u16::<NativeEndian>(0)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u32::<NativeEndian>(self.type_.0)?;
+        a.write_all(&self.data)?;
+
+        Ok(a.into_inner())
+    }
+}
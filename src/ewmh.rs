@@ -0,0 +1,1100 @@
+//! Typed access to the Extended Window Manager Hints (`_NET_*`).
+//!
+//! All the atoms are interned at once with `Atoms::intern`. Properties are
+//! types implementing `NetProperty` and are read and written with `get` and
+//! `set`, the client messages sent to the root window to ask the window
+//! manager for changes are built with the `request_*` functions and decoded
+//! by window managers with `NetMessage::from_event`.
+
+use ::std::io;
+
+use ::futures;
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+
+use ::event::ClientMessageEvent;
+use ::event::Event;
+use ::icccm::PropertyData;
+use ::protocol::ReplyFuture;
+use ::xproto;
+use ::xproto::Atom;
+use ::xproto::GetPropertyReply;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+use ::Client;
+
+macro_rules! atoms {
+    ($($field:ident: $name:expr),+) => {
+        /// Atoms used by the EWMH.
+        #[derive(Debug, Clone, Copy)]
+        pub struct Atoms {
+            pub utf8_string: Atom,
+            $(pub $field: Atom,)+
+        }
+
+        impl Atoms {
+            /// Interns all the atoms with a single round trip.
+            pub fn intern(client: Client) -> ReplyFuture<Atoms> {
+                let names: &[&[u8]] = &[b"UTF8_STRING", $($name),+];
+
+                Box::new(xproto::intern_atoms(client, names).map(|(client, atoms)| {
+                    let mut atoms = atoms.into_iter();
+                    let atoms = Atoms {
+                        utf8_string: atoms.next().unwrap(),
+                        $($field: atoms.next().unwrap(),)+
+                    };
+
+                    (client, atoms)
+                }))
+            }
+        }
+    }
+}
+
+atoms! {
+    net_supported: b"_NET_SUPPORTED",
+    net_client_list: b"_NET_CLIENT_LIST",
+    net_client_list_stacking: b"_NET_CLIENT_LIST_STACKING",
+    net_number_of_desktops: b"_NET_NUMBER_OF_DESKTOPS",
+    net_desktop_geometry: b"_NET_DESKTOP_GEOMETRY",
+    net_desktop_viewport: b"_NET_DESKTOP_VIEWPORT",
+    net_current_desktop: b"_NET_CURRENT_DESKTOP",
+    net_desktop_names: b"_NET_DESKTOP_NAMES",
+    net_active_window: b"_NET_ACTIVE_WINDOW",
+    net_workarea: b"_NET_WORKAREA",
+    net_supporting_wm_check: b"_NET_SUPPORTING_WM_CHECK",
+    net_virtual_roots: b"_NET_VIRTUAL_ROOTS",
+    net_desktop_layout: b"_NET_DESKTOP_LAYOUT",
+    net_showing_desktop: b"_NET_SHOWING_DESKTOP",
+
+    net_close_window: b"_NET_CLOSE_WINDOW",
+    net_moveresize_window: b"_NET_MOVERESIZE_WINDOW",
+    net_wm_moveresize: b"_NET_WM_MOVERESIZE",
+    net_restack_window: b"_NET_RESTACK_WINDOW",
+    net_request_frame_extents: b"_NET_REQUEST_FRAME_EXTENTS",
+
+    net_wm_name: b"_NET_WM_NAME",
+    net_wm_visible_name: b"_NET_WM_VISIBLE_NAME",
+    net_wm_icon_name: b"_NET_WM_ICON_NAME",
+    net_wm_visible_icon_name: b"_NET_WM_VISIBLE_ICON_NAME",
+    net_wm_desktop: b"_NET_WM_DESKTOP",
+    net_wm_window_type: b"_NET_WM_WINDOW_TYPE",
+    net_wm_state: b"_NET_WM_STATE",
+    net_wm_allowed_actions: b"_NET_WM_ALLOWED_ACTIONS",
+    net_wm_strut: b"_NET_WM_STRUT",
+    net_wm_strut_partial: b"_NET_WM_STRUT_PARTIAL",
+    net_wm_icon_geometry: b"_NET_WM_ICON_GEOMETRY",
+    net_wm_icon: b"_NET_WM_ICON",
+    net_wm_pid: b"_NET_WM_PID",
+    net_wm_handled_icons: b"_NET_WM_HANDLED_ICONS",
+    net_wm_user_time: b"_NET_WM_USER_TIME",
+    net_wm_user_time_window: b"_NET_WM_USER_TIME_WINDOW",
+    net_frame_extents: b"_NET_FRAME_EXTENTS",
+    net_wm_opaque_region: b"_NET_WM_OPAQUE_REGION",
+    net_wm_bypass_compositor: b"_NET_WM_BYPASS_COMPOSITOR",
+
+    net_wm_ping: b"_NET_WM_PING",
+    net_wm_sync_request: b"_NET_WM_SYNC_REQUEST",
+    net_wm_sync_request_counter: b"_NET_WM_SYNC_REQUEST_COUNTER",
+    net_wm_fullscreen_monitors: b"_NET_WM_FULLSCREEN_MONITORS",
+
+    net_wm_window_type_desktop: b"_NET_WM_WINDOW_TYPE_DESKTOP",
+    net_wm_window_type_dock: b"_NET_WM_WINDOW_TYPE_DOCK",
+    net_wm_window_type_toolbar: b"_NET_WM_WINDOW_TYPE_TOOLBAR",
+    net_wm_window_type_menu: b"_NET_WM_WINDOW_TYPE_MENU",
+    net_wm_window_type_utility: b"_NET_WM_WINDOW_TYPE_UTILITY",
+    net_wm_window_type_splash: b"_NET_WM_WINDOW_TYPE_SPLASH",
+    net_wm_window_type_dialog: b"_NET_WM_WINDOW_TYPE_DIALOG",
+    net_wm_window_type_dropdown_menu: b"_NET_WM_WINDOW_TYPE_DROPDOWN_MENU",
+    net_wm_window_type_popup_menu: b"_NET_WM_WINDOW_TYPE_POPUP_MENU",
+    net_wm_window_type_tooltip: b"_NET_WM_WINDOW_TYPE_TOOLTIP",
+    net_wm_window_type_notification: b"_NET_WM_WINDOW_TYPE_NOTIFICATION",
+    net_wm_window_type_combo: b"_NET_WM_WINDOW_TYPE_COMBO",
+    net_wm_window_type_dnd: b"_NET_WM_WINDOW_TYPE_DND",
+    net_wm_window_type_normal: b"_NET_WM_WINDOW_TYPE_NORMAL",
+
+    net_wm_state_modal: b"_NET_WM_STATE_MODAL",
+    net_wm_state_sticky: b"_NET_WM_STATE_STICKY",
+    net_wm_state_maximized_vert: b"_NET_WM_STATE_MAXIMIZED_VERT",
+    net_wm_state_maximized_horz: b"_NET_WM_STATE_MAXIMIZED_HORZ",
+    net_wm_state_shaded: b"_NET_WM_STATE_SHADED",
+    net_wm_state_skip_taskbar: b"_NET_WM_STATE_SKIP_TASKBAR",
+    net_wm_state_skip_pager: b"_NET_WM_STATE_SKIP_PAGER",
+    net_wm_state_hidden: b"_NET_WM_STATE_HIDDEN",
+    net_wm_state_fullscreen: b"_NET_WM_STATE_FULLSCREEN",
+    net_wm_state_above: b"_NET_WM_STATE_ABOVE",
+    net_wm_state_below: b"_NET_WM_STATE_BELOW",
+    net_wm_state_demands_attention: b"_NET_WM_STATE_DEMANDS_ATTENTION",
+    net_wm_state_focused: b"_NET_WM_STATE_FOCUSED",
+
+    net_wm_action_move: b"_NET_WM_ACTION_MOVE",
+    net_wm_action_resize: b"_NET_WM_ACTION_RESIZE",
+    net_wm_action_minimize: b"_NET_WM_ACTION_MINIMIZE",
+    net_wm_action_shade: b"_NET_WM_ACTION_SHADE",
+    net_wm_action_stick: b"_NET_WM_ACTION_STICK",
+    net_wm_action_maximize_horz: b"_NET_WM_ACTION_MAXIMIZE_HORZ",
+    net_wm_action_maximize_vert: b"_NET_WM_ACTION_MAXIMIZE_VERT",
+    net_wm_action_fullscreen: b"_NET_WM_ACTION_FULLSCREEN",
+    net_wm_action_change_desktop: b"_NET_WM_ACTION_CHANGE_DESKTOP",
+    net_wm_action_close: b"_NET_WM_ACTION_CLOSE",
+    net_wm_action_above: b"_NET_WM_ACTION_ABOVE",
+    net_wm_action_below: b"_NET_WM_ACTION_BELOW"
+}
+
+/// A window property defined by the EWMH.
+pub trait NetProperty: Sized {
+    /// The property name.
+    fn name(atoms: &Atoms) -> Atom;
+
+    fn encode(&self, atoms: &Atoms) -> PropertyData;
+
+    /// Decodes the property, `None` is returned if it doesn't exist or it
+    /// has an unexpected format.
+    fn decode(atoms: &Atoms, reply: &GetPropertyReply) -> Option<Self>;
+}
+
+/// Sets the property `P` of `window`. A value longer than a request, like
+/// a large `NetWmIcon`, replaces the property with its first chunk and
+/// appends the next ones.
+pub fn set<P: NetProperty>(client: Client,
+                           atoms: &Atoms,
+                           window: Window,
+                           value: &P)
+                           -> ReplyFuture<()> {
+    let data = value.encode(atoms);
+    let property = P::name(atoms);
+    let max_size = (client.get_server_info().maximum_request_length as usize * 4 - 24) & !3;
+
+    let mut chunks: Vec<&[u8]> = data.data.chunks(max_size).collect();
+    if chunks.is_empty() {
+        chunks.push(&[]);
+    }
+    let requests = chunks.into_iter()
+        .enumerate()
+        .map(|(i, chunk)| {
+            xproto::ChangeProperty {
+                mode: if i == 0 {
+                    xproto::PropMode::Replace
+                } else {
+                    xproto::PropMode::Append
+                },
+                window,
+                property,
+                type_: data.type_,
+                format: data.format,
+                data: chunk.to_vec(),
+            }
+        })
+        .collect();
+
+    Box::new(client.send_all(requests).map(|(client, _)| (client, ())))
+}
+
+/// Reads the property `P` of `window`.
+pub fn get<P: NetProperty + 'static>(client: Client,
+                                     atoms: &Atoms,
+                                     window: Window)
+                                     -> ReplyFuture<Option<P>> {
+    let atoms = *atoms;
+
+    Box::new(client.perform(xproto::GetProperty {
+            delete: false,
+            window,
+            property: P::name(&atoms),
+            type_: xproto::ATOM_ANY,
+            long_offset: 0,
+            long_length: u32::MAX / 4,
+        })
+        .map(move |(client, reply)| (client, P::decode(&atoms, &reply))))
+}
+
+fn cardinals<T: Copy + Into<u32>>(type_: Atom, values: &[T]) -> PropertyData {
+    let mut data = vec![0u8; values.len() * 4];
+    for (chunk, &value) in data.chunks_mut(4).zip(values) {
+        NativeEndian::write_u32(chunk, value.into());
+    }
+
+    PropertyData {
+        type_,
+        format: 32,
+        data,
+    }
+}
+
+/// Returns the values of a format 32 property if it has at least `min_len`
+/// of them and their count is a multiple of `step`.
+fn values(reply: &GetPropertyReply, min_len: usize, step: usize) -> Option<Vec<u32>> {
+    let v = reply.value32();
+    if reply.format != 32 || v.len() < min_len || !v.len().is_multiple_of(step) {
+        return None;
+    }
+
+    Some(v)
+}
+
+fn utf8(atoms: &Atoms, text: &str) -> PropertyData {
+    PropertyData {
+        type_: atoms.utf8_string,
+        format: 8,
+        data: text.as_bytes().to_vec(),
+    }
+}
+
+/// Declares a property holding a single 32 bit value.
+macro_rules! value_property {
+    ($(#[$attr:meta])* $name:ident($type_:ty), $atom:ident, $prop_type:path) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct $name(pub $type_);
+
+        impl NetProperty for $name {
+            fn name(atoms: &Atoms) -> Atom {
+                atoms.$atom
+            }
+
+            fn encode(&self, _: &Atoms) -> PropertyData {
+                cardinals($prop_type, &[self.0])
+            }
+
+            fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<$name> {
+                values(reply, 1, 1).map(|v| $name(v[0].into()))
+            }
+        }
+    }
+}
+
+/// Declares a property holding a list of 32 bit values.
+macro_rules! list_property {
+    ($(#[$attr:meta])* $name:ident($type_:ty), $atom:ident, $prop_type:path) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name(pub Vec<$type_>);
+
+        impl NetProperty for $name {
+            fn name(atoms: &Atoms) -> Atom {
+                atoms.$atom
+            }
+
+            fn encode(&self, _: &Atoms) -> PropertyData {
+                cardinals($prop_type, &self.0)
+            }
+
+            fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<$name> {
+                values(reply, 0, 1).map(|v| $name(v.into_iter().map(From::from).collect()))
+            }
+        }
+    }
+}
+
+/// Declares a property holding an UTF-8 string.
+macro_rules! text_property {
+    ($(#[$attr:meta])* $name:ident, $atom:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name(pub String);
+
+        impl NetProperty for $name {
+            fn name(atoms: &Atoms) -> Atom {
+                atoms.$atom
+            }
+
+            fn encode(&self, atoms: &Atoms) -> PropertyData {
+                utf8(atoms, &self.0)
+            }
+
+            fn decode(atoms: &Atoms, reply: &GetPropertyReply) -> Option<$name> {
+                if reply.format != 8 || reply.type_ != atoms.utf8_string {
+                    return None;
+                }
+
+                Some($name(String::from_utf8_lossy(&reply.value).into_owned()))
+            }
+        }
+    }
+}
+
+/// Declares a property holding a list of rectangles.
+macro_rules! rects_property {
+    ($(#[$attr:meta])* $name:ident, $atom:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name(pub Vec<Rect>);
+
+        impl NetProperty for $name {
+            fn name(atoms: &Atoms) -> Atom {
+                atoms.$atom
+            }
+
+            fn encode(&self, _: &Atoms) -> PropertyData {
+                let v: Vec<u32> = self.0.iter().flat_map(|r| r.to_values()).collect();
+                cardinals(xproto::ATOM_CARDINAL, &v)
+            }
+
+            fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<$name> {
+                values(reply, 0, 4).map(|v| $name(v.chunks(4).map(Rect::from_values).collect()))
+            }
+        }
+    }
+}
+
+/// Declares a property holding the four borders of a window.
+macro_rules! extents_property {
+    ($(#[$attr:meta])* $name:ident, $atom:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub struct $name {
+            pub left: u32,
+            pub right: u32,
+            pub top: u32,
+            pub bottom: u32,
+        }
+
+        impl NetProperty for $name {
+            fn name(atoms: &Atoms) -> Atom {
+                atoms.$atom
+            }
+
+            fn encode(&self, _: &Atoms) -> PropertyData {
+                cardinals(xproto::ATOM_CARDINAL, &[self.left, self.right, self.top, self.bottom])
+            }
+
+            fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<$name> {
+                values(reply, 4, 1).map(|v| {
+                    $name {
+                        left: v[0],
+                        right: v[1],
+                        top: v[2],
+                        bottom: v[3],
+                    }
+                })
+            }
+        }
+    }
+}
+
+/// A rectangle in root window coordinates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    fn to_values(self) -> Vec<u32> {
+        vec![self.x as u32, self.y as u32, self.width, self.height]
+    }
+
+    fn from_values(v: &[u32]) -> Rect {
+        Rect {
+            x: v[0] as i32,
+            y: v[1] as i32,
+            width: v[2],
+            height: v[3],
+        }
+    }
+}
+
+list_property!(
+    /// `_NET_SUPPORTED`, the hints supported by the window manager.
+    NetSupported(Atom), net_supported, xproto::ATOM_ATOM);
+
+list_property!(
+    /// `_NET_CLIENT_LIST`, managed windows in initial mapping order.
+    NetClientList(Window), net_client_list, xproto::ATOM_WINDOW);
+
+list_property!(
+    /// `_NET_CLIENT_LIST_STACKING`, managed windows in bottom-to-top
+    /// stacking order.
+    NetClientListStacking(Window), net_client_list_stacking, xproto::ATOM_WINDOW);
+
+value_property!(
+    /// `_NET_NUMBER_OF_DESKTOPS`.
+    NetNumberOfDesktops(u32), net_number_of_desktops, xproto::ATOM_CARDINAL);
+
+/// `_NET_DESKTOP_GEOMETRY`, the size of the large desktop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetDesktopGeometry {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl NetProperty for NetDesktopGeometry {
+    fn name(atoms: &Atoms) -> Atom {
+        atoms.net_desktop_geometry
+    }
+
+    fn encode(&self, _: &Atoms) -> PropertyData {
+        cardinals(xproto::ATOM_CARDINAL, &[self.width, self.height])
+    }
+
+    fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<NetDesktopGeometry> {
+        values(reply, 2, 1).map(|v| {
+            NetDesktopGeometry {
+                width: v[0],
+                height: v[1],
+            }
+        })
+    }
+}
+
+/// `_NET_DESKTOP_VIEWPORT`, the top left corner of each desktop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetDesktopViewport(pub Vec<(u32, u32)>);
+
+impl NetProperty for NetDesktopViewport {
+    fn name(atoms: &Atoms) -> Atom {
+        atoms.net_desktop_viewport
+    }
+
+    fn encode(&self, _: &Atoms) -> PropertyData {
+        let v: Vec<u32> = self.0.iter().flat_map(|&(x, y)| vec![x, y]).collect();
+        cardinals(xproto::ATOM_CARDINAL, &v)
+    }
+
+    fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<NetDesktopViewport> {
+        values(reply, 0, 2).map(|v| NetDesktopViewport(v.chunks(2).map(|c| (c[0], c[1])).collect()))
+    }
+}
+
+value_property!(
+    /// `_NET_CURRENT_DESKTOP`, the index of the current desktop.
+    NetCurrentDesktop(u32), net_current_desktop, xproto::ATOM_CARDINAL);
+
+/// `_NET_DESKTOP_NAMES`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetDesktopNames(pub Vec<String>);
+
+impl NetProperty for NetDesktopNames {
+    fn name(atoms: &Atoms) -> Atom {
+        atoms.net_desktop_names
+    }
+
+    fn encode(&self, atoms: &Atoms) -> PropertyData {
+        let mut text = String::new();
+        for name in &self.0 {
+            text.push_str(name);
+            text.push('\0');
+        }
+
+        utf8(atoms, &text)
+    }
+
+    fn decode(atoms: &Atoms, reply: &GetPropertyReply) -> Option<NetDesktopNames> {
+        if reply.format != 8 || reply.type_ != atoms.utf8_string {
+            return None;
+        }
+
+        let value = reply.value.strip_suffix(b"\0").unwrap_or(&reply.value);
+        if value.is_empty() {
+            return Some(NetDesktopNames(Vec::new()));
+        }
+
+        Some(NetDesktopNames(value.split(|&b| b == 0)
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .collect()))
+    }
+}
+
+value_property!(
+    /// `_NET_ACTIVE_WINDOW`, the window with the focus or zero.
+    NetActiveWindow(Window), net_active_window, xproto::ATOM_WINDOW);
+
+rects_property!(
+    /// `_NET_WORKAREA`, the area of each desktop not covered by docks.
+    NetWorkarea, net_workarea);
+
+value_property!(
+    /// `_NET_SUPPORTING_WM_CHECK`, a child window of the window manager, set
+    /// on the root and on the child itself.
+    NetSupportingWmCheck(Window), net_supporting_wm_check, xproto::ATOM_WINDOW);
+
+list_property!(
+    /// `_NET_VIRTUAL_ROOTS`.
+    NetVirtualRoots(Window), net_virtual_roots, xproto::ATOM_WINDOW);
+
+/// Direction in which desktops are numbered in a `NetDesktopLayout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Horizontal = 0,
+    Vertical = 1,
+}
+
+/// Corner where desktop zero is in a `NetDesktopLayout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft = 0,
+    TopRight = 1,
+    BottomRight = 2,
+    BottomLeft = 3,
+}
+
+/// `_NET_DESKTOP_LAYOUT`, set by pagers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetDesktopLayout {
+    pub orientation: Orientation,
+    pub columns: u32,
+    pub rows: u32,
+    pub starting_corner: Corner,
+}
+
+impl NetProperty for NetDesktopLayout {
+    fn name(atoms: &Atoms) -> Atom {
+        atoms.net_desktop_layout
+    }
+
+    fn encode(&self, _: &Atoms) -> PropertyData {
+        cardinals(xproto::ATOM_CARDINAL,
+                  &[self.orientation as u32, self.columns, self.rows, self.starting_corner as u32])
+    }
+
+    fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<NetDesktopLayout> {
+        // The starting corner is optional.
+        let mut v = values(reply, 3, 1)?;
+        v.resize(4, 0);
+
+        Some(NetDesktopLayout {
+            orientation: if v[0] == 1 {
+                Orientation::Vertical
+            } else {
+                Orientation::Horizontal
+            },
+            columns: v[1],
+            rows: v[2],
+            starting_corner: match v[3] {
+                1 => Corner::TopRight,
+                2 => Corner::BottomRight,
+                3 => Corner::BottomLeft,
+                _ => Corner::TopLeft,
+            },
+        })
+    }
+}
+
+/// `_NET_SHOWING_DESKTOP`, whether the window manager is in "showing the
+/// desktop" mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetShowingDesktop(pub bool);
+
+impl NetProperty for NetShowingDesktop {
+    fn name(atoms: &Atoms) -> Atom {
+        atoms.net_showing_desktop
+    }
+
+    fn encode(&self, _: &Atoms) -> PropertyData {
+        cardinals(xproto::ATOM_CARDINAL, &[self.0 as u32])
+    }
+
+    fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<NetShowingDesktop> {
+        values(reply, 1, 1).map(|v| NetShowingDesktop(v[0] != 0))
+    }
+}
+
+text_property!(
+    /// `_NET_WM_NAME`, the window title in UTF-8.
+    NetWmName, net_wm_name);
+
+text_property!(
+    /// `_NET_WM_VISIBLE_NAME`, the title displayed by the window manager.
+    NetWmVisibleName, net_wm_visible_name);
+
+text_property!(
+    /// `_NET_WM_ICON_NAME`.
+    NetWmIconName, net_wm_icon_name);
+
+text_property!(
+    /// `_NET_WM_VISIBLE_ICON_NAME`.
+    NetWmVisibleIconName, net_wm_visible_icon_name);
+
+/// Value of `NetWmDesktop` for windows visible on all desktops.
+pub const ALL_DESKTOPS: u32 = 0xFFFFFFFF;
+
+value_property!(
+    /// `_NET_WM_DESKTOP`, the desktop of the window or `ALL_DESKTOPS`.
+    NetWmDesktop(u32), net_wm_desktop, xproto::ATOM_CARDINAL);
+
+list_property!(
+    /// `_NET_WM_WINDOW_TYPE`, the functional type of the window in order of
+    /// preference, one of the `net_wm_window_type_*` atoms.
+    NetWmWindowType(Atom), net_wm_window_type, xproto::ATOM_ATOM);
+
+list_property!(
+    /// `_NET_WM_STATE`, a list of `net_wm_state_*` atoms.
+    NetWmState(Atom), net_wm_state, xproto::ATOM_ATOM);
+
+list_property!(
+    /// `_NET_WM_ALLOWED_ACTIONS`, a list of `net_wm_action_*` atoms.
+    NetWmAllowedActions(Atom), net_wm_allowed_actions, xproto::ATOM_ATOM);
+
+extents_property!(
+    /// `_NET_WM_STRUT`, space reserved at the edges of the screen.
+    NetWmStrut, net_wm_strut);
+
+/// `_NET_WM_STRUT_PARTIAL`, space reserved at the edges of the screen with
+/// the range of each edge it applies to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NetWmStrutPartial {
+    pub left: u32,
+    pub right: u32,
+    pub top: u32,
+    pub bottom: u32,
+    pub left_start_y: u32,
+    pub left_end_y: u32,
+    pub right_start_y: u32,
+    pub right_end_y: u32,
+    pub top_start_x: u32,
+    pub top_end_x: u32,
+    pub bottom_start_x: u32,
+    pub bottom_end_x: u32,
+}
+
+impl NetProperty for NetWmStrutPartial {
+    fn name(atoms: &Atoms) -> Atom {
+        atoms.net_wm_strut_partial
+    }
+
+    fn encode(&self, _: &Atoms) -> PropertyData {
+        cardinals(xproto::ATOM_CARDINAL,
+                  &[self.left,
+                    self.right,
+                    self.top,
+                    self.bottom,
+                    self.left_start_y,
+                    self.left_end_y,
+                    self.right_start_y,
+                    self.right_end_y,
+                    self.top_start_x,
+                    self.top_end_x,
+                    self.bottom_start_x,
+                    self.bottom_end_x])
+    }
+
+    fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<NetWmStrutPartial> {
+        values(reply, 12, 1).map(|v| {
+            NetWmStrutPartial {
+                left: v[0],
+                right: v[1],
+                top: v[2],
+                bottom: v[3],
+                left_start_y: v[4],
+                left_end_y: v[5],
+                right_start_y: v[6],
+                right_end_y: v[7],
+                top_start_x: v[8],
+                top_end_x: v[9],
+                bottom_start_x: v[10],
+                bottom_end_x: v[11],
+            }
+        })
+    }
+}
+
+/// `_NET_WM_ICON_GEOMETRY`, where the window is iconified to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetWmIconGeometry(pub Rect);
+
+impl NetProperty for NetWmIconGeometry {
+    fn name(atoms: &Atoms) -> Atom {
+        atoms.net_wm_icon_geometry
+    }
+
+    fn encode(&self, _: &Atoms) -> PropertyData {
+        cardinals(xproto::ATOM_CARDINAL, &self.0.to_values())
+    }
+
+    fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<NetWmIconGeometry> {
+        values(reply, 4, 1).map(|v| NetWmIconGeometry(Rect::from_values(&v)))
+    }
+}
+
+/// An icon image, `data` has `width * height` pixels in ARGB order
+/// (`0xAARRGGBB`), row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Icon {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u32>,
+}
+
+/// `_NET_WM_ICON`, icons of different sizes for the window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetWmIcon(pub Vec<Icon>);
+
+impl NetProperty for NetWmIcon {
+    fn name(atoms: &Atoms) -> Atom {
+        atoms.net_wm_icon
+    }
+
+    fn encode(&self, _: &Atoms) -> PropertyData {
+        let mut v = Vec::new();
+        for icon in &self.0 {
+            v.push(icon.width);
+            v.push(icon.height);
+            v.extend_from_slice(&icon.data);
+        }
+
+        cardinals(xproto::ATOM_CARDINAL, &v)
+    }
+
+    fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<NetWmIcon> {
+        let v = values(reply, 0, 1)?;
+        let mut icons = Vec::new();
+        let mut rest = &v[..];
+
+        while rest.len() >= 2 {
+            let (width, height) = (rest[0], rest[1]);
+            let len = width as usize * height as usize;
+            if rest.len() - 2 < len {
+                break;
+            }
+
+            icons.push(Icon {
+                width,
+                height,
+                data: rest[2..2 + len].to_vec(),
+            });
+            rest = &rest[2 + len..];
+        }
+
+        Some(NetWmIcon(icons))
+    }
+}
+
+value_property!(
+    /// `_NET_WM_PID`, the process id of the client.
+    NetWmPid(u32), net_wm_pid, xproto::ATOM_CARDINAL);
+
+value_property!(
+    /// `_NET_WM_HANDLED_ICONS`, set by pagers that handle iconified windows.
+    NetWmHandledIcons(u32), net_wm_handled_icons, xproto::ATOM_CARDINAL);
+
+value_property!(
+    /// `_NET_WM_USER_TIME`, the time of the last user activity in the
+    /// window.
+    NetWmUserTime(Timestamp), net_wm_user_time, xproto::ATOM_CARDINAL);
+
+value_property!(
+    /// `_NET_WM_USER_TIME_WINDOW`, the window where `_NET_WM_USER_TIME` is
+    /// updated.
+    NetWmUserTimeWindow(Window), net_wm_user_time_window, xproto::ATOM_WINDOW);
+
+extents_property!(
+    /// `_NET_FRAME_EXTENTS`, the size of the window manager decorations.
+    NetFrameExtents, net_frame_extents);
+
+rects_property!(
+    /// `_NET_WM_OPAQUE_REGION`, the fully opaque parts of the window, in
+    /// window coordinates.
+    NetWmOpaqueRegion, net_wm_opaque_region);
+
+value_property!(
+    /// `_NET_WM_SYNC_REQUEST_COUNTER`, the SYNC counter the client updates
+    /// after handling a `_NET_WM_SYNC_REQUEST` message.
+    NetWmSyncRequestCounter(::sync::Counter), net_wm_sync_request_counter, xproto::ATOM_CARDINAL);
+
+value_property!(
+    /// `_NET_WM_BYPASS_COMPOSITOR`, 1 to ask for unredirection, 2 to ask
+    /// to stay composited.
+    NetWmBypassCompositor(u32), net_wm_bypass_compositor, xproto::ATOM_CARDINAL);
+
+/// Who is sending a request to the window manager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// Clients following an older version of the specification.
+    Legacy = 0,
+    Application = 1,
+    Pager = 2,
+}
+
+fn source_from_u32(value: u32) -> Source {
+    match value {
+        1 => Source::Application,
+        2 => Source::Pager,
+        _ => Source::Legacy,
+    }
+}
+
+/// Change of a `_NET_WM_STATE` request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateAction {
+    Remove = 0,
+    Add = 1,
+    Toggle = 2,
+}
+
+/// New geometry of a `_NET_MOVERESIZE_WINDOW` request, only the given
+/// values are changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MoveResize {
+    /// Gravity used to interpret the position, zero means the gravity in
+    /// `WM_NORMAL_HINTS`.
+    pub gravity: u32,
+
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+/// A client message sent to the root window asking the window manager for
+/// a change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetMessage {
+    ActiveWindow {
+        window: Window,
+        source: Source,
+        time: Timestamp,
+        current_active: Window,
+    },
+    WmState {
+        window: Window,
+        action: StateAction,
+        first: Atom,
+        second: Atom,
+        source: Source,
+    },
+    CloseWindow {
+        window: Window,
+        time: Timestamp,
+        source: Source,
+    },
+    MoveResizeWindow {
+        window: Window,
+        geometry: MoveResize,
+        source: Source,
+    },
+    /// The window of this message is the root window of the screen.
+    CurrentDesktop {
+        root: Window,
+        desktop: u32,
+        time: Timestamp,
+    },
+    WmDesktop {
+        window: Window,
+        desktop: u32,
+        source: Source,
+    },
+}
+
+impl NetMessage {
+    /// Encodes the message as a client message event.
+    pub fn to_event(&self, atoms: &Atoms) -> ClientMessageEvent {
+        match *self {
+            NetMessage::ActiveWindow { window, source, time, current_active } => {
+                ClientMessageEvent::new32(window,
+                                          atoms.net_active_window,
+                                          [source as u32, time, current_active.0, 0, 0])
+            }
+            NetMessage::WmState { window, action, first, second, source } => {
+                ClientMessageEvent::new32(window,
+                                          atoms.net_wm_state,
+                                          [action as u32, first.0, second.0, source as u32, 0])
+            }
+            NetMessage::CloseWindow { window, time, source } => {
+                ClientMessageEvent::new32(window,
+                                          atoms.net_close_window,
+                                          [time, source as u32, 0, 0, 0])
+            }
+            NetMessage::MoveResizeWindow { window, geometry, source } => {
+                let mut flags = geometry.gravity & 0xff | (source as u32) << 12;
+                let mut data = [0u32; 5];
+
+                if let Some(x) = geometry.x {
+                    flags |= 1 << 8;
+                    data[1] = x as u32;
+                }
+                if let Some(y) = geometry.y {
+                    flags |= 1 << 9;
+                    data[2] = y as u32;
+                }
+                if let Some(width) = geometry.width {
+                    flags |= 1 << 10;
+                    data[3] = width;
+                }
+                if let Some(height) = geometry.height {
+                    flags |= 1 << 11;
+                    data[4] = height;
+                }
+                data[0] = flags;
+
+                ClientMessageEvent::new32(window, atoms.net_moveresize_window, data)
+            }
+            NetMessage::CurrentDesktop { root, desktop, time } => {
+                ClientMessageEvent::new32(root,
+                                          atoms.net_current_desktop,
+                                          [desktop, time, 0, 0, 0])
+            }
+            NetMessage::WmDesktop { window, desktop, source } => {
+                ClientMessageEvent::new32(window,
+                                          atoms.net_wm_desktop,
+                                          [desktop, source as u32, 0, 0, 0])
+            }
+        }
+    }
+
+    /// Decodes a client message received on the root window.
+    pub fn from_event(atoms: &Atoms, event: &Event) -> Option<NetMessage> {
+        let ev = match *event {
+            Event::ClientMessage(ref ev) if ev.format == 32 => ev,
+            _ => return None,
+        };
+
+        let window = ev.window;
+        let d = ev.data32();
+
+        if ev.type_ == atoms.net_active_window {
+            Some(NetMessage::ActiveWindow {
+                window,
+                source: source_from_u32(d[0]),
+                time: d[1],
+                current_active: Window(d[2]),
+            })
+        } else if ev.type_ == atoms.net_wm_state {
+            let action = match d[0] {
+                0 => StateAction::Remove,
+                1 => StateAction::Add,
+                2 => StateAction::Toggle,
+                _ => return None,
+            };
+
+            Some(NetMessage::WmState {
+                window,
+                action,
+                first: Atom(d[1]),
+                second: Atom(d[2]),
+                source: source_from_u32(d[3]),
+            })
+        } else if ev.type_ == atoms.net_close_window {
+            Some(NetMessage::CloseWindow {
+                window,
+                time: d[0],
+                source: source_from_u32(d[1]),
+            })
+        } else if ev.type_ == atoms.net_moveresize_window {
+            let flags = d[0];
+            let flag = |bit: u32| flags & (1 << bit) != 0;
+
+            Some(NetMessage::MoveResizeWindow {
+                window,
+                geometry: MoveResize {
+                    gravity: flags & 0xff,
+                    x: if flag(8) { Some(d[1] as i32) } else { None },
+                    y: if flag(9) { Some(d[2] as i32) } else { None },
+                    width: if flag(10) { Some(d[3]) } else { None },
+                    height: if flag(11) { Some(d[4]) } else { None },
+                },
+                source: source_from_u32((flags >> 12) & 0xf),
+            })
+        } else if ev.type_ == atoms.net_current_desktop {
+            Some(NetMessage::CurrentDesktop {
+                root: window,
+                desktop: d[0],
+                time: d[1],
+            })
+        } else if ev.type_ == atoms.net_wm_desktop {
+            Some(NetMessage::WmDesktop {
+                window,
+                desktop: d[0],
+                source: source_from_u32(d[1]),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Sends `message` to the window manager through `root`.
+pub fn request(client: Client,
+               atoms: &Atoms,
+               root: Window,
+               message: &NetMessage)
+               -> ReplyFuture<()> {
+    let event = match message.to_event(atoms).encode() {
+        Ok(event) => event,
+        Err(e) => return Box::new(futures::failed::<_, io::Error>(e)),
+    };
+
+    client.perform(xproto::SendEvent {
+        propagate: false,
+        destination: root,
+        event_mask: xproto::EVENT_SUBSTRUCTURE_NOTIFY | xproto::EVENT_SUBSTRUCTURE_REDIRECT,
+        event,
+    })
+}
+
+/// Asks the window manager to activate `window`.
+pub fn request_active_window(client: Client,
+                             atoms: &Atoms,
+                             root: Window,
+                             window: Window,
+                             time: Timestamp)
+                             -> ReplyFuture<()> {
+    request(client,
+            atoms,
+            root,
+            &NetMessage::ActiveWindow {
+                window,
+                source: Source::Application,
+                time,
+                current_active: Window::NONE,
+            })
+}
+
+/// Asks the window manager to add, remove or toggle one or two states
+/// (`net_wm_state_*` atoms) of `window`, `second` may be
+/// `Atom::NONE`.
+pub fn request_wm_state(client: Client,
+                        atoms: &Atoms,
+                        root: Window,
+                        window: Window,
+                        action: StateAction,
+                        first: Atom,
+                        second: Atom)
+                        -> ReplyFuture<()> {
+    request(client,
+            atoms,
+            root,
+            &NetMessage::WmState {
+                window,
+                action,
+                first,
+                second,
+                source: Source::Application,
+            })
+}
+
+/// Asks the window manager to close `window`.
+pub fn request_close_window(client: Client,
+                            atoms: &Atoms,
+                            root: Window,
+                            window: Window,
+                            time: Timestamp)
+                            -> ReplyFuture<()> {
+    request(client,
+            atoms,
+            root,
+            &NetMessage::CloseWindow {
+                window,
+                time,
+                source: Source::Pager,
+            })
+}
+
+/// Asks the window manager to move and/or resize `window`.
+pub fn request_moveresize_window(client: Client,
+                                 atoms: &Atoms,
+                                 root: Window,
+                                 window: Window,
+                                 geometry: MoveResize)
+                                 -> ReplyFuture<()> {
+    request(client,
+            atoms,
+            root,
+            &NetMessage::MoveResizeWindow {
+                window,
+                geometry,
+                source: Source::Pager,
+            })
+}
@@ -0,0 +1,600 @@
+//! Typed access to the window properties defined by the ICCCM.
+//!
+//! Each property is a type implementing `WmProperty`, which knows how to
+//! encode itself to property data and decode itself from a `GetProperty`
+//! reply, they are set and read with `set` and `get`.
+
+use ::std::io;
+
+use ::futures;
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+
+use ::event::ClientMessageEvent;
+use ::event::Event;
+use ::protocol::ReplyFuture;
+use ::xproto;
+use ::xproto::Atom;
+use ::xproto::GetPropertyReply;
+use ::xproto::Pixmap;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+use ::xproto::WinGravity;
+use ::Client;
+
+/// Atoms used by the ICCCM properties that aren't predefined.
+#[derive(Debug, Clone, Copy)]
+pub struct Atoms {
+    pub wm_protocols: Atom,
+    pub wm_delete_window: Atom,
+    pub wm_take_focus: Atom,
+    pub utf8_string: Atom,
+    pub compound_text: Atom,
+}
+
+const ATOM_NAMES: [&[u8]; 5] = [b"WM_PROTOCOLS",
+                                b"WM_DELETE_WINDOW",
+                                b"WM_TAKE_FOCUS",
+                                b"UTF8_STRING",
+                                b"COMPOUND_TEXT"];
+
+impl Atoms {
+    /// Interns all the atoms.
+    pub fn intern(client: Client) -> ReplyFuture<Atoms> {
+        Box::new(xproto::intern_atoms(client, &ATOM_NAMES).map(|(client, atoms)| {
+            let atoms = Atoms {
+                wm_protocols: atoms[0],
+                wm_delete_window: atoms[1],
+                wm_take_focus: atoms[2],
+                utf8_string: atoms[3],
+                compound_text: atoms[4],
+            };
+
+            (client, atoms)
+        }))
+    }
+}
+
+/// The encoded data of a property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyData {
+    pub type_: Atom,
+    pub format: u8,
+    pub data: Vec<u8>,
+}
+
+/// A window property with a well known name and type.
+pub trait WmProperty: Sized {
+    /// The property name.
+    fn name(atoms: &Atoms) -> Atom;
+
+    fn encode(&self, atoms: &Atoms) -> PropertyData;
+
+    /// Decodes the property, `None` is returned if it doesn't exist or it
+    /// has an unexpected type or format.
+    fn decode(atoms: &Atoms, reply: &GetPropertyReply) -> Option<Self>;
+}
+
+/// Sets the property `P` of `window`.
+pub fn set<P: WmProperty>(client: Client,
+                          atoms: &Atoms,
+                          window: Window,
+                          value: &P)
+                          -> ReplyFuture<()> {
+    let data = value.encode(atoms);
+
+    client.perform(xproto::ChangeProperty {
+        mode: xproto::PropMode::Replace,
+        window,
+        property: P::name(atoms),
+        type_: data.type_,
+        format: data.format,
+        data: data.data,
+    })
+}
+
+/// Reads the property `P` of `window`.
+pub fn get<P: WmProperty + 'static>(client: Client,
+                                    atoms: &Atoms,
+                                    window: Window)
+                                    -> ReplyFuture<Option<P>> {
+    let atoms = *atoms;
+
+    Box::new(client.perform(xproto::GetProperty {
+            delete: false,
+            window,
+            property: P::name(&atoms),
+            type_: xproto::ATOM_ANY,
+            long_offset: 0,
+            long_length: u32::MAX / 4,
+        })
+        .map(move |(client, reply)| (client, P::decode(&atoms, &reply))))
+}
+
+fn to_bytes<T: Copy + Into<u32>>(values: &[T]) -> Vec<u8> {
+    let mut buf = vec![0u8; values.len() * 4];
+    for (chunk, &value) in buf.chunks_mut(4).zip(values) {
+        NativeEndian::write_u32(chunk, value.into());
+    }
+
+    buf
+}
+
+fn from_bytes(reply: &GetPropertyReply, min_len: usize) -> Option<Vec<u32>> {
+    if reply.format != 32 || reply.value.len() < min_len * 4 {
+        return None;
+    }
+
+    Some(reply.value32())
+}
+
+/// Encodes a text property as `STRING` if it only has Latin-1 characters,
+/// otherwise as `UTF8_STRING`.
+fn encode_text(atoms: &Atoms, text: &str) -> PropertyData {
+    if text.chars().all(|c| (c as u32) < 0x100) {
+        PropertyData {
+            type_: xproto::ATOM_STRING,
+            format: 8,
+            data: text.chars().map(|c| c as u8).collect(),
+        }
+    } else {
+        PropertyData {
+            type_: atoms.utf8_string,
+            format: 8,
+            data: text.as_bytes().to_vec(),
+        }
+    }
+}
+
+/// Decodes a text property, `COMPOUND_TEXT` is only decoded if it's plain
+/// ASCII.
+fn decode_text(atoms: &Atoms, reply: &GetPropertyReply) -> Option<String> {
+    if reply.format != 8 {
+        return None;
+    }
+
+    if reply.type_ == xproto::ATOM_STRING {
+        Some(reply.value.iter().map(|&b| b as char).collect())
+    } else if reply.type_ == atoms.utf8_string ||
+              (reply.type_ == atoms.compound_text && reply.value.is_ascii()) {
+        Some(String::from_utf8_lossy(&reply.value).into_owned())
+    } else {
+        None
+    }
+}
+
+/// `WM_NAME`, the window title.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WmName(pub String);
+
+impl WmProperty for WmName {
+    fn name(_: &Atoms) -> Atom {
+        xproto::ATOM_WM_NAME
+    }
+
+    fn encode(&self, atoms: &Atoms) -> PropertyData {
+        encode_text(atoms, &self.0)
+    }
+
+    fn decode(atoms: &Atoms, reply: &GetPropertyReply) -> Option<WmName> {
+        decode_text(atoms, reply).map(WmName)
+    }
+}
+
+/// `WM_CLIENT_MACHINE`, the name of the machine running the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WmClientMachine(pub String);
+
+impl WmProperty for WmClientMachine {
+    fn name(_: &Atoms) -> Atom {
+        xproto::ATOM_WM_CLIENT_MACHINE
+    }
+
+    fn encode(&self, atoms: &Atoms) -> PropertyData {
+        encode_text(atoms, &self.0)
+    }
+
+    fn decode(atoms: &Atoms, reply: &GetPropertyReply) -> Option<WmClientMachine> {
+        decode_text(atoms, reply).map(WmClientMachine)
+    }
+}
+
+/// `WM_CLASS`, used to lookup resources of the application.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WmClass {
+    pub instance: String,
+    pub class: String,
+}
+
+impl WmProperty for WmClass {
+    fn name(_: &Atoms) -> Atom {
+        xproto::ATOM_WM_CLASS
+    }
+
+    fn encode(&self, _: &Atoms) -> PropertyData {
+        let mut data = Vec::new();
+        data.extend_from_slice(self.instance.as_bytes());
+        data.push(0);
+        data.extend_from_slice(self.class.as_bytes());
+        data.push(0);
+
+        PropertyData {
+            type_: xproto::ATOM_STRING,
+            format: 8,
+            data,
+        }
+    }
+
+    fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<WmClass> {
+        if reply.format != 8 || reply.type_ != xproto::ATOM_STRING {
+            return None;
+        }
+
+        let mut parts = reply.value.split(|&b| b == 0);
+        let instance = parts.next().unwrap_or(&[]);
+        let class = parts.next().unwrap_or(&[]);
+
+        Some(WmClass {
+            instance: String::from_utf8_lossy(instance).into_owned(),
+            class: String::from_utf8_lossy(class).into_owned(),
+        })
+    }
+}
+
+/// `WM_TRANSIENT_FOR`, the window this one is a dialog of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WmTransientFor(pub Window);
+
+impl WmProperty for WmTransientFor {
+    fn name(_: &Atoms) -> Atom {
+        xproto::ATOM_WM_TRANSIENT_FOR
+    }
+
+    fn encode(&self, _: &Atoms) -> PropertyData {
+        PropertyData {
+            type_: xproto::ATOM_WINDOW,
+            format: 32,
+            data: to_bytes(&[self.0]),
+        }
+    }
+
+    fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<WmTransientFor> {
+        from_bytes(reply, 1).map(|values| WmTransientFor(Window(values[0])))
+    }
+}
+
+/// `WM_PROTOCOLS`, the protocols the client takes part in, like
+/// `WM_DELETE_WINDOW` and `WM_TAKE_FOCUS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WmProtocols(pub Vec<Atom>);
+
+impl WmProperty for WmProtocols {
+    fn name(atoms: &Atoms) -> Atom {
+        atoms.wm_protocols
+    }
+
+    fn encode(&self, _: &Atoms) -> PropertyData {
+        PropertyData {
+            type_: xproto::ATOM_ATOM,
+            format: 32,
+            data: to_bytes(&self.0),
+        }
+    }
+
+    fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<WmProtocols> {
+        from_bytes(reply, 0).map(|values| WmProtocols(values.into_iter().map(Atom).collect()))
+    }
+}
+
+const US_POSITION: u32 = 1;
+const US_SIZE: u32 = 2;
+const P_POSITION: u32 = 4;
+const P_SIZE: u32 = 8;
+const P_MIN_SIZE: u32 = 16;
+const P_MAX_SIZE: u32 = 32;
+const P_RESIZE_INC: u32 = 64;
+const P_ASPECT: u32 = 128;
+const P_BASE_SIZE: u32 = 256;
+const P_WIN_GRAVITY: u32 = 512;
+
+/// Whether a position or size was specified by the user or by the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintSource {
+    User,
+    Program,
+}
+
+/// `WM_NORMAL_HINTS`, of type `WM_SIZE_HINTS`, the size constraints of the
+/// window in its normal state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WmSizeHints {
+    pub position: Option<(HintSource, i32, i32)>,
+    pub size: Option<(HintSource, i32, i32)>,
+    pub min_size: Option<(i32, i32)>,
+    pub max_size: Option<(i32, i32)>,
+    pub resize_inc: Option<(i32, i32)>,
+
+    /// Minimum and maximum aspect ratios as numerator and denominator.
+    pub aspect: Option<((i32, i32), (i32, i32))>,
+
+    pub base_size: Option<(i32, i32)>,
+    pub win_gravity: Option<WinGravity>,
+}
+
+fn win_gravity_from_u32(value: u32) -> Option<WinGravity> {
+    match value {
+        0 => Some(WinGravity::Unmap),
+        1 => Some(WinGravity::NorthWest),
+        2 => Some(WinGravity::North),
+        3 => Some(WinGravity::NorthEast),
+        4 => Some(WinGravity::West),
+        5 => Some(WinGravity::Center),
+        6 => Some(WinGravity::East),
+        7 => Some(WinGravity::SouthWest),
+        8 => Some(WinGravity::South),
+        9 => Some(WinGravity::SouthEast),
+        10 => Some(WinGravity::Static),
+        _ => None,
+    }
+}
+
+impl WmProperty for WmSizeHints {
+    fn name(_: &Atoms) -> Atom {
+        xproto::ATOM_WM_NORMAL_HINTS
+    }
+
+    fn encode(&self, _: &Atoms) -> PropertyData {
+        let mut v = [0u32; 18];
+
+        if let Some((source, x, y)) = self.position {
+            v[0] |= if source == HintSource::User { US_POSITION } else { P_POSITION };
+            v[1] = x as u32;
+            v[2] = y as u32;
+        }
+        if let Some((source, width, height)) = self.size {
+            v[0] |= if source == HintSource::User { US_SIZE } else { P_SIZE };
+            v[3] = width as u32;
+            v[4] = height as u32;
+        }
+        if let Some((width, height)) = self.min_size {
+            v[0] |= P_MIN_SIZE;
+            v[5] = width as u32;
+            v[6] = height as u32;
+        }
+        if let Some((width, height)) = self.max_size {
+            v[0] |= P_MAX_SIZE;
+            v[7] = width as u32;
+            v[8] = height as u32;
+        }
+        if let Some((width, height)) = self.resize_inc {
+            v[0] |= P_RESIZE_INC;
+            v[9] = width as u32;
+            v[10] = height as u32;
+        }
+        if let Some(((min_num, min_den), (max_num, max_den))) = self.aspect {
+            v[0] |= P_ASPECT;
+            v[11] = min_num as u32;
+            v[12] = min_den as u32;
+            v[13] = max_num as u32;
+            v[14] = max_den as u32;
+        }
+        if let Some((width, height)) = self.base_size {
+            v[0] |= P_BASE_SIZE;
+            v[15] = width as u32;
+            v[16] = height as u32;
+        }
+        if let Some(gravity) = self.win_gravity {
+            v[0] |= P_WIN_GRAVITY;
+            v[17] = gravity as u32;
+        }
+
+        PropertyData {
+            type_: xproto::ATOM_WM_SIZE_HINTS,
+            format: 32,
+            data: to_bytes(&v),
+        }
+    }
+
+    fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<WmSizeHints> {
+        // Pre-ICCCM clients use a 15 element version without base size
+        // and gravity.
+        let mut v = from_bytes(reply, 15)?;
+        v.resize(18, 0);
+
+        let flags = v[0];
+        let pair = |flag: u32, i: usize| if flags & flag != 0 {
+            Some((v[i] as i32, v[i + 1] as i32))
+        } else {
+            None
+        };
+        let source = |user: u32, program: u32, i: usize| if flags & user != 0 {
+            Some((HintSource::User, v[i] as i32, v[i + 1] as i32))
+        } else if flags & program != 0 {
+            Some((HintSource::Program, v[i] as i32, v[i + 1] as i32))
+        } else {
+            None
+        };
+
+        Some(WmSizeHints {
+            position: source(US_POSITION, P_POSITION, 1),
+            size: source(US_SIZE, P_SIZE, 3),
+            min_size: pair(P_MIN_SIZE, 5),
+            max_size: pair(P_MAX_SIZE, 7),
+            resize_inc: pair(P_RESIZE_INC, 9),
+            aspect: pair(P_ASPECT, 11).and_then(|min| pair(P_ASPECT, 13).map(|max| (min, max))),
+            base_size: pair(P_BASE_SIZE, 15),
+            win_gravity: if flags & P_WIN_GRAVITY != 0 {
+                win_gravity_from_u32(v[17])
+            } else {
+                None
+            },
+        })
+    }
+}
+
+const INPUT_HINT: u32 = 1;
+const STATE_HINT: u32 = 2;
+const ICON_PIXMAP_HINT: u32 = 4;
+const ICON_WINDOW_HINT: u32 = 8;
+const ICON_POSITION_HINT: u32 = 16;
+const ICON_MASK_HINT: u32 = 32;
+const WINDOW_GROUP_HINT: u32 = 64;
+const URGENCY_HINT: u32 = 256;
+
+/// The state a window should be in when mapped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WmState {
+    Withdrawn = 0,
+    Normal = 1,
+    Iconic = 3,
+}
+
+/// `WM_HINTS`, additional hints to the window manager.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WmHints {
+    /// Whether the client relies on the window manager to get keyboard
+    /// input.
+    pub input: Option<bool>,
+
+    pub initial_state: Option<WmState>,
+    pub icon_pixmap: Option<Pixmap>,
+    pub icon_window: Option<Window>,
+    pub icon_position: Option<(i32, i32)>,
+    pub icon_mask: Option<Pixmap>,
+    pub window_group: Option<Window>,
+    pub urgent: bool,
+}
+
+impl WmProperty for WmHints {
+    fn name(_: &Atoms) -> Atom {
+        xproto::ATOM_WM_HINTS
+    }
+
+    fn encode(&self, _: &Atoms) -> PropertyData {
+        let mut v = [0u32; 9];
+
+        if let Some(input) = self.input {
+            v[0] |= INPUT_HINT;
+            v[1] = input as u32;
+        }
+        if let Some(state) = self.initial_state {
+            v[0] |= STATE_HINT;
+            v[2] = state as u32;
+        }
+        if let Some(pixmap) = self.icon_pixmap {
+            v[0] |= ICON_PIXMAP_HINT;
+            v[3] = pixmap.0;
+        }
+        if let Some(window) = self.icon_window {
+            v[0] |= ICON_WINDOW_HINT;
+            v[4] = window.0;
+        }
+        if let Some((x, y)) = self.icon_position {
+            v[0] |= ICON_POSITION_HINT;
+            v[5] = x as u32;
+            v[6] = y as u32;
+        }
+        if let Some(mask) = self.icon_mask {
+            v[0] |= ICON_MASK_HINT;
+            v[7] = mask.0;
+        }
+        if let Some(group) = self.window_group {
+            v[0] |= WINDOW_GROUP_HINT;
+            v[8] = group.0;
+        }
+        if self.urgent {
+            v[0] |= URGENCY_HINT;
+        }
+
+        PropertyData {
+            type_: xproto::ATOM_WM_HINTS,
+            format: 32,
+            data: to_bytes(&v),
+        }
+    }
+
+    fn decode(_: &Atoms, reply: &GetPropertyReply) -> Option<WmHints> {
+        // Pre-ICCCM clients use an 8 element version without the group.
+        let mut v = from_bytes(reply, 8)?;
+        v.resize(9, 0);
+
+        let flags = v[0];
+        let value = |flag: u32, i: usize| if flags & flag != 0 { Some(v[i]) } else { None };
+
+        Some(WmHints {
+            input: value(INPUT_HINT, 1).map(|input| input != 0),
+            initial_state: value(STATE_HINT, 2).and_then(|state| match state {
+                0 => Some(WmState::Withdrawn),
+                1 => Some(WmState::Normal),
+                3 => Some(WmState::Iconic),
+                _ => None,
+            }),
+            icon_pixmap: value(ICON_PIXMAP_HINT, 3).map(Pixmap),
+            icon_window: value(ICON_WINDOW_HINT, 4).map(Window),
+            icon_position: value(ICON_POSITION_HINT, 5).map(|x| (x as i32, v[6] as i32)),
+            icon_mask: value(ICON_MASK_HINT, 7).map(Pixmap),
+            window_group: value(WINDOW_GROUP_HINT, 8).map(Window),
+            urgent: flags & URGENCY_HINT != 0,
+        })
+    }
+}
+
+/// A `WM_PROTOCOLS` client message sent by the window manager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WmMessage {
+    /// The user asked to close `window`.
+    DeleteWindow { window: Window, time: Timestamp },
+
+    /// The client should set the input focus, if desired.
+    TakeFocus { window: Window, time: Timestamp },
+}
+
+impl WmMessage {
+    /// Decodes `event` if it's a `WM_PROTOCOLS` message.
+    pub fn from_event(atoms: &Atoms, event: &Event) -> Option<WmMessage> {
+        let ev = match *event {
+            Event::ClientMessage(ref ev) if ev.type_ == atoms.wm_protocols && ev.format == 32 => ev,
+            _ => return None,
+        };
+
+        let data = ev.data32();
+        if data[0] == atoms.wm_delete_window.0 {
+            Some(WmMessage::DeleteWindow {
+                window: ev.window,
+                time: data[1],
+            })
+        } else if data[0] == atoms.wm_take_focus.0 {
+            Some(WmMessage::TakeFocus {
+                window: ev.window,
+                time: data[1],
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Asks the client owning `window` to close it with `WM_DELETE_WINDOW`,
+/// the window should list it in its `WM_PROTOCOLS`.
+pub fn send_delete_window(client: Client,
+                          atoms: &Atoms,
+                          window: Window,
+                          time: Timestamp)
+                          -> ReplyFuture<()> {
+    let event = ClientMessageEvent::new32(window,
+                                          atoms.wm_protocols,
+                                          [atoms.wm_delete_window.0, time, 0, 0, 0]);
+
+    let event = match event.encode() {
+        Ok(event) => event,
+        Err(e) => return Box::new(futures::failed::<_, io::Error>(e)),
+    };
+
+    client.perform(xproto::SendEvent {
+        propagate: false,
+        destination: window,
+        event_mask: 0,
+        event,
+    })
+}
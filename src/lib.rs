@@ -39,11 +39,20 @@ extern crate tokio_io;
 extern crate futures;
 extern crate byteorder;
 extern crate xauth;
+extern crate libc;
+#[cfg(feature = "text")]
+extern crate ab_glyph;
 
 use std::io;
 use std::io::Read;
 use std::io::Write;
 use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::any::TypeId;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::io::RawFd;
+use std::sync::Arc;
+use std::sync::Mutex;
 
 use byteorder::ReadBytesExt;
 use byteorder::WriteBytesExt;
@@ -59,11 +68,24 @@ pub use xauth::Xauth;
 mod macros;
 mod utils;
 
+mod transport;
+mod negotiation;
+
 mod setup_error;
 pub use setup_error::*;
 
 pub mod protocol;
 
+pub mod xid;
+pub use xid::XidAllocator;
+
+pub mod resource;
+pub use resource::Owned;
+
+/// Number of ids requested with `XCMiscGetXIDList` when there isn't any
+/// free range.
+const XID_LIST_SIZE: u32 = 256;
+
 /// Function used to calculate pad for unused bytes.
 pub fn pad(e: usize) -> usize {
     ((4 - (e % 4)) % 4)
@@ -74,7 +96,25 @@ pub struct Client {
     socket: UnixStream,
     server_info: ServerInfo,
     extensions: HashMap<&'static [u8], protocol::ExtensionInfo>,
-    xid: XidData,
+
+    /// Versions agreed on with the version requests of the extensions.
+    versions: HashMap<&'static [u8], protocol::Version>,
+
+    xid: XidAllocator,
+    sequence: u16,
+    events: VecDeque<Vec<u8>>,
+
+    /// Replies read while waiting for the reply of another request, like
+    /// the next replies of a request with several ones.
+    replies: VecDeque<Vec<u8>>,
+
+    released: Arc<Mutex<resource::Released>>,
+
+    /// File descriptors sent with the next write.
+    fds_out: Vec<RawFd>,
+
+    /// File descriptors received and not yet taken by a reply.
+    fds_in: VecDeque<RawFd>,
 }
 
 impl Client {
@@ -139,29 +179,68 @@ impl Client {
                 }
             })
             .map(|(socket, server_info)| {
-                let xid = XidData::new(&server_info);
+                let xid = XidAllocator::new(server_info.resource_id_base,
+                                            server_info.resource_id_mask);
                 Client {
                     socket: socket,
                     server_info: server_info,
                     extensions: HashMap::new(),
+                    versions: HashMap::new(),
                     xid: xid,
+                    sequence: 0,
+                    events: VecDeque::new(),
+                    replies: VecDeque::new(),
+                    released: Arc::default(),
+                    fds_out: Vec::new(),
+                    fds_in: VecDeque::new(),
                 }
             })
             .boxed()
     }
 
+    /// Connects like `connect` and queries `extensions` with a single round
+    /// trip, so their first requests don't wait for `QueryExtension`.
+    pub fn connect_with_extensions<D>(display: D,
+                                      auth_info: &Xauth,
+                                      handle: Handle,
+                                      extensions: &[&'static [u8]])
+                                      -> Box<dyn Future<Item = Self, Error = SetupError>>
+        where D: Into<Option<u16>>
+    {
+        let extensions = extensions.to_vec();
+
+        Box::new(Client::connect(display, auth_info, handle).and_then(move |client| {
+            client.prefetch_extensions(&extensions)
+                .map(|(client, _)| client)
+                .map_err(SetupError::from)
+        }))
+    }
+
     pub fn perform<Req: protocol::Request>
-        (self,
+        (mut self,
          mut request: Req)
          -> Box<Future<Item = (Self, <Req as protocol::Request>::Reply), Error = io::Error>> {
-        let req_data = request.encode().unwrap();
+        let data = match request.encode().and_then(|data| self.check_length(data)) {
+            Ok(data) => data,
+            Err(e) => return Box::new(futures::failed(e)),
+        };
+        if let Err(e) = self.queue_fds(request.fds()) {
+            return Box::new(futures::failed(e));
+        }
+        let mut req_data = self.take_released();
+        req_data.extend(data);
+        self.sequence = self.sequence.wrapping_add(1);
         Box::new(tokio_io::io::write_all(self, req_data)
             .and_then(|(client, _)| Req::decode(client)))
     }
 
+    /// Performs an extension request, the extension is queried and its
+    /// version negotiated on its first request. Fails with
+    /// `protocol::Error::ExtensionNotPresent` without sending anything if the
+    /// server doesn't have the extension.
     pub fn perform_ex<Req: protocol::ExtensionRequest + 'static>
         (self,
-         mut request: Req)
+         request: Req)
          -> Box<Future<Item = (Self, <Req as protocol::ExtensionRequest>::Reply), Error = io::Error>> {
         let extension_name = Req::extension_name();
         let maybe_extension = {
@@ -169,114 +248,368 @@ impl Client {
         };
 
         if let Some(info) = maybe_extension {
-            let req_data = request.encode(&info).unwrap();
-            Box::new(tokio_io::io::write_all(self, req_data)
-                .and_then(|(client, _)| Req::decode(client)))
+            self.send_ex(info, request)
         } else {
             Box::new(self.perform(xproto::QueryExtension { name: extension_name.to_owned() })
                 .and_then(move |(mut client, info)| {
+                    // Missing extensions are kept too, to fail their next
+                    // requests without querying them again.
                     client.extensions.insert(extension_name, info);
-
-                    let req_data = request.encode(&info).unwrap();
-                    tokio_io::io::write_all(client, req_data)
-                        .and_then(|(client, _)| Req::decode(client))
+                    client.send_ex(info, request)
                 }))
         }
     }
 
-    /// Generates a XID.
-    pub fn generate_id(mut self) -> Box<Future<Item = (Client, xproto::Xid), Error = io::Error>> {
-        if self.xid.last >= (self.xid.max - self.xid.inc + 1) {
-            if self.xid.last == 0 {
-                self.xid.max = self.server_info.resource_id_mask;
-                let xid = self.xid.last | self.xid.base;
-                Box::new(futures::finished((self, xid)))
-            } else {
-                Box::new(self.perform_ex(xc_misc::XCMiscGetXIDRange)
-                    .and_then(|(mut client, range)| {
-                        if range.start_id == 0 && range.count == 1 {
-                            return Err(io::Error::new(io::ErrorKind::InvalidData,
-                                                      "Server is out of XIDs"));
-                        }
+    /// Sends an extension request once the extension was queried.
+    fn send_ex<Req: protocol::ExtensionRequest + 'static>(mut self,
+                                                         info: protocol::ExtensionInfo,
+                                                         mut request: Req)
+                                                         -> protocol::ReplyFuture<Req::Reply> {
+        let extension_name = Req::extension_name();
+        if !info.present {
+            let e = protocol::Error::ExtensionNotPresent(extension_name);
+            return Box::new(futures::failed(io::Error::new(io::ErrorKind::Unsupported, e)));
+        }
 
-                        client.xid.last = range.start_id;
-                        client.xid.max = range.start_id + (range.count - 1) * client.xid.inc;
+        // The version request is sent first, its reply is kept when the
+        // request is the version request itself.
+        let mut version_of = None;
+        if let Some(negotiator) = negotiation::find(extension_name) {
+            if negotiator.request == TypeId::of::<Req>() {
+                version_of = Some(negotiator.version);
+            } else if !self.versions.contains_key(extension_name) {
+                return Box::new((negotiator.negotiate)(self)
+                    .and_then(move |(client, _)| client.send_ex(info, request)));
+            }
+        }
 
-                        let xid = client.xid.last | client.xid.base;
-                        Ok((client, xid))
-                    }))
+        let data = match request.encode(&info).and_then(|data| self.check_length(data)) {
+            Ok(data) => data,
+            Err(e) => return Box::new(futures::failed(e)),
+        };
+        if let Err(e) = self.queue_fds(request.fds()) {
+            return Box::new(futures::failed(e));
+        }
+        let mut req_data = self.take_released();
+        req_data.extend(data);
+        self.sequence = self.sequence.wrapping_add(1);
+        Box::new(tokio_io::io::write_all(self, req_data)
+            .and_then(|(client, _)| Req::decode(client))
+            .map(move |(mut client, reply)| {
+                if let Some(version) = version_of.and_then(|version| version(&reply)) {
+                    client.versions.insert(extension_name, version);
+                }
+                (client, reply)
+            }))
+    }
+
+    /// Resolves to the version of an extension agreed on with `Req`, sent
+    /// only if the version wasn't negotiated yet. `perform_ex` negotiates it
+    /// before the first request of the extension, this is only needed to
+    /// check the version before that. Fails with
+    /// `protocol::Error::ExtensionNotPresent` if the server doesn't have the
+    /// extension.
+    pub fn negotiate<Req>(self) -> protocol::ReplyFuture<protocol::Version>
+        where Req: protocol::VersionRequest + 'static
+    {
+        let extension_name = Req::extension_name();
+        if let Some(&version) = self.versions.get(extension_name) {
+            return Box::new(futures::finished((self, version)));
+        }
+
+        Box::new(self.perform_ex(Req::latest()).map(move |(mut client, reply)| {
+            let version = Req::version(&reply);
+            client.versions.insert(extension_name, version);
+            (client, version)
+        }))
+    }
+
+    /// Sends all of `requests` in a single write without waiting for their
+    /// replies. Resolves to the sequence number of the first request, the
+    /// replies must be read in order with `protocol::read_reply_to`.
+    pub fn send_all<Req: protocol::Request>(mut self,
+                                            requests: Vec<Req>)
+                                            -> protocol::ReplyFuture<u16> {
+        let mut data = self.take_released();
+        let first = self.sequence.wrapping_add(1);
+        for mut request in requests {
+            if let Err(e) = self.queue_fds(request.fds()) {
+                return Box::new(futures::failed(e));
             }
-        } else {
-            self.xid.last += self.xid.inc;
-            let xid = self.xid.last | self.xid.base;
-            Box::new(futures::finished((self, xid)))
+            match request.encode().and_then(|req_data| self.check_length(req_data)) {
+                Ok(req_data) => data.extend(req_data),
+                Err(e) => return Box::new(futures::failed(e)),
+            }
+            self.sequence = self.sequence.wrapping_add(1);
+        }
+
+        Box::new(tokio_io::io::write_all(self, data).map(move |(client, _)| (client, first)))
+    }
+
+    /// Fails with `io::ErrorKind::InvalidInput` if an encoded request is
+    /// longer than the server accepts.
+    fn check_length(&self, data: Vec<u8>) -> io::Result<Vec<u8>> {
+        let maximum = self.server_info.maximum_request_length as usize * 4;
+        if data.len() > maximum {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      format!("request of {} bytes is longer than the {} bytes \
+                                               accepted by the server",
+                                              data.len(),
+                                              maximum)));
+        }
+
+        Ok(data)
+    }
+
+    /// Takes the requests freeing the dropped `Owned` resources, to be sent
+    /// before the next request, and returns their ids to the allocator.
+    fn take_released(&mut self) -> Vec<u8> {
+        let (data, count, ids) = self.released.lock().unwrap().take();
+        self.sequence = self.sequence.wrapping_add(count);
+        self.xid.add_ids(&ids);
+        data
+    }
+
+    /// Queues file descriptors to be sent with the next write, they are
+    /// duplicated so the caller keeps owning them.
+    fn queue_fds(&mut self, fds: Vec<RawFd>) -> io::Result<()> {
+        for fd in fds {
+            self.fds_out.push(transport::dup(fd)?);
+        }
+
+        Ok(())
+    }
+
+    /// Takes `count` file descriptors received with a reply, the caller owns
+    /// them.
+    pub(crate) fn take_fds(&mut self, count: usize) -> io::Result<Vec<RawFd>> {
+        if self.fds_in.len() < count {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                      "the reply didn't carry the expected file descriptors"));
+        }
+
+        Ok(self.fds_in.drain(..count).collect())
+    }
+
+    /// Generates a XID without contacting the server, `None` is returned
+    /// when all the ids are used, then `reclaim_ids` must be called or
+    /// `next_id` used instead.
+    pub fn generate_id<T: From<xproto::Xid>>(&self) -> Option<T> {
+        self.xid.allocate().map(T::from)
+    }
+
+    /// Returns `xid` to be reused by `generate_id`. It must be called only
+    /// after the request freeing the resource (like `FreePixmap` or
+    /// `DestroyWindow`) was sent.
+    pub fn free_id<T: Into<xproto::Xid>>(&self, id: T) {
+        self.xid.free(id.into())
+    }
+
+    /// Asks the server for the ids no longer in use, with the XC-MISC
+    /// extension. Resolves to the number of ids available.
+    pub fn reclaim_ids(self) -> protocol::ReplyFuture<u64> {
+        Box::new(self.perform_ex(xc_misc::XCMiscGetXIDRange)
+            .and_then(|(client, range)| -> protocol::ReplyFuture<u64> {
+                // Some servers return a single zero id instead of an empty
+                // range.
+                if range.count > 0 && !(range.start_id == 0 && range.count == 1) {
+                    client.xid.add_range(range.start_id, range.count);
+                    let available = client.xid.available();
+                    return Box::new(futures::finished((client, available)));
+                }
+
+                // No contiguous range left, ask for scattered ids.
+                Box::new(client.perform_ex(xc_misc::XCMiscGetXIDList { count: XID_LIST_SIZE })
+                    .map(|(client, ids)| {
+                        client.xid.add_ids(&ids);
+                        let available = client.xid.available();
+                        (client, available)
+                    }))
+            })
+            .and_then(|(client, available)| {
+                if available == 0 {
+                    Err(io::Error::other("Server is out of XIDs"))
+                } else {
+                    Ok((client, available))
+                }
+            }))
+    }
+
+    /// Generates a XID, reclaiming unused ids from the server if needed.
+    pub fn next_id<T: From<xproto::Xid> + 'static>(self) -> protocol::ReplyFuture<T> {
+        if let Some(xid) = self.generate_id() {
+            return Box::new(futures::finished((self, xid)));
         }
+
+        Box::new(self.reclaim_ids().and_then(|(client, _)| match client.generate_id() {
+            Some(xid) => Ok((client, xid)),
+            None => Err(io::Error::other("No XIDs left")),
+        }))
     }
 
     /// Returns the server information structure.
     pub fn get_server_info(&self) -> &ServerInfo {
         &self.server_info
     }
-}
 
-impl Read for Client {
-    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.socket.read(buf)
+    /// Queries all of `names` not known yet with a single round trip, instead
+    /// of one on the first request of each extension. Resolves to their
+    /// information, in the same order.
+    pub fn prefetch_extensions(self,
+                               names: &[&'static [u8]])
+                               -> protocol::ReplyFuture<Vec<protocol::ExtensionInfo>> {
+        let names = names.to_vec();
+        let missing: Vec<&'static [u8]> = names.iter()
+            .cloned()
+            .filter(|name| !self.extensions.contains_key(name))
+            .collect();
+
+        let queried: protocol::ReplyFuture<Vec<protocol::ExtensionInfo>> = if missing.is_empty() {
+            Box::new(futures::finished((self, Vec::new())))
+        } else {
+            xproto::query_extensions(self, &missing)
+        };
+
+        Box::new(queried.map(move |(mut client, infos)| {
+            for (name, info) in missing.into_iter().zip(infos) {
+                client.extensions.insert(name, info);
+            }
+
+            let infos = names.iter().map(|name| client.extensions[name]).collect();
+            (client, infos)
+        }))
     }
-}
 
-impl Write for Client {
-    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.socket.write(buf)
+    /// Returns the information of all the extensions queried, including the
+    /// ones the server doesn't have.
+    pub fn extensions(&self) -> &HashMap<&'static [u8], protocol::ExtensionInfo> {
+        &self.extensions
     }
 
-    fn flush(&mut self) -> io::Result<()> {
-        self.socket.flush()
+    /// Returns the information of an extension already queried, which
+    /// happens on its first request. Used to decode the extension events.
+    pub fn extension_info(&self, name: &[u8]) -> Option<protocol::ExtensionInfo> {
+        self.extensions.get(name).cloned()
     }
-}
 
-impl tokio_io::AsyncRead for Client {}
-impl tokio_io::AsyncWrite for Client {
-    fn shutdown(&mut self) -> futures::Poll<(), io::Error> {
-        tokio_io::AsyncWrite::shutdown(&mut self.socket)
+    /// Returns the version of an extension agreed on, `None` if it wasn't
+    /// used yet.
+    pub fn extension_version(&self, name: &[u8]) -> Option<protocol::Version> {
+        self.versions.get(name).cloned()
+    }
+
+    /// Removes the queued error of the request with the given sequence
+    /// number, used to check a request without reply after a round trip.
+    pub(crate) fn take_error(&mut self, sequence: u16) -> Option<protocol::Error> {
+        let position = self.events.iter().position(|packet| {
+            packet[0] == protocol::ERROR &&
+            protocol::Error::read(packet).sequence_number() == Some(sequence)
+        });
+
+        position.and_then(|i| self.events.remove(i)).map(|packet| protocol::Error::read(&packet))
+    }
+
+    /// Returns the next event (or error of a request without reply) sent by
+    /// the server, waiting for one if none is queued.
+    pub fn next_event(mut self) -> protocol::ReplyFuture<event::Event> {
+        if let Some(packet) = self.events.pop_front() {
+            return Box::new(futures::finished((self, event::Event::read(&packet))));
+        }
+
+        Box::new(protocol::read_packet(self)
+            .map(|(client, packet)| (client, event::Event::read(&packet))))
+    }
+
+    /// Waits for the first event matching `pred`, queued events are checked
+    /// first. Events not matching are kept, in order, to be returned by
+    /// `next_event` later.
+    pub fn wait_for_event<F>(mut self, mut pred: F) -> protocol::ReplyFuture<event::Event>
+        where F: FnMut(&event::Event) -> bool + 'static
+    {
+        let position = self.events.iter().position(|packet| pred(&event::Event::read(packet)));
+        if let Some(i) = position {
+            let packet = self.events.remove(i).unwrap();
+            return Box::new(futures::finished((self, event::Event::read(&packet))));
+        }
+
+        Box::new(futures::future::loop_fn((self, pred, Vec::new()),
+                                          |(client, mut pred, mut skipped)| {
+            protocol::read_packet(client).map(move |(mut client, packet)| {
+                let ev = event::Event::read(&packet);
+                if pred(&ev) {
+                    client.events.extend(skipped);
+                    futures::future::Loop::Break((client, ev))
+                } else {
+                    skipped.push(packet);
+                    futures::future::Loop::Continue((client, pred, skipped))
+                }
+            })
+        }))
     }
 }
 
-impl<'a> Read for &'a Client {
+impl Read for Client {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        (&self.socket).read(buf)
+        if let futures::Async::NotReady = self.socket.poll_read() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        match transport::recv_with_fds(self.socket.as_raw_fd(), buf, &mut self.fds_in) {
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.socket.need_read();
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+            result => result,
+        }
     }
 }
 
-impl<'a> Write for &'a Client {
+impl Write for Client {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        (&self.socket).write(buf)
+        if self.fds_out.is_empty() {
+            return self.socket.write(buf);
+        }
+
+        if let futures::Async::NotReady = self.socket.poll_write() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        match transport::send_with_fds(self.socket.as_raw_fd(), buf, &self.fds_out) {
+            Ok(n) => {
+                for fd in self.fds_out.drain(..) {
+                    transport::close(fd);
+                }
+                Ok(n)
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.socket.need_write();
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+            Err(e) => Err(e),
+        }
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        (&self.socket).flush()
+        self.socket.flush()
     }
 }
 
-
-struct XidData {
-    pub last: u32,
-    pub max: u32,
-    pub base: u32,
-    pub inc: u32,
+impl tokio_io::AsyncRead for Client {}
+impl tokio_io::AsyncWrite for Client {
+    fn shutdown(&mut self) -> futures::Poll<(), io::Error> {
+        tokio_io::AsyncWrite::shutdown(&mut self.socket)
+    }
 }
 
-impl XidData {
-    pub fn new(server_info: &ServerInfo) -> XidData {
-        XidData {
-            last: 0,
-            max: 0,
-            base: server_info.resource_id_base,
-            inc: server_info.resource_id_mask & !server_info.resource_id_mask,
+impl Drop for Client {
+    fn drop(&mut self) {
+        for fd in self.fds_out.drain(..).chain(self.fds_in.drain(..)) {
+            transport::close(fd);
         }
     }
 }
 
+
 /// Information received if the connection is accepted.
 #[derive(Debug)]
 pub struct ServerInfo {
@@ -482,8 +815,8 @@ impl From<u8> for BackingStores {
 
 #[derive(Debug)]
 pub struct Screen {
-    pub root: u32,
-    pub default_colormap: u32,
+    pub root: xproto::Window,
+    pub default_colormap: xproto::Colormap,
     pub white_pixel: u32,
     pub black_pixel: u32,
     pub current_input_masks: u32,
@@ -493,7 +826,7 @@ pub struct Screen {
     pub height_millimeters: u16,
     pub min_installed_maps: u16,
     pub max_installed_maps: u16,
-    pub root_visual: u32,
+    pub root_visual: xproto::Visualid,
     pub backing_stores: BackingStores,
     pub save_unders: bool,
     pub root_depth: u8,
@@ -525,8 +858,8 @@ impl Screen {
         }
 
         Ok(Screen {
-            root: root,
-            default_colormap: default_colormap,
+            root: xproto::Window(root),
+            default_colormap: xproto::Colormap(default_colormap),
             white_pixel: white_pixel,
             black_pixel: black_pixel,
             current_input_masks: current_input_masks,
@@ -536,7 +869,7 @@ impl Screen {
             height_millimeters: height_millimeters,
             min_installed_maps: min_installed_maps,
             max_installed_maps: max_installed_maps,
-            root_visual: root_visual,
+            root_visual: xproto::Visualid(root_visual),
             backing_stores: BackingStores::from(backing_stores),
             save_unders: save_unders,
             root_depth: root_depth,
@@ -596,7 +929,7 @@ impl From<u8> for VisualClass {
 
 #[derive(Debug)]
 pub struct Visual {
-    pub id: u32,
+    pub id: xproto::Visualid,
     pub class: VisualClass,
     pub bits_per_rgb_value: u8,
     pub colormap_entries: u16,
@@ -617,7 +950,7 @@ impl Visual {
         try!(a.read_u32::<NativeEndian>());
 
         Ok(Visual {
-            id: id,
+            id: xproto::Visualid(id),
             class: VisualClass::from(class),
             bits_per_rgb_value: bits_per_rgb_value,
             colormap_entries: colormap_entries,
@@ -628,5 +961,30 @@ impl Visual {
     }
 }
 
+pub mod event;
 pub mod xproto;
 pub mod xc_misc;
+pub mod randr;
+pub mod render;
+pub mod shm;
+pub mod xfixes;
+pub mod damage;
+pub mod composite;
+pub mod shape;
+pub mod xinput;
+pub mod xkb;
+pub mod xtest;
+pub mod sync;
+pub mod present;
+pub mod screensaver;
+pub mod dpms;
+pub mod xinerama;
+pub mod record;
+pub mod xres;
+pub mod dbe;
+#[cfg(feature = "text")]
+pub mod text;
+pub mod clipboard;
+pub mod monitors;
+pub mod icccm;
+pub mod ewmh;
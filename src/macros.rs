@@ -7,3 +7,31 @@ macro_rules! try_future {
         }
     }
 }
+
+/// Declares newtypes of protocol ids, converted from and to `Xid`.
+macro_rules! declare_ids {
+    ($($(#[$attr:meta])* $id:ident),+) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+            pub struct $id(pub ::xproto::Xid);
+
+            impl $id {
+                /// The `None` value of the protocol.
+                pub const NONE: $id = $id(0);
+            }
+
+            impl From<::xproto::Xid> for $id {
+                fn from(xid: ::xproto::Xid) -> $id {
+                    $id(xid)
+                }
+            }
+
+            impl From<$id> for ::xproto::Xid {
+                fn from(id: $id) -> ::xproto::Xid {
+                    id.0
+                }
+            }
+        )+
+    }
+}
@@ -0,0 +1,216 @@
+//! The list of monitors of a screen, from the best source the server
+//! supports.
+//!
+//! RANDR 1.5 monitors are used first, then the Xinerama screens, and the
+//! whole X screen as a single monitor if none of them is available.
+
+use ::futures;
+use ::futures::Future;
+
+use ::protocol::ExtensionInfo;
+use ::protocol::ReplyFuture;
+use ::protocol::Version;
+use ::randr;
+use ::randr::MonitorInfo;
+use ::randr::RRGetMonitors;
+use ::randr::RRQueryVersion;
+use ::xinerama;
+use ::xinerama::XineramaIsActive;
+use ::xinerama::XineramaQueryScreens;
+use ::xproto::Atom;
+use ::xproto::QueryExtension;
+use ::xproto::Rectangle;
+use ::xproto::Window;
+use ::Client;
+use ::Screen;
+
+/// Where the geometry of a `Monitor` comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorSource {
+    RandR,
+    Xinerama,
+
+    /// The `Screen` of the server information.
+    Core,
+}
+
+/// A rectangle of the screen shown by a monitor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monitor {
+    /// The name of a RANDR monitor, `Atom::NONE` for the other sources.
+    pub name: Atom,
+
+    /// The RANDR primary monitor, or the first monitor of the other
+    /// sources.
+    pub primary: bool,
+
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+
+    /// Physical size, zero if unknown.
+    pub width_mm: u32,
+    pub height_mm: u32,
+
+    pub source: MonitorSource,
+}
+
+impl Monitor {
+    pub fn from_randr(info: &MonitorInfo) -> Monitor {
+        Monitor {
+            name: info.name,
+            primary: info.primary,
+            x: info.x,
+            y: info.y,
+            width: info.width,
+            height: info.height,
+            width_mm: info.width_mm,
+            height_mm: info.height_mm,
+            source: MonitorSource::RandR,
+        }
+    }
+
+    /// The monitor of the Xinerama screen at `index`.
+    pub fn from_xinerama(index: usize, screen: &Rectangle) -> Monitor {
+        Monitor {
+            name: Atom::NONE,
+            primary: index == 0,
+            x: screen.x,
+            y: screen.y,
+            width: screen.width,
+            height: screen.height,
+            width_mm: 0,
+            height_mm: 0,
+            source: MonitorSource::Xinerama,
+        }
+    }
+
+    /// The whole X screen as a single monitor.
+    pub fn from_screen(screen: &Screen) -> Monitor {
+        Monitor {
+            name: Atom::NONE,
+            primary: true,
+            x: 0,
+            y: 0,
+            width: screen.width_pixels,
+            height: screen.height_pixels,
+            width_mm: screen.width_millimeters as u32,
+            height_mm: screen.height_millimeters as u32,
+            source: MonitorSource::Core,
+        }
+    }
+
+    pub fn rectangle(&self) -> Rectangle {
+        Rectangle {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Whether the point is on the monitor.
+    pub fn contains(&self, x: i16, y: i16) -> bool {
+        let (x, y) = (x as i32, y as i32);
+        x >= self.x as i32 && x < self.x as i32 + self.width as i32 &&
+        y >= self.y as i32 && y < self.y as i32 + self.height as i32
+    }
+}
+
+/// Returns the monitor showing a point, or the primary one if the point is
+/// outside all of them.
+pub fn monitor_at(monitors: &[Monitor], x: i16, y: i16) -> Option<&Monitor> {
+    monitors.iter()
+        .find(|monitor| monitor.contains(x, y))
+        .or_else(|| monitors.iter().find(|monitor| monitor.primary))
+        .or_else(|| monitors.first())
+}
+
+/// Lists the monitors of the screen of `root`, there is always at least
+/// one.
+pub fn monitors(client: Client, root: Window) -> ReplyFuture<Vec<Monitor>> {
+    Box::new(randr_monitors(client, root)
+        .and_then(|(client, monitors)| -> ReplyFuture<Vec<Monitor>> {
+            if !monitors.is_empty() {
+                return Box::new(futures::finished((client, monitors)));
+            }
+
+            Box::new(xinerama_monitors(client))
+        })
+        .map(move |(client, monitors)| {
+            if !monitors.is_empty() {
+                return (client, monitors);
+            }
+
+            let monitors = client.get_server_info()
+                .roots
+                .iter()
+                .find(|screen| screen.root == root)
+                .map(Monitor::from_screen)
+                .into_iter()
+                .collect();
+            (client, monitors)
+        }))
+}
+
+/// The active RANDR monitors, empty if RANDR 1.5 isn't supported.
+fn randr_monitors(client: Client, root: Window) -> ReplyFuture<Vec<Monitor>> {
+    Box::new(query_extension(client, randr::EXTENSION_NAME)
+        .and_then(|(client, info)| -> ReplyFuture<bool> {
+            if !info.present {
+                return Box::new(futures::finished((client, false)));
+            }
+
+            Box::new(client.negotiate::<RRQueryVersion>()
+                .map(|(client, version)| (client, version >= Version::new(1, 5))))
+        })
+        .and_then(move |(client, supported)| -> ReplyFuture<Vec<Monitor>> {
+            if !supported {
+                return Box::new(futures::finished((client, Vec::new())));
+            }
+
+            Box::new(client.perform_ex(RRGetMonitors {
+                    window: root,
+                    get_active: true,
+                })
+                .map(|(client, reply)| {
+                    (client, reply.monitors.iter().map(Monitor::from_randr).collect())
+                }))
+        }))
+}
+
+/// The Xinerama screens, empty if Xinerama isn't active.
+fn xinerama_monitors(client: Client) -> ReplyFuture<Vec<Monitor>> {
+    Box::new(query_extension(client, xinerama::EXTENSION_NAME)
+        .and_then(|(client, info)| -> ReplyFuture<bool> {
+            if !info.present {
+                return Box::new(futures::finished((client, false)));
+            }
+
+            Box::new(client.perform_ex(XineramaIsActive))
+        })
+        .and_then(|(client, active)| -> ReplyFuture<Vec<Monitor>> {
+            if !active {
+                return Box::new(futures::finished((client, Vec::new())));
+            }
+
+            Box::new(client.perform_ex(XineramaQueryScreens).map(|(client, screens)| {
+                let monitors = screens.iter()
+                    .enumerate()
+                    .map(|(i, screen)| Monitor::from_xinerama(i, screen))
+                    .collect();
+                (client, monitors)
+            }))
+        }))
+}
+
+/// Returns the information of an extension, queried only if it isn't
+/// known yet.
+fn query_extension(client: Client, name: &[u8]) -> ReplyFuture<ExtensionInfo> {
+    if let Some(info) = client.extension_info(name) {
+        return Box::new(futures::finished((client, info)));
+    }
+
+    Box::new(client.perform(QueryExtension { name: name.to_owned() }))
+}
@@ -0,0 +1,65 @@
+//! The version requests of the extensions, sent by `Client::perform_ex`
+//! before the first request of an extension. Some servers reject the
+//! requests of clients that didn't negotiate a version, like SYNC without
+//! `SyncInitialize` or XFIXES without `XFixesQueryVersion`.
+
+use ::std::any::Any;
+use ::std::any::TypeId;
+
+use ::protocol::ReplyFuture;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::Client;
+
+/// Negotiates the version of an extension.
+#[derive(Clone, Copy)]
+pub struct Negotiator {
+    /// The type of the version request.
+    pub request: TypeId,
+
+    /// Sends the version request, resolving to the version agreed on.
+    pub negotiate: fn(Client) -> ReplyFuture<Version>,
+
+    /// The version agreed on in a reply of the version request.
+    pub version: fn(&dyn Any) -> Option<Version>,
+}
+
+fn negotiator<Req: VersionRequest + 'static>() -> Negotiator {
+    Negotiator {
+        request: TypeId::of::<Req>(),
+        negotiate: Client::negotiate::<Req>,
+        version: version::<Req>,
+    }
+}
+
+fn version<Req: VersionRequest>(reply: &dyn Any) -> Option<Version> {
+    reply.downcast_ref::<Req::Reply>().map(Req::version)
+}
+
+/// Returns how to negotiate the version of `extension_name`.
+pub fn find(extension_name: &[u8]) -> Option<Negotiator> {
+    let negotiator = match extension_name {
+        ::composite::EXTENSION_NAME => negotiator::<::composite::CompositeQueryVersion>(),
+        ::damage::EXTENSION_NAME => negotiator::<::damage::DamageQueryVersion>(),
+        ::dbe::EXTENSION_NAME => negotiator::<::dbe::DbeGetVersion>(),
+        ::dpms::EXTENSION_NAME => negotiator::<::dpms::DPMSGetVersion>(),
+        ::present::EXTENSION_NAME => negotiator::<::present::PresentQueryVersion>(),
+        ::randr::EXTENSION_NAME => negotiator::<::randr::RRQueryVersion>(),
+        ::record::EXTENSION_NAME => negotiator::<::record::RecordQueryVersion>(),
+        ::render::EXTENSION_NAME => negotiator::<::render::RenderQueryVersion>(),
+        ::screensaver::EXTENSION_NAME => negotiator::<::screensaver::ScreenSaverQueryVersion>(),
+        ::shape::EXTENSION_NAME => negotiator::<::shape::ShapeQueryVersion>(),
+        ::shm::EXTENSION_NAME => negotiator::<::shm::ShmQueryVersion>(),
+        ::sync::EXTENSION_NAME => negotiator::<::sync::SyncInitialize>(),
+        ::xc_misc::EXTENSION_NAME => negotiator::<::xc_misc::XCMiscGetVersion>(),
+        ::xfixes::EXTENSION_NAME => negotiator::<::xfixes::XFixesQueryVersion>(),
+        ::xinerama::EXTENSION_NAME => negotiator::<::xinerama::XineramaQueryVersion>(),
+        ::xinput::EXTENSION_NAME => negotiator::<::xinput::XIQueryVersion>(),
+        ::xkb::EXTENSION_NAME => negotiator::<::xkb::XkbUseExtension>(),
+        ::xres::EXTENSION_NAME => negotiator::<::xres::XResQueryVersion>(),
+        ::xtest::EXTENSION_NAME => negotiator::<::xtest::XTestGetVersion>(),
+        _ => return None,
+    };
+
+    Some(negotiator)
+}
@@ -0,0 +1,239 @@
+//! PRESENT extension requests and events, with a frame scheduler showing
+//! pixmaps in sync with the vertical blank.
+
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+
+use ::event::Event;
+use ::protocol::ExtensionInfo;
+use ::protocol::GENERIC_EVENT;
+use ::sync::Fence;
+use ::xproto::Pixmap;
+use ::xproto::Window;
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    present_query_version,
+    present_pixmap,
+    present_notify_msc,
+    present_select_input
+}
+
+mod scheduler;
+pub use self::scheduler::*;
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"Present";
+
+declare_ids! {
+    /// An event selection made with `PresentSelectInput`.
+    EventId
+}
+
+/// Options of `PresentPixmap`.
+pub type Options = u32;
+
+/// Shows the pixmap right away if the target MSC is already passed,
+/// which may tear.
+pub const OPTION_ASYNC: Options = 1;
+
+/// Always copies the pixmap instead of flipping to it.
+pub const OPTION_COPY: Options = 2;
+
+/// The target is an UST (time in microseconds) instead of a MSC.
+pub const OPTION_UST: Options = 4;
+
+/// Lets the server flip to a pixmap not matching the window format.
+pub const OPTION_SUBOPTIMAL: Options = 8;
+
+/// Events selected with `PresentSelectInput`.
+pub type EventMask = u32;
+pub const EVENT_MASK_CONFIGURE_NOTIFY: EventMask = 1;
+pub const EVENT_MASK_COMPLETE_NOTIFY: EventMask = 2;
+pub const EVENT_MASK_IDLE_NOTIFY: EventMask = 4;
+
+/// A window getting the `CompleteNotify` event of a `PresentPixmap`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Notify {
+    pub window: Window,
+    pub serial: u32,
+}
+
+/// The request completed by a `CompleteNotify` event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompleteKind {
+    Pixmap,
+    NotifyMSC,
+}
+
+impl From<u8> for CompleteKind {
+    fn from(kind: u8) -> CompleteKind {
+        match kind {
+            1 => CompleteKind::NotifyMSC,
+            _ => CompleteKind::Pixmap,
+        }
+    }
+}
+
+/// How a pixmap was shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompleteMode {
+    Copy,
+    Flip,
+
+    /// A later `PresentPixmap` replaced it before it was shown.
+    Skip,
+
+    /// Copied, but the pixmap could have been flipped with a different
+    /// format.
+    SuboptimalCopy,
+}
+
+impl From<u8> for CompleteMode {
+    fn from(mode: u8) -> CompleteMode {
+        match mode {
+            1 => CompleteMode::Flip,
+            2 => CompleteMode::Skip,
+            3 => CompleteMode::SuboptimalCopy,
+            _ => CompleteMode::Copy,
+        }
+    }
+}
+
+/// The PRESENT events, sent as GenericEvents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PresentEvent {
+    ConfigureNotify(PresentConfigureNotifyEvent),
+    CompleteNotify(PresentCompleteNotifyEvent),
+    IdleNotify(PresentIdleNotifyEvent),
+}
+
+/// The window was resized or moved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PresentConfigureNotifyEvent {
+    pub eid: EventId,
+    pub window: Window,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub off_x: i16,
+    pub off_y: i16,
+
+    /// The size the pixmaps should have.
+    pub pixmap_width: u16,
+    pub pixmap_height: u16,
+
+    pub pixmap_flags: u32,
+}
+
+/// A `PresentPixmap` or `PresentNotifyMSC` request was completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PresentCompleteNotifyEvent {
+    pub kind: CompleteKind,
+    pub mode: CompleteMode,
+    pub eid: EventId,
+    pub window: Window,
+    pub serial: u32,
+
+    /// The time of the vertical blank, in microseconds.
+    pub ust: u64,
+
+    /// The MSC at which the request was completed.
+    pub msc: u64,
+}
+
+/// The pixmap of a `PresentPixmap` request can be reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PresentIdleNotifyEvent {
+    pub eid: EventId,
+    pub window: Window,
+    pub serial: u32,
+    pub pixmap: Pixmap,
+    pub idle_fence: Fence,
+}
+
+/// The `evtype` of the events.
+const CONFIGURE_NOTIFY: u16 = 0;
+const COMPLETE_NOTIFY: u16 = 1;
+const IDLE_NOTIFY: u16 = 2;
+
+impl PresentEvent {
+    /// Decodes `event` if it's a PRESENT event, `info` is the information
+    /// of the extension as returned by `Client::extension_info`.
+    pub fn from_event(info: &ExtensionInfo, event: &Event) -> Option<PresentEvent> {
+        match *event {
+            Event::Unknown(ref buf) => PresentEvent::read(info, buf),
+            _ => None,
+        }
+    }
+
+    /// Decodes a GenericEvent packet, `None` is returned for the events of
+    /// other extensions.
+    pub fn read(info: &ExtensionInfo, buf: &[u8]) -> Option<PresentEvent> {
+        if !info.present || buf.len() < 32 || buf[0] & 0x7f != GENERIC_EVENT ||
+           buf[1] != info.major_opcode {
+            return None;
+        }
+
+        let u32_at = |at: usize| NativeEndian::read_u32(&buf[at..at + 4]);
+
+        let event = match NativeEndian::read_u16(&buf[8..10]) {
+            CONFIGURE_NOTIFY if buf.len() >= 40 => {
+                PresentEvent::ConfigureNotify(PresentConfigureNotifyEvent {
+                    eid: EventId(u32_at(12)),
+                    window: Window(u32_at(16)),
+                    x: NativeEndian::read_i16(&buf[20..22]),
+                    y: NativeEndian::read_i16(&buf[22..24]),
+                    width: NativeEndian::read_u16(&buf[24..26]),
+                    height: NativeEndian::read_u16(&buf[26..28]),
+                    off_x: NativeEndian::read_i16(&buf[28..30]),
+                    off_y: NativeEndian::read_i16(&buf[30..32]),
+                    pixmap_width: NativeEndian::read_u16(&buf[32..34]),
+                    pixmap_height: NativeEndian::read_u16(&buf[34..36]),
+                    pixmap_flags: u32_at(36),
+                })
+            }
+            COMPLETE_NOTIFY if buf.len() >= 40 => {
+                PresentEvent::CompleteNotify(PresentCompleteNotifyEvent {
+                    kind: CompleteKind::from(buf[10]),
+                    mode: CompleteMode::from(buf[11]),
+                    eid: EventId(u32_at(12)),
+                    window: Window(u32_at(16)),
+                    serial: u32_at(20),
+                    ust: NativeEndian::read_u64(&buf[24..32]),
+                    msc: NativeEndian::read_u64(&buf[32..40]),
+                })
+            }
+            IDLE_NOTIFY => {
+                PresentEvent::IdleNotify(PresentIdleNotifyEvent {
+                    eid: EventId(u32_at(12)),
+                    window: Window(u32_at(16)),
+                    serial: u32_at(20),
+                    pixmap: Pixmap(u32_at(24)),
+                    idle_fence: Fence(u32_at(28)),
+                })
+            }
+            _ => return None,
+        };
+
+        Some(event)
+    }
+
+    /// The event selection the event was sent for.
+    pub fn eid(&self) -> EventId {
+        match *self {
+            PresentEvent::ConfigureNotify(ref ev) => ev.eid,
+            PresentEvent::CompleteNotify(ref ev) => ev.eid,
+            PresentEvent::IdleNotify(ref ev) => ev.eid,
+        }
+    }
+}
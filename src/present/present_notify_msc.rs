@@ -0,0 +1,56 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::present::EXTENSION_NAME;
+use ::xproto::Window;
+use ::Client;
+
+const PRESENT_OPCODE: u8 = 2;
+
+/// Asks for a `CompleteNotify` event of kind `NotifyMsc` when the MSC of
+/// the CRTC showing `window` reaches the target, like `PresentPixmap`
+/// without a pixmap.
+#[derive(Debug, Clone, Copy)]
+pub struct PresentNotifyMSC {
+    pub window: Window,
+    pub serial: u32,
+    pub target_msc: u64,
+    pub divisor: u64,
+    pub remainder: u64,
+}
+
+impl ExtensionRequest for PresentNotifyMSC {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(PRESENT_OPCODE)?;
+        a.write_u16::<NativeEndian>(10)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u32::<NativeEndian>(self.serial)?;
+        a.write_all(&[0; 4])?;
+        a.write_u64::<NativeEndian>(self.target_msc)?;
+        a.write_u64::<NativeEndian>(self.divisor)?;
+        a.write_u64::<NativeEndian>(self.remainder)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
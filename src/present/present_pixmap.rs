@@ -0,0 +1,129 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::present::EXTENSION_NAME;
+use ::present::Notify;
+use ::present::Options;
+use ::randr::Crtc;
+use ::sync::Fence;
+use ::xfixes::Region;
+use ::xproto::Pixmap;
+use ::xproto::Window;
+use ::Client;
+
+const PRESENT_OPCODE: u8 = 1;
+
+/// Shows the content of `pixmap` in `window` at the first MSC (vertical
+/// blank counter) after `target_msc` for which `msc % divisor ==
+/// remainder`, or right away if the target is already reached.
+#[derive(Debug, Clone)]
+pub struct PresentPixmap {
+    pub window: Window,
+    pub pixmap: Pixmap,
+
+    /// Sent back in the `CompleteNotify` and `IdleNotify` events.
+    pub serial: u32,
+
+    /// The part of the pixmap with valid content, `Region::NONE` for all
+    /// of it.
+    pub valid: Region,
+
+    /// The part of the window to update, `Region::NONE` for all of it.
+    pub update: Region,
+
+    /// Position of the pixmap in the window.
+    pub x_off: i16,
+    pub y_off: i16,
+
+    /// The CRTC to synchronize with, `Crtc::NONE` to let the server pick
+    /// one.
+    pub target_crtc: Crtc,
+
+    /// Fence waited for before showing the pixmap, triggered by the client
+    /// when its rendering is done, or `Fence::NONE`.
+    pub wait_fence: Fence,
+
+    /// Fence triggered by the server when the pixmap can be reused, or
+    /// `Fence::NONE`.
+    pub idle_fence: Fence,
+
+    pub options: Options,
+    pub target_msc: u64,
+    pub divisor: u64,
+    pub remainder: u64,
+
+    /// Other windows getting a `CompleteNotify` event.
+    pub notifies: Vec<Notify>,
+}
+
+impl PresentPixmap {
+    /// Shows `pixmap` in the whole `window` at the next vertical blank.
+    pub fn new(window: Window, pixmap: Pixmap, serial: u32) -> PresentPixmap {
+        PresentPixmap {
+            window,
+            pixmap,
+            serial,
+            valid: Region::NONE,
+            update: Region::NONE,
+            x_off: 0,
+            y_off: 0,
+            target_crtc: Crtc::NONE,
+            wait_fence: Fence::NONE,
+            idle_fence: Fence::NONE,
+            options: 0,
+            target_msc: 0,
+            divisor: 0,
+            remainder: 0,
+            notifies: Vec::new(),
+        }
+    }
+}
+
+impl ExtensionRequest for PresentPixmap {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(PRESENT_OPCODE)?;
+        a.write_u16::<NativeEndian>(18 + 2 * self.notifies.len() as u16)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u32::<NativeEndian>(self.pixmap.0)?;
+        a.write_u32::<NativeEndian>(self.serial)?;
+        a.write_u32::<NativeEndian>(self.valid.0)?;
+        a.write_u32::<NativeEndian>(self.update.0)?;
+        a.write_i16::<NativeEndian>(self.x_off)?;
+        a.write_i16::<NativeEndian>(self.y_off)?;
+        a.write_u32::<NativeEndian>(self.target_crtc.0)?;
+        a.write_u32::<NativeEndian>(self.wait_fence.0)?;
+        a.write_u32::<NativeEndian>(self.idle_fence.0)?;
+        a.write_u32::<NativeEndian>(self.options)?;
+        a.write_all(&[0; 4])?;
+        a.write_u64::<NativeEndian>(self.target_msc)?;
+        a.write_u64::<NativeEndian>(self.divisor)?;
+        a.write_u64::<NativeEndian>(self.remainder)?;
+        for notify in &self.notifies {
+            a.write_u32::<NativeEndian>(notify.window.0)?;
+            a.write_u32::<NativeEndian>(notify.serial)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,51 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::present::EXTENSION_NAME;
+use ::present::EventId;
+use ::present::EventMask;
+use ::xproto::Window;
+use ::Client;
+
+const PRESENT_OPCODE: u8 = 3;
+
+/// Selects the PRESENT events of a window, `eid` is a new id naming the
+/// selection, it's freed when the mask is set to zero.
+#[derive(Debug, Clone, Copy)]
+pub struct PresentSelectInput {
+    pub eid: EventId,
+    pub window: Window,
+    pub event_mask: EventMask,
+}
+
+impl ExtensionRequest for PresentSelectInput {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(PRESENT_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u32::<NativeEndian>(self.eid.0)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u32::<NativeEndian>(self.event_mask)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
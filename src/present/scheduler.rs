@@ -0,0 +1,296 @@
+use ::std::io;
+
+use ::futures::Async;
+use ::futures::Future;
+use ::futures::Poll;
+use ::futures::Stream;
+
+use ::event::Event;
+use ::present::EXTENSION_NAME;
+use ::present::EVENT_MASK_COMPLETE_NOTIFY;
+use ::present::EVENT_MASK_CONFIGURE_NOTIFY;
+use ::present::EVENT_MASK_IDLE_NOTIFY;
+use ::present::EventId;
+use ::present::Options;
+use ::present::PresentCompleteNotifyEvent;
+use ::present::PresentEvent;
+use ::present::PresentPixmap;
+use ::present::PresentSelectInput;
+use ::protocol::ExtensionInfo;
+use ::protocol::ReplyFuture;
+use ::xproto::Pixmap;
+use ::xproto::Window;
+use ::Client;
+
+/// Shows the frames of a window once per vertical blank.
+///
+/// It keeps the MSC and UST of the last completed frame to target the next
+/// blank, and a pool of pixmaps, busy from the `present` call showing them
+/// to their `IdleNotify` event. The events must be passed to `handle`, or
+/// read by the `Completions` stream.
+#[derive(Debug, Clone)]
+pub struct FrameScheduler {
+    info: ExtensionInfo,
+    window: Window,
+    eid: EventId,
+    serial: u32,
+
+    /// The pixmaps of the pool with the serial showing them, if busy.
+    pixmaps: Vec<(Pixmap, Option<u32>)>,
+
+    msc: u64,
+    ust: u64,
+    target_msc: u64,
+    frame_interval: Option<u64>,
+    pixmap_size: Option<(u16, u16)>,
+}
+
+impl FrameScheduler {
+    /// Selects the PRESENT events of `window` and creates a scheduler for
+    /// it.
+    pub fn new(client: Client, window: Window) -> ReplyFuture<FrameScheduler> {
+        Box::new(client.next_id::<EventId>()
+            .and_then(move |(client, eid)| {
+                client.perform_ex(PresentSelectInput {
+                        eid,
+                        window,
+                        event_mask: EVENT_MASK_CONFIGURE_NOTIFY | EVENT_MASK_COMPLETE_NOTIFY |
+                                    EVENT_MASK_IDLE_NOTIFY,
+                    })
+                    .map(move |(client, _)| (client, eid))
+            })
+            .and_then(move |(client, eid)| {
+                let info = client.extension_info(EXTENSION_NAME)
+                    .ok_or_else(|| io::Error::other("PRESENT extension not queried"))?;
+                Ok((client, FrameScheduler::with_selection(info, window, eid)))
+            }))
+    }
+
+    /// Creates a scheduler for an event selection already made with
+    /// `PresentSelectInput`, including the complete and idle events.
+    pub fn with_selection(info: ExtensionInfo, window: Window, eid: EventId) -> FrameScheduler {
+        FrameScheduler {
+            info,
+            window,
+            eid,
+            serial: 0,
+            pixmaps: Vec::new(),
+            msc: 0,
+            ust: 0,
+            target_msc: 0,
+            frame_interval: None,
+            pixmap_size: None,
+        }
+    }
+
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    /// Adds a pixmap to the pool.
+    pub fn add_pixmap(&mut self, pixmap: Pixmap) {
+        if !self.pixmaps.iter().any(|&(p, _)| p == pixmap) {
+            self.pixmaps.push((pixmap, None));
+        }
+    }
+
+    /// Removes a pixmap from the pool, returning whether it was idle. A
+    /// busy pixmap must not be freed before its `IdleNotify` event.
+    pub fn remove_pixmap(&mut self, pixmap: Pixmap) -> bool {
+        match self.pixmaps.iter().position(|&(p, _)| p == pixmap) {
+            Some(i) => self.pixmaps.remove(i).1.is_none(),
+            None => false,
+        }
+    }
+
+    /// A pixmap of the pool not used by the server, to draw the next frame.
+    pub fn idle_pixmap(&self) -> Option<Pixmap> {
+        self.pixmaps.iter().find(|&&(_, serial)| serial.is_none()).map(|&(pixmap, _)| pixmap)
+    }
+
+    /// Whether `pixmap` is waiting to be shown or still shown.
+    pub fn is_busy(&self, pixmap: Pixmap) -> bool {
+        self.pixmaps.iter().any(|&(p, serial)| p == pixmap && serial.is_some())
+    }
+
+    /// The MSC of the last completed frame, zero before the first one.
+    pub fn msc(&self) -> u64 {
+        self.msc
+    }
+
+    /// The time of the last completed frame in microseconds.
+    pub fn ust(&self) -> u64 {
+        self.ust
+    }
+
+    /// The time between two vertical blanks in microseconds, estimated
+    /// from the completed frames.
+    pub fn frame_interval(&self) -> Option<u64> {
+        self.frame_interval
+    }
+
+    /// The estimated time of the vertical blank at `msc`.
+    pub fn ust_at(&self, msc: u64) -> Option<u64> {
+        let interval = self.frame_interval?;
+        if msc >= self.msc {
+            Some(self.ust + (msc - self.msc) * interval)
+        } else {
+            self.ust.checked_sub((self.msc - msc) * interval)
+        }
+    }
+
+    /// The size of the pixmaps from the last `ConfigureNotify` event.
+    pub fn pixmap_size(&self) -> Option<(u16, u16)> {
+        self.pixmap_size
+    }
+
+    /// The MSC the next frame is shown at, the blank after the last
+    /// completed or queued frame. Zero, showing it at once, before the
+    /// first completion.
+    pub fn next_msc(&self) -> u64 {
+        if self.msc == 0 {
+            0
+        } else {
+            self.msc.max(self.target_msc) + 1
+        }
+    }
+
+    /// Shows `pixmap` at the next vertical blank, resolving to the serial
+    /// of the frame. The pixmap is added to the pool if needed, and is busy
+    /// until its `IdleNotify` event.
+    pub fn present(&mut self,
+                   client: Client,
+                   pixmap: Pixmap,
+                   options: Options)
+                   -> ReplyFuture<u32> {
+        self.serial = self.serial.wrapping_add(1);
+        let serial = self.serial;
+
+        self.add_pixmap(pixmap);
+        for entry in &mut self.pixmaps {
+            if entry.0 == pixmap {
+                entry.1 = Some(serial);
+            }
+        }
+
+        let target_msc = self.next_msc();
+        self.target_msc = target_msc;
+
+        let mut request = PresentPixmap::new(self.window, pixmap, serial);
+        request.options = options;
+        request.target_msc = target_msc;
+
+        Box::new(client.perform_ex(request).map(move |(client, _)| (client, serial)))
+    }
+
+    /// Updates the state with `event`, returning the `CompleteNotify` events
+    /// of the window.
+    pub fn handle(&mut self, event: &Event) -> Option<PresentCompleteNotifyEvent> {
+        let event = match PresentEvent::from_event(&self.info, event) {
+            Some(ref event) if event.eid() == self.eid => event.clone(),
+            _ => return None,
+        };
+
+        match event {
+            PresentEvent::ConfigureNotify(ev) => {
+                self.pixmap_size = Some((ev.pixmap_width, ev.pixmap_height));
+                None
+            }
+            PresentEvent::CompleteNotify(ev) => {
+                if self.msc != 0 && ev.msc > self.msc && ev.ust > self.ust {
+                    self.frame_interval = Some((ev.ust - self.ust) / (ev.msc - self.msc));
+                }
+                if ev.msc >= self.msc {
+                    self.msc = ev.msc;
+                    self.ust = ev.ust;
+                }
+
+                Some(ev)
+            }
+            PresentEvent::IdleNotify(ev) => {
+                for entry in &mut self.pixmaps {
+                    if entry.0 == ev.pixmap && entry.1 == Some(ev.serial) {
+                        entry.1 = None;
+                    }
+                }
+
+                None
+            }
+        }
+    }
+
+    /// A stream of the `CompleteNotify` events of the window, reading the
+    /// PRESENT events of the selection and leaving the other events queued
+    /// in the client.
+    pub fn completions(self, client: Client) -> Completions {
+        Completions {
+            scheduler: self,
+            client: Some(client),
+            reading: None,
+        }
+    }
+}
+
+/// Stream of the frames completed by a `FrameScheduler`.
+///
+/// The events are read only when the stream is polled, so once an item is
+/// returned `into_inner` gives back the client to present the next frame.
+pub struct Completions {
+    scheduler: FrameScheduler,
+    client: Option<Client>,
+    reading: Option<ReplyFuture<Event>>,
+}
+
+impl Completions {
+    pub fn scheduler(&self) -> &FrameScheduler {
+        &self.scheduler
+    }
+
+    pub fn scheduler_mut(&mut self) -> &mut FrameScheduler {
+        &mut self.scheduler
+    }
+
+    /// Returns the client and the scheduler, `None` is returned while an
+    /// event is being read or after an error.
+    pub fn into_inner(self) -> Option<(Client, FrameScheduler)> {
+        let scheduler = self.scheduler;
+        self.client.map(|client| (client, scheduler))
+    }
+}
+
+impl Stream for Completions {
+    type Item = PresentCompleteNotifyEvent;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, io::Error> {
+        loop {
+            if self.reading.is_none() {
+                let client = match self.client.take() {
+                    Some(client) => client,
+                    None => return Ok(Async::Ready(None)),
+                };
+
+                let info = self.scheduler.info;
+                let eid = self.scheduler.eid;
+                self.reading = Some(client.wait_for_event(move |event| {
+                    PresentEvent::from_event(&info, event).is_some_and(|event| event.eid() == eid)
+                }));
+            }
+
+            let (client, event) = match self.reading.as_mut().unwrap().poll() {
+                Ok(Async::Ready(result)) => result,
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Err(e) => {
+                    self.reading = None;
+                    return Err(e);
+                }
+            };
+
+            self.reading = None;
+            self.client = Some(client);
+            if let Some(ev) = self.scheduler.handle(&event) {
+                return Ok(Async::Ready(Some(ev)));
+            }
+        }
+    }
+}
@@ -1,20 +1,35 @@
 //! Here basic protocol communication is described like requests and replies.
 
+use ::std::error;
+use ::std::fmt;
 use ::std::io;
-use ::std::io::Read;
+use ::std::os::unix::io::RawFd;
 
 use ::futures::Future;
+use ::futures::Stream;
+use ::futures::stream;
 use ::byteorder::NativeEndian;
-use ::byteorder::ReadBytesExt;
+use ::byteorder::ByteOrder;
+use ::tokio_io;
 
 use ::Client;
 
+/// A future resolving to the client and the decoded reply of a request.
+pub type ReplyFuture<T> = Box<dyn Future<Item = (Client, T), Error = io::Error>>;
+
 /// An X11 Protocol request.
 pub trait Request {
     type Reply: 'static;
 
     fn encode(&mut self) -> io::Result<Vec<u8>>;
-    fn decode(client: Client) -> Box<Future<Item = (Client, Self::Reply), Error = io::Error>>;
+    fn decode(client: Client) -> ReplyFuture<Self::Reply>;
+
+    /// File descriptors sent along with the request, as `SCM_RIGHTS`
+    /// ancillary data with its first bytes. They are duplicated when the
+    /// request is performed, so the request keeps owning them.
+    fn fds(&self) -> Vec<RawFd> {
+        Vec::new()
+    }
 }
 
 /// This is used for requests that don't return a reply.
@@ -28,7 +43,189 @@ pub trait ExtensionRequest {
 
     fn extension_name() -> &'static [u8];
     fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>>;
-    fn decode(client: Client) -> Box<Future<Item = (Client, Self::Reply), Error = io::Error>>;
+    fn decode(client: Client) -> ReplyFuture<Self::Reply>;
+
+    /// File descriptors sent along with the request, like `Request::fds`.
+    fn fds(&self) -> Vec<RawFd> {
+        Vec::new()
+    }
+}
+
+/// A version of an extension, ordered by major then minor version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl Version {
+    pub fn new(major: u32, minor: u32) -> Version {
+        Version { major, minor }
+    }
+}
+
+/// The request negotiating the version of an extension, sent once per
+/// connection by `Client::perform_ex` before the first request of the
+/// extension.
+pub trait VersionRequest: ExtensionRequest {
+    /// The request asking for the latest version implemented by the crate.
+    fn latest() -> Self;
+
+    /// The version agreed on by the server.
+    fn version(reply: &Self::Reply) -> Version;
+}
+
+/// A reply carrying file descriptors, read with `read_reply_fds`. They are
+/// owned by the receiver of the reply, which must close them.
+pub trait ReplyFds {
+    fn reply_fds(&self) -> Vec<RawFd>;
+}
+
+/// Code of the first byte of a reply packet.
+pub const REPLY: u8 = 1;
+
+/// Code of the first byte of an error packet.
+pub const ERROR: u8 = 0;
+
+/// Event code of the `GenericEvent`, which like replies carries additional
+/// data after the first 32 bytes.
+pub const GENERIC_EVENT: u8 = 35;
+
+/// Reads a single packet (reply, error or event) from the connection.
+///
+/// Replies and generic events are returned with all of their additional
+/// data appended.
+pub fn read_packet(client: Client) -> ReplyFuture<Vec<u8>> {
+    let buf: [u8; 32] = [0u8; 32];
+    Box::new(tokio_io::io::read_exact(client, buf).and_then(|(client, buf)| {
+        let extra = if buf[0] == REPLY || (buf[0] & 0x7f) == GENERIC_EVENT {
+            NativeEndian::read_u32(&buf[4..8]) as usize * 4
+        } else {
+            0
+        };
+
+        tokio_io::io::read_exact(client, vec![0u8; extra]).map(move |(client, data)| {
+            let mut packet = buf.to_vec();
+            packet.extend_from_slice(&data);
+            (client, packet)
+        })
+    }))
+}
+
+/// Reads the reply of the last request sent.
+///
+/// Events and errors of previous requests received while waiting are
+/// queued on the client and can be retrieved with `Client::next_event`. If
+/// the last request failed the error is returned as an `io::Error` wrapping
+/// an `Error`.
+pub fn read_reply(client: Client) -> ReplyFuture<Vec<u8>> {
+    let sequence = client.sequence;
+    read_reply_to(client, sequence)
+}
+
+/// Reads the reply of the request with the given sequence number, used when
+/// several requests were sent with `Client::send_all`, or to read the next
+/// reply of a request with several ones.
+///
+/// Replies of later requests read meanwhile are kept on the client to be
+/// returned when asked for. Replies of earlier requests are dropped, their
+/// sequence numbers are reused once they wrap.
+pub fn read_reply_to(mut client: Client, sequence: u16) -> ReplyFuture<Vec<u8>> {
+    client.replies.retain(|packet| !is_earlier(reply_sequence(packet), sequence));
+    let position = client.replies.iter().position(|packet| reply_sequence(packet) == sequence);
+    if let Some(i) = position {
+        let packet = client.replies.remove(i).unwrap();
+        return Box::new(::futures::finished((client, packet)));
+    }
+
+    Box::new(::futures::future::loop_fn(client, move |client| {
+        read_packet(client).and_then(move |(mut client, packet)| {
+            match packet[0] {
+                REPLY if reply_sequence(&packet) == sequence => {
+                    Ok(::futures::future::Loop::Break((client, packet)))
+                }
+                REPLY if is_earlier(reply_sequence(&packet), sequence) => {
+                    Ok(::futures::future::Loop::Continue(client))
+                }
+                REPLY => {
+                    client.replies.push_back(packet);
+                    Ok(::futures::future::Loop::Continue(client))
+                }
+                ERROR => {
+                    let err = Error::read(&packet);
+                    if err.sequence_number() == Some(sequence) {
+                        Err(io::Error::other(err))
+                    } else {
+                        client.events.push_back(packet);
+                        Ok(::futures::future::Loop::Continue(client))
+                    }
+                }
+                _ => {
+                    client.events.push_back(packet);
+                    Ok(::futures::future::Loop::Continue(client))
+                }
+            }
+        })
+    }))
+}
+
+/// Sends all of `requests` in a single write with `Client::send_all`, then
+/// reads their replies in order with `read`. Resolves to the decoded
+/// replies, in the order of the requests.
+pub fn pipeline<Req, T>(client: Client,
+                        requests: Vec<Req>,
+                        read: fn(&[u8]) -> io::Result<T>)
+                        -> ReplyFuture<Vec<T>>
+    where Req: Request,
+          T: 'static
+{
+    let count = requests.len() as u16;
+
+    Box::new(client.send_all(requests).and_then(move |(client, first)| {
+        stream::iter_ok::<_, io::Error>(0..count)
+            .fold((client, Vec::new()), move |(client, mut replies), i| {
+                read_reply_to(client, first.wrapping_add(i)).and_then(move |(client, buf)| {
+                    replies.push(read(&buf)?);
+                    Ok((client, replies))
+                })
+            })
+    }))
+}
+
+/// The sequence number of the request a reply answers.
+fn reply_sequence(packet: &[u8]) -> u16 {
+    NativeEndian::read_u16(&packet[2..4])
+}
+
+/// Whether the request with the sequence number `sequence` was sent before
+/// the one with `than`, the numbers wrap after 65535.
+fn is_earlier(sequence: u16, than: u16) -> bool {
+    (than.wrapping_sub(sequence) as i16) > 0
+}
+
+/// Reads the reply of the last request sent along with the `count` file
+/// descriptors attached to it.
+pub fn read_reply_fds(client: Client, count: usize) -> ReplyFuture<(Vec<u8>, Vec<RawFd>)> {
+    Box::new(read_reply(client).and_then(move |(mut client, buf)| {
+        let fds = client.take_fds(count)?;
+        Ok((client, (buf, fds)))
+    }))
+}
+
+/// Converts the length of a request in 4-byte units to its length field,
+/// failing with `io::ErrorKind::InvalidInput` if it doesn't fit in 16 bits.
+pub fn request_length(words: usize) -> io::Result<u16> {
+    if words > 0xffff {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                  format!("request of {} words is too long", words)));
+    }
+
+    Ok(words as u16)
+}
+
+/// Returns a future that resolves immediately to a `VoidReply`.
+pub fn void_reply(client: Client) -> ReplyFuture<VoidReply> {
+    Box::new(::futures::finished((client, ())))
 }
 
 /// A potocol error.
@@ -194,10 +391,112 @@ pub enum Error {
         major_opcode: u8,
     },
 
+    /// An error defined by an extension, `error_code` is relative to the
+    /// whole connection, not to the extension `first_error`.
+    Extension {
+        error_code: u8,
+        sequence_number: u16,
+        bad_value: u32,
+        minor_opcode: u16,
+        major_opcode: u8,
+    },
+
+    /// The server doesn't have the extension, no request was sent. Returned
+    /// as an `io::Error` of kind `Unsupported`.
+    ExtensionNotPresent(&'static [u8]),
+
     /// An I/O error occurred during writing/reading.
     Io(io::Error),
 }
 
+impl Error {
+    /// Decodes an error from a 32 bytes error packet.
+    pub fn read(buf: &[u8]) -> Error {
+        let error_code = buf[1];
+        let sequence_number = NativeEndian::read_u16(&buf[2..4]);
+        let value = NativeEndian::read_u32(&buf[4..8]);
+        let minor_opcode = NativeEndian::read_u16(&buf[8..10]);
+        let major_opcode = buf[10];
+
+        match error_code {
+            1 => Error::Request { sequence_number, minor_opcode, major_opcode },
+            2 => Error::Value { sequence_number, bad_value: value, minor_opcode, major_opcode },
+            3 => Error::Window { sequence_number, bad_resource_id: value, minor_opcode, major_opcode },
+            4 => Error::Pixmap { sequence_number, bad_resource_id: value, minor_opcode, major_opcode },
+            5 => Error::Atom { sequence_number, bad_atom_id: value, minor_opcode, major_opcode },
+            6 => Error::Cursor { sequence_number, bad_resource_id: value, minor_opcode, major_opcode },
+            7 => Error::Font { sequence_number, bad_resource_id: value, minor_opcode, major_opcode },
+            8 => Error::Match { sequence_number, minor_opcode, major_opcode },
+            9 => Error::Drawable { sequence_number, bad_resource_id: value, minor_opcode, major_opcode },
+            10 => Error::Access { sequence_number, minor_opcode, major_opcode },
+            11 => Error::Alloc { sequence_number, minor_opcode, major_opcode },
+            12 => Error::Colormap { sequence_number, bad_resource_id: value, minor_opcode, major_opcode },
+            13 => Error::GContext { sequence_number, bad_resource_id: value, minor_opcode, major_opcode },
+            14 => Error::IDChoice { sequence_number, bad_resource_id: value, minor_opcode, major_opcode },
+            15 => Error::Name { sequence_number, minor_opcode, major_opcode },
+            16 => Error::Length { sequence_number, minor_opcode, major_opcode },
+            17 => Error::Implementation { sequence_number, minor_opcode, major_opcode },
+            _ => {
+                Error::Extension {
+                    error_code,
+                    sequence_number,
+                    bad_value: value,
+                    minor_opcode,
+                    major_opcode,
+                }
+            }
+        }
+    }
+
+    /// The sequence number of the request that failed, `None` for I/O
+    /// errors and requests not sent.
+    pub fn sequence_number(&self) -> Option<u16> {
+        match *self {
+            Error::Request { sequence_number, .. } |
+            Error::Value { sequence_number, .. } |
+            Error::Window { sequence_number, .. } |
+            Error::Pixmap { sequence_number, .. } |
+            Error::Atom { sequence_number, .. } |
+            Error::Cursor { sequence_number, .. } |
+            Error::Font { sequence_number, .. } |
+            Error::Match { sequence_number, .. } |
+            Error::Drawable { sequence_number, .. } |
+            Error::Access { sequence_number, .. } |
+            Error::Alloc { sequence_number, .. } |
+            Error::Colormap { sequence_number, .. } |
+            Error::GContext { sequence_number, .. } |
+            Error::IDChoice { sequence_number, .. } |
+            Error::Name { sequence_number, .. } |
+            Error::Length { sequence_number, .. } |
+            Error::Implementation { sequence_number, .. } |
+            Error::Extension { sequence_number, .. } => Some(sequence_number),
+            Error::ExtensionNotPresent(_) |
+            Error::Io(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref e) => write!(f, "I/O error: {}", e),
+            Error::ExtensionNotPresent(name) => {
+                write!(f, "extension {} not present", String::from_utf8_lossy(name))
+            }
+            _ => write!(f, "X protocol error: {:?}", self),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
 impl From<io::Error> for Error {
     fn from(e: io::Error) -> Error {
         Error::Io(e)
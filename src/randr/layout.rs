@@ -0,0 +1,340 @@
+use ::std::cmp;
+use ::std::io;
+
+use ::futures;
+use ::futures::Future;
+use ::futures::Stream;
+
+use ::protocol::ReplyFuture;
+use ::randr::ConfigStatus;
+use ::randr::Connection;
+use ::randr::Crtc;
+use ::randr::Mode;
+use ::randr::ModeInfo;
+use ::randr::Output;
+use ::randr::Rotation;
+use ::randr::RRGetCrtcInfo;
+use ::randr::RRGetOutputInfo;
+use ::randr::RRGetScreenResourcesCurrent;
+use ::randr::RRSetCrtcConfig;
+use ::randr::RRSetScreenSize;
+use ::randr::ROTATE_0;
+use ::randr::ROTATE_90;
+use ::randr::ROTATE_270;
+use ::xproto::CURRENT_TIME;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+use ::Client;
+
+/// The configuration of a CRTC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrtcConfig {
+    pub crtc: Crtc,
+    pub x: i16,
+    pub y: i16,
+
+    /// `Mode::NONE` if the CRTC is disabled.
+    pub mode: Mode,
+
+    pub rotation: Rotation,
+    pub outputs: Vec<Output>,
+}
+
+impl CrtcConfig {
+    /// Whether the CRTC shows something.
+    pub fn is_enabled(&self) -> bool {
+        self.mode != Mode::NONE && !self.outputs.is_empty()
+    }
+
+    fn disabled(crtc: Crtc) -> CrtcConfig {
+        CrtcConfig {
+            crtc,
+            x: 0,
+            y: 0,
+            mode: Mode::NONE,
+            rotation: ROTATE_0,
+            outputs: Vec::new(),
+        }
+    }
+}
+
+/// The state of an output, it's only read by `Layout`.
+#[derive(Debug, Clone)]
+pub struct OutputState {
+    pub output: Output,
+    pub name: String,
+    pub connection: Connection,
+
+    /// The CRTC showing the output, `Crtc::NONE` if it's disabled.
+    pub crtc: Crtc,
+
+    /// The CRTCs that can show the output.
+    pub crtcs: Vec<Crtc>,
+
+    pub modes: Vec<Mode>,
+    pub preferred_mode: Option<Mode>,
+    pub mm_width: u32,
+    pub mm_height: u32,
+}
+
+/// A snapshot of the screen configuration.
+///
+/// A layout is read with `Layout::current`, modified (for example with
+/// `enable_output` and `disable_output`) and applied with `apply`, which
+/// only reconfigures the CRTCs listed by `diff`.
+#[derive(Debug, Clone)]
+pub struct Layout {
+    pub root: Window,
+
+    /// When the hardware configuration was read, the layout can't be
+    /// applied if it changed since.
+    pub config_timestamp: Timestamp,
+
+    pub modes: Vec<ModeInfo>,
+    pub outputs: Vec<OutputState>,
+    pub crtcs: Vec<CrtcConfig>,
+}
+
+impl Layout {
+    /// Reads the current configuration of the screen of `root`.
+    pub fn current(client: Client, root: Window) -> ReplyFuture<Layout> {
+        Box::new(client.perform_ex(RRGetScreenResourcesCurrent { window: root })
+            .and_then(move |(client, resources)| {
+                let layout = Layout {
+                    root,
+                    config_timestamp: resources.config_timestamp,
+                    modes: resources.modes,
+                    outputs: Vec::new(),
+                    crtcs: Vec::new(),
+                };
+                let config_timestamp = layout.config_timestamp;
+                let crtcs = resources.crtcs;
+
+                futures::stream::iter_ok::<_, io::Error>(resources.outputs)
+                    .fold((client, layout), move |(client, mut layout), output| {
+                        client.perform_ex(RRGetOutputInfo { output, config_timestamp })
+                            .map(move |(client, info)| {
+                                layout.outputs.push(OutputState {
+                                    output,
+                                    preferred_mode: info.preferred_mode(),
+                                    name: info.name,
+                                    connection: info.connection,
+                                    crtc: info.crtc,
+                                    crtcs: info.crtcs,
+                                    modes: info.modes,
+                                    mm_width: info.mm_width,
+                                    mm_height: info.mm_height,
+                                });
+                                (client, layout)
+                            })
+                    })
+                    .and_then(move |(client, layout)| {
+                        futures::stream::iter_ok::<_, io::Error>(crtcs)
+                            .fold((client, layout), move |(client, mut layout), crtc| {
+                                client.perform_ex(RRGetCrtcInfo { crtc, config_timestamp })
+                                    .map(move |(client, info)| {
+                                        layout.crtcs.push(CrtcConfig {
+                                            crtc,
+                                            x: info.x,
+                                            y: info.y,
+                                            mode: info.mode,
+                                            rotation: info.rotation,
+                                            outputs: info.outputs,
+                                        });
+                                        (client, layout)
+                                    })
+                            })
+                    })
+            }))
+    }
+
+    /// Finds an output by name, like `"HDMI-1"`.
+    pub fn output_by_name(&self, name: &str) -> Option<&OutputState> {
+        self.outputs.iter().find(|state| state.name == name)
+    }
+
+    /// Returns the configuration of `crtc`.
+    pub fn crtc(&self, crtc: Crtc) -> Option<&CrtcConfig> {
+        self.crtcs.iter().find(|config| config.crtc == crtc)
+    }
+
+    /// Returns the enabled CRTC showing `output`.
+    pub fn crtc_of(&self, output: Output) -> Option<&CrtcConfig> {
+        self.crtcs.iter().find(|config| config.is_enabled() && config.outputs.contains(&output))
+    }
+
+    /// Size of the CRTC, swapped if it's rotated by 90 or 270 degrees.
+    pub fn crtc_size(&self, config: &CrtcConfig) -> Option<(u16, u16)> {
+        if !config.is_enabled() {
+            return None;
+        }
+
+        let mode = self.modes.iter().find(|info| info.id == config.mode)?;
+        if config.rotation & (ROTATE_90 | ROTATE_270) != 0 {
+            Some((mode.height, mode.width))
+        } else {
+            Some((mode.width, mode.height))
+        }
+    }
+
+    /// The screen size needed to show all the enabled CRTCs.
+    pub fn screen_size(&self) -> (u16, u16) {
+        self.crtcs.iter().fold((0, 0), |(width, height), config| {
+            match self.crtc_size(config) {
+                Some((w, h)) => {
+                    (cmp::max(width, (config.x as i32 + w as i32).max(0) as u16),
+                     cmp::max(height, (config.y as i32 + h as i32).max(0) as u16))
+                }
+                None => (width, height),
+            }
+        })
+    }
+
+    /// Shows `output` with `mode` at (`x`, `y`), using the CRTC currently
+    /// showing it or a free one. Returns `false` if no CRTC can be used.
+    pub fn enable_output(&mut self,
+                         output: Output,
+                         mode: Mode,
+                         x: i16,
+                         y: i16,
+                         rotation: Rotation)
+                         -> bool {
+        let crtc = match self.crtc_of(output) {
+            Some(config) => Some(config.crtc),
+            None => {
+                let possible = match self.outputs.iter().find(|state| state.output == output) {
+                    Some(state) => &state.crtcs,
+                    None => return false,
+                };
+
+                self.crtcs
+                    .iter()
+                    .find(|config| !config.is_enabled() && possible.contains(&config.crtc))
+                    .map(|config| config.crtc)
+            }
+        };
+
+        match crtc.and_then(|crtc| self.crtcs.iter_mut().find(|config| config.crtc == crtc)) {
+            Some(config) => {
+                config.x = x;
+                config.y = y;
+                config.mode = mode;
+                config.rotation = rotation;
+                if !config.outputs.contains(&output) {
+                    config.outputs = vec![output];
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stops showing `output`, the CRTC is disabled if it doesn't show any
+    /// other output.
+    pub fn disable_output(&mut self, output: Output) {
+        for config in &mut self.crtcs {
+            config.outputs.retain(|&o| o != output);
+            if config.outputs.is_empty() {
+                *config = CrtcConfig::disabled(config.crtc);
+            }
+        }
+    }
+
+    /// Returns the CRTC configurations of `target` that differ from this
+    /// layout.
+    pub fn diff(&self, target: &Layout) -> Vec<CrtcConfig> {
+        target.crtcs
+            .iter()
+            .filter(|config| {
+                match self.crtc(config.crtc) {
+                    Some(current) if !current.is_enabled() && !config.is_enabled() => false,
+                    Some(current) => current != *config,
+                    None => true,
+                }
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Changes the configuration from this layout to `target`.
+    ///
+    /// The changed CRTCs are disabled first, then the screen is resized to
+    /// fit `target` keeping its DPI and finally the CRTCs are enabled with
+    /// their new configuration.
+    pub fn apply(&self, client: Client, target: &Layout) -> ReplyFuture<()> {
+        let changes = self.diff(target);
+        if changes.is_empty() {
+            return Box::new(futures::finished((client, ())));
+        }
+
+        let root = self.root;
+        let config_timestamp = self.config_timestamp;
+        let (width, height) = target.screen_size();
+        let (mm_width, mm_height) = {
+            let screen = client.get_server_info().roots.iter().find(|screen| screen.root == root);
+            match screen {
+                Some(screen) if screen.width_pixels > 0 && screen.height_pixels > 0 => {
+                    (width as u32 * screen.width_millimeters as u32 / screen.width_pixels as u32,
+                     height as u32 * screen.height_millimeters as u32 /
+                     screen.height_pixels as u32)
+                }
+                // 96 DPI.
+                _ => (width as u32 * 254 / 960, height as u32 * 254 / 960),
+            }
+        };
+
+        let disable: Vec<CrtcConfig> = changes.iter()
+            .filter(|config| self.crtc(config.crtc).is_some_and(|c| c.is_enabled()))
+            .map(|config| CrtcConfig::disabled(config.crtc))
+            .collect();
+        let enable: Vec<CrtcConfig> =
+            changes.into_iter().filter(|config| config.is_enabled()).collect();
+
+        Box::new(set_crtcs(client, config_timestamp, disable)
+            .and_then(move |(client, _)| -> ReplyFuture<()> {
+                if width == 0 || height == 0 {
+                    return Box::new(futures::finished((client, ())));
+                }
+
+                client.perform_ex(RRSetScreenSize {
+                    window: root,
+                    width,
+                    height,
+                    mm_width,
+                    mm_height,
+                })
+            })
+            .and_then(move |(client, _)| set_crtcs(client, config_timestamp, enable)))
+    }
+}
+
+/// Sets the configuration of several CRTCs, one after another.
+fn set_crtcs(client: Client,
+             config_timestamp: Timestamp,
+             configs: Vec<CrtcConfig>)
+             -> ReplyFuture<()> {
+    Box::new(futures::stream::iter_ok::<_, io::Error>(configs)
+        .fold(client, move |client, config| {
+            client.perform_ex(RRSetCrtcConfig {
+                    crtc: config.crtc,
+                    timestamp: CURRENT_TIME,
+                    config_timestamp,
+                    x: config.x,
+                    y: config.y,
+                    mode: config.mode,
+                    rotation: config.rotation,
+                    outputs: config.outputs,
+                })
+                .and_then(|(client, reply)| match reply.status {
+                    ConfigStatus::Success => Ok(client),
+                    ConfigStatus::InvalidConfigTime => {
+                        Err(io::Error::other("RANDR configuration changed since the layout \
+                                              was read"))
+                    }
+                    status => {
+                        Err(io::Error::other(format!("RRSetCrtcConfig failed: {:?}", status)))
+                    }
+                })
+        })
+        .map(|client| (client, ())))
+}
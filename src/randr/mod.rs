@@ -0,0 +1,233 @@
+//! RANDR extension requests, events and a layout snapshot to change the
+//! screen configuration.
+
+use ::std::io;
+use ::std::io::Read;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::xproto::Atom;
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    rr_query_version,
+    rr_select_input,
+    rr_set_screen_size,
+    rr_get_screen_resources,
+    rr_get_screen_resources_current,
+    rr_get_output_info,
+    rr_get_crtc_info,
+    rr_set_crtc_config,
+    rr_get_output_property,
+    rr_change_output_property,
+    rr_get_monitors,
+    rr_set_monitor
+}
+
+mod notify;
+pub use self::notify::*;
+
+mod layout;
+pub use self::layout::*;
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"RANDR";
+
+declare_ids! {
+    /// An output (a connector like a monitor port).
+    Output,
+
+    /// A CRTC, the scanout engine showing a region of the screen on
+    /// outputs.
+    Crtc,
+
+    /// A display mode.
+    Mode
+}
+
+/// Rotations and reflections of a CRTC.
+pub type Rotation = u16;
+pub const ROTATE_0: Rotation = 1;
+pub const ROTATE_90: Rotation = 2;
+pub const ROTATE_180: Rotation = 4;
+pub const ROTATE_270: Rotation = 8;
+pub const REFLECT_X: Rotation = 16;
+pub const REFLECT_Y: Rotation = 32;
+
+/// Events selected with `RRSelectInput`.
+pub type NotifyMask = u16;
+pub const NOTIFY_MASK_SCREEN_CHANGE: NotifyMask = 1;
+pub const NOTIFY_MASK_CRTC_CHANGE: NotifyMask = 2;
+pub const NOTIFY_MASK_OUTPUT_CHANGE: NotifyMask = 4;
+pub const NOTIFY_MASK_OUTPUT_PROPERTY: NotifyMask = 8;
+
+/// Result of the requests changing the configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigStatus {
+    Success,
+    InvalidConfigTime,
+    InvalidTime,
+    Failed,
+}
+
+impl From<u8> for ConfigStatus {
+    fn from(status: u8) -> ConfigStatus {
+        match status {
+            0 => ConfigStatus::Success,
+            1 => ConfigStatus::InvalidConfigTime,
+            2 => ConfigStatus::InvalidTime,
+            _ => ConfigStatus::Failed,
+        }
+    }
+}
+
+/// Whether a monitor is connected to an output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connection {
+    Connected,
+    Disconnected,
+    Unknown,
+}
+
+impl From<u8> for Connection {
+    fn from(connection: u8) -> Connection {
+        match connection {
+            0 => Connection::Connected,
+            1 => Connection::Disconnected,
+            _ => Connection::Unknown,
+        }
+    }
+}
+
+/// Description of a display mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModeInfo {
+    pub id: Mode,
+    pub width: u16,
+    pub height: u16,
+    pub dot_clock: u32,
+    pub hsync_start: u16,
+    pub hsync_end: u16,
+    pub htotal: u16,
+    pub hskew: u16,
+    pub vsync_start: u16,
+    pub vsync_end: u16,
+    pub vtotal: u16,
+    pub mode_flags: u32,
+    pub name: String,
+}
+
+impl ModeInfo {
+    /// Refresh rate in Hz, zero if unknown.
+    pub fn refresh_rate(&self) -> f64 {
+        let mut vtotal = self.vtotal as f64;
+        if self.mode_flags & MODE_FLAG_DOUBLE_SCAN != 0 {
+            vtotal *= 2.0;
+        }
+        if self.mode_flags & MODE_FLAG_INTERLACE != 0 {
+            vtotal /= 2.0;
+        }
+
+        if self.htotal == 0 || vtotal == 0.0 {
+            0.0
+        } else {
+            self.dot_clock as f64 / (self.htotal as f64 * vtotal)
+        }
+    }
+}
+
+pub const MODE_FLAG_INTERLACE: u32 = 0x10;
+pub const MODE_FLAG_DOUBLE_SCAN: u32 = 0x20;
+
+/// A monitor, a rectangle of the screen shown by one or more outputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonitorInfo {
+    pub name: Atom,
+    pub primary: bool,
+
+    /// Whether the monitor was created by the server.
+    pub automatic: bool,
+
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub width_mm: u32,
+    pub height_mm: u32,
+    pub outputs: Vec<Output>,
+}
+
+impl MonitorInfo {
+    fn read<A: Read>(a: &mut A) -> io::Result<MonitorInfo> {
+        let name = Atom(a.read_u32::<NativeEndian>()?);
+        let primary = a.read_u8()? != 0;
+        let automatic = a.read_u8()? != 0;
+        let num_outputs = a.read_u16::<NativeEndian>()?;
+        let x = a.read_i16::<NativeEndian>()?;
+        let y = a.read_i16::<NativeEndian>()?;
+        let width = a.read_u16::<NativeEndian>()?;
+        let height = a.read_u16::<NativeEndian>()?;
+        let width_mm = a.read_u32::<NativeEndian>()?;
+        let height_mm = a.read_u32::<NativeEndian>()?;
+        let outputs = read_ids(a, num_outputs as usize)?;
+
+        Ok(MonitorInfo {
+            name,
+            primary,
+            automatic,
+            x,
+            y,
+            width,
+            height,
+            width_mm,
+            height_mm,
+            outputs,
+        })
+    }
+
+    fn write<A: Write>(&self, a: &mut A) -> io::Result<()> {
+        a.write_u32::<NativeEndian>(self.name.0)?;
+        a.write_u8(self.primary as u8)?;
+        a.write_u8(self.automatic as u8)?;
+        a.write_u16::<NativeEndian>(self.outputs.len() as u16)?;
+        a.write_i16::<NativeEndian>(self.x)?;
+        a.write_i16::<NativeEndian>(self.y)?;
+        a.write_u16::<NativeEndian>(self.width)?;
+        a.write_u16::<NativeEndian>(self.height)?;
+        a.write_u32::<NativeEndian>(self.width_mm)?;
+        a.write_u32::<NativeEndian>(self.height_mm)?;
+        for output in &self.outputs {
+            a.write_u32::<NativeEndian>(output.0)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads `count` ids of type `T`.
+fn read_ids<A: Read, T: From<u32>>(a: &mut A, count: usize) -> io::Result<Vec<T>> {
+    let mut ids = Vec::with_capacity(count);
+    for _ in 0..count {
+        ids.push(T::from(a.read_u32::<NativeEndian>()?));
+    }
+
+    Ok(ids)
+}
+
+/// Reads a string of `len` bytes.
+fn read_string<A: Read>(a: &mut A, len: usize) -> io::Result<String> {
+    let mut buf = vec![0u8; len];
+    a.read_exact(&mut buf)?;
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
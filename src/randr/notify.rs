@@ -0,0 +1,190 @@
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+
+use ::event::Event;
+use ::protocol::ExtensionInfo;
+use ::randr::Connection;
+use ::randr::Crtc;
+use ::randr::Mode;
+use ::randr::Output;
+use ::randr::Rotation;
+use ::xproto::Atom;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+
+/// Offsets of the RANDR events from the extension `first_event`.
+const SCREEN_CHANGE_NOTIFY: u8 = 0;
+const NOTIFY: u8 = 1;
+
+/// Sub-codes of the `RRNotify` event.
+const NOTIFY_CRTC_CHANGE: u8 = 0;
+const NOTIFY_OUTPUT_CHANGE: u8 = 1;
+const NOTIFY_OUTPUT_PROPERTY: u8 = 2;
+
+/// An event of the RANDR extension, selected with `RRSelectInput`.
+#[derive(Debug, Clone)]
+pub enum RandrEvent {
+    ScreenChangeNotify(ScreenChangeNotifyEvent),
+    CrtcChangeNotify(CrtcChangeNotifyEvent),
+    OutputChangeNotify(OutputChangeNotifyEvent),
+    OutputPropertyNotify(OutputPropertyNotifyEvent),
+}
+
+impl RandrEvent {
+    /// Decodes `event` if it's a RANDR event, `info` is the information of
+    /// the extension as returned by `Client::extension_info`.
+    pub fn from_event(info: &ExtensionInfo, event: &Event) -> Option<RandrEvent> {
+        match *event {
+            Event::Unknown(ref buf) => RandrEvent::read(info, buf),
+            _ => None,
+        }
+    }
+
+    /// Decodes an event packet.
+    pub fn read(info: &ExtensionInfo, buf: &[u8]) -> Option<RandrEvent> {
+        if !info.present || info.first_event == 0 {
+            return None;
+        }
+
+        let code = (buf[0] & 0x7f).wrapping_sub(info.first_event);
+
+        match code {
+            SCREEN_CHANGE_NOTIFY => {
+                Some(RandrEvent::ScreenChangeNotify(ScreenChangeNotifyEvent::read(buf)))
+            }
+            NOTIFY => {
+                match buf[1] {
+                    NOTIFY_CRTC_CHANGE => {
+                        Some(RandrEvent::CrtcChangeNotify(CrtcChangeNotifyEvent::read(buf)))
+                    }
+                    NOTIFY_OUTPUT_CHANGE => {
+                        Some(RandrEvent::OutputChangeNotify(OutputChangeNotifyEvent::read(buf)))
+                    }
+                    NOTIFY_OUTPUT_PROPERTY => {
+                        Some(RandrEvent::OutputPropertyNotify(OutputPropertyNotifyEvent::read(buf)))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Sent when the screen size or configuration changes.
+#[derive(Debug, Clone, Copy)]
+pub struct ScreenChangeNotifyEvent {
+    pub rotation: Rotation,
+    pub timestamp: Timestamp,
+    pub config_timestamp: Timestamp,
+    pub root: Window,
+    pub window: Window,
+    pub size_id: u16,
+    pub subpixel_order: u16,
+    pub width: u16,
+    pub height: u16,
+    pub mm_width: u16,
+    pub mm_height: u16,
+}
+
+impl ScreenChangeNotifyEvent {
+    fn read(buf: &[u8]) -> ScreenChangeNotifyEvent {
+        ScreenChangeNotifyEvent {
+            rotation: buf[1] as Rotation,
+            timestamp: NativeEndian::read_u32(&buf[4..8]),
+            config_timestamp: NativeEndian::read_u32(&buf[8..12]),
+            root: Window(NativeEndian::read_u32(&buf[12..16])),
+            window: Window(NativeEndian::read_u32(&buf[16..20])),
+            size_id: NativeEndian::read_u16(&buf[20..22]),
+            subpixel_order: NativeEndian::read_u16(&buf[22..24]),
+            width: NativeEndian::read_u16(&buf[24..26]),
+            height: NativeEndian::read_u16(&buf[26..28]),
+            mm_width: NativeEndian::read_u16(&buf[28..30]),
+            mm_height: NativeEndian::read_u16(&buf[30..32]),
+        }
+    }
+}
+
+/// Sent when the configuration of a CRTC changes.
+#[derive(Debug, Clone, Copy)]
+pub struct CrtcChangeNotifyEvent {
+    pub timestamp: Timestamp,
+    pub window: Window,
+    pub crtc: Crtc,
+    pub mode: Mode,
+    pub rotation: Rotation,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl CrtcChangeNotifyEvent {
+    fn read(buf: &[u8]) -> CrtcChangeNotifyEvent {
+        CrtcChangeNotifyEvent {
+            timestamp: NativeEndian::read_u32(&buf[4..8]),
+            window: Window(NativeEndian::read_u32(&buf[8..12])),
+            crtc: Crtc(NativeEndian::read_u32(&buf[12..16])),
+            mode: Mode(NativeEndian::read_u32(&buf[16..20])),
+            rotation: NativeEndian::read_u16(&buf[20..22]),
+            x: NativeEndian::read_i16(&buf[24..26]),
+            y: NativeEndian::read_i16(&buf[26..28]),
+            width: NativeEndian::read_u16(&buf[28..30]),
+            height: NativeEndian::read_u16(&buf[30..32]),
+        }
+    }
+}
+
+/// Sent when an output is connected, disconnected or reconfigured.
+#[derive(Debug, Clone, Copy)]
+pub struct OutputChangeNotifyEvent {
+    pub timestamp: Timestamp,
+    pub config_timestamp: Timestamp,
+    pub window: Window,
+    pub output: Output,
+    pub crtc: Crtc,
+    pub mode: Mode,
+    pub rotation: Rotation,
+    pub connection: Connection,
+    pub subpixel_order: u8,
+}
+
+impl OutputChangeNotifyEvent {
+    fn read(buf: &[u8]) -> OutputChangeNotifyEvent {
+        OutputChangeNotifyEvent {
+            timestamp: NativeEndian::read_u32(&buf[4..8]),
+            config_timestamp: NativeEndian::read_u32(&buf[8..12]),
+            window: Window(NativeEndian::read_u32(&buf[12..16])),
+            output: Output(NativeEndian::read_u32(&buf[16..20])),
+            crtc: Crtc(NativeEndian::read_u32(&buf[20..24])),
+            mode: Mode(NativeEndian::read_u32(&buf[24..28])),
+            rotation: NativeEndian::read_u16(&buf[28..30]),
+            connection: Connection::from(buf[30]),
+            subpixel_order: buf[31],
+        }
+    }
+}
+
+/// Sent when a property of an output changes.
+#[derive(Debug, Clone, Copy)]
+pub struct OutputPropertyNotifyEvent {
+    pub window: Window,
+    pub output: Output,
+    pub atom: Atom,
+    pub timestamp: Timestamp,
+
+    /// Whether the property was deleted.
+    pub deleted: bool,
+}
+
+impl OutputPropertyNotifyEvent {
+    fn read(buf: &[u8]) -> OutputPropertyNotifyEvent {
+        OutputPropertyNotifyEvent {
+            window: Window(NativeEndian::read_u32(&buf[4..8])),
+            output: Output(NativeEndian::read_u32(&buf[8..12])),
+            atom: Atom(NativeEndian::read_u32(&buf[12..16])),
+            timestamp: NativeEndian::read_u32(&buf[16..20]),
+            deleted: buf[20] != 0,
+        }
+    }
+}
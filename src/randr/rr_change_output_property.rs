@@ -0,0 +1,70 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::randr::EXTENSION_NAME;
+use ::randr::Output;
+use ::xproto::Atom;
+use ::xproto::PropMode;
+use ::Client;
+use ::pad;
+
+const RANDR_OPCODE: u8 = 13;
+
+/// Changes a property of an output, like `ChangeProperty` does for
+/// windows.
+#[derive(Debug)]
+pub struct RRChangeOutputProperty {
+    pub output: Output,
+    pub property: Atom,
+    pub type_: Atom,
+
+    /// Whether the data is a list of 8, 16 or 32 bit quantities.
+    pub format: u8,
+
+    pub mode: PropMode,
+
+    /// The property data, in native byte order.
+    pub data: Vec<u8>,
+}
+
+impl ExtensionRequest for RRChangeOutputProperty {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let n = self.data.len();
+        let p = pad(n);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RANDR_OPCODE)?;
+        a.write_u16::<NativeEndian>((6 + (n + p) / 4) as u16)?;
+        a.write_u32::<NativeEndian>(self.output.0)?;
+        a.write_u32::<NativeEndian>(self.property.0)?;
+        a.write_u32::<NativeEndian>(self.type_.0)?;
+        a.write_u8(self.format)?;
+        a.write_u8(self.mode as u8)?;
+        a.write_u16::<NativeEndian>(0)?;
+        a.write_u32::<NativeEndian>((n / (self.format as usize / 8)) as u32)?;
+        a.write_all(&self.data)?;
+        a.write_all(&[0; 3][..p])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
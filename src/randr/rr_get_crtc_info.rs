@@ -0,0 +1,115 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::randr::EXTENSION_NAME;
+use ::randr::ConfigStatus;
+use ::randr::Crtc;
+use ::randr::Mode;
+use ::randr::Output;
+use ::randr::Rotation;
+use ::randr::read_ids;
+use ::xproto::Timestamp;
+use ::Client;
+
+const RANDR_OPCODE: u8 = 20;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RRGetCrtcInfo {
+    pub crtc: Crtc,
+
+    /// The `config_timestamp` of the screen resources.
+    pub config_timestamp: Timestamp,
+}
+
+impl ExtensionRequest for RRGetCrtcInfo {
+    type Reply = RRGetCrtcInfoReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RANDR_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.crtc.0)?;
+        a.write_u32::<NativeEndian>(self.config_timestamp)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let mut a = io::Cursor::new(buf);
+
+            a.read_u8()?;
+            let status = a.read_u8()?;
+            a.read_u16::<NativeEndian>()?;
+            a.read_u32::<NativeEndian>()?;
+            let timestamp = a.read_u32::<NativeEndian>()?;
+            let x = a.read_i16::<NativeEndian>()?;
+            let y = a.read_i16::<NativeEndian>()?;
+            let width = a.read_u16::<NativeEndian>()?;
+            let height = a.read_u16::<NativeEndian>()?;
+            let mode = Mode(a.read_u32::<NativeEndian>()?);
+            let rotation = a.read_u16::<NativeEndian>()?;
+            let rotations = a.read_u16::<NativeEndian>()?;
+            let num_outputs = a.read_u16::<NativeEndian>()?;
+            let num_possible_outputs = a.read_u16::<NativeEndian>()?;
+
+            let outputs = read_ids(&mut a, num_outputs as usize)?;
+            let possible_outputs = read_ids(&mut a, num_possible_outputs as usize)?;
+
+            let reply = RRGetCrtcInfoReply {
+                status: ConfigStatus::from(status),
+                timestamp,
+                x,
+                y,
+                width,
+                height,
+                mode,
+                rotation,
+                rotations,
+                outputs,
+                possible_outputs,
+            };
+
+            Ok((client, reply))
+        }))
+    }
+}
+
+/// Reply of `RRGetCrtcInfo` request.
+#[derive(Debug, Clone)]
+pub struct RRGetCrtcInfoReply {
+    pub status: ConfigStatus,
+    pub timestamp: Timestamp,
+
+    /// Region of the screen shown, the size takes the rotation into
+    /// account.
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+
+    /// The current mode, `Mode::NONE` if the CRTC is disabled.
+    pub mode: Mode,
+
+    pub rotation: Rotation,
+
+    /// Rotations and reflections supported.
+    pub rotations: Rotation,
+
+    pub outputs: Vec<Output>,
+    pub possible_outputs: Vec<Output>,
+}
@@ -0,0 +1,74 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::randr::EXTENSION_NAME;
+use ::randr::MonitorInfo;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+use ::Client;
+
+const RANDR_OPCODE: u8 = 42;
+
+/// Lists the monitors of the screen of `window`, RANDR 1.5.
+#[derive(Debug, Clone, Copy)]
+pub struct RRGetMonitors {
+    pub window: Window,
+
+    /// Only list the monitors showing something.
+    pub get_active: bool,
+}
+
+impl ExtensionRequest for RRGetMonitors {
+    type Reply = RRGetMonitorsReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RANDR_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u8(self.get_active as u8)?;
+        a.write_all(&[0; 3])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let mut a = io::Cursor::new(buf);
+            a.set_position(8);
+
+            let timestamp = a.read_u32::<NativeEndian>()?;
+            let num_monitors = a.read_u32::<NativeEndian>()?;
+            a.set_position(32);
+
+            let mut monitors = Vec::with_capacity(num_monitors as usize);
+            for _ in 0..num_monitors {
+                monitors.push(MonitorInfo::read(&mut a)?);
+            }
+
+            Ok((client, RRGetMonitorsReply { timestamp, monitors }))
+        }))
+    }
+}
+
+/// Reply of `RRGetMonitors` request.
+#[derive(Debug, Clone)]
+pub struct RRGetMonitorsReply {
+    pub timestamp: Timestamp,
+    pub monitors: Vec<MonitorInfo>,
+}
@@ -0,0 +1,139 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::randr::EXTENSION_NAME;
+use ::randr::ConfigStatus;
+use ::randr::Connection;
+use ::randr::Crtc;
+use ::randr::Mode;
+use ::randr::Output;
+use ::randr::read_ids;
+use ::randr::read_string;
+use ::xproto::Timestamp;
+use ::Client;
+
+const RANDR_OPCODE: u8 = 9;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RRGetOutputInfo {
+    pub output: Output,
+
+    /// The `config_timestamp` of the screen resources.
+    pub config_timestamp: Timestamp,
+}
+
+impl ExtensionRequest for RRGetOutputInfo {
+    type Reply = RRGetOutputInfoReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RANDR_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.output.0)?;
+        a.write_u32::<NativeEndian>(self.config_timestamp)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let mut a = io::Cursor::new(buf);
+
+            a.read_u8()?;
+            let status = a.read_u8()?;
+            a.read_u16::<NativeEndian>()?;
+            a.read_u32::<NativeEndian>()?;
+            let timestamp = a.read_u32::<NativeEndian>()?;
+            let crtc = Crtc(a.read_u32::<NativeEndian>()?);
+            let mm_width = a.read_u32::<NativeEndian>()?;
+            let mm_height = a.read_u32::<NativeEndian>()?;
+            let connection = a.read_u8()?;
+            let subpixel_order = a.read_u8()?;
+            let num_crtcs = a.read_u16::<NativeEndian>()?;
+            let num_modes = a.read_u16::<NativeEndian>()?;
+            let num_preferred = a.read_u16::<NativeEndian>()?;
+            let num_clones = a.read_u16::<NativeEndian>()?;
+            let name_len = a.read_u16::<NativeEndian>()?;
+
+            let crtcs = read_ids(&mut a, num_crtcs as usize)?;
+            let modes = read_ids(&mut a, num_modes as usize)?;
+            let clones = read_ids(&mut a, num_clones as usize)?;
+            let name = read_string(&mut a, name_len as usize)?;
+
+            let reply = RRGetOutputInfoReply {
+                status: ConfigStatus::from(status),
+                timestamp,
+                crtc,
+                mm_width,
+                mm_height,
+                connection: Connection::from(connection),
+                subpixel_order,
+                crtcs,
+                modes,
+                num_preferred,
+                clones,
+                name,
+            };
+
+            Ok((client, reply))
+        }))
+    }
+}
+
+/// Reply of `RRGetOutputInfo` request.
+#[derive(Debug, Clone)]
+pub struct RRGetOutputInfoReply {
+    /// `InvalidConfigTime` if the configuration changed since the
+    /// resources were read.
+    pub status: ConfigStatus,
+
+    pub timestamp: Timestamp,
+
+    /// The CRTC showing the output, `Crtc::NONE` if it's disabled.
+    pub crtc: Crtc,
+
+    /// Physical size of the monitor.
+    pub mm_width: u32,
+    pub mm_height: u32,
+
+    pub connection: Connection,
+    pub subpixel_order: u8,
+
+    /// The CRTCs that can show the output.
+    pub crtcs: Vec<Crtc>,
+
+    /// The modes supported by the monitor, the first `num_preferred` are
+    /// the preferred ones.
+    pub modes: Vec<Mode>,
+    pub num_preferred: u16,
+
+    /// Outputs that can show the same CRTC as this one.
+    pub clones: Vec<Output>,
+
+    pub name: String,
+}
+
+impl RRGetOutputInfoReply {
+    /// The preferred mode of the monitor, if any.
+    pub fn preferred_mode(&self) -> Option<Mode> {
+        if self.num_preferred > 0 {
+            self.modes.first().cloned()
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::randr::EXTENSION_NAME;
+use ::randr::Output;
+use ::xproto;
+use ::xproto::Atom;
+use ::xproto::GetPropertyReply;
+use ::Client;
+
+const RANDR_OPCODE: u8 = 15;
+
+/// Reads a property of an output, like `GetProperty` does for windows.
+#[derive(Debug, Clone, Copy)]
+pub struct RRGetOutputProperty {
+    pub output: Output,
+    pub property: Atom,
+
+    /// The expected type, `ATOM_ANY` matches any.
+    pub type_: Atom,
+
+    /// Offset and length of the data, in 32 bit units.
+    pub long_offset: u32,
+    pub long_length: u32,
+
+    pub delete: bool,
+
+    /// Whether to read the value to be set on the next configuration
+    /// change instead of the current one.
+    pub pending: bool,
+}
+
+impl ExtensionRequest for RRGetOutputProperty {
+    type Reply = GetPropertyReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RANDR_OPCODE)?;
+        a.write_u16::<NativeEndian>(7)?;
+        a.write_u32::<NativeEndian>(self.output.0)?;
+        a.write_u32::<NativeEndian>(self.property.0)?;
+        a.write_u32::<NativeEndian>(self.type_.0)?;
+        a.write_u32::<NativeEndian>(self.long_offset)?;
+        a.write_u32::<NativeEndian>(self.long_length)?;
+        a.write_u8(self.delete as u8)?;
+        a.write_u8(self.pending as u8)?;
+        a.write_u16::<NativeEndian>(0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let format = buf[1];
+            let len = NativeEndian::read_u32(&buf[16..20]) as usize * (format as usize / 8);
+
+            let reply = GetPropertyReply {
+                format,
+                type_: Atom(NativeEndian::read_u32(&buf[8..12])),
+                bytes_after: NativeEndian::read_u32(&buf[12..16]),
+                value: buf[32..32 + len].to_vec(),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reads the EDID block of the monitor connected to `output`, `None` is
+/// returned if the driver doesn't provide it.
+pub fn get_edid(client: Client, output: Output) -> ReplyFuture<Option<Vec<u8>>> {
+    Box::new(client.perform(xproto::InternAtom {
+            only_if_exists: true,
+            name: b"EDID".to_vec(),
+        })
+        .and_then(move |(client, edid)| -> ReplyFuture<Option<Vec<u8>>> {
+            if edid == xproto::ATOM_NONE {
+                return Box::new(::futures::finished((client, None)));
+            }
+
+            Box::new(client.perform_ex(RRGetOutputProperty {
+                    output,
+                    property: edid,
+                    type_: xproto::ATOM_INTEGER,
+                    long_offset: 0,
+                    long_length: 128,
+                    delete: false,
+                    pending: false,
+                })
+                .map(|(client, reply)| {
+                    let edid = if reply.format == 8 && !reply.value.is_empty() {
+                        Some(reply.value)
+                    } else {
+                        None
+                    };
+
+                    (client, edid)
+                }))
+        }))
+}
@@ -0,0 +1,140 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::randr::EXTENSION_NAME;
+use ::randr::Crtc;
+use ::randr::Mode;
+use ::randr::ModeInfo;
+use ::randr::Output;
+use ::randr::read_ids;
+use ::randr::read_string;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+use ::Client;
+
+const RANDR_OPCODE: u8 = 8;
+
+/// Lists the CRTCs, outputs and modes of the screen of `window`, polling
+/// the hardware for changes (which may be slow). See
+/// `RRGetScreenResourcesCurrent`.
+#[derive(Debug, Clone, Copy)]
+pub struct RRGetScreenResources {
+    pub window: Window,
+}
+
+impl ExtensionRequest for RRGetScreenResources {
+    type Reply = RRGetScreenResourcesReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RANDR_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let mut a = io::Cursor::new(buf);
+            a.set_position(8);
+
+            let timestamp = a.read_u32::<NativeEndian>()?;
+            let config_timestamp = a.read_u32::<NativeEndian>()?;
+            let num_crtcs = a.read_u16::<NativeEndian>()?;
+            let num_outputs = a.read_u16::<NativeEndian>()?;
+            let num_modes = a.read_u16::<NativeEndian>()?;
+            a.read_u16::<NativeEndian>()?;
+            a.set_position(32);
+
+            let crtcs = read_ids(&mut a, num_crtcs as usize)?;
+            let outputs = read_ids(&mut a, num_outputs as usize)?;
+
+            let mut modes = Vec::with_capacity(num_modes as usize);
+            let mut name_lens = Vec::with_capacity(num_modes as usize);
+            for _ in 0..num_modes {
+                let id = Mode(a.read_u32::<NativeEndian>()?);
+                let width = a.read_u16::<NativeEndian>()?;
+                let height = a.read_u16::<NativeEndian>()?;
+                let dot_clock = a.read_u32::<NativeEndian>()?;
+                let hsync_start = a.read_u16::<NativeEndian>()?;
+                let hsync_end = a.read_u16::<NativeEndian>()?;
+                let htotal = a.read_u16::<NativeEndian>()?;
+                let hskew = a.read_u16::<NativeEndian>()?;
+                let vsync_start = a.read_u16::<NativeEndian>()?;
+                let vsync_end = a.read_u16::<NativeEndian>()?;
+                let vtotal = a.read_u16::<NativeEndian>()?;
+                name_lens.push(a.read_u16::<NativeEndian>()?);
+                let mode_flags = a.read_u32::<NativeEndian>()?;
+
+                modes.push(ModeInfo {
+                    id,
+                    width,
+                    height,
+                    dot_clock,
+                    hsync_start,
+                    hsync_end,
+                    htotal,
+                    hskew,
+                    vsync_start,
+                    vsync_end,
+                    vtotal,
+                    mode_flags,
+                    name: String::new(),
+                });
+            }
+
+            // The names of all the modes follow, one after another.
+            for (mode, len) in modes.iter_mut().zip(name_lens) {
+                mode.name = read_string(&mut a, len as usize)?;
+            }
+
+            let reply = RRGetScreenResourcesReply {
+                timestamp,
+                config_timestamp,
+                crtcs,
+                outputs,
+                modes,
+            };
+
+            Ok((client, reply))
+        }))
+    }
+}
+
+/// Reply of `RRGetScreenResources` and `RRGetScreenResourcesCurrent`
+/// requests.
+#[derive(Debug, Clone)]
+pub struct RRGetScreenResourcesReply {
+    /// When the configuration was last changed.
+    pub timestamp: Timestamp,
+
+    /// When the hardware configuration was last changed, needed to change
+    /// the configuration.
+    pub config_timestamp: Timestamp,
+
+    pub crtcs: Vec<Crtc>,
+    pub outputs: Vec<Output>,
+    pub modes: Vec<ModeInfo>,
+}
+
+impl RRGetScreenResourcesReply {
+    /// Finds the information of `mode`.
+    pub fn mode(&self, mode: Mode) -> Option<&ModeInfo> {
+        self.modes.iter().find(|info| info.id == mode)
+    }
+}
@@ -0,0 +1,46 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::ExtensionInfo;
+use ::randr::EXTENSION_NAME;
+use ::randr::RRGetScreenResources;
+use ::randr::RRGetScreenResourcesReply;
+use ::xproto::Window;
+use ::Client;
+
+const RANDR_OPCODE: u8 = 25;
+
+/// Like `RRGetScreenResources` but returns the resources known by the
+/// server without polling the hardware.
+#[derive(Debug, Clone, Copy)]
+pub struct RRGetScreenResourcesCurrent {
+    pub window: Window,
+}
+
+impl ExtensionRequest for RRGetScreenResourcesCurrent {
+    type Reply = RRGetScreenResourcesReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RANDR_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        // Both replies have the same format.
+        RRGetScreenResources::decode(client)
+    }
+}
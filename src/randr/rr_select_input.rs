@@ -0,0 +1,48 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::randr::EXTENSION_NAME;
+use ::randr::NotifyMask;
+use ::xproto::Window;
+use ::Client;
+
+const RANDR_OPCODE: u8 = 4;
+
+/// Selects the RANDR events reported to `window`.
+#[derive(Debug, Clone, Copy)]
+pub struct RRSelectInput {
+    pub window: Window,
+    pub enable: NotifyMask,
+}
+
+impl ExtensionRequest for RRSelectInput {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RANDR_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u16::<NativeEndian>(self.enable)?;
+        a.write_u16::<NativeEndian>(0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
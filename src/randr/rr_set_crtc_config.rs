@@ -0,0 +1,84 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::randr::EXTENSION_NAME;
+use ::randr::ConfigStatus;
+use ::randr::Crtc;
+use ::randr::Mode;
+use ::randr::Output;
+use ::randr::Rotation;
+use ::xproto::Timestamp;
+use ::Client;
+
+const RANDR_OPCODE: u8 = 21;
+
+/// Configures a CRTC, it's disabled with `Mode::NONE` and no outputs.
+#[derive(Debug, Clone)]
+pub struct RRSetCrtcConfig {
+    pub crtc: Crtc,
+    pub timestamp: Timestamp,
+
+    /// The `config_timestamp` of the screen resources.
+    pub config_timestamp: Timestamp,
+
+    pub x: i16,
+    pub y: i16,
+    pub mode: Mode,
+    pub rotation: Rotation,
+    pub outputs: Vec<Output>,
+}
+
+impl ExtensionRequest for RRSetCrtcConfig {
+    type Reply = RRSetCrtcConfigReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RANDR_OPCODE)?;
+        a.write_u16::<NativeEndian>(7 + self.outputs.len() as u16)?;
+        a.write_u32::<NativeEndian>(self.crtc.0)?;
+        a.write_u32::<NativeEndian>(self.timestamp)?;
+        a.write_u32::<NativeEndian>(self.config_timestamp)?;
+        a.write_i16::<NativeEndian>(self.x)?;
+        a.write_i16::<NativeEndian>(self.y)?;
+        a.write_u32::<NativeEndian>(self.mode.0)?;
+        a.write_u16::<NativeEndian>(self.rotation)?;
+        a.write_u16::<NativeEndian>(0)?;
+        for output in &self.outputs {
+            a.write_u32::<NativeEndian>(output.0)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = RRSetCrtcConfigReply {
+                status: ConfigStatus::from(buf[1]),
+                timestamp: NativeEndian::read_u32(&buf[8..12]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `RRSetCrtcConfig` request.
+#[derive(Debug, Clone, Copy)]
+pub struct RRSetCrtcConfigReply {
+    pub status: ConfigStatus,
+    pub timestamp: Timestamp,
+}
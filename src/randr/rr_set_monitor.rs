@@ -0,0 +1,47 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::randr::EXTENSION_NAME;
+use ::randr::MonitorInfo;
+use ::xproto::Window;
+use ::Client;
+
+const RANDR_OPCODE: u8 = 43;
+
+/// Creates or replaces the monitor with the same name, RANDR 1.5.
+#[derive(Debug, Clone)]
+pub struct RRSetMonitor {
+    pub window: Window,
+    pub monitor: MonitorInfo,
+}
+
+impl ExtensionRequest for RRSetMonitor {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RANDR_OPCODE)?;
+        a.write_u16::<NativeEndian>(8 + self.monitor.outputs.len() as u16)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        self.monitor.write(&mut a)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
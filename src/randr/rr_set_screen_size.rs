@@ -0,0 +1,53 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::randr::EXTENSION_NAME;
+use ::xproto::Window;
+use ::Client;
+
+const RANDR_OPCODE: u8 = 7;
+
+/// Changes the size of the screen of `window`, every enabled CRTC must fit
+/// in the new size.
+#[derive(Debug, Clone, Copy)]
+pub struct RRSetScreenSize {
+    pub window: Window,
+    pub width: u16,
+    pub height: u16,
+    pub mm_width: u32,
+    pub mm_height: u32,
+}
+
+impl ExtensionRequest for RRSetScreenSize {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RANDR_OPCODE)?;
+        a.write_u16::<NativeEndian>(5)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u16::<NativeEndian>(self.width)?;
+        a.write_u16::<NativeEndian>(self.height)?;
+        a.write_u32::<NativeEndian>(self.mm_width)?;
+        a.write_u32::<NativeEndian>(self.mm_height)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
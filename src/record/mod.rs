@@ -0,0 +1,363 @@
+//! RECORD extension requests, intercepting the protocol traffic of other
+//! clients.
+//!
+//! A context lists the clients and the protocol ranges to intercept. Once
+//! enabled with `RecordStream::enable` the intercepted data is sent as a
+//! stream of replies, usually on a connection dedicated to it.
+
+use ::std::io;
+use ::std::io::Read;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::event;
+use ::event::Event;
+use ::protocol;
+use ::xproto::Timestamp;
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    record_query_version,
+    record_create_context,
+    record_register_clients,
+    record_unregister_clients,
+    record_get_context,
+    record_enable_context,
+    record_disable_context,
+    record_free_context
+}
+
+mod stream;
+pub use self::stream::*;
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"RECORD";
+
+declare_ids! {
+    /// A record context.
+    Context
+}
+
+/// The clients of a context, a resource id of a client or one of the
+/// `CLIENT_SPEC_*` constants.
+pub type ClientSpec = u32;
+pub const CLIENT_SPEC_CURRENT_CLIENTS: ClientSpec = 1;
+pub const CLIENT_SPEC_FUTURE_CLIENTS: ClientSpec = 2;
+pub const CLIENT_SPEC_ALL_CLIENTS: ClientSpec = 3;
+
+/// Extra data sent before each intercepted protocol element.
+pub type ElementHeader = u8;
+
+/// The server time before each element sent by the server.
+pub const ELEMENT_HEADER_FROM_SERVER_TIME: ElementHeader = 1;
+
+/// The server time before each request.
+pub const ELEMENT_HEADER_FROM_CLIENT_TIME: ElementHeader = 2;
+
+/// The sequence number before each request.
+pub const ELEMENT_HEADER_FROM_CLIENT_SEQUENCE: ElementHeader = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Range8 {
+    pub first: u8,
+    pub last: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Range16 {
+    pub first: u16,
+    pub last: u16,
+}
+
+/// A range of extension requests or replies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExtRange {
+    pub major: Range8,
+    pub minor: Range16,
+}
+
+/// The protocol elements intercepted by a context, empty ranges have a
+/// zero `first`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Range {
+    pub core_requests: Range8,
+    pub core_replies: Range8,
+    pub ext_requests: ExtRange,
+    pub ext_replies: ExtRange,
+
+    /// Events sent to clients.
+    pub delivered_events: Range8,
+
+    /// Input events, as processed by the server before being delivered.
+    pub device_events: Range8,
+
+    pub errors: Range8,
+    pub client_started: bool,
+    pub client_died: bool,
+}
+
+impl Range {
+    /// The key, button and motion device events.
+    pub fn input() -> Range {
+        Range {
+            device_events: Range8 {
+                first: event::KEY_PRESS,
+                last: event::MOTION_NOTIFY,
+            },
+            ..Range::default()
+        }
+    }
+
+    fn read<A: Read>(a: &mut A) -> io::Result<Range> {
+        let mut buf = [0u8; 24];
+        a.read_exact(&mut buf)?;
+
+        let range8 = |at: usize| {
+            Range8 {
+                first: buf[at],
+                last: buf[at + 1],
+            }
+        };
+        let ext_range = |at: usize| {
+            ExtRange {
+                major: range8(at),
+                minor: Range16 {
+                    first: NativeEndian::read_u16(&buf[at + 2..at + 4]),
+                    last: NativeEndian::read_u16(&buf[at + 4..at + 6]),
+                },
+            }
+        };
+
+        Ok(Range {
+            core_requests: range8(0),
+            core_replies: range8(2),
+            ext_requests: ext_range(4),
+            ext_replies: ext_range(10),
+            delivered_events: range8(16),
+            device_events: range8(18),
+            errors: range8(20),
+            client_started: buf[22] != 0,
+            client_died: buf[23] != 0,
+        })
+    }
+
+    fn write<A: Write>(&self, a: &mut A) -> io::Result<()> {
+        let range8s = [self.core_requests, self.core_replies];
+        for range in &range8s {
+            a.write_u8(range.first)?;
+            a.write_u8(range.last)?;
+        }
+        for range in &[self.ext_requests, self.ext_replies] {
+            a.write_u8(range.major.first)?;
+            a.write_u8(range.major.last)?;
+            a.write_u16::<NativeEndian>(range.minor.first)?;
+            a.write_u16::<NativeEndian>(range.minor.last)?;
+        }
+        for range in &[self.delivered_events, self.device_events, self.errors] {
+            a.write_u8(range.first)?;
+            a.write_u8(range.last)?;
+        }
+        a.write_u8(self.client_started as u8)?;
+        a.write_u8(self.client_died as u8)?;
+
+        Ok(())
+    }
+}
+
+/// The ranges a context intercepts for a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientInfo {
+    pub client_resource: ClientSpec,
+    pub ranges: Vec<Range>,
+}
+
+/// What the data of a `RecordData` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    /// Replies, events and errors sent by the server.
+    FromServer,
+
+    /// Requests sent by a client.
+    FromClient,
+
+    /// The connection setup of a new client.
+    ClientStarted,
+
+    ClientDied,
+
+    /// The first reply once the context is enabled.
+    StartOfData,
+
+    /// The last reply once the context is disabled.
+    EndOfData,
+}
+
+impl From<u8> for Category {
+    fn from(category: u8) -> Category {
+        match category {
+            0 => Category::FromServer,
+            1 => Category::FromClient,
+            2 => Category::ClientStarted,
+            3 => Category::ClientDied,
+            4 => Category::StartOfData,
+            _ => Category::EndOfData,
+        }
+    }
+}
+
+/// A reply of `RecordEnableContext`, the data intercepted from a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordData {
+    pub category: Category,
+    pub element_header: ElementHeader,
+
+    /// Whether the client uses another byte order, the requests are then
+    /// not decoded.
+    pub client_swapped: bool,
+
+    /// The resource id base of the client.
+    pub id_base: u32,
+
+    pub server_time: Timestamp,
+
+    /// The sequence number of the last request of the client.
+    pub recorded_sequence_number: u32,
+
+    /// The intercepted protocol elements, with their element headers.
+    pub data: Vec<u8>,
+}
+
+/// A protocol element intercepted by a context.
+#[derive(Debug)]
+pub enum Intercepted {
+    /// An event or an error, decoded with `Event::read`.
+    Event(Event),
+
+    /// A reply, the raw packet is kept.
+    Reply(Vec<u8>),
+
+    /// A request, the raw packet is kept.
+    Request(Vec<u8>),
+}
+
+/// An intercepted element with its header.
+#[derive(Debug)]
+pub struct Element {
+    /// The server time, if the element header asks for it.
+    pub time: Option<Timestamp>,
+
+    /// The sequence number of a request, if the element header asks for
+    /// it.
+    pub sequence: Option<u32>,
+
+    pub data: Intercepted,
+}
+
+impl RecordData {
+    /// Decodes a reply of `RecordEnableContext`.
+    pub fn read(buf: &[u8]) -> RecordData {
+        RecordData {
+            category: Category::from(buf[1]),
+            element_header: buf[8],
+            client_swapped: buf[9] != 0,
+            id_base: NativeEndian::read_u32(&buf[12..16]),
+            server_time: NativeEndian::read_u32(&buf[16..20]),
+            recorded_sequence_number: NativeEndian::read_u32(&buf[20..24]),
+            data: buf[32..].to_vec(),
+        }
+    }
+
+    /// Splits the data in protocol elements, the ones truncated or sent by
+    /// a client with another byte order are skipped.
+    pub fn elements(&self) -> Vec<Element> {
+        let mut elements = Vec::new();
+        let mut rest = &self.data[..];
+
+        let from_server = match self.category {
+            Category::FromServer => true,
+            Category::FromClient if !self.client_swapped => false,
+            _ => return elements,
+        };
+
+        loop {
+            let mut time = None;
+            let mut sequence = None;
+            if from_server && self.element_header & ELEMENT_HEADER_FROM_SERVER_TIME != 0 ||
+               !from_server && self.element_header & ELEMENT_HEADER_FROM_CLIENT_TIME != 0 {
+                if rest.len() < 4 {
+                    break;
+                }
+                time = Some(NativeEndian::read_u32(&rest[..4]));
+                rest = &rest[4..];
+            }
+            if !from_server && self.element_header & ELEMENT_HEADER_FROM_CLIENT_SEQUENCE != 0 {
+                if rest.len() < 4 {
+                    break;
+                }
+                sequence = Some(NativeEndian::read_u32(&rest[..4]));
+                rest = &rest[4..];
+            }
+
+            let len = match element_len(rest, from_server) {
+                Some(len) if len > 0 && len <= rest.len() => len,
+                _ => break,
+            };
+            let (packet, tail) = rest.split_at(len);
+            rest = tail;
+
+            let data = if !from_server {
+                Intercepted::Request(packet.to_vec())
+            } else if packet[0] == protocol::REPLY {
+                Intercepted::Reply(packet.to_vec())
+            } else {
+                Intercepted::Event(Event::read(packet))
+            };
+
+            elements.push(Element {
+                time,
+                sequence,
+                data,
+            });
+        }
+
+        elements
+    }
+}
+
+/// The length of the protocol element at the start of `buf`.
+fn element_len(buf: &[u8], from_server: bool) -> Option<usize> {
+    if from_server {
+        if buf.len() < 32 {
+            return None;
+        }
+
+        if buf[0] == protocol::REPLY || buf[0] & 0x7f == protocol::GENERIC_EVENT {
+            Some(32 + NativeEndian::read_u32(&buf[4..8]) as usize * 4)
+        } else {
+            Some(32)
+        }
+    } else {
+        if buf.len() < 4 {
+            return None;
+        }
+
+        // A zero length is followed by the length of a BIG-REQUESTS
+        // request.
+        match NativeEndian::read_u16(&buf[2..4]) {
+            0 if buf.len() >= 8 => Some(NativeEndian::read_u32(&buf[4..8]) as usize * 4),
+            0 => None,
+            len => Some(len as usize * 4),
+        }
+    }
+}
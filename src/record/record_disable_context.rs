@@ -0,0 +1,46 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::record::EXTENSION_NAME;
+use ::record::Context;
+use ::Client;
+
+const RECORD_OPCODE: u8 = 6;
+
+/// Disables an enabled context, it must be sent from another connection
+/// than the one that enabled it. The last reply of `RecordEnableContext` is
+/// then sent.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordDisableContext {
+    pub context: Context,
+}
+
+impl ExtensionRequest for RecordDisableContext {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RECORD_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.context.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
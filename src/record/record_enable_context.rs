@@ -0,0 +1,46 @@
+use ::std::io;
+
+use ::futures;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::ExtensionInfo;
+use ::record::EXTENSION_NAME;
+use ::record::Context;
+use ::Client;
+
+const RECORD_OPCODE: u8 = 5;
+
+/// Enables a context, the intercepted data is then sent as replies until
+/// the context is disabled. The reply is the sequence number of the
+/// request, the replies are read with `RecordStream`.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordEnableContext {
+    pub context: Context,
+}
+
+impl ExtensionRequest for RecordEnableContext {
+    type Reply = u16;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RECORD_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.context.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        let sequence = client.sequence;
+        Box::new(futures::finished((client, sequence)))
+    }
+}
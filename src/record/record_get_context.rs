@@ -0,0 +1,86 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::record::EXTENSION_NAME;
+use ::record::ClientInfo;
+use ::record::Context;
+use ::record::ElementHeader;
+use ::record::Range;
+use ::Client;
+
+const RECORD_OPCODE: u8 = 4;
+
+/// Returns the clients and ranges of a context.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordGetContext {
+    pub context: Context,
+}
+
+impl ExtensionRequest for RecordGetContext {
+    type Reply = RecordGetContextReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RECORD_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.context.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let mut a = io::Cursor::new(&buf);
+
+            a.set_position(12);
+            let count = a.read_u32::<NativeEndian>()?;
+            a.set_position(32);
+
+            let mut clients = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                let client_resource = a.read_u32::<NativeEndian>()?;
+                let num_ranges = a.read_u32::<NativeEndian>()?;
+
+                let mut ranges = Vec::with_capacity(num_ranges as usize);
+                for _ in 0..num_ranges {
+                    ranges.push(Range::read(&mut a)?);
+                }
+
+                clients.push(ClientInfo {
+                    client_resource,
+                    ranges,
+                });
+            }
+
+            let reply = RecordGetContextReply {
+                enabled: buf[1] != 0,
+                element_header: buf[8],
+                clients,
+            };
+
+            Ok((client, reply))
+        }))
+    }
+}
+
+/// Reply of `RecordGetContext` request.
+#[derive(Debug, Clone)]
+pub struct RecordGetContextReply {
+    pub enabled: bool,
+    pub element_header: ElementHeader,
+    pub clients: Vec<ClientInfo>,
+}
@@ -0,0 +1,75 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::record::EXTENSION_NAME;
+use ::Client;
+
+const RECORD_OPCODE: u8 = 0;
+
+/// Returns the version of the extension supported by the server.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordQueryVersion {
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl ExtensionRequest for RecordQueryVersion {
+    type Reply = RecordQueryVersionReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RECORD_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u16::<NativeEndian>(self.major_version)?;
+        a.write_u16::<NativeEndian>(self.minor_version)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = RecordQueryVersionReply {
+                major_version: NativeEndian::read_u16(&buf[8..10]),
+                minor_version: NativeEndian::read_u16(&buf[10..12]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `RecordQueryVersion` request.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordQueryVersionReply {
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl VersionRequest for RecordQueryVersion {
+    fn latest() -> RecordQueryVersion {
+        RecordQueryVersion {
+            major_version: 1,
+            minor_version: 13,
+        }
+    }
+
+    fn version(reply: &RecordQueryVersionReply) -> Version {
+        Version::new(u32::from(reply.major_version), u32::from(reply.minor_version))
+    }
+}
@@ -0,0 +1,63 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::record::EXTENSION_NAME;
+use ::record::ClientSpec;
+use ::record::Context;
+use ::record::ElementHeader;
+use ::record::Range;
+use ::Client;
+
+const RECORD_OPCODE: u8 = 2;
+
+/// Adds clients and ranges to a context, the ranges of the clients alreadyn/// in it are replaced.
+#[derive(Debug, Clone)]
+pub struct RecordRegisterClients {
+    pub context: Context,
+    pub element_header: ElementHeader,
+    pub client_specs: Vec<ClientSpec>,
+    pub ranges: Vec<Range>,
+}
+
+impl ExtensionRequest for RecordRegisterClients {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let length = 5 + self.client_specs.len() + 6 * self.ranges.len();
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RECORD_OPCODE)?;
+        a.write_u16::<NativeEndian>(length as u16)?;
+        a.write_u32::<NativeEndian>(self.context.0)?;
+        a.write_u8(self.element_header)?;
+        a.write_all(&[0; 3])?;
+        a.write_u32::<NativeEndian>(self.client_specs.len() as u32)?;
+        a.write_u32::<NativeEndian>(self.ranges.len() as u32)?;
+        for &spec in &self.client_specs {
+            a.write_u32::<NativeEndian>(spec)?;
+        }
+        for range in &self.ranges {
+            range.write(&mut a)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
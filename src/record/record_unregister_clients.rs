@@ -0,0 +1,50 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::record::EXTENSION_NAME;
+use ::record::ClientSpec;
+use ::record::Context;
+use ::Client;
+
+const RECORD_OPCODE: u8 = 3;
+
+/// Removes clients from a context.
+#[derive(Debug, Clone)]
+pub struct RecordUnregisterClients {
+    pub context: Context,
+    pub client_specs: Vec<ClientSpec>,
+}
+
+impl ExtensionRequest for RecordUnregisterClients {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RECORD_OPCODE)?;
+        a.write_u16::<NativeEndian>(3 + self.client_specs.len() as u16)?;
+        a.write_u32::<NativeEndian>(self.context.0)?;
+        a.write_u32::<NativeEndian>(self.client_specs.len() as u32)?;
+        for &spec in &self.client_specs {
+            a.write_u32::<NativeEndian>(spec)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,94 @@
+use ::std::io;
+
+use ::futures::Async;
+use ::futures::Future;
+use ::futures::Poll;
+use ::futures::Stream;
+
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply_to;
+use ::record::Category;
+use ::record::Context;
+use ::record::RecordData;
+use ::record::RecordEnableContext;
+use ::Client;
+
+/// Stream of the replies of an enabled context.
+///
+/// The server sends them until the context is disabled, meanwhile the
+/// connection can't be used for other requests, so a connection dedicated
+/// to recording is recommended. `RecordDisableContext` has to be sent from
+/// another connection, the stream then ends after the `EndOfData` reply.
+pub struct RecordStream {
+    client: Option<Client>,
+    sequence: u16,
+    reading: Option<ReplyFuture<Vec<u8>>>,
+    done: bool,
+}
+
+impl RecordStream {
+    /// Enables `context` and returns the stream of its replies.
+    pub fn enable(client: Client, context: Context)
+                  -> Box<dyn Future<Item = RecordStream, Error = io::Error>> {
+        Box::new(client.perform_ex(RecordEnableContext { context })
+            .map(|(client, sequence)| RecordStream::resume(client, sequence)))
+    }
+
+    /// Reads the replies of the `RecordEnableContext` request with the given
+    /// sequence number.
+    pub fn resume(client: Client, sequence: u16) -> RecordStream {
+        RecordStream {
+            client: Some(client),
+            sequence,
+            reading: None,
+            done: false,
+        }
+    }
+
+    /// The sequence number of the `RecordEnableContext` request.
+    pub fn sequence(&self) -> u16 {
+        self.sequence
+    }
+
+    /// Returns the client, `None` is returned while a reply is being read
+    /// or after an error.
+    pub fn into_client(self) -> Option<Client> {
+        self.client
+    }
+}
+
+impl Stream for RecordStream {
+    type Item = RecordData;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, io::Error> {
+        if self.done {
+            return Ok(Async::Ready(None));
+        }
+
+        if self.reading.is_none() {
+            let client = match self.client.take() {
+                Some(client) => client,
+                None => return Ok(Async::Ready(None)),
+            };
+
+            self.reading = Some(read_reply_to(client, self.sequence));
+        }
+
+        let (client, buf) = match self.reading.as_mut().unwrap().poll() {
+            Ok(Async::Ready(result)) => result,
+            Ok(Async::NotReady) => return Ok(Async::NotReady),
+            Err(e) => {
+                self.reading = None;
+                return Err(e);
+            }
+        };
+
+        self.reading = None;
+        self.client = Some(client);
+
+        let data = RecordData::read(&buf);
+        self.done = data.category == Category::EndOfData;
+        Ok(Async::Ready(Some(data)))
+    }
+}
@@ -0,0 +1,477 @@
+//! RENDER extension requests, used for anti-aliased drawing and
+//! compositing.
+
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::xproto::Atom;
+use ::xproto::Pixmap;
+
+pub use ::xproto::Rectangle;
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    render_query_version,
+    render_query_pict_formats,
+    render_create_picture,
+    render_change_picture,
+    render_free_picture,
+    render_composite,
+    render_fill_rectangles,
+    render_trapezoids,
+    render_triangles,
+    render_create_glyph_set,
+    render_free_glyph_set,
+    render_add_glyphs,
+    render_composite_glyphs,
+    render_set_picture_transform,
+    render_set_picture_filter,
+    render_create_solid_fill,
+    render_create_linear_gradient,
+    render_create_radial_gradient,
+    render_create_conical_gradient
+}
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"RENDER";
+
+declare_ids! {
+    /// A picture, a drawable with a format used by the RENDER requests.
+    Picture,
+
+    /// A picture format.
+    Pictformat,
+
+    /// A set of glyphs.
+    Glyphset
+}
+
+/// A glyph id of a `Glyphset`.
+pub type Glyph = u32;
+
+/// Compositing operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PictOp {
+    Clear = 0,
+    Src = 1,
+    Dst = 2,
+    Over = 3,
+    OverReverse = 4,
+    In = 5,
+    InReverse = 6,
+    Out = 7,
+    OutReverse = 8,
+    Atop = 9,
+    AtopReverse = 10,
+    Xor = 11,
+    Add = 12,
+    Saturate = 13,
+    Multiply = 48,
+    Screen = 49,
+    Overlay = 50,
+    Darken = 51,
+    Lighten = 52,
+    ColorDodge = 53,
+    ColorBurn = 54,
+    HardLight = 55,
+    SoftLight = 56,
+    Difference = 57,
+    Exclusion = 58,
+    HslHue = 59,
+    HslSaturation = 60,
+    HslColor = 61,
+    HslLuminosity = 62,
+}
+
+/// A 16.16 fixed point number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Fixed(pub i32);
+
+impl Fixed {
+    pub const ONE: Fixed = Fixed(0x10000);
+
+    pub fn from_f64(value: f64) -> Fixed {
+        Fixed((value * 65536.0).round() as i32)
+    }
+
+    pub fn from_int(value: i16) -> Fixed {
+        Fixed((value as i32) << 16)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / 65536.0
+    }
+}
+
+/// A color with 16 bit channels, premultiplied by alpha.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Color {
+    pub red: u16,
+    pub green: u16,
+    pub blue: u16,
+    pub alpha: u16,
+}
+
+impl Color {
+    /// Creates a color from non premultiplied 8 bit channels.
+    pub fn from_rgba8(red: u8, green: u8, blue: u8, alpha: u8) -> Color {
+        let premultiply = |c: u8| (c as u32 * alpha as u32 * 0x101 / 0xff) as u16;
+
+        Color {
+            red: premultiply(red),
+            green: premultiply(green),
+            blue: premultiply(blue),
+            alpha: alpha as u16 * 0x101,
+        }
+    }
+
+    fn write<A: Write>(&self, a: &mut A) -> io::Result<()> {
+        a.write_u16::<NativeEndian>(self.red)?;
+        a.write_u16::<NativeEndian>(self.green)?;
+        a.write_u16::<NativeEndian>(self.blue)?;
+        a.write_u16::<NativeEndian>(self.alpha)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PointFix {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+
+impl PointFix {
+    pub fn new(x: f64, y: f64) -> PointFix {
+        PointFix {
+            x: Fixed::from_f64(x),
+            y: Fixed::from_f64(y),
+        }
+    }
+
+    fn write<A: Write>(&self, a: &mut A) -> io::Result<()> {
+        a.write_i32::<NativeEndian>(self.x.0)?;
+        a.write_i32::<NativeEndian>(self.y.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LineFix {
+    pub p1: PointFix,
+    pub p2: PointFix,
+}
+
+/// A trapezoid with horizontal top and bottom edges, the sides are given
+/// by two lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Trapezoid {
+    pub top: Fixed,
+    pub bottom: Fixed,
+    pub left: LineFix,
+    pub right: LineFix,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Triangle {
+    pub p1: PointFix,
+    pub p2: PointFix,
+    pub p3: PointFix,
+}
+
+/// A projective transformation matrix, in row order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transform(pub [[Fixed; 3]; 3]);
+
+impl Transform {
+    pub fn identity() -> Transform {
+        let zero = Fixed(0);
+        Transform([[Fixed::ONE, zero, zero], [zero, Fixed::ONE, zero], [zero, zero, Fixed::ONE]])
+    }
+
+    /// Scales the source by (`sx`, `sy`), note the transform maps
+    /// destination to source coordinates so the matrix holds the inverse.
+    pub fn scale(sx: f64, sy: f64) -> Transform {
+        let zero = Fixed(0);
+        Transform([[Fixed::from_f64(1.0 / sx), zero, zero],
+                   [zero, Fixed::from_f64(1.0 / sy), zero],
+                   [zero, zero, Fixed::ONE]])
+    }
+}
+
+/// A color stop of a gradient, `offset` goes from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stop {
+    pub offset: Fixed,
+    pub color: Color,
+}
+
+/// Writes the stops of a gradient, all the offsets come first.
+fn write_stops<A: Write>(a: &mut A, stops: &[Stop]) -> io::Result<()> {
+    a.write_u32::<NativeEndian>(stops.len() as u32)?;
+    for stop in stops {
+        a.write_i32::<NativeEndian>(stop.offset.0)?;
+    }
+    for stop in stops {
+        stop.color.write(a)?;
+    }
+
+    Ok(())
+}
+
+/// How a picture is repeated outside of its bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repeat {
+    None = 0,
+    Normal = 1,
+    Pad = 2,
+    Reflect = 3,
+}
+
+/// Attributes of a picture, used by `RenderCreatePicture` and
+/// `RenderChangePicture`.
+#[derive(Debug, Clone, Copy)]
+pub struct PictureAttributes {
+    repeat: Repeat,
+    alpha_map: Picture,
+    alpha_x_origin: i16,
+    alpha_y_origin: i16,
+    clip_x_origin: i16,
+    clip_y_origin: i16,
+    clip_mask: Pixmap,
+    graphics_exposures: bool,
+    subwindow_mode_include_inferiors: bool,
+    poly_edge_smooth: bool,
+    poly_mode_imprecise: bool,
+    dither: Atom,
+    component_alpha: bool,
+
+    value_mask: u32,
+}
+
+impl Default for PictureAttributes {
+    fn default() -> PictureAttributes {
+        PictureAttributes::new()
+    }
+}
+
+impl PictureAttributes {
+    pub fn new() -> PictureAttributes {
+        PictureAttributes {
+            repeat: Repeat::None,
+            alpha_map: Picture::NONE,
+            alpha_x_origin: 0,
+            alpha_y_origin: 0,
+            clip_x_origin: 0,
+            clip_y_origin: 0,
+            clip_mask: Pixmap::NONE,
+            graphics_exposures: false,
+            subwindow_mode_include_inferiors: false,
+            poly_edge_smooth: true,
+            poly_mode_imprecise: false,
+            dither: Atom::NONE,
+            component_alpha: false,
+
+            value_mask: 0,
+        }
+    }
+
+    pub fn repeat(&mut self, value: Repeat) -> &mut Self {
+        self.repeat = value;
+        self.value_mask |= 0x0001;
+        self
+    }
+
+    pub fn alpha_map(&mut self, value: Picture) -> &mut Self {
+        self.alpha_map = value;
+        self.value_mask |= 0x0002;
+        self
+    }
+
+    pub fn alpha_origin(&mut self, x: i16, y: i16) -> &mut Self {
+        self.alpha_x_origin = x;
+        self.alpha_y_origin = y;
+        self.value_mask |= 0x0004 | 0x0008;
+        self
+    }
+
+    pub fn clip_origin(&mut self, x: i16, y: i16) -> &mut Self {
+        self.clip_x_origin = x;
+        self.clip_y_origin = y;
+        self.value_mask |= 0x0010 | 0x0020;
+        self
+    }
+
+    pub fn clip_mask(&mut self, value: Pixmap) -> &mut Self {
+        self.clip_mask = value;
+        self.value_mask |= 0x0040;
+        self
+    }
+
+    pub fn graphics_exposures(&mut self, value: bool) -> &mut Self {
+        self.graphics_exposures = value;
+        self.value_mask |= 0x0080;
+        self
+    }
+
+    /// Whether drawing on a window picture also draws over its children.
+    pub fn subwindow_mode_include_inferiors(&mut self, value: bool) -> &mut Self {
+        self.subwindow_mode_include_inferiors = value;
+        self.value_mask |= 0x0100;
+        self
+    }
+
+    /// Whether the edges of trapezoids and triangles are anti-aliased.
+    pub fn poly_edge_smooth(&mut self, value: bool) -> &mut Self {
+        self.poly_edge_smooth = value;
+        self.value_mask |= 0x0200;
+        self
+    }
+
+    pub fn poly_mode_imprecise(&mut self, value: bool) -> &mut Self {
+        self.poly_mode_imprecise = value;
+        self.value_mask |= 0x0400;
+        self
+    }
+
+    pub fn dither(&mut self, value: Atom) -> &mut Self {
+        self.dither = value;
+        self.value_mask |= 0x0800;
+        self
+    }
+
+    /// Whether each color channel of a mask is applied separately, used
+    /// for sub-pixel text.
+    pub fn component_alpha(&mut self, value: bool) -> &mut Self {
+        self.component_alpha = value;
+        self.value_mask |= 0x1000;
+        self
+    }
+
+    pub fn build(self) -> Self {
+        self
+    }
+
+    /// Encodes the value mask and list, returning the number of values.
+    fn encode(&self) -> io::Result<(Vec<u8>, u16)> {
+        let values = [self.repeat as u32,
+                      self.alpha_map.0,
+                      self.alpha_x_origin as u32,
+                      self.alpha_y_origin as u32,
+                      self.clip_x_origin as u32,
+                      self.clip_y_origin as u32,
+                      self.clip_mask.0,
+                      self.graphics_exposures as u32,
+                      self.subwindow_mode_include_inferiors as u32,
+                      !self.poly_edge_smooth as u32,
+                      self.poly_mode_imprecise as u32,
+                      self.dither.0,
+                      self.component_alpha as u32];
+
+        let mut count = 0u16;
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u32::<NativeEndian>(self.value_mask)?;
+        for (i, &value) in values.iter().enumerate() {
+            if self.value_mask & (1 << i) != 0 {
+                a.write_u32::<NativeEndian>(value)?;
+                count += 1;
+            }
+        }
+
+        Ok((a.into_inner(), count))
+    }
+}
+
+/// Layout of the channels of a direct format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DirectFormat {
+    pub red_shift: u16,
+    pub red_mask: u16,
+    pub green_shift: u16,
+    pub green_mask: u16,
+    pub blue_shift: u16,
+    pub blue_mask: u16,
+    pub alpha_shift: u16,
+    pub alpha_mask: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PictType {
+    Indexed,
+    Direct,
+}
+
+/// Description of a picture format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PictFormInfo {
+    pub id: Pictformat,
+    pub type_: PictType,
+    pub depth: u8,
+    pub direct: DirectFormat,
+    pub colormap: ::xproto::Colormap,
+}
+
+/// The well known formats every server has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StandardFormat {
+    Argb32,
+    Rgb24,
+    A8,
+    A4,
+    A1,
+}
+
+impl StandardFormat {
+    /// Returns the format matching `name`, like `"ARGB32"`.
+    pub fn from_name(name: &str) -> Option<StandardFormat> {
+        match name {
+            "ARGB32" => Some(StandardFormat::Argb32),
+            "RGB24" => Some(StandardFormat::Rgb24),
+            "A8" => Some(StandardFormat::A8),
+            "A4" => Some(StandardFormat::A4),
+            "A1" => Some(StandardFormat::A1),
+            _ => None,
+        }
+    }
+
+    /// Whether `info` is this format.
+    pub fn matches(self, info: &PictFormInfo) -> bool {
+        let (depth, direct) = match self {
+            StandardFormat::Argb32 => (32, direct(16, 8, 0, 0xff, 24, 0xff)),
+            StandardFormat::Rgb24 => (24, direct(16, 8, 0, 0xff, 0, 0)),
+            StandardFormat::A8 => (8, direct(0, 0, 0, 0, 0, 0xff)),
+            StandardFormat::A4 => (4, direct(0, 0, 0, 0, 0, 0xf)),
+            StandardFormat::A1 => (1, direct(0, 0, 0, 0, 0, 0x1)),
+        };
+
+        info.type_ == PictType::Direct && info.depth == depth && info.direct == direct
+    }
+}
+
+fn direct(red_shift: u16,
+          green_shift: u16,
+          blue_shift: u16,
+          color_mask: u16,
+          alpha_shift: u16,
+          alpha_mask: u16)
+          -> DirectFormat {
+    DirectFormat {
+        red_shift,
+        red_mask: color_mask,
+        green_shift,
+        green_mask: color_mask,
+        blue_shift,
+        blue_mask: color_mask,
+        alpha_shift,
+        alpha_mask,
+    }
+}
@@ -0,0 +1,97 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::request_length;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::render::EXTENSION_NAME;
+use ::render::Glyph;
+use ::render::Glyphset;
+use ::Client;
+use ::pad;
+
+const RENDER_OPCODE: u8 = 20;
+
+/// Metrics of a glyph image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GlyphInfo {
+    pub width: u16,
+    pub height: u16,
+
+    /// Position of the origin in the image, e.g. the bearing left of the
+    /// glyph and the ascent above the baseline.
+    pub x: i16,
+    pub y: i16,
+
+    /// Where the next glyph is drawn, relative to this one.
+    pub x_off: i16,
+    pub y_off: i16,
+}
+
+/// A glyph to upload.
+#[derive(Debug, Clone)]
+pub struct GlyphData {
+    pub id: Glyph,
+    pub info: GlyphInfo,
+
+    /// The image, in the format of the glyph set with each row padded to
+    /// 4 bytes.
+    pub image: Vec<u8>,
+}
+
+/// Adds glyphs to a glyph set, replacing the glyphs with the same id.
+#[derive(Debug, Clone)]
+pub struct RenderAddGlyphs {
+    pub glyphset: Glyphset,
+    pub glyphs: Vec<GlyphData>,
+}
+
+impl ExtensionRequest for RenderAddGlyphs {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let n = self.glyphs.len();
+        let data_len: usize = self.glyphs.iter().map(|glyph| glyph.image.len()).sum();
+        let p = pad(data_len);
+        let length = request_length(3 + 4 * n + (data_len + p) / 4)?;
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RENDER_OPCODE)?;
+        a.write_u16::<NativeEndian>(length)?;
+        a.write_u32::<NativeEndian>(self.glyphset.0)?;
+        a.write_u32::<NativeEndian>(n as u32)?;
+        for glyph in &self.glyphs {
+            a.write_u32::<NativeEndian>(glyph.id)?;
+        }
+        for glyph in &self.glyphs {
+            a.write_u16::<NativeEndian>(glyph.info.width)?;
+            a.write_u16::<NativeEndian>(glyph.info.height)?;
+            a.write_i16::<NativeEndian>(glyph.info.x)?;
+            a.write_i16::<NativeEndian>(glyph.info.y)?;
+            a.write_i16::<NativeEndian>(glyph.info.x_off)?;
+            a.write_i16::<NativeEndian>(glyph.info.y_off)?;
+        }
+        for glyph in &self.glyphs {
+            a.write_all(&glyph.image)?;
+        }
+        a.write_all(&[0; 3][..p])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,49 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::render::EXTENSION_NAME;
+use ::render::Picture;
+use ::render::PictureAttributes;
+use ::Client;
+
+const RENDER_OPCODE: u8 = 5;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RenderChangePicture {
+    pub picture: Picture,
+    pub attrs: PictureAttributes,
+}
+
+impl ExtensionRequest for RenderChangePicture {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let (buf, n) = self.attrs.encode()?;
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RENDER_OPCODE)?;
+        a.write_u16::<NativeEndian>(2 + n)?;
+        a.write_u32::<NativeEndian>(self.picture.0)?;
+        a.write_all(&buf)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
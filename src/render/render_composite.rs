@@ -0,0 +1,70 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::render::EXTENSION_NAME;
+use ::render::PictOp;
+use ::render::Picture;
+use ::Client;
+
+const RENDER_OPCODE: u8 = 8;
+
+/// Combines `src`, through `mask` if it isn't `Picture::NONE`, with a
+/// rectangle of `dst`.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderComposite {
+    pub op: PictOp,
+    pub src: Picture,
+    pub mask: Picture,
+    pub dst: Picture,
+    pub src_x: i16,
+    pub src_y: i16,
+    pub mask_x: i16,
+    pub mask_y: i16,
+    pub dst_x: i16,
+    pub dst_y: i16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl ExtensionRequest for RenderComposite {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RENDER_OPCODE)?;
+        a.write_u16::<NativeEndian>(9)?;
+        a.write_u8(self.op as u8)?;
+        a.write_all(&[0; 3])?;
+        a.write_u32::<NativeEndian>(self.src.0)?;
+        a.write_u32::<NativeEndian>(self.mask.0)?;
+        a.write_u32::<NativeEndian>(self.dst.0)?;
+        a.write_i16::<NativeEndian>(self.src_x)?;
+        a.write_i16::<NativeEndian>(self.src_y)?;
+        a.write_i16::<NativeEndian>(self.mask_x)?;
+        a.write_i16::<NativeEndian>(self.mask_y)?;
+        a.write_i16::<NativeEndian>(self.dst_x)?;
+        a.write_i16::<NativeEndian>(self.dst_y)?;
+        a.write_u16::<NativeEndian>(self.width)?;
+        a.write_u16::<NativeEndian>(self.height)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
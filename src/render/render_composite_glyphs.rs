@@ -0,0 +1,136 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::render::EXTENSION_NAME;
+use ::render::Glyph;
+use ::render::Glyphset;
+use ::render::PictOp;
+use ::render::Pictformat;
+use ::render::Picture;
+use ::Client;
+use ::pad;
+
+/// Maximum number of glyphs of an element.
+const MAX_GLYPHS: usize = 254;
+
+/// An element of a `CompositeGlyphs` request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GlyphItem {
+    /// Glyphs drawn one after another, starting at (`dx`, `dy`) from the
+    /// end of the previous element.
+    Glyphs { dx: i16, dy: i16, glyphs: Vec<Glyph> },
+
+    /// Draws the following glyphs from another glyph set.
+    Glyphset(Glyphset),
+}
+
+/// Encodes the elements with glyph ids of `size` bytes.
+fn write_items<A: Write>(a: &mut A, items: &[GlyphItem], size: usize) -> io::Result<()> {
+    for item in items {
+        match *item {
+            GlyphItem::Glyphs { dx, dy, ref glyphs } => {
+                // Long runs are split, the following elements continue
+                // where the previous one ended.
+                for (i, chunk) in glyphs.chunks(MAX_GLYPHS).enumerate() {
+                    let (dx, dy) = if i == 0 { (dx, dy) } else { (0, 0) };
+
+                    a.write_u8(chunk.len() as u8)?;
+                    a.write_all(&[0; 3])?;
+                    a.write_i16::<NativeEndian>(dx)?;
+                    a.write_i16::<NativeEndian>(dy)?;
+                    for &glyph in chunk {
+                        match size {
+                            1 => a.write_u8(glyph as u8)?,
+                            2 => a.write_u16::<NativeEndian>(glyph as u16)?,
+                            _ => a.write_u32::<NativeEndian>(glyph)?,
+                        }
+                    }
+                    a.write_all(&[0; 3][..pad(chunk.len() * size)])?;
+                }
+            }
+            GlyphItem::Glyphset(glyphset) => {
+                a.write_u8(255)?;
+                a.write_all(&[0; 7])?;
+                a.write_u32::<NativeEndian>(glyphset.0)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+macro_rules! composite_glyphs {
+    ($($(#[$attr:meta])* $name:ident, $opcode:expr, $size:expr;)+) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug, Clone)]
+            pub struct $name {
+                pub op: PictOp,
+                pub src: Picture,
+                pub dst: Picture,
+
+                /// Format used to combine the glyphs before compositing,
+                /// `Pictformat::NONE` composites each glyph separately.
+                pub mask_format: Pictformat,
+
+                pub glyphset: Glyphset,
+                pub src_x: i16,
+                pub src_y: i16,
+                pub items: Vec<GlyphItem>,
+            }
+
+            impl ExtensionRequest for $name {
+                type Reply = VoidReply;
+
+                fn extension_name() -> &'static [u8] {
+                    EXTENSION_NAME
+                }
+
+                fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+                    let mut items = vec![];
+                    write_items(&mut items, &self.items, $size)?;
+
+                    let mut a = io::Cursor::new(vec![]);
+
+                    a.write_u8(info.major_opcode)?;
+                    a.write_u8($opcode)?;
+                    a.write_u16::<NativeEndian>((7 + items.len() / 4) as u16)?;
+                    a.write_u8(self.op as u8)?;
+                    a.write_all(&[0; 3])?;
+                    a.write_u32::<NativeEndian>(self.src.0)?;
+                    a.write_u32::<NativeEndian>(self.dst.0)?;
+                    a.write_u32::<NativeEndian>(self.mask_format.0)?;
+                    a.write_u32::<NativeEndian>(self.glyphset.0)?;
+                    a.write_i16::<NativeEndian>(self.src_x)?;
+                    a.write_i16::<NativeEndian>(self.src_y)?;
+                    a.write_all(&items)?;
+
+                    Ok(a.into_inner())
+                }
+
+                fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+                    void_reply(client)
+                }
+            }
+        )+
+    }
+}
+
+composite_glyphs! {
+    /// Draws glyphs with ids up to 255.
+    RenderCompositeGlyphs8, 23, 1;
+
+    /// Draws glyphs with ids up to 65535.
+    RenderCompositeGlyphs16, 24, 2;
+
+    /// Draws glyphs with 32 bit ids.
+    RenderCompositeGlyphs32, 25, 4;
+}
@@ -0,0 +1,48 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::render::EXTENSION_NAME;
+use ::render::Glyphset;
+use ::render::Pictformat;
+use ::Client;
+
+const RENDER_OPCODE: u8 = 17;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RenderCreateGlyphSet {
+    pub gsid: Glyphset,
+
+    /// The format of the glyph images, usually `A8`.
+    pub format: Pictformat,
+}
+
+impl ExtensionRequest for RenderCreateGlyphSet {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RENDER_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.gsid.0)?;
+        a.write_u32::<NativeEndian>(self.format.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
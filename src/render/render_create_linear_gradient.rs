@@ -0,0 +1,54 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::request_length;
+use ::render::EXTENSION_NAME;
+use ::render::Picture;
+use ::render::PointFix;
+use ::render::Stop;
+use ::render::write_stops;
+use ::Client;
+
+const RENDER_OPCODE: u8 = 34;
+
+/// Creates a source picture with a gradient from `p1` to `p2`.
+#[derive(Debug, Clone)]
+pub struct RenderCreateLinearGradient {
+    pub picture: Picture,
+    pub p1: PointFix,
+    pub p2: PointFix,
+    pub stops: Vec<Stop>,
+}
+
+impl ExtensionRequest for RenderCreateLinearGradient {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RENDER_OPCODE)?;
+        a.write_u16::<NativeEndian>(request_length(7 + 3 * self.stops.len())?)?;
+        a.write_u32::<NativeEndian>(self.picture.0)?;
+        self.p1.write(&mut a)?;
+        self.p2.write(&mut a)?;
+        write_stops(&mut a, &self.stops)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,59 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::request_length;
+use ::render::EXTENSION_NAME;
+use ::render::Fixed;
+use ::render::Picture;
+use ::render::PointFix;
+use ::render::Stop;
+use ::render::write_stops;
+use ::Client;
+
+const RENDER_OPCODE: u8 = 35;
+
+/// Creates a source picture with a gradient between two circles.
+#[derive(Debug, Clone)]
+pub struct RenderCreateRadialGradient {
+    pub picture: Picture,
+    pub inner: PointFix,
+    pub outer: PointFix,
+    pub inner_radius: Fixed,
+    pub outer_radius: Fixed,
+    pub stops: Vec<Stop>,
+}
+
+impl ExtensionRequest for RenderCreateRadialGradient {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RENDER_OPCODE)?;
+        a.write_u16::<NativeEndian>(request_length(9 + 3 * self.stops.len())?)?;
+        a.write_u32::<NativeEndian>(self.picture.0)?;
+        self.inner.write(&mut a)?;
+        self.outer.write(&mut a)?;
+        a.write_i32::<NativeEndian>(self.inner_radius.0)?;
+        a.write_i32::<NativeEndian>(self.outer_radius.0)?;
+        write_stops(&mut a, &self.stops)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
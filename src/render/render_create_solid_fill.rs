@@ -0,0 +1,48 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::render::EXTENSION_NAME;
+use ::render::Color;
+use ::render::Picture;
+use ::Client;
+
+const RENDER_OPCODE: u8 = 33;
+
+/// Creates a picture of infinite size filled with `color`, usable as a
+/// source.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderCreateSolidFill {
+    pub picture: Picture,
+    pub color: Color,
+}
+
+impl ExtensionRequest for RenderCreateSolidFill {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RENDER_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u32::<NativeEndian>(self.picture.0)?;
+        self.color.write(&mut a)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
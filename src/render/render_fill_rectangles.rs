@@ -0,0 +1,58 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::request_length;
+use ::render::EXTENSION_NAME;
+use ::render::Color;
+use ::render::PictOp;
+use ::render::Picture;
+use ::render::Rectangle;
+use ::Client;
+
+const RENDER_OPCODE: u8 = 26;
+
+/// Fills rectangles of `dst` with a solid color.
+#[derive(Debug, Clone)]
+pub struct RenderFillRectangles {
+    pub op: PictOp,
+    pub dst: Picture,
+    pub color: Color,
+    pub rects: Vec<Rectangle>,
+}
+
+impl ExtensionRequest for RenderFillRectangles {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RENDER_OPCODE)?;
+        a.write_u16::<NativeEndian>(request_length(5 + 2 * self.rects.len())?)?;
+        a.write_u8(self.op as u8)?;
+        a.write_all(&[0; 3])?;
+        a.write_u32::<NativeEndian>(self.dst.0)?;
+        self.color.write(&mut a)?;
+        for rect in &self.rects {
+            rect.write(&mut a)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
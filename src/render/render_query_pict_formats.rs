@@ -0,0 +1,202 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::render::EXTENSION_NAME;
+use ::render::DirectFormat;
+use ::render::PictFormInfo;
+use ::render::PictType;
+use ::render::Pictformat;
+use ::render::StandardFormat;
+use ::xproto::Colormap;
+use ::xproto::Visualid;
+use ::Client;
+use ::ServerInfo;
+use ::Visual;
+
+const RENDER_OPCODE: u8 = 1;
+
+/// Lists the picture formats supported and the formats of the visuals.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderQueryPictFormats;
+
+impl ExtensionRequest for RenderQueryPictFormats {
+    type Reply = RenderQueryPictFormatsReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RENDER_OPCODE)?;
+        a.write_u16::<NativeEndian>(1)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let mut a = io::Cursor::new(buf);
+            a.set_position(8);
+
+            let num_formats = a.read_u32::<NativeEndian>()?;
+            let num_screens = a.read_u32::<NativeEndian>()?;
+            a.read_u32::<NativeEndian>()?;
+            a.read_u32::<NativeEndian>()?;
+            let num_subpixels = a.read_u32::<NativeEndian>()?;
+            a.set_position(32);
+
+            let mut formats = Vec::with_capacity(num_formats as usize);
+            for _ in 0..num_formats {
+                let id = Pictformat(a.read_u32::<NativeEndian>()?);
+                let type_ = if a.read_u8()? == 0 {
+                    PictType::Indexed
+                } else {
+                    PictType::Direct
+                };
+                let depth = a.read_u8()?;
+                a.read_u16::<NativeEndian>()?;
+                let direct = DirectFormat {
+                    red_shift: a.read_u16::<NativeEndian>()?,
+                    red_mask: a.read_u16::<NativeEndian>()?,
+                    green_shift: a.read_u16::<NativeEndian>()?,
+                    green_mask: a.read_u16::<NativeEndian>()?,
+                    blue_shift: a.read_u16::<NativeEndian>()?,
+                    blue_mask: a.read_u16::<NativeEndian>()?,
+                    alpha_shift: a.read_u16::<NativeEndian>()?,
+                    alpha_mask: a.read_u16::<NativeEndian>()?,
+                };
+                let colormap = Colormap(a.read_u32::<NativeEndian>()?);
+
+                formats.push(PictFormInfo {
+                    id,
+                    type_,
+                    depth,
+                    direct,
+                    colormap,
+                });
+            }
+
+            let mut screens = Vec::with_capacity(num_screens as usize);
+            for _ in 0..num_screens {
+                let num_depths = a.read_u32::<NativeEndian>()?;
+                let fallback = Pictformat(a.read_u32::<NativeEndian>()?);
+
+                let mut depths = Vec::with_capacity(num_depths as usize);
+                for _ in 0..num_depths {
+                    let depth = a.read_u8()?;
+                    a.read_u8()?;
+                    let num_visuals = a.read_u16::<NativeEndian>()?;
+                    a.read_u32::<NativeEndian>()?;
+
+                    let mut visuals = Vec::with_capacity(num_visuals as usize);
+                    for _ in 0..num_visuals {
+                        let visual = Visualid(a.read_u32::<NativeEndian>()?);
+                        let format = Pictformat(a.read_u32::<NativeEndian>()?);
+                        visuals.push((visual, format));
+                    }
+
+                    depths.push(PictDepth { depth, visuals });
+                }
+
+                screens.push(PictScreen { depths, fallback });
+            }
+
+            let mut subpixels = Vec::with_capacity(num_subpixels as usize);
+            for _ in 0..num_subpixels {
+                subpixels.push(a.read_u32::<NativeEndian>()?);
+            }
+
+            let reply = RenderQueryPictFormatsReply {
+                formats,
+                screens,
+                subpixels,
+            };
+
+            Ok((client, reply))
+        }))
+    }
+}
+
+/// The formats of the visuals of a depth.
+#[derive(Debug, Clone)]
+pub struct PictDepth {
+    pub depth: u8,
+    pub visuals: Vec<(Visualid, Pictformat)>,
+}
+
+/// The formats of the visuals of a screen, in the same order as
+/// `ServerInfo::roots`.
+#[derive(Debug, Clone)]
+pub struct PictScreen {
+    pub depths: Vec<PictDepth>,
+    pub fallback: Pictformat,
+}
+
+/// Reply of `RenderQueryPictFormats` request.
+#[derive(Debug, Clone)]
+pub struct RenderQueryPictFormatsReply {
+    pub formats: Vec<PictFormInfo>,
+    pub screens: Vec<PictScreen>,
+
+    /// Sub-pixel order of each screen.
+    pub subpixels: Vec<u32>,
+}
+
+impl RenderQueryPictFormatsReply {
+    /// Returns the information of `format`.
+    pub fn format(&self, format: Pictformat) -> Option<&PictFormInfo> {
+        self.formats.iter().find(|info| info.id == format)
+    }
+
+    /// Finds one of the standard formats.
+    pub fn find_standard(&self, format: StandardFormat) -> Option<&PictFormInfo> {
+        self.formats.iter().find(|info| format.matches(info))
+    }
+
+    /// Finds a standard format by name: `"ARGB32"`, `"RGB24"`, `"A8"`,
+    /// `"A4"` or `"A1"`.
+    pub fn find_by_name(&self, name: &str) -> Option<&PictFormInfo> {
+        StandardFormat::from_name(name).and_then(|format| self.find_standard(format))
+    }
+
+    /// Returns the format of the windows using `visual`.
+    pub fn find_visual_format(&self, visual: Visualid) -> Option<&PictFormInfo> {
+        self.screens
+            .iter()
+            .flat_map(|screen| &screen.depths)
+            .flat_map(|depth| &depth.visuals)
+            .find(|&&(v, _)| v == visual)
+            .and_then(|&(_, format)| self.format(format))
+    }
+
+    /// Returns the visual of `server_info` using `format`, with its depth,
+    /// like the 32 bit visual needed for translucent windows.
+    pub fn find_format_visual<'a>(&self,
+                                  server_info: &'a ServerInfo,
+                                  format: Pictformat)
+                                  -> Option<(u8, &'a Visual)> {
+        let visual = self.screens
+            .iter()
+            .flat_map(|screen| &screen.depths)
+            .flat_map(|depth| &depth.visuals)
+            .find(|&&(_, f)| f == format)
+            .map(|&(visual, _)| visual)?;
+
+        server_info.roots
+            .iter()
+            .flat_map(|screen| &screen.allowed_depths)
+            .flat_map(|depth| depth.visuals.iter().map(move |v| (depth.depth, v)))
+            .find(|&(_, v)| v.id == visual)
+    }
+}
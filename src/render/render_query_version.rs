@@ -0,0 +1,76 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::render::EXTENSION_NAME;
+use ::Client;
+
+const RENDER_OPCODE: u8 = 0;
+
+/// Negotiates the protocol version, it must be sent before any other
+/// RENDER request.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderQueryVersion {
+    pub major_version: u32,
+    pub minor_version: u32,
+}
+
+impl ExtensionRequest for RenderQueryVersion {
+    type Reply = RenderQueryVersionReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RENDER_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.major_version)?;
+        a.write_u32::<NativeEndian>(self.minor_version)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = RenderQueryVersionReply {
+                major_version: NativeEndian::read_u32(&buf[8..12]),
+                minor_version: NativeEndian::read_u32(&buf[12..16]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `RenderQueryVersion` request.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderQueryVersionReply {
+    pub major_version: u32,
+    pub minor_version: u32,
+}
+
+impl VersionRequest for RenderQueryVersion {
+    fn latest() -> RenderQueryVersion {
+        RenderQueryVersion {
+            major_version: 0,
+            minor_version: 11,
+        }
+    }
+
+    fn version(reply: &RenderQueryVersionReply) -> Version {
+        Version::new(reply.major_version, reply.minor_version)
+    }
+}
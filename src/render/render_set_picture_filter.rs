@@ -0,0 +1,62 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::render::EXTENSION_NAME;
+use ::render::Fixed;
+use ::render::Picture;
+use ::Client;
+use ::pad;
+
+const RENDER_OPCODE: u8 = 30;
+
+/// Sets the filter used when the picture is transformed, like `"nearest"`,
+/// `"bilinear"` or `"convolution"`.
+#[derive(Debug, Clone)]
+pub struct RenderSetPictureFilter {
+    pub picture: Picture,
+    pub filter: Vec<u8>,
+
+    /// Parameters of the filter, like the matrix of a convolution.
+    pub values: Vec<Fixed>,
+}
+
+impl ExtensionRequest for RenderSetPictureFilter {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let n = self.filter.len();
+        let p = pad(n);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RENDER_OPCODE)?;
+        a.write_u16::<NativeEndian>((3 + (n + p) / 4 + self.values.len()) as u16)?;
+        a.write_u32::<NativeEndian>(self.picture.0)?;
+        a.write_u16::<NativeEndian>(n as u16)?;
+        a.write_u16::<NativeEndian>(0)?;
+        a.write_all(&self.filter)?;
+        a.write_all(&[0; 3][..p])?;
+        for value in &self.values {
+            a.write_i32::<NativeEndian>(value.0)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,50 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::render::EXTENSION_NAME;
+use ::render::Picture;
+use ::render::Transform;
+use ::Client;
+
+const RENDER_OPCODE: u8 = 28;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RenderSetPictureTransform {
+    pub picture: Picture,
+    pub transform: Transform,
+}
+
+impl ExtensionRequest for RenderSetPictureTransform {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RENDER_OPCODE)?;
+        a.write_u16::<NativeEndian>(11)?;
+        a.write_u32::<NativeEndian>(self.picture.0)?;
+        for row in &self.transform.0 {
+            for value in row {
+                a.write_i32::<NativeEndian>(value.0)?;
+            }
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
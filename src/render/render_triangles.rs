@@ -0,0 +1,66 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::request_length;
+use ::render::EXTENSION_NAME;
+use ::render::PictOp;
+use ::render::Pictformat;
+use ::render::Picture;
+use ::render::Triangle;
+use ::Client;
+
+const RENDER_OPCODE: u8 = 11;
+
+/// Like `RenderTrapezoids` with triangles.
+#[derive(Debug, Clone)]
+pub struct RenderTriangles {
+    pub op: PictOp,
+    pub src: Picture,
+    pub dst: Picture,
+    pub mask_format: Pictformat,
+    pub src_x: i16,
+    pub src_y: i16,
+    pub triangles: Vec<Triangle>,
+}
+
+impl ExtensionRequest for RenderTriangles {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(RENDER_OPCODE)?;
+        a.write_u16::<NativeEndian>(request_length(6 + 6 * self.triangles.len())?)?;
+        a.write_u8(self.op as u8)?;
+        a.write_all(&[0; 3])?;
+        a.write_u32::<NativeEndian>(self.src.0)?;
+        a.write_u32::<NativeEndian>(self.dst.0)?;
+        a.write_u32::<NativeEndian>(self.mask_format.0)?;
+        a.write_i16::<NativeEndian>(self.src_x)?;
+        a.write_i16::<NativeEndian>(self.src_y)?;
+        for triangle in &self.triangles {
+            triangle.p1.write(&mut a)?;
+            triangle.p2.write(&mut a)?;
+            triangle.p3.write(&mut a)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
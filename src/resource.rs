@@ -0,0 +1,116 @@
+//! Owned resource handles.
+//!
+//! An `Owned` handle frees its resource when dropped, since requests can't
+//! be sent from `drop` the request is queued and sent before the next
+//! request performed by the `Client`, then the id is returned to be reused.
+
+use ::std::io;
+use ::std::mem;
+use ::std::ops::Deref;
+use ::std::sync::Arc;
+use ::std::sync::Mutex;
+
+use ::protocol::Request;
+use ::xproto;
+use ::xproto::Xid;
+use ::Client;
+
+/// A resource that is freed with a request.
+pub trait Resource: Copy + Into<Xid> {
+    /// Encodes the request freeing the resource.
+    fn free_request(self) -> io::Result<Vec<u8>>;
+}
+
+macro_rules! impl_resource {
+    ($($resource:ident => $request:ident { $field:ident },)+) => {
+        $(
+            impl Resource for xproto::$resource {
+                fn free_request(self) -> io::Result<Vec<u8>> {
+                    xproto::$request { $field: self }.encode()
+                }
+            }
+        )+
+    }
+}
+
+impl_resource! {
+    Window => DestroyWindow { window },
+    Pixmap => FreePixmap { pixmap },
+    Gcontext => FreeGC { gc },
+    Font => CloseFont { font },
+    Colormap => FreeColormap { cmap },
+    Cursor => FreeCursor { cursor },
+}
+
+/// Requests of the dropped handles waiting to be sent.
+#[derive(Debug, Default)]
+pub(crate) struct Released {
+    data: Vec<u8>,
+    count: u16,
+    ids: Vec<Xid>,
+}
+
+impl Released {
+    /// Takes the queued requests, returning their data, the number of
+    /// requests and the ids freed by them.
+    pub(crate) fn take(&mut self) -> (Vec<u8>, u16, Vec<Xid>) {
+        let count = mem::replace(&mut self.count, 0);
+        (mem::take(&mut self.data), count, mem::take(&mut self.ids))
+    }
+}
+
+/// A resource freed (or destroyed) when the handle is dropped.
+#[derive(Debug)]
+pub struct Owned<T: Resource> {
+    resource: T,
+
+    /// `None` once ownership is given up.
+    released: Option<Arc<Mutex<Released>>>,
+}
+
+impl<T: Resource> Owned<T> {
+    /// Takes ownership of `resource`, which must have been created by
+    /// `client`.
+    pub fn new(client: &Client, resource: T) -> Owned<T> {
+        Owned {
+            resource,
+            released: Some(client.released.clone()),
+        }
+    }
+
+    /// Returns the resource.
+    pub fn get(&self) -> T {
+        self.resource
+    }
+
+    /// Gives up ownership, the resource is no longer freed on drop.
+    pub fn into_inner(mut self) -> T {
+        self.released = None;
+        self.resource
+    }
+}
+
+impl<T: Resource> Deref for Owned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.resource
+    }
+}
+
+impl<T: Resource> Drop for Owned<T> {
+    fn drop(&mut self) {
+        let released = match self.released.take() {
+            Some(released) => released,
+            None => return,
+        };
+
+        // The free requests can't fail to encode.
+        if let Ok(data) = self.resource.free_request() {
+            let mut released = released.lock().unwrap();
+            released.data.extend(data);
+            released.count = released.count.wrapping_add(1);
+            released.ids.push(self.resource.into());
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! MIT-SCREEN-SAVER extension requests and events, reporting the state of
+//! the screen saver and the time since the last user input.
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+
+use ::event::Event;
+use ::protocol::ExtensionInfo;
+use ::protocol::ReplyFuture;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+use ::Client;
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    screensaver_query_version,
+    screensaver_query_info,
+    screensaver_select_input,
+    screensaver_set_attributes,
+    screensaver_unset_attributes,
+    screensaver_suspend
+}
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"MIT-SCREEN-SAVER";
+
+/// Events selected with `ScreenSaverSelectInput`.
+pub type EventMask = u32;
+
+/// The screen saver starts or stops.
+pub const EVENT_MASK_NOTIFY: EventMask = 1;
+
+/// The screen saver changes its pattern.
+pub const EVENT_MASK_CYCLE: EventMask = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Off,
+    On,
+
+    /// The screen saver changed its pattern, only in events.
+    Cycle,
+
+    Disabled,
+}
+
+impl From<u8> for State {
+    fn from(state: u8) -> State {
+        match state {
+            0 => State::Off,
+            1 => State::On,
+            2 => State::Cycle,
+            _ => State::Disabled,
+        }
+    }
+}
+
+/// How the screen saver is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// The screen is blanked by the video hardware.
+    Blanked,
+
+    /// The server draws the screen saver.
+    Internal,
+
+    /// A client draws the screen saver in the window set with
+    /// `ScreenSaverSetAttributes`.
+    External,
+}
+
+impl From<u8> for Kind {
+    fn from(kind: u8) -> Kind {
+        match kind {
+            0 => Kind::Blanked,
+            1 => Kind::Internal,
+            _ => Kind::External,
+        }
+    }
+}
+
+/// The screen saver started, stopped or changed its pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScreenSaverNotifyEvent {
+    pub state: State,
+    pub time: Timestamp,
+    pub root: Window,
+
+    /// The screen saver window.
+    pub window: Window,
+
+    pub kind: Kind,
+
+    /// Whether it was started or stopped by `ForceScreenSaver`.
+    pub forced: bool,
+}
+
+/// Offset of the `ScreenSaverNotify` event from the extension `first_event`.
+const SCREEN_SAVER_NOTIFY: u8 = 0;
+
+impl ScreenSaverNotifyEvent {
+    /// Decodes `event` if it's a `ScreenSaverNotify` event, `info` is the
+    /// information of the extension as returned by `Client::extension_info`.
+    pub fn from_event(info: &ExtensionInfo, event: &Event) -> Option<ScreenSaverNotifyEvent> {
+        match *event {
+            Event::Unknown(ref buf) => ScreenSaverNotifyEvent::read(info, buf),
+            _ => None,
+        }
+    }
+
+    /// Decodes an event packet.
+    pub fn read(info: &ExtensionInfo, buf: &[u8]) -> Option<ScreenSaverNotifyEvent> {
+        if !info.present || info.first_event == 0 ||
+           (buf[0] & 0x7f).wrapping_sub(info.first_event) != SCREEN_SAVER_NOTIFY {
+            return None;
+        }
+
+        Some(ScreenSaverNotifyEvent {
+            state: State::from(buf[1]),
+            time: NativeEndian::read_u32(&buf[4..8]),
+            root: Window(NativeEndian::read_u32(&buf[8..12])),
+            window: Window(NativeEndian::read_u32(&buf[12..16])),
+            kind: Kind::from(buf[16]),
+            forced: buf[17] != 0,
+        })
+    }
+}
+
+/// Returns the milliseconds since the last user input on the first screen.
+pub fn idle_time(client: Client) -> ReplyFuture<u32> {
+    let root = client.get_server_info().roots[0].root;
+
+    Box::new(client.perform_ex(ScreenSaverQueryInfo { drawable: root.into() })
+        .map(|(client, info)| (client, info.ms_since_user_input)))
+}
@@ -0,0 +1,83 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::screensaver::EXTENSION_NAME;
+use ::screensaver::EventMask;
+use ::screensaver::Kind;
+use ::screensaver::State;
+use ::xproto::Drawable;
+use ::xproto::Window;
+use ::Client;
+
+const SCREENSAVER_OPCODE: u8 = 1;
+
+/// Returns the state of the screen saver of the screen of `drawable`,
+/// and the time since the last user input.
+#[derive(Debug, Clone, Copy)]
+pub struct ScreenSaverQueryInfo {
+    pub drawable: Drawable,
+}
+
+impl ExtensionRequest for ScreenSaverQueryInfo {
+    type Reply = ScreenSaverQueryInfoReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SCREENSAVER_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.drawable.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = ScreenSaverQueryInfoReply {
+                state: State::from(buf[1]),
+                saver_window: Window(NativeEndian::read_u32(&buf[8..12])),
+                ms_until_server: NativeEndian::read_u32(&buf[12..16]),
+                ms_since_user_input: NativeEndian::read_u32(&buf[16..20]),
+                event_mask: NativeEndian::read_u32(&buf[20..24]),
+                kind: Kind::from(buf[24]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `ScreenSaverQueryInfo` request.
+#[derive(Debug, Clone, Copy)]
+pub struct ScreenSaverQueryInfoReply {
+    pub state: State,
+
+    /// The window of the screen saver, it exists even when the screen
+    /// saver is off.
+    pub saver_window: Window,
+
+    /// Milliseconds before the screen saver starts if it's off, or before
+    /// the next pattern change if it's on.
+    pub ms_until_server: u32,
+
+    /// Milliseconds since the last user input.
+    pub ms_since_user_input: u32,
+
+    /// The events selected by the client.
+    pub event_mask: EventMask,
+
+    pub kind: Kind,
+}
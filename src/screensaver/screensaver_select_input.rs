@@ -0,0 +1,47 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::screensaver::EXTENSION_NAME;
+use ::screensaver::EventMask;
+use ::xproto::Drawable;
+use ::Client;
+
+const SCREENSAVER_OPCODE: u8 = 2;
+
+/// Selects the `ScreenSaverNotify` events of the screen of `drawable`.
+#[derive(Debug, Clone, Copy)]
+pub struct ScreenSaverSelectInput {
+    pub drawable: Drawable,
+    pub event_mask: EventMask,
+}
+
+impl ExtensionRequest for ScreenSaverSelectInput {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SCREENSAVER_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.drawable.0)?;
+        a.write_u32::<NativeEndian>(self.event_mask)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,73 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::screensaver::EXTENSION_NAME;
+use ::xproto::Drawable;
+use ::xproto::Visualid;
+use ::xproto::WindowAttributes;
+use ::Client;
+
+const SCREENSAVER_OPCODE: u8 = 3;
+
+/// Sets the geometry and attributes of the screen saver window of the
+/// screen of `drawable`, the client draws the screen saver in it. The
+/// window is created when the screen saver starts and destroyed when it
+/// stops.
+#[derive(Debug, Clone, Copy)]
+pub struct ScreenSaverSetAttributes {
+    pub drawable: Drawable,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub border_width: u16,
+
+    /// One of the `WINDOW_CLASS_*` constants.
+    pub class: u8,
+
+    pub depth: u8,
+    pub visual: Visualid,
+    pub attrs: WindowAttributes,
+}
+
+impl ExtensionRequest for ScreenSaverSetAttributes {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let (buf, n) = self.attrs.encode()?;
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SCREENSAVER_OPCODE)?;
+        a.write_u16::<NativeEndian>(7 + n)?;
+        a.write_u32::<NativeEndian>(self.drawable.0)?;
+        a.write_i16::<NativeEndian>(self.x)?;
+        a.write_i16::<NativeEndian>(self.y)?;
+        a.write_u16::<NativeEndian>(self.width)?;
+        a.write_u16::<NativeEndian>(self.height)?;
+        a.write_u16::<NativeEndian>(self.border_width)?;
+        a.write_u8(self.class)?;
+        a.write_u8(self.depth)?;
+        a.write_u32::<NativeEndian>(self.visual.0)?;
+        a.write_all(&buf)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
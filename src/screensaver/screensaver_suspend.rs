@@ -0,0 +1,45 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::screensaver::EXTENSION_NAME;
+use ::Client;
+
+const SCREENSAVER_OPCODE: u8 = 5;
+
+/// Prevents the screen saver (and DPMS) from starting while `suspend` is
+/// set, like a video player does. The calls are counted and undone when
+/// the client disconnects.
+#[derive(Debug, Clone, Copy)]
+pub struct ScreenSaverSuspend {
+    pub suspend: bool,
+}
+
+impl ExtensionRequest for ScreenSaverSuspend {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SCREENSAVER_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.suspend as u32)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,44 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::screensaver::EXTENSION_NAME;
+use ::xproto::Drawable;
+use ::Client;
+
+const SCREENSAVER_OPCODE: u8 = 4;
+
+/// Removes the attributes set by `ScreenSaverSetAttributes`.
+#[derive(Debug, Clone, Copy)]
+pub struct ScreenSaverUnsetAttributes {
+    pub drawable: Drawable,
+}
+
+impl ExtensionRequest for ScreenSaverUnsetAttributes {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SCREENSAVER_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.drawable.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,137 @@
+//! SHAPE extension, gives windows non-rectangular bounding, clip and input
+//! shapes.
+
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+
+use ::event::Event;
+use ::protocol::ExtensionInfo;
+use ::xproto::Rectangle;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    shape_query_version,
+    shape_rectangles,
+    shape_mask,
+    shape_combine,
+    shape_offset,
+    shape_query_extents,
+    shape_select_input,
+    shape_input_selected,
+    shape_get_rectangles
+}
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"SHAPE";
+
+/// The shapes of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeKind {
+    /// The area of the window and its border, outside of it the windows
+    /// below are visible.
+    Bounding = 0,
+
+    /// The area where the contents of the window are drawn.
+    Clip = 1,
+
+    /// The area receiving pointer events, outside of it they go to the
+    /// windows below.
+    Input = 2,
+}
+
+impl From<u8> for ShapeKind {
+    fn from(kind: u8) -> ShapeKind {
+        match kind {
+            1 => ShapeKind::Clip,
+            2 => ShapeKind::Input,
+            _ => ShapeKind::Bounding,
+        }
+    }
+}
+
+/// How a shape is combined with the current one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeOp {
+    Set = 0,
+    Union = 1,
+    Intersect = 2,
+    Subtract = 3,
+
+    /// Keeps the parts of the new shape not in the current one.
+    Invert = 4,
+}
+
+/// How the rectangles of a shape are sorted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ordering {
+    Unsorted = 0,
+    YSorted = 1,
+    YXSorted = 2,
+    YXBanded = 3,
+}
+
+impl From<u8> for Ordering {
+    fn from(ordering: u8) -> Ordering {
+        match ordering {
+            1 => Ordering::YSorted,
+            2 => Ordering::YXSorted,
+            3 => Ordering::YXBanded,
+            _ => Ordering::Unsorted,
+        }
+    }
+}
+
+/// Offset of the `ShapeNotify` event from the extension `first_event`.
+const SHAPE_NOTIFY: u8 = 0;
+
+/// Sent when a shape of a window changes.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeNotifyEvent {
+    pub kind: ShapeKind,
+    pub window: Window,
+
+    /// The bounding box of the shape.
+    pub extents: Rectangle,
+
+    pub timestamp: Timestamp,
+
+    /// Whether the window has a shape of this kind.
+    pub shaped: bool,
+}
+
+impl ShapeNotifyEvent {
+    /// Decodes `event` if it's a `ShapeNotify` event, `info` is the
+    /// information of the extension as returned by `Client::extension_info`.
+    pub fn from_event(info: &ExtensionInfo, event: &Event) -> Option<ShapeNotifyEvent> {
+        match *event {
+            Event::Unknown(ref buf) => ShapeNotifyEvent::read(info, buf),
+            _ => None,
+        }
+    }
+
+    /// Decodes an event packet.
+    pub fn read(info: &ExtensionInfo, buf: &[u8]) -> Option<ShapeNotifyEvent> {
+        if !info.present || info.first_event == 0 ||
+           (buf[0] & 0x7f).wrapping_sub(info.first_event) != SHAPE_NOTIFY {
+            return None;
+        }
+
+        Some(ShapeNotifyEvent {
+            kind: ShapeKind::from(buf[1]),
+            window: Window(NativeEndian::read_u32(&buf[4..8])),
+            extents: Rectangle::read(&mut &buf[8..16]).ok()?,
+            timestamp: NativeEndian::read_u32(&buf[16..20]),
+            shaped: buf[20] != 0,
+        })
+    }
+}
@@ -0,0 +1,73 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::shape::EXTENSION_NAME;
+use ::shape::Ordering;
+use ::shape::ShapeKind;
+use ::xproto::Rectangle;
+use ::xproto::Window;
+use ::Client;
+
+const SHAPE_OPCODE: u8 = 8;
+
+/// Returns the rectangles of a shape of a window.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeGetRectangles {
+    pub window: Window,
+    pub kind: ShapeKind,
+}
+
+impl ExtensionRequest for ShapeGetRectangles {
+    type Reply = ShapeGetRectanglesReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SHAPE_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u8(self.kind as u8)?;
+        a.write_all(&[0; 3])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let count = NativeEndian::read_u32(&buf[8..12]) as usize;
+            let mut a = io::Cursor::new(&buf[32..]);
+            let mut rectangles = Vec::with_capacity(count);
+            for _ in 0..count {
+                rectangles.push(Rectangle::read(&mut a)?);
+            }
+
+            let reply = ShapeGetRectanglesReply {
+                ordering: Ordering::from(buf[1]),
+                rectangles,
+            };
+
+            Ok((client, reply))
+        }))
+    }
+}
+
+/// Reply of `ShapeGetRectangles` request.
+#[derive(Debug, Clone)]
+pub struct ShapeGetRectanglesReply {
+    pub ordering: Ordering,
+    pub rectangles: Vec<Rectangle>,
+}
@@ -0,0 +1,55 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::shape::EXTENSION_NAME;
+use ::xproto::Window;
+use ::Client;
+
+const SHAPE_OPCODE: u8 = 7;
+
+/// Returns whether this client selected the `ShapeNotify` events of a
+/// window.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeInputSelected {
+    pub window: Window,
+}
+
+impl ExtensionRequest for ShapeInputSelected {
+    type Reply = ShapeInputSelectedReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SHAPE_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = ShapeInputSelectedReply { enabled: buf[1] != 0 };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `ShapeInputSelected` request.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeInputSelectedReply {
+    pub enabled: bool,
+}
@@ -0,0 +1,60 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::shape::EXTENSION_NAME;
+use ::shape::ShapeKind;
+use ::shape::ShapeOp;
+use ::xproto::Pixmap;
+use ::xproto::Window;
+use ::Client;
+
+const SHAPE_OPCODE: u8 = 2;
+
+/// Combines a shape of a window with the set bits of a bitmap.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeMask {
+    pub op: ShapeOp,
+    pub dest_kind: ShapeKind,
+    pub dest: Window,
+    pub x_offset: i16,
+    pub y_offset: i16,
+
+    /// A bitmap, `Pixmap::NONE` removes the shape.
+    pub source: Pixmap,
+}
+
+impl ExtensionRequest for ShapeMask {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SHAPE_OPCODE)?;
+        a.write_u16::<NativeEndian>(5)?;
+        a.write_u8(self.op as u8)?;
+        a.write_u8(self.dest_kind as u8)?;
+        a.write_u16::<NativeEndian>(0)?;
+        a.write_u32::<NativeEndian>(self.dest.0)?;
+        a.write_i16::<NativeEndian>(self.x_offset)?;
+        a.write_i16::<NativeEndian>(self.y_offset)?;
+        a.write_u32::<NativeEndian>(self.source.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,53 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::shape::EXTENSION_NAME;
+use ::shape::ShapeKind;
+use ::xproto::Window;
+use ::Client;
+
+const SHAPE_OPCODE: u8 = 4;
+
+/// Moves a shape of a window.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeOffset {
+    pub dest_kind: ShapeKind,
+    pub dest: Window,
+    pub x_offset: i16,
+    pub y_offset: i16,
+}
+
+impl ExtensionRequest for ShapeOffset {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SHAPE_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u8(self.dest_kind as u8)?;
+        a.write_all(&[0; 3])?;
+        a.write_u32::<NativeEndian>(self.dest.0)?;
+        a.write_i16::<NativeEndian>(self.x_offset)?;
+        a.write_i16::<NativeEndian>(self.y_offset)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
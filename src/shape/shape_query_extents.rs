@@ -0,0 +1,69 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::shape::EXTENSION_NAME;
+use ::xproto::Rectangle;
+use ::xproto::Window;
+use ::Client;
+
+const SHAPE_OPCODE: u8 = 5;
+
+/// Returns the bounding boxes of the bounding and clip shapes of a window.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeQueryExtents {
+    pub window: Window,
+}
+
+impl ExtensionRequest for ShapeQueryExtents {
+    type Reply = ShapeQueryExtentsReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SHAPE_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let reply = ShapeQueryExtentsReply {
+                bounding_shaped: buf[8] != 0,
+                clip_shaped: buf[9] != 0,
+                bounding: Rectangle::read(&mut &buf[12..20])?,
+                clip: Rectangle::read(&mut &buf[20..28])?,
+            };
+
+            Ok((client, reply))
+        }))
+    }
+}
+
+/// Reply of `ShapeQueryExtents` request.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeQueryExtentsReply {
+    /// Whether the window has a bounding shape, else `bounding` is the
+    /// rectangle of the window and its border.
+    pub bounding_shaped: bool,
+
+    /// Whether the window has a clip shape, else `clip` is the rectangle
+    /// of the window.
+    pub clip_shaped: bool,
+
+    pub bounding: Rectangle,
+    pub clip: Rectangle,
+}
@@ -0,0 +1,67 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::shape::EXTENSION_NAME;
+use ::Client;
+
+const SHAPE_OPCODE: u8 = 0;
+
+/// Returns the version of the extension, input shapes need 1.1.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeQueryVersion;
+
+impl ExtensionRequest for ShapeQueryVersion {
+    type Reply = ShapeQueryVersionReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SHAPE_OPCODE)?;
+        a.write_u16::<NativeEndian>(1)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = ShapeQueryVersionReply {
+                major_version: NativeEndian::read_u16(&buf[8..10]),
+                minor_version: NativeEndian::read_u16(&buf[10..12]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `ShapeQueryVersion` request.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeQueryVersionReply {
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl VersionRequest for ShapeQueryVersion {
+    fn latest() -> ShapeQueryVersion {
+        ShapeQueryVersion
+    }
+
+    fn version(reply: &ShapeQueryVersionReply) -> Version {
+        Version::new(u32::from(reply.major_version), u32::from(reply.minor_version))
+    }
+}
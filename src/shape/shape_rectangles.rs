@@ -0,0 +1,67 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::request_length;
+use ::shape::EXTENSION_NAME;
+use ::shape::Ordering;
+use ::shape::ShapeKind;
+use ::shape::ShapeOp;
+use ::xproto::Rectangle;
+use ::xproto::Window;
+use ::Client;
+
+const SHAPE_OPCODE: u8 = 1;
+
+/// Combines a shape of a window with a list of rectangles.
+#[derive(Debug, Clone)]
+pub struct ShapeRectangles {
+    pub op: ShapeOp,
+    pub dest_kind: ShapeKind,
+
+    /// How the rectangles are sorted, the server may fail if they aren't.
+    pub ordering: Ordering,
+
+    pub dest: Window,
+    pub x_offset: i16,
+    pub y_offset: i16,
+    pub rectangles: Vec<Rectangle>,
+}
+
+impl ExtensionRequest for ShapeRectangles {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SHAPE_OPCODE)?;
+        a.write_u16::<NativeEndian>(request_length(4 + 2 * self.rectangles.len())?)?;
+        a.write_u8(self.op as u8)?;
+        a.write_u8(self.dest_kind as u8)?;
+        a.write_u8(self.ordering as u8)?;
+        a.write_u8(0)?;
+        a.write_u32::<NativeEndian>(self.dest.0)?;
+        a.write_i16::<NativeEndian>(self.x_offset)?;
+        a.write_i16::<NativeEndian>(self.y_offset)?;
+        for rect in &self.rectangles {
+            rect.write(&mut a)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
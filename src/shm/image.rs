@@ -0,0 +1,305 @@
+use ::std::cmp;
+use ::std::io;
+
+use ::futures;
+use ::futures::Future;
+use ::futures::Stream;
+
+use ::protocol::ReplyFuture;
+use ::shm::EXTENSION_NAME;
+use ::shm::Seg;
+use ::shm::Segment;
+use ::shm::ShmAttach;
+use ::shm::ShmAttachFd;
+use ::shm::ShmDetach;
+use ::shm::ShmGetImage;
+use ::shm::ShmPutImage;
+use ::shm::ShmQueryVersion;
+use ::xproto::Drawable;
+use ::xproto::Gcontext;
+use ::xproto::GetImage;
+use ::xproto::IMAGE_FORMAT_Z_PIXMAP;
+use ::xproto::PutImage;
+use ::xproto::QueryExtension;
+use ::Client;
+
+/// Where the pixels of an image are stored.
+#[derive(Debug)]
+enum Storage {
+    Shared { shmseg: Seg, segment: Segment },
+    Local(Vec<u8>),
+}
+
+/// An image in the `ZPixmap` format of the server.
+///
+/// The pixels are stored in a shared memory segment when possible, else in
+/// a buffer sent with `PutImage` and read with `GetImage`.
+#[derive(Debug)]
+pub struct Image {
+    pub width: u16,
+    pub height: u16,
+    pub depth: u8,
+    pub bytes_per_line: usize,
+    storage: Storage,
+}
+
+impl Image {
+    /// Creates an image, a segment is used if the server supports MIT-SHM
+    /// and can attach it.
+    pub fn new(client: Client, width: u16, height: u16, depth: u8) -> ReplyFuture<Image> {
+        let bytes_per_line = match bytes_per_line(&client, width, depth) {
+            Some(bytes_per_line) => bytes_per_line,
+            None => {
+                return Box::new(futures::failed(io::Error::new(io::ErrorKind::InvalidInput,
+                                                               "no pixmap format for the depth")))
+            }
+        };
+        let len = bytes_per_line * height as usize;
+        let local = move |client: Client| {
+            let image = Image {
+                width,
+                height,
+                depth,
+                bytes_per_line,
+                storage: Storage::Local(vec![0; len]),
+            };
+            (client, image)
+        };
+
+        Box::new(client.perform(QueryExtension { name: EXTENSION_NAME.to_vec() })
+            .and_then(move |(client, info)| -> ReplyFuture<Image> {
+                if !info.present {
+                    return Box::new(futures::finished(local(client)));
+                }
+
+                Box::new(client.perform_ex(ShmQueryVersion)
+                    .and_then(|(client, version)| {
+                        client.next_id().map(move |(client, shmseg)| (client, (version, shmseg)))
+                    })
+                    .and_then(move |(client, (version, shmseg))| -> ReplyFuture<Image> {
+                        // Memory files are attached with `ShmAttachFd`, since 1.2.
+                        let fd_passing = (version.major_version, version.minor_version) >= (1, 2);
+                        let mut segment = match create_segment(cmp::max(len, 1), fd_passing) {
+                            Ok(segment) => segment,
+                            Err(_) => {
+                                client.free_id(shmseg);
+                                return Box::new(futures::finished(local(client)));
+                            }
+                        };
+
+                        let attach = match (segment.fd(), segment.shmid()) {
+                            (Some(fd), _) => {
+                                client.perform_ex(ShmAttachFd { shmseg, fd, read_only: false })
+                            }
+                            (None, Some(shmid)) => {
+                                client.perform_ex(ShmAttach { shmseg, shmid, read_only: false })
+                            }
+                            (None, None) => {
+                                client.free_id(shmseg);
+                                return Box::new(futures::finished(local(client)));
+                            }
+                        };
+
+                        Box::new(attach.and_then(|(client, _)| {
+                                // The attach fails if the server can't access the
+                                // segment, for example when it runs in another IPC
+                                // namespace.
+                                let sequence = client.sequence;
+                                client.perform_ex(ShmQueryVersion)
+                                    .map(move |(client, _)| (client, sequence))
+                            })
+                            .map(move |(mut client, sequence)| {
+                                if client.take_error(sequence).is_some() {
+                                    client.free_id(shmseg);
+                                    return local(client);
+                                }
+
+                                segment.remove_id();
+                                let image = Image {
+                                    width,
+                                    height,
+                                    depth,
+                                    bytes_per_line,
+                                    storage: Storage::Shared { shmseg, segment },
+                                };
+                                (client, image)
+                            }))
+                    }))
+            }))
+    }
+
+    /// Whether the image is stored in a shared memory segment.
+    pub fn is_shared(&self) -> bool {
+        match self.storage {
+            Storage::Shared { .. } => true,
+            Storage::Local(_) => false,
+        }
+    }
+
+    /// The pixels, `bytes_per_line` bytes per row.
+    pub fn data(&self) -> &[u8] {
+        match self.storage {
+            Storage::Shared { ref segment, .. } => {
+                &segment.as_slice()[..self.bytes_per_line * self.height as usize]
+            }
+            Storage::Local(ref data) => data,
+        }
+    }
+
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        let len = self.bytes_per_line * self.height as usize;
+        match self.storage {
+            Storage::Shared { ref mut segment, .. } => &mut segment.as_mut_slice()[..len],
+            Storage::Local(ref mut data) => data,
+        }
+    }
+
+    /// Draws the image at (`x`, `y`).
+    ///
+    /// A shared image may still be read by the server when the future
+    /// resolves, it shouldn't be modified until a reply to a later request
+    /// is received.
+    pub fn put(&self,
+               client: Client,
+               drawable: Drawable,
+               gc: Gcontext,
+               x: i16,
+               y: i16)
+               -> ReplyFuture<()> {
+        let shmseg = match self.storage {
+            Storage::Shared { shmseg, .. } => shmseg,
+            Storage::Local(ref data) => return self.put_local(client, drawable, gc, x, y, data),
+        };
+
+        client.perform_ex(ShmPutImage {
+            drawable,
+            gc,
+            total_width: self.width,
+            total_height: self.height,
+            src_x: 0,
+            src_y: 0,
+            src_width: self.width,
+            src_height: self.height,
+            dst_x: x,
+            dst_y: y,
+            depth: self.depth,
+            format: IMAGE_FORMAT_Z_PIXMAP,
+            send_event: false,
+            shmseg,
+            offset: 0,
+        })
+    }
+
+    /// Sends the image with `PutImage`, in bands of rows fitting in the
+    /// maximum request length.
+    fn put_local(&self,
+                 client: Client,
+                 drawable: Drawable,
+                 gc: Gcontext,
+                 x: i16,
+                 y: i16,
+                 data: &[u8])
+                 -> ReplyFuture<()> {
+        let max_len = client.get_server_info().maximum_request_length as usize * 4 - 24;
+        let rows = cmp::max(1, max_len / cmp::max(self.bytes_per_line, 1));
+
+        let requests: Vec<PutImage> = data.chunks(rows * self.bytes_per_line)
+            .enumerate()
+            .map(|(i, band)| {
+                PutImage {
+                    format: IMAGE_FORMAT_Z_PIXMAP,
+                    drawable,
+                    gc,
+                    width: self.width,
+                    height: (band.len() / self.bytes_per_line) as u16,
+                    dst_x: x,
+                    dst_y: y + (i * rows) as i16,
+                    left_pad: 0,
+                    depth: self.depth,
+                    data: band.to_vec(),
+                }
+            })
+            .collect();
+
+        Box::new(futures::stream::iter_ok::<_, io::Error>(requests)
+            .fold(client, |client, request| client.perform(request).map(|(client, _)| client))
+            .map(|client| (client, ())))
+    }
+
+    /// Reads the contents of a rectangle of `drawable` with the size of the
+    /// image into it.
+    pub fn get(self, client: Client, drawable: Drawable, x: i16, y: i16) -> ReplyFuture<Image> {
+        let shmseg = match self.storage {
+            Storage::Shared { shmseg, .. } => shmseg,
+            Storage::Local(_) => {
+                let request = GetImage {
+                    format: IMAGE_FORMAT_Z_PIXMAP,
+                    drawable,
+                    x,
+                    y,
+                    width: self.width,
+                    height: self.height,
+                    plane_mask: u32::MAX,
+                };
+
+                let mut image = self;
+                return Box::new(client.perform(request).map(move |(client, reply)| {
+                    let data = image.data_mut();
+                    let len = cmp::min(data.len(), reply.data.len());
+                    data[..len].copy_from_slice(&reply.data[..len]);
+                    (client, image)
+                }));
+            }
+        };
+
+        Box::new(client.perform_ex(ShmGetImage {
+                drawable,
+                x,
+                y,
+                width: self.width,
+                height: self.height,
+                plane_mask: u32::MAX,
+                format: IMAGE_FORMAT_Z_PIXMAP,
+                shmseg,
+                offset: 0,
+            })
+            .map(move |(client, _)| (client, self)))
+    }
+
+    /// Detaches the segment of the image from the server.
+    pub fn free(self, client: Client) -> ReplyFuture<()> {
+        match self.storage {
+            Storage::Shared { shmseg, .. } => {
+                Box::new(client.perform_ex(ShmDetach { shmseg }).map(move |(client, _)| {
+                    client.free_id(shmseg);
+                    (client, ())
+                }))
+            }
+            Storage::Local(_) => Box::new(futures::finished((client, ()))),
+        }
+    }
+}
+
+/// Creates a memory file if it can be attached, else a System V segment.
+#[cfg(target_os = "linux")]
+fn create_segment(len: usize, fd_passing: bool) -> io::Result<Segment> {
+    if fd_passing {
+        Segment::memfd(len).or_else(|_| Segment::sysv(len))
+    } else {
+        Segment::sysv(len)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn create_segment(len: usize, _: bool) -> io::Result<Segment> {
+    Segment::sysv(len)
+}
+
+/// Bytes of a row of a `ZPixmap` image of `depth`.
+fn bytes_per_line(client: &Client, width: u16, depth: u8) -> Option<usize> {
+    let format = client.get_server_info().pixmap_formats.iter().find(|f| f.depth == depth)?;
+    let pad = cmp::max(format.scanline_pad as usize, 8);
+    let bits = width as usize * format.bits_per_pixel as usize;
+
+    Some(bits.div_ceil(pad) * pad / 8)
+}
@@ -0,0 +1,73 @@
+//! MIT-SHM extension, transfers images through memory shared with the
+//! server instead of the connection.
+
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+
+use ::event::Event;
+use ::protocol::ExtensionInfo;
+use ::xproto::Drawable;
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    shm_query_version,
+    shm_attach,
+    shm_detach,
+    shm_put_image,
+    shm_get_image,
+    shm_create_pixmap,
+    shm_attach_fd,
+    shm_create_segment
+}
+
+mod segment;
+pub use self::segment::*;
+
+mod image;
+pub use self::image::*;
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"MIT-SHM";
+
+declare_ids! {
+    /// A segment attached to the server.
+    Seg
+}
+
+/// Sent when the server is done with a `ShmPutImage` request sent with
+/// `send_event`.
+#[derive(Debug, Clone, Copy)]
+pub struct ShmCompletionEvent {
+    pub drawable: Drawable,
+    pub minor_event: u16,
+    pub major_event: u8,
+    pub shmseg: Seg,
+    pub offset: u32,
+}
+
+impl ShmCompletionEvent {
+    /// Decodes `event` if it's a `ShmCompletion` event.
+    pub fn from_event(info: &ExtensionInfo, event: &Event) -> Option<ShmCompletionEvent> {
+        match *event {
+            Event::Unknown(ref buf) if info.present && info.first_event != 0 &&
+                                       buf[0] & 0x7f == info.first_event => {
+                Some(ShmCompletionEvent {
+                    drawable: Drawable(NativeEndian::read_u32(&buf[4..8])),
+                    minor_event: NativeEndian::read_u16(&buf[8..10]),
+                    major_event: buf[10],
+                    shmseg: Seg(NativeEndian::read_u32(&buf[12..16])),
+                    offset: NativeEndian::read_u32(&buf[16..20]),
+                })
+            }
+            _ => None,
+        }
+    }
+}
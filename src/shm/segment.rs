@@ -0,0 +1,161 @@
+#![allow(unsafe_code)]
+
+use ::std::io;
+use ::std::os::unix::io::RawFd;
+use ::std::ptr;
+use ::std::slice;
+
+use ::libc;
+
+/// Where the memory of a segment comes from.
+#[derive(Debug)]
+enum Backing {
+    /// A System V shared memory segment.
+    SysV { shmid: libc::c_int, removed: bool },
+
+    /// A memory file mapped in the process.
+    Memfd { fd: RawFd },
+}
+
+/// Memory shared with the X server.
+///
+/// System V segments are attached with `ShmAttach` and memory files with
+/// `ShmAttachFd`. The memory is unmapped when the segment is dropped.
+#[derive(Debug)]
+pub struct Segment {
+    addr: *mut u8,
+    len: usize,
+    backing: Backing,
+}
+
+impl Segment {
+    /// Creates a System V shared memory segment of `len` bytes.
+    pub fn sysv(len: usize) -> io::Result<Segment> {
+        let shmid = unsafe { libc::shmget(libc::IPC_PRIVATE, len, libc::IPC_CREAT | 0o600) };
+        if shmid == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let addr = unsafe { libc::shmat(shmid, ptr::null(), 0) };
+        if addr as isize == -1 {
+            let err = io::Error::last_os_error();
+            unsafe { libc::shmctl(shmid, libc::IPC_RMID, ptr::null_mut()) };
+            return Err(err);
+        }
+
+        Ok(Segment {
+            addr: addr as *mut u8,
+            len,
+            backing: Backing::SysV { shmid, removed: false },
+        })
+    }
+
+    /// Creates a memory file of `len` bytes.
+    #[cfg(target_os = "linux")]
+    pub fn memfd(len: usize) -> io::Result<Segment> {
+        let fd = unsafe { libc::memfd_create(b"xrb-shm\0".as_ptr() as *const libc::c_char,
+                                             libc::MFD_CLOEXEC) };
+        if fd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        unsafe { Segment::map(fd, len) }.inspect_err(|_| {
+            unsafe { libc::close(fd) };
+        })
+    }
+
+    /// Maps `len` bytes of a shared memory file, like the one received with
+    /// `ShmCreateSegment`. The segment owns the file descriptor.
+    ///
+    /// # Safety
+    /// `fd` must be a valid file descriptor not owned by anything else.
+    pub unsafe fn from_fd(fd: RawFd, len: usize) -> io::Result<Segment> {
+        Segment::map(fd, len)
+    }
+
+    unsafe fn map(fd: RawFd, len: usize) -> io::Result<Segment> {
+        let mut stat: libc::stat = ::std::mem::zeroed();
+        if libc::fstat(fd, &mut stat) == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        if (stat.st_size as usize) < len && libc::ftruncate(fd, len as libc::off_t) == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let addr = libc::mmap(ptr::null_mut(),
+                              len,
+                              libc::PROT_READ | libc::PROT_WRITE,
+                              libc::MAP_SHARED,
+                              fd,
+                              0);
+        if addr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Segment {
+            addr: addr as *mut u8,
+            len,
+            backing: Backing::Memfd { fd },
+        })
+    }
+
+    /// Id of the System V segment, passed to `ShmAttach`.
+    pub fn shmid(&self) -> Option<u32> {
+        match self.backing {
+            Backing::SysV { shmid, .. } => Some(shmid as u32),
+            Backing::Memfd { .. } => None,
+        }
+    }
+
+    /// File descriptor of the memory file, passed to `ShmAttachFd`.
+    pub fn fd(&self) -> Option<RawFd> {
+        match self.backing {
+            Backing::SysV { .. } => None,
+            Backing::Memfd { fd } => Some(fd),
+        }
+    }
+
+    /// Marks the System V segment to be destroyed once the process and the
+    /// server detach it, so it isn't leaked if the process dies. Must be
+    /// called after the server attached it.
+    pub fn remove_id(&mut self) {
+        if let Backing::SysV { shmid, ref mut removed } = self.backing {
+            if !*removed {
+                unsafe { libc::shmctl(shmid, libc::IPC_RMID, ptr::null_mut()) };
+                *removed = true;
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.addr, self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.addr, self.len) }
+    }
+}
+
+impl Drop for Segment {
+    fn drop(&mut self) {
+        self.remove_id();
+
+        match self.backing {
+            Backing::SysV { .. } => unsafe {
+                libc::shmdt(self.addr as *const libc::c_void);
+            },
+            Backing::Memfd { fd } => unsafe {
+                libc::munmap(self.addr as *mut libc::c_void, self.len);
+                libc::close(fd);
+            },
+        }
+    }
+}
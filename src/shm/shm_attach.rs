@@ -0,0 +1,52 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::shm::EXTENSION_NAME;
+use ::shm::Seg;
+use ::Client;
+
+const SHM_OPCODE: u8 = 1;
+
+/// Attaches a System V shared memory segment to the server.
+#[derive(Debug, Clone, Copy)]
+pub struct ShmAttach {
+    pub shmseg: Seg,
+    pub shmid: u32,
+
+    /// Whether the server only reads from the segment.
+    pub read_only: bool,
+}
+
+impl ExtensionRequest for ShmAttach {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SHM_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u32::<NativeEndian>(self.shmseg.0)?;
+        a.write_u32::<NativeEndian>(self.shmid)?;
+        a.write_u8(self.read_only as u8)?;
+        a.write_all(&[0; 3])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,57 @@
+use ::std::io;
+use ::std::io::Write;
+use ::std::os::unix::io::RawFd;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::shm::EXTENSION_NAME;
+use ::shm::Seg;
+use ::Client;
+
+const SHM_OPCODE: u8 = 6;
+
+/// Attaches a shared memory file to the server, the file descriptor is
+/// sent along with the request.
+#[derive(Debug, Clone, Copy)]
+pub struct ShmAttachFd {
+    pub shmseg: Seg,
+    pub fd: RawFd,
+
+    /// Whether the server only reads from the segment.
+    pub read_only: bool,
+}
+
+impl ExtensionRequest for ShmAttachFd {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SHM_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.shmseg.0)?;
+        a.write_u8(self.read_only as u8)?;
+        a.write_all(&[0; 3])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+
+    fn fds(&self) -> Vec<RawFd> {
+        vec![self.fd]
+    }
+}
@@ -0,0 +1,63 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::shm::EXTENSION_NAME;
+use ::shm::Seg;
+use ::xproto::Drawable;
+use ::xproto::Pixmap;
+use ::Client;
+
+const SHM_OPCODE: u8 = 5;
+
+/// Creates a pixmap whose contents are stored in a segment.
+#[derive(Debug, Clone, Copy)]
+pub struct ShmCreatePixmap {
+    pub pid: Pixmap,
+
+    /// Selects the screen of the pixmap.
+    pub drawable: Drawable,
+
+    pub width: u16,
+    pub height: u16,
+    pub depth: u8,
+    pub shmseg: Seg,
+    pub offset: u32,
+}
+
+impl ExtensionRequest for ShmCreatePixmap {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SHM_OPCODE)?;
+        a.write_u16::<NativeEndian>(7)?;
+        a.write_u32::<NativeEndian>(self.pid.0)?;
+        a.write_u32::<NativeEndian>(self.drawable.0)?;
+        a.write_u16::<NativeEndian>(self.width)?;
+        a.write_u16::<NativeEndian>(self.height)?;
+        a.write_u8(self.depth)?;
+        a.write_all(&[0; 3])?;
+        a.write_u32::<NativeEndian>(self.shmseg.0)?;
+        a.write_u32::<NativeEndian>(self.offset)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
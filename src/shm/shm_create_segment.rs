@@ -0,0 +1,72 @@
+use ::std::io;
+use ::std::io::Write;
+use ::std::os::unix::io::RawFd;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::ReplyFds;
+use ::protocol::read_reply_fds;
+use ::protocol::ExtensionInfo;
+use ::shm::EXTENSION_NAME;
+use ::shm::Seg;
+use ::Client;
+
+const SHM_OPCODE: u8 = 7;
+
+/// Asks the server to create a shared memory file of `size` bytes, its file
+/// descriptor is sent with the reply.
+#[derive(Debug, Clone, Copy)]
+pub struct ShmCreateSegment {
+    pub shmseg: Seg,
+    pub size: u32,
+
+    /// Whether the server only reads from the segment.
+    pub read_only: bool,
+}
+
+impl ExtensionRequest for ShmCreateSegment {
+    type Reply = ShmCreateSegmentReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SHM_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u32::<NativeEndian>(self.shmseg.0)?;
+        a.write_u32::<NativeEndian>(self.size)?;
+        a.write_u8(self.read_only as u8)?;
+        a.write_all(&[0; 3])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply_fds(client, 1).map(|(client, (_, fds))| {
+            let reply = ShmCreateSegmentReply { fd: fds[0] };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `ShmCreateSegment` request.
+#[derive(Debug, Clone, Copy)]
+pub struct ShmCreateSegmentReply {
+    /// The shared memory file, map it with `Segment::from_fd`.
+    pub fd: RawFd,
+}
+
+impl ReplyFds for ShmCreateSegmentReply {
+    fn reply_fds(&self) -> Vec<RawFd> {
+        vec![self.fd]
+    }
+}
@@ -0,0 +1,90 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::shm::EXTENSION_NAME;
+use ::shm::Seg;
+use ::xproto::Drawable;
+use ::xproto::ImageFormat;
+use ::xproto::Visualid;
+use ::Client;
+
+const SHM_OPCODE: u8 = 4;
+
+/// Copies a rectangle of a drawable into a segment, the data is there once
+/// the reply is received.
+#[derive(Debug, Clone, Copy)]
+pub struct ShmGetImage {
+    pub drawable: Drawable,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+
+    /// Planes to read, `u32::MAX` reads all of them.
+    pub plane_mask: u32,
+
+    pub format: ImageFormat,
+    pub shmseg: Seg,
+
+    /// Offset of the image in the segment.
+    pub offset: u32,
+}
+
+impl ExtensionRequest for ShmGetImage {
+    type Reply = ShmGetImageReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SHM_OPCODE)?;
+        a.write_u16::<NativeEndian>(8)?;
+        a.write_u32::<NativeEndian>(self.drawable.0)?;
+        a.write_i16::<NativeEndian>(self.x)?;
+        a.write_i16::<NativeEndian>(self.y)?;
+        a.write_u16::<NativeEndian>(self.width)?;
+        a.write_u16::<NativeEndian>(self.height)?;
+        a.write_u32::<NativeEndian>(self.plane_mask)?;
+        a.write_u8(self.format)?;
+        a.write_all(&[0; 3])?;
+        a.write_u32::<NativeEndian>(self.shmseg.0)?;
+        a.write_u32::<NativeEndian>(self.offset)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = ShmGetImageReply {
+                depth: buf[1],
+                visual: Visualid(NativeEndian::read_u32(&buf[8..12])),
+                size: NativeEndian::read_u32(&buf[12..16]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `ShmGetImage` request.
+#[derive(Debug, Clone, Copy)]
+pub struct ShmGetImageReply {
+    pub depth: u8,
+    pub visual: Visualid,
+
+    /// Number of bytes written in the segment.
+    pub size: u32,
+}
@@ -0,0 +1,87 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::shm::EXTENSION_NAME;
+use ::shm::Seg;
+use ::xproto::Drawable;
+use ::xproto::Gcontext;
+use ::xproto::ImageFormat;
+use ::Client;
+
+const SHM_OPCODE: u8 = 3;
+
+/// Draws a rectangle of an image stored in a segment.
+#[derive(Debug, Clone, Copy)]
+pub struct ShmPutImage {
+    pub drawable: Drawable,
+    pub gc: Gcontext,
+
+    /// Size of the whole image in the segment.
+    pub total_width: u16,
+    pub total_height: u16,
+
+    /// The rectangle of the image drawn.
+    pub src_x: u16,
+    pub src_y: u16,
+    pub src_width: u16,
+    pub src_height: u16,
+    pub dst_x: i16,
+    pub dst_y: i16,
+
+    pub depth: u8,
+    pub format: ImageFormat,
+
+    /// Whether a `ShmCompletion` event is sent when the server is done
+    /// reading the segment.
+    pub send_event: bool,
+
+    pub shmseg: Seg,
+
+    /// Offset of the image in the segment.
+    pub offset: u32,
+}
+
+impl ExtensionRequest for ShmPutImage {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SHM_OPCODE)?;
+        a.write_u16::<NativeEndian>(10)?;
+        a.write_u32::<NativeEndian>(self.drawable.0)?;
+        a.write_u32::<NativeEndian>(self.gc.0)?;
+        a.write_u16::<NativeEndian>(self.total_width)?;
+        a.write_u16::<NativeEndian>(self.total_height)?;
+        a.write_u16::<NativeEndian>(self.src_x)?;
+        a.write_u16::<NativeEndian>(self.src_y)?;
+        a.write_u16::<NativeEndian>(self.src_width)?;
+        a.write_u16::<NativeEndian>(self.src_height)?;
+        a.write_i16::<NativeEndian>(self.dst_x)?;
+        a.write_i16::<NativeEndian>(self.dst_y)?;
+        a.write_u8(self.depth)?;
+        a.write_u8(self.format)?;
+        a.write_u8(self.send_event as u8)?;
+        a.write_u8(0)?;
+        a.write_u32::<NativeEndian>(self.shmseg.0)?;
+        a.write_u32::<NativeEndian>(self.offset)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,81 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::shm::EXTENSION_NAME;
+use ::xproto::ImageFormat;
+use ::Client;
+
+const SHM_OPCODE: u8 = 0;
+
+/// Returns the version of the extension and whether shared pixmaps are
+/// supported.
+#[derive(Debug, Clone, Copy)]
+pub struct ShmQueryVersion;
+
+impl ExtensionRequest for ShmQueryVersion {
+    type Reply = ShmQueryVersionReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SHM_OPCODE)?;
+        a.write_u16::<NativeEndian>(1)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = ShmQueryVersionReply {
+                shared_pixmaps: buf[1] != 0,
+                major_version: NativeEndian::read_u16(&buf[8..10]),
+                minor_version: NativeEndian::read_u16(&buf[10..12]),
+                uid: NativeEndian::read_u16(&buf[12..14]),
+                gid: NativeEndian::read_u16(&buf[14..16]),
+                pixmap_format: buf[16],
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `ShmQueryVersion` request.
+#[derive(Debug, Clone, Copy)]
+pub struct ShmQueryVersionReply {
+    /// Whether `ShmCreatePixmap` is supported.
+    pub shared_pixmaps: bool,
+
+    pub major_version: u16,
+    pub minor_version: u16,
+    pub uid: u16,
+    pub gid: u16,
+
+    /// Format of the shared pixmaps.
+    pub pixmap_format: ImageFormat,
+}
+
+impl VersionRequest for ShmQueryVersion {
+    fn latest() -> ShmQueryVersion {
+        ShmQueryVersion
+    }
+
+    fn version(reply: &ShmQueryVersionReply) -> Version {
+        Version::new(u32::from(reply.major_version), u32::from(reply.minor_version))
+    }
+}
@@ -0,0 +1,394 @@
+//! SYNC extension requests and events: counters, alarms, fences and the
+//! `_NET_WM_SYNC_REQUEST` protocol.
+
+use ::std::io;
+use ::std::io::Read;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::event::Event;
+use ::protocol::ExtensionInfo;
+use ::xproto::Timestamp;
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    sync_initialize,
+    sync_list_system_counters,
+    sync_create_counter,
+    sync_set_counter,
+    sync_change_counter,
+    sync_query_counter,
+    sync_destroy_counter,
+    sync_await,
+    sync_create_alarm,
+    sync_change_alarm,
+    sync_query_alarm,
+    sync_destroy_alarm,
+    sync_create_fence,
+    sync_trigger_fence,
+    sync_reset_fence,
+    sync_destroy_fence,
+    sync_query_fence,
+    sync_await_fence
+}
+
+mod sync_request;
+pub use self::sync_request::*;
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"SYNC";
+
+declare_ids! {
+    /// A 64 bits counter.
+    Counter,
+
+    /// An alarm, reporting when a counter reaches a value.
+    Alarm,
+
+    /// A fence, triggered when the rendering queued before is done.
+    Fence
+}
+
+/// Name of the system counter of the server time in milliseconds.
+pub const SERVERTIME: &str = "SERVERTIME";
+
+/// Name of the system counter of the time since the last input in
+/// milliseconds.
+pub const IDLETIME: &str = "IDLETIME";
+
+/// How the value of a trigger is interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Absolute = 0,
+
+    /// Relative to the value of the counter when the trigger is set.
+    Relative = 1,
+}
+
+impl From<u32> for ValueType {
+    fn from(value_type: u32) -> ValueType {
+        match value_type {
+            1 => ValueType::Relative,
+            _ => ValueType::Absolute,
+        }
+    }
+}
+
+/// How the value of a counter is compared to the one of a trigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestType {
+    /// The counter goes from below to at least the value.
+    PositiveTransition = 0,
+
+    /// The counter goes from above to at most the value.
+    NegativeTransition = 1,
+
+    /// The counter is at least the value.
+    PositiveComparison = 2,
+
+    /// The counter is at most the value.
+    NegativeComparison = 3,
+}
+
+impl From<u32> for TestType {
+    fn from(test_type: u32) -> TestType {
+        match test_type {
+            1 => TestType::NegativeTransition,
+            2 => TestType::PositiveComparison,
+            3 => TestType::NegativeComparison,
+            _ => TestType::PositiveTransition,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlarmState {
+    Active,
+    Inactive,
+    Destroyed,
+}
+
+impl From<u8> for AlarmState {
+    fn from(state: u8) -> AlarmState {
+        match state {
+            0 => AlarmState::Active,
+            1 => AlarmState::Inactive,
+            _ => AlarmState::Destroyed,
+        }
+    }
+}
+
+/// A condition on the value of a counter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Trigger {
+    /// The counter, `Counter::NONE` for a trigger that is never true.
+    pub counter: Counter,
+    pub wait_type: ValueType,
+    pub wait_value: i64,
+    pub test_type: TestType,
+}
+
+impl Trigger {
+    fn read<A: Read>(a: &mut A) -> io::Result<Trigger> {
+        Ok(Trigger {
+            counter: Counter(a.read_u32::<NativeEndian>()?),
+            wait_type: ValueType::from(a.read_u32::<NativeEndian>()?),
+            wait_value: read_int64(a)?,
+            test_type: TestType::from(a.read_u32::<NativeEndian>()?),
+        })
+    }
+
+    fn write<A: Write>(&self, a: &mut A) -> io::Result<()> {
+        a.write_u32::<NativeEndian>(self.counter.0)?;
+        a.write_u32::<NativeEndian>(self.wait_type as u32)?;
+        write_int64(a, self.wait_value)?;
+        a.write_u32::<NativeEndian>(self.test_type as u32)?;
+
+        Ok(())
+    }
+}
+
+/// A trigger of `SyncAwait`, a `CounterNotify` event is sent if the
+/// difference between the counter and the trigger value reaches
+/// `event_threshold` when the trigger is true.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitCondition {
+    pub trigger: Trigger,
+    pub event_threshold: i64,
+}
+
+/// A counter maintained by the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemCounter {
+    pub counter: Counter,
+
+    /// The approximate number of units between changes of the counter.
+    pub resolution: i64,
+
+    pub name: String,
+}
+
+/// Attributes of an alarm, used by `SyncCreateAlarm` and `SyncChangeAlarm`.
+#[derive(Debug, Clone, Copy)]
+pub struct AlarmAttributes {
+    counter: Counter,
+    value_type: ValueType,
+    value: i64,
+    test_type: TestType,
+    delta: i64,
+    events: bool,
+
+    value_mask: u32,
+}
+
+impl Default for AlarmAttributes {
+    fn default() -> AlarmAttributes {
+        AlarmAttributes::new()
+    }
+}
+
+impl AlarmAttributes {
+    pub fn new() -> AlarmAttributes {
+        AlarmAttributes {
+            counter: Counter::NONE,
+            value_type: ValueType::Absolute,
+            value: 0,
+            test_type: TestType::PositiveComparison,
+            delta: 1,
+            events: true,
+
+            value_mask: 0,
+        }
+    }
+
+    pub fn counter(&mut self, value: Counter) -> &mut Self {
+        self.counter = value;
+        self.value_mask |= 0x0001;
+        self
+    }
+
+    pub fn value_type(&mut self, value: ValueType) -> &mut Self {
+        self.value_type = value;
+        self.value_mask |= 0x0002;
+        self
+    }
+
+    pub fn value(&mut self, value: i64) -> &mut Self {
+        self.value = value;
+        self.value_mask |= 0x0004;
+        self
+    }
+
+    pub fn test_type(&mut self, value: TestType) -> &mut Self {
+        self.test_type = value;
+        self.value_mask |= 0x0008;
+        self
+    }
+
+    /// Added to the trigger value each time the alarm fires.
+    pub fn delta(&mut self, value: i64) -> &mut Self {
+        self.delta = value;
+        self.value_mask |= 0x0010;
+        self
+    }
+
+    /// Whether the client gets `AlarmNotify` events.
+    pub fn events(&mut self, value: bool) -> &mut Self {
+        self.events = value;
+        self.value_mask |= 0x0020;
+        self
+    }
+
+    /// Sets the counter, value and test of the trigger.
+    pub fn trigger(&mut self, trigger: Trigger) -> &mut Self {
+        self.counter(trigger.counter)
+            .value_type(trigger.wait_type)
+            .value(trigger.wait_value)
+            .test_type(trigger.test_type)
+    }
+
+    pub fn build(self) -> Self {
+        self
+    }
+
+    /// Encodes the value mask and list, returning the number of words of
+    /// the list.
+    fn encode(&self) -> io::Result<(Vec<u8>, u16)> {
+        let mut count = 0u16;
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u32::<NativeEndian>(self.value_mask)?;
+        if self.value_mask & 0x0001 != 0 {
+            a.write_u32::<NativeEndian>(self.counter.0)?;
+            count += 1;
+        }
+        if self.value_mask & 0x0002 != 0 {
+            a.write_u32::<NativeEndian>(self.value_type as u32)?;
+            count += 1;
+        }
+        if self.value_mask & 0x0004 != 0 {
+            write_int64(&mut a, self.value)?;
+            count += 2;
+        }
+        if self.value_mask & 0x0008 != 0 {
+            a.write_u32::<NativeEndian>(self.test_type as u32)?;
+            count += 1;
+        }
+        if self.value_mask & 0x0010 != 0 {
+            write_int64(&mut a, self.delta)?;
+            count += 2;
+        }
+        if self.value_mask & 0x0020 != 0 {
+            a.write_u32::<NativeEndian>(self.events as u32)?;
+            count += 1;
+        }
+
+        Ok((a.into_inner(), count))
+    }
+}
+
+/// Reads an `INT64`, sent as a signed high word followed by the low word.
+fn read_int64<A: Read>(a: &mut A) -> io::Result<i64> {
+    let hi = a.read_i32::<NativeEndian>()?;
+    let lo = a.read_u32::<NativeEndian>()?;
+    Ok((hi as i64) << 32 | lo as i64)
+}
+
+/// Writes an `INT64` as its high and low words.
+fn write_int64<A: Write>(a: &mut A, value: i64) -> io::Result<()> {
+    a.write_i32::<NativeEndian>((value >> 32) as i32)?;
+    a.write_u32::<NativeEndian>(value as u32)
+}
+
+/// The SYNC events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncEvent {
+    CounterNotify(SyncCounterNotifyEvent),
+    AlarmNotify(SyncAlarmNotifyEvent),
+}
+
+/// A trigger of `SyncAwait` was true or its counter was destroyed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncCounterNotifyEvent {
+    /// Whether the event was generated by `SyncAwait`.
+    pub kind: u8,
+    pub counter: Counter,
+    pub wait_value: i64,
+    pub counter_value: i64,
+    pub timestamp: Timestamp,
+
+    /// Number of the events still to come for the same request.
+    pub count: u16,
+
+    pub destroyed: bool,
+}
+
+/// An alarm was triggered or changed state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncAlarmNotifyEvent {
+    pub alarm: Alarm,
+    pub counter_value: i64,
+    pub alarm_value: i64,
+    pub timestamp: Timestamp,
+    pub state: AlarmState,
+}
+
+/// Offsets of the events from the extension `first_event`.
+const COUNTER_NOTIFY: u8 = 0;
+const ALARM_NOTIFY: u8 = 1;
+
+impl SyncEvent {
+    /// Decodes `event` if it's a SYNC event, `info` is the information of
+    /// the extension as returned by `Client::extension_info`.
+    pub fn from_event(info: &ExtensionInfo, event: &Event) -> Option<SyncEvent> {
+        match *event {
+            Event::Unknown(ref buf) => SyncEvent::read(info, buf),
+            _ => None,
+        }
+    }
+
+    /// Decodes an event packet.
+    pub fn read(info: &ExtensionInfo, buf: &[u8]) -> Option<SyncEvent> {
+        if !info.present || info.first_event == 0 {
+            return None;
+        }
+
+        let int64 = |at: usize| read_int64(&mut &buf[at..at + 8]).ok();
+
+        match (buf[0] & 0x7f).wrapping_sub(info.first_event) {
+            COUNTER_NOTIFY => {
+                Some(SyncEvent::CounterNotify(SyncCounterNotifyEvent {
+                    kind: buf[1],
+                    counter: Counter(NativeEndian::read_u32(&buf[4..8])),
+                    wait_value: int64(8)?,
+                    counter_value: int64(16)?,
+                    timestamp: NativeEndian::read_u32(&buf[24..28]),
+                    count: NativeEndian::read_u16(&buf[28..30]),
+                    destroyed: buf[30] != 0,
+                }))
+            }
+            ALARM_NOTIFY => {
+                Some(SyncEvent::AlarmNotify(SyncAlarmNotifyEvent {
+                    alarm: Alarm(NativeEndian::read_u32(&buf[4..8])),
+                    counter_value: int64(8)?,
+                    alarm_value: int64(16)?,
+                    timestamp: NativeEndian::read_u32(&buf[24..28]),
+                    state: AlarmState::from(buf[28]),
+                }))
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::sync::EXTENSION_NAME;
+use ::sync::WaitCondition;
+use ::sync::write_int64;
+use ::Client;
+
+const SYNC_OPCODE: u8 = 7;
+
+/// Blocks the client until one of the triggers is true, the requests
+/// sent after are not processed before.
+#[derive(Debug, Clone)]
+pub struct SyncAwait {
+    pub wait_list: Vec<WaitCondition>,
+}
+
+impl ExtensionRequest for SyncAwait {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SYNC_OPCODE)?;
+        a.write_u16::<NativeEndian>(1 + 7 * self.wait_list.len() as u16)?;
+        for condition in &self.wait_list {
+            condition.trigger.write(&mut a)?;
+            write_int64(&mut a, condition.event_threshold)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
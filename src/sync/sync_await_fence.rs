@@ -0,0 +1,46 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::sync::EXTENSION_NAME;
+use ::sync::Fence;
+use ::Client;
+
+const SYNC_OPCODE: u8 = 19;
+
+/// Blocks the client until one of the fences is triggered.
+#[derive(Debug, Clone)]
+pub struct SyncAwaitFence {
+    pub fences: Vec<Fence>,
+}
+
+impl ExtensionRequest for SyncAwaitFence {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SYNC_OPCODE)?;
+        a.write_u16::<NativeEndian>(1 + self.fences.len() as u16)?;
+        for fence in &self.fences {
+            a.write_u32::<NativeEndian>(fence.0)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
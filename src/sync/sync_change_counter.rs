@@ -0,0 +1,47 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::sync::EXTENSION_NAME;
+use ::sync::Counter;
+use ::sync::write_int64;
+use ::Client;
+
+const SYNC_OPCODE: u8 = 4;
+
+/// Adds `amount` to the value of a counter created by this client.
+#[derive(Debug, Clone, Copy)]
+pub struct SyncChangeCounter {
+    pub counter: Counter,
+    pub amount: i64,
+}
+
+impl ExtensionRequest for SyncChangeCounter {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SYNC_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u32::<NativeEndian>(self.counter.0)?;
+        write_int64(&mut a, self.amount)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,51 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::sync::EXTENSION_NAME;
+use ::sync::Alarm;
+use ::sync::AlarmAttributes;
+use ::Client;
+
+const SYNC_OPCODE: u8 = 8;
+
+/// Creates an alarm, the attributes not set default to a trigger that is
+/// never true, a delta of 1 and events enabled.
+#[derive(Debug, Clone, Copy)]
+pub struct SyncCreateAlarm {
+    pub id: Alarm,
+    pub attrs: AlarmAttributes,
+}
+
+impl ExtensionRequest for SyncCreateAlarm {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let (buf, n) = self.attrs.encode()?;
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SYNC_OPCODE)?;
+        a.write_u16::<NativeEndian>(3 + n)?;
+        a.write_u32::<NativeEndian>(self.id.0)?;
+        a.write_all(&buf)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,52 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::sync::EXTENSION_NAME;
+use ::sync::Fence;
+use ::xproto::Drawable;
+use ::Client;
+
+const SYNC_OPCODE: u8 = 14;
+
+/// Creates a fence on the screen of `drawable`, triggered once the
+/// rendering queued before `SyncTriggerFence` is done.
+#[derive(Debug, Clone, Copy)]
+pub struct SyncCreateFence {
+    pub drawable: Drawable,
+    pub fence: Fence,
+    pub initially_triggered: bool,
+}
+
+impl ExtensionRequest for SyncCreateFence {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SYNC_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u32::<NativeEndian>(self.drawable.0)?;
+        a.write_u32::<NativeEndian>(self.fence.0)?;
+        a.write_u8(self.initially_triggered as u8)?;
+        a.write_all(&[0; 3])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
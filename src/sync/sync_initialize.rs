@@ -0,0 +1,77 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::sync::EXTENSION_NAME;
+use ::Client;
+
+const SYNC_OPCODE: u8 = 0;
+
+/// Initializes the extension, it must be sent before the other requests.
+/// Fences need version 3.1.
+#[derive(Debug, Clone, Copy)]
+pub struct SyncInitialize {
+    pub desired_major_version: u8,
+    pub desired_minor_version: u8,
+}
+
+impl ExtensionRequest for SyncInitialize {
+    type Reply = SyncInitializeReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SYNC_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u8(self.desired_major_version)?;
+        a.write_u8(self.desired_minor_version)?;
+        a.write_all(&[0; 2])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = SyncInitializeReply {
+                major_version: buf[8],
+                minor_version: buf[9],
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `SyncInitialize` request.
+#[derive(Debug, Clone, Copy)]
+pub struct SyncInitializeReply {
+    pub major_version: u8,
+    pub minor_version: u8,
+}
+
+impl VersionRequest for SyncInitialize {
+    fn latest() -> SyncInitialize {
+        SyncInitialize {
+            desired_major_version: 3,
+            desired_minor_version: 1,
+        }
+    }
+
+    fn version(reply: &SyncInitializeReply) -> Version {
+        Version::new(u32::from(reply.major_version), u32::from(reply.minor_version))
+    }
+}
@@ -0,0 +1,90 @@
+use ::std::io;
+use ::std::io::Read;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::sync::EXTENSION_NAME;
+use ::sync::Counter;
+use ::sync::SystemCounter;
+use ::sync::read_int64;
+use ::Client;
+
+const SYNC_OPCODE: u8 = 1;
+
+/// Lists the counters maintained by the server, like `SERVERTIME` and
+/// `IDLETIME`.
+#[derive(Debug, Clone, Copy)]
+pub struct SyncListSystemCounters;
+
+impl ExtensionRequest for SyncListSystemCounters {
+    type Reply = SyncListSystemCountersReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SYNC_OPCODE)?;
+        a.write_u16::<NativeEndian>(1)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let reply = SyncListSystemCountersReply::read(&buf)?;
+            Ok((client, reply))
+        }))
+    }
+}
+
+/// Reply of `SyncListSystemCounters` request.
+#[derive(Debug, Clone)]
+pub struct SyncListSystemCountersReply {
+    pub counters: Vec<SystemCounter>,
+}
+
+impl SyncListSystemCountersReply {
+    pub(crate) fn read(buf: &[u8]) -> io::Result<SyncListSystemCountersReply> {
+        let mut a = io::Cursor::new(buf);
+
+        a.set_position(8);
+        let count = a.read_u32::<NativeEndian>()?;
+        a.set_position(32);
+
+        let mut counters = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let counter = Counter(a.read_u32::<NativeEndian>()?);
+            let resolution = read_int64(&mut a)?;
+            let name_len = a.read_u16::<NativeEndian>()? as usize;
+
+            let mut name = vec![0u8; name_len];
+            a.read_exact(&mut name)?;
+            let pad = (4 - (14 + name_len) % 4) % 4;
+            a.set_position(a.position() + pad as u64);
+
+            counters.push(SystemCounter {
+                counter,
+                resolution,
+                name: String::from_utf8_lossy(&name).into_owned(),
+            });
+        }
+
+        Ok(SyncListSystemCountersReply { counters })
+    }
+
+    /// Finds a counter by name.
+    pub fn find(&self, name: &str) -> Option<&SystemCounter> {
+        self.counters.iter().find(|counter| counter.name == name)
+    }
+}
@@ -0,0 +1,65 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::sync::EXTENSION_NAME;
+use ::sync::Alarm;
+use ::sync::AlarmState;
+use ::sync::Trigger;
+use ::sync::read_int64;
+use ::Client;
+
+const SYNC_OPCODE: u8 = 10;
+
+/// Returns the attributes of an alarm.
+#[derive(Debug, Clone, Copy)]
+pub struct SyncQueryAlarm {
+    pub alarm: Alarm,
+}
+
+impl ExtensionRequest for SyncQueryAlarm {
+    type Reply = SyncQueryAlarmReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SYNC_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.alarm.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let reply = SyncQueryAlarmReply {
+                trigger: Trigger::read(&mut &buf[8..28])?,
+                delta: read_int64(&mut &buf[28..36])?,
+                events: buf[36] != 0,
+                state: AlarmState::from(buf[37]),
+            };
+
+            Ok((client, reply))
+        }))
+    }
+}
+
+/// Reply of `SyncQueryAlarm` request.
+#[derive(Debug, Clone, Copy)]
+pub struct SyncQueryAlarmReply {
+    pub trigger: Trigger,
+    pub delta: i64,
+    pub events: bool,
+    pub state: AlarmState,
+}
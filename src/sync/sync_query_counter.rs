@@ -0,0 +1,48 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::sync::EXTENSION_NAME;
+use ::sync::Counter;
+use ::sync::read_int64;
+use ::Client;
+
+const SYNC_OPCODE: u8 = 5;
+
+/// Returns the value of a counter.
+#[derive(Debug, Clone, Copy)]
+pub struct SyncQueryCounter {
+    pub counter: Counter,
+}
+
+impl ExtensionRequest for SyncQueryCounter {
+    type Reply = i64;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(SYNC_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.counter.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let value = read_int64(&mut &buf[8..16])?;
+            Ok((client, value))
+        }))
+    }
+}
@@ -0,0 +1,145 @@
+use ::futures::Future;
+
+use ::event::Event;
+use ::ewmh;
+use ::icccm;
+use ::protocol::ReplyFuture;
+use ::sync::Counter;
+use ::sync::SyncCreateCounter;
+use ::sync::SyncDestroyCounter;
+use ::sync::SyncSetCounter;
+use ::xproto::Atom;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+use ::Client;
+
+/// The client side of the `_NET_WM_SYNC_REQUEST` protocol, letting the
+/// window manager wait for a window to be redrawn after a resize.
+///
+/// The window manager sends the value to set before a `ConfigureNotify`,
+/// once the window is redrawn `frame_drawn` sets the counter to it.
+#[derive(Debug, Clone)]
+pub struct SyncRequest {
+    counter: Counter,
+    wm_protocols: Atom,
+    net_wm_sync_request: Atom,
+    pending: Option<i64>,
+    time: Timestamp,
+}
+
+impl SyncRequest {
+    /// Creates the counter of `window`, sets `_NET_WM_SYNC_REQUEST_COUNTER`
+    /// and adds `_NET_WM_SYNC_REQUEST` to its `WM_PROTOCOLS`. It should be
+    /// done before the window is mapped.
+    pub fn new(client: Client,
+               icccm_atoms: &icccm::Atoms,
+               ewmh_atoms: &ewmh::Atoms,
+               window: Window)
+               -> ReplyFuture<SyncRequest> {
+        let icccm_atoms = *icccm_atoms;
+        let ewmh_atoms = *ewmh_atoms;
+
+        Box::new(client.next_id::<Counter>()
+            .and_then(|(client, counter)| {
+                client.perform_ex(SyncCreateCounter {
+                        id: counter,
+                        initial_value: 0,
+                    })
+                    .map(move |(client, _)| (client, counter))
+            })
+            .and_then(move |(client, counter)| {
+                ewmh::set(client,
+                          &ewmh_atoms,
+                          window,
+                          &ewmh::NetWmSyncRequestCounter(counter))
+                    .map(move |(client, _)| (client, counter))
+            })
+            .and_then(move |(client, counter)| {
+                icccm::get::<icccm::WmProtocols>(client, &icccm_atoms, window)
+                    .map(move |(client, protocols)| (client, counter, protocols))
+            })
+            .and_then(move |(client, counter, protocols)| -> ReplyFuture<Counter> {
+                let mut protocols = protocols.unwrap_or_else(|| icccm::WmProtocols(Vec::new()));
+                if protocols.0.contains(&ewmh_atoms.net_wm_sync_request) {
+                    return Box::new(::futures::finished((client, counter)));
+                }
+
+                protocols.0.push(ewmh_atoms.net_wm_sync_request);
+                Box::new(icccm::set(client, &icccm_atoms, window, &protocols)
+                    .map(move |(client, _)| (client, counter)))
+            })
+            .map(move |(client, counter)| {
+                let sync_request = SyncRequest::from_counter(counter,
+                                                             icccm_atoms.wm_protocols,
+                                                             ewmh_atoms.net_wm_sync_request);
+                (client, sync_request)
+            }))
+    }
+
+    /// Uses an existing counter, already advertised on the window, with the
+    /// `WM_PROTOCOLS` and `_NET_WM_SYNC_REQUEST` atoms.
+    pub fn from_counter(counter: Counter,
+                        wm_protocols: Atom,
+                        net_wm_sync_request: Atom)
+                        -> SyncRequest {
+        SyncRequest {
+            counter,
+            wm_protocols,
+            net_wm_sync_request,
+            pending: None,
+            time: 0,
+        }
+    }
+
+    pub fn counter(&self) -> Counter {
+        self.counter
+    }
+
+    /// Handles `event` if it's a `_NET_WM_SYNC_REQUEST` message, returning
+    /// whether it was one.
+    pub fn handle(&mut self, event: &Event) -> bool {
+        let ev = match *event {
+            Event::ClientMessage(ref ev) if ev.type_ == self.wm_protocols && ev.format == 32 => ev,
+            _ => return false,
+        };
+
+        let data = ev.data32();
+        if data[0] != self.net_wm_sync_request.0 {
+            return false;
+        }
+
+        self.time = data[1];
+        self.pending = Some((data[3] as i32 as i64) << 32 | data[2] as i64);
+        true
+    }
+
+    /// The value to set once the next frame is drawn, if the window
+    /// manager asked for one.
+    pub fn pending(&self) -> Option<i64> {
+        self.pending
+    }
+
+    /// The time of the last `_NET_WM_SYNC_REQUEST` message.
+    pub fn time(&self) -> Timestamp {
+        self.time
+    }
+
+    /// Tells the window manager the window was redrawn, does nothing if
+    /// no value is pending.
+    pub fn frame_drawn(&mut self, client: Client) -> ReplyFuture<()> {
+        match self.pending.take() {
+            Some(value) => {
+                Box::new(client.perform_ex(SyncSetCounter {
+                    counter: self.counter,
+                    value,
+                }))
+            }
+            None => Box::new(::futures::finished((client, ()))),
+        }
+    }
+
+    /// Destroys the counter.
+    pub fn destroy(self, client: Client) -> ReplyFuture<()> {
+        Box::new(client.perform_ex(SyncDestroyCounter { counter: self.counter }))
+    }
+}
@@ -0,0 +1,406 @@
+//! Client side text rendering on top of RENDER glyph sets.
+//!
+//! Glyphs are rasterised from TrueType or OpenType fonts, uploaded lazily
+//! to a glyph set and drawn with `CompositeGlyphs`, like Xft does. This
+//! module needs the `text` feature.
+
+use ::std::collections::HashMap;
+use ::std::fs;
+use ::std::io;
+use ::std::path::Path;
+use ::std::sync::Arc;
+use ::std::sync::Mutex;
+
+use ::ab_glyph::Font;
+use ::ab_glyph::GlyphId;
+use ::ab_glyph::PxScale;
+use ::ab_glyph::ScaleFont;
+use ::futures;
+use ::futures::Future;
+use ::futures::Stream;
+
+pub use ::ab_glyph::FontArc;
+
+use ::protocol::ReplyFuture;
+use ::render::Glyph;
+use ::render::GlyphData;
+use ::render::GlyphInfo;
+use ::render::GlyphItem;
+use ::render::Glyphset;
+use ::render::PictOp;
+use ::render::Pictformat;
+use ::render::Picture;
+use ::render::RenderAddGlyphs;
+use ::render::RenderCompositeGlyphs32;
+use ::render::RenderCreateGlyphSet;
+use ::render::RenderFreeGlyphSet;
+use ::render::RenderQueryPictFormats;
+use ::render::StandardFormat;
+use ::Client;
+
+/// Maximum size of the glyphs uploaded with one `AddGlyphs` request.
+const MAX_UPLOAD: usize = 64 * 1024;
+
+/// Size of an `AddGlyphs` request with a single glyph, without its image.
+const ADD_GLYPH_SIZE: usize = 12 + 4 + 12;
+
+/// Loads a font from a TrueType or OpenType file.
+pub fn load_font<P: AsRef<Path>>(path: P) -> io::Result<FontArc> {
+    let data = fs::read(path)?;
+    FontArc::try_from_vec(data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Identifies a rasterised glyph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct GlyphKey {
+    /// Index of the font in the fallback list.
+    font: usize,
+
+    /// Bits of the size in pixels.
+    size: u32,
+
+    glyph: u16,
+}
+
+/// Glyphs rasterised by `GlyphCache::prepare`, added to the cache with
+/// `GlyphCache::insert` once they were uploaded.
+#[derive(Debug, Clone, Default)]
+pub struct NewGlyphs(HashMap<GlyphKey, (Glyph, i16)>);
+
+impl NewGlyphs {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// A glyph placed by `GlyphCache::layout`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionedGlyph {
+    /// Index of the font in the fallback list.
+    pub font: usize,
+
+    pub glyph: GlyphId,
+
+    /// Position of the origin from the start of the string.
+    pub x: f32,
+}
+
+/// Rasterised glyphs of a list of fonts.
+///
+/// The glyphs are rasterised in the A8 format the first time they are
+/// drawn, `prepare` returns the glyphs that must be uploaded to the glyph
+/// set along with the elements drawing the string. Glyphs whose image
+/// doesn't fit in a request are uploaded without an image, only their
+/// advance is kept.
+#[derive(Debug)]
+pub struct GlyphCache {
+    fonts: Vec<FontArc>,
+    /// Shared with the futures of `TextRenderer::draw`, which add the
+    /// glyphs once they were uploaded.
+    glyphs: Arc<Mutex<HashMap<GlyphKey, (Glyph, i16)>>>,
+    next_glyph: Glyph,
+    max_image: usize,
+}
+
+impl Clone for GlyphCache {
+    fn clone(&self) -> GlyphCache {
+        GlyphCache {
+            fonts: self.fonts.clone(),
+            glyphs: Arc::new(Mutex::new(self.glyphs.lock().unwrap().clone())),
+            next_glyph: self.next_glyph,
+            max_image: self.max_image,
+        }
+    }
+}
+
+impl GlyphCache {
+    /// Creates a cache drawing with `fonts`, a character missing in a font
+    /// is drawn with the next font having it. Fails if `fonts` is empty.
+    pub fn new(fonts: Vec<FontArc>) -> io::Result<GlyphCache> {
+        if fonts.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "at least one font is needed"));
+        }
+
+        Ok(GlyphCache {
+            fonts,
+            glyphs: Arc::new(Mutex::new(HashMap::new())),
+            next_glyph: 1,
+            max_image: 0xffff * 4 - ADD_GLYPH_SIZE,
+        })
+    }
+
+    /// Limits the glyph images to what a request of
+    /// `maximum_request_length` 4-byte units can carry, see
+    /// `ServerInfo::maximum_request_length`. `TextRenderer::new` sets it
+    /// from the server.
+    pub fn set_maximum_request_length(&mut self, maximum_request_length: u16) {
+        self.max_image = (maximum_request_length as usize * 4).saturating_sub(ADD_GLYPH_SIZE);
+    }
+
+    pub fn fonts(&self) -> &[FontArc] {
+        &self.fonts
+    }
+
+    /// Number of glyphs uploaded.
+    pub fn len(&self) -> usize {
+        self.glyphs.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.lock().unwrap().is_empty()
+    }
+
+    /// Adds the glyphs returned by `prepare` once they were uploaded.
+    pub fn insert(&mut self, glyphs: NewGlyphs) {
+        self.glyphs.lock().unwrap().extend(glyphs.0);
+    }
+
+    /// Distance from the baseline to the top of the first font.
+    pub fn ascent(&self, size: f32) -> f32 {
+        self.fonts[0].as_scaled(PxScale::from(size)).ascent()
+    }
+
+    /// Distance from the baseline to the bottom of the first font, usually
+    /// negative.
+    pub fn descent(&self, size: f32) -> f32 {
+        self.fonts[0].as_scaled(PxScale::from(size)).descent()
+    }
+
+    /// Places the glyphs of `text`, kerning the pairs of glyphs of the same
+    /// font.
+    pub fn layout(&self, text: &str, size: f32) -> Vec<PositionedGlyph> {
+        let scale = PxScale::from(size);
+        let mut glyphs = Vec::with_capacity(text.len());
+        let mut pen = 0.0;
+        let mut previous: Option<(usize, GlyphId)> = None;
+
+        for c in text.chars().filter(|c| !c.is_control()) {
+            let (font, glyph) = self.glyph_of(c);
+            let scaled = self.fonts[font].as_scaled(scale);
+
+            if let Some((previous_font, previous_glyph)) = previous {
+                if previous_font == font {
+                    pen += scaled.kern(previous_glyph, glyph);
+                }
+            }
+
+            glyphs.push(PositionedGlyph { font, glyph, x: pen });
+            pen += scaled.h_advance(glyph);
+            previous = Some((font, glyph));
+        }
+
+        glyphs
+    }
+
+    /// Width of `text` in pixels.
+    pub fn measure(&self, text: &str, size: f32) -> f32 {
+        let scale = PxScale::from(size);
+        self.layout(text, size).last().map_or(0.0, |last| {
+            last.x + self.fonts[last.font].as_scaled(scale).h_advance(last.glyph)
+        })
+    }
+
+    /// Returns the glyphs of `text` not uploaded yet and the elements drawing
+    /// it with its baseline starting at (`x`, `y`). The new glyphs are only
+    /// cached once passed to `insert`, they are rasterised again until then.
+    pub fn prepare(&mut self,
+                   text: &str,
+                   size: f32,
+                   x: i16,
+                   y: i16)
+                   -> (Vec<GlyphData>, Vec<GlyphItem>, NewGlyphs) {
+        let mut uploads = Vec::new();
+        let mut items = Vec::new();
+        let mut new = NewGlyphs::default();
+
+        // The position after the last glyph drawn, and the elements are
+        // relative to it.
+        let mut current = 0i32;
+        let mut dy = y;
+
+        for positioned in self.layout(text, size) {
+            let (id, x_off) =
+                self.rasterise(positioned.font, positioned.glyph, size, &mut new, &mut uploads);
+            let target = x as i32 + positioned.x.round() as i32;
+
+            match items.last_mut() {
+                Some(&mut GlyphItem::Glyphs { ref mut glyphs, .. }) if target == current => {
+                    glyphs.push(id);
+                }
+                _ => {
+                    items.push(GlyphItem::Glyphs {
+                        dx: (target - current) as i16,
+                        dy,
+                        glyphs: vec![id],
+                    });
+                    dy = 0;
+                }
+            }
+
+            current = target + x_off as i32;
+        }
+
+        (uploads, items, new)
+    }
+
+    /// Returns the id and advance of a glyph, rasterising it into `uploads`
+    /// and `new` if it isn't cached yet.
+    fn rasterise(&mut self,
+                 font: usize,
+                 glyph: GlyphId,
+                 size: f32,
+                 new: &mut NewGlyphs,
+                 uploads: &mut Vec<GlyphData>)
+                 -> (Glyph, i16) {
+        let key = GlyphKey {
+            font,
+            size: size.to_bits(),
+            glyph: glyph.0,
+        };
+        if let Some(&cached) = self.glyphs.lock().unwrap().get(&key).or_else(|| new.0.get(&key)) {
+            return cached;
+        }
+
+        let font = &self.fonts[font];
+        let x_off = font.as_scaled(PxScale::from(size)).h_advance(glyph).round() as i16;
+        let mut info = GlyphInfo { x_off, ..GlyphInfo::default() };
+        let mut image = Vec::new();
+
+        if let Some(outlined) = font.outline_glyph(glyph.with_scale(size)) {
+            let bounds = outlined.px_bounds();
+            let width = bounds.width() as usize;
+            let height = bounds.height() as usize;
+            // Rows of A8 images are padded to 4 bytes.
+            let stride = (width + 3) & !3;
+
+            if stride * height <= self.max_image {
+                image = vec![0; stride * height];
+                outlined.draw(|x, y, coverage| {
+                    let (x, y) = (x as usize, y as usize);
+                    if x < width && y < height {
+                        image[y * stride + x] = (coverage.min(1.0) * 255.0).round() as u8;
+                    }
+                });
+
+                info.width = width as u16;
+                info.height = height as u16;
+                info.x = -bounds.min.x as i16;
+                info.y = -bounds.min.y as i16;
+            }
+        }
+
+        let id = self.next_glyph;
+        self.next_glyph += 1;
+        new.0.insert(key, (id, x_off));
+        uploads.push(GlyphData { id, info, image });
+
+        (id, x_off)
+    }
+
+    /// Returns the first font having a glyph for `c`, the missing glyph of
+    /// the first font is used if none has it.
+    fn glyph_of(&self, c: char) -> (usize, GlyphId) {
+        self.fonts
+            .iter()
+            .map(|font| font.glyph_id(c))
+            .enumerate()
+            .find(|&(_, glyph)| glyph.0 != 0)
+            .unwrap_or((0, GlyphId(0)))
+    }
+}
+
+/// Draws text with a glyph set of the server.
+#[derive(Debug, Clone)]
+pub struct TextRenderer {
+    pub glyphset: Glyphset,
+    pub cache: GlyphCache,
+}
+
+impl TextRenderer {
+    /// Creates the glyph set used to draw with `fonts`.
+    pub fn new(client: Client, fonts: Vec<FontArc>) -> ReplyFuture<TextRenderer> {
+        let mut cache = match GlyphCache::new(fonts) {
+            Ok(cache) => cache,
+            Err(e) => return Box::new(futures::failed(e)),
+        };
+        cache.set_maximum_request_length(client.get_server_info().maximum_request_length);
+
+        Box::new(client.perform_ex(RenderQueryPictFormats)
+            .and_then(|(client, formats)| {
+                let format = formats.find_standard(StandardFormat::A8)
+                    .map(|info| info.id)
+                    .ok_or_else(|| io::Error::other("the server doesn't have the A8 format"));
+                futures::done(format).join(client.next_id())
+            })
+            .and_then(|(format, (client, glyphset)): (Pictformat, (Client, Glyphset))| {
+                client.perform_ex(RenderCreateGlyphSet {
+                        gsid: glyphset,
+                        format,
+                    })
+                    .map(move |(client, _)| (client, TextRenderer { glyphset, cache }))
+            }))
+    }
+
+    /// Draws `text` on `dst` with its baseline starting at `position`, the
+    /// color is taken from `src`, usually a solid fill picture. The new
+    /// glyphs are only cached once uploaded, if the future fails or is
+    /// dropped the next draw uploads them again.
+    pub fn draw(&mut self,
+                client: Client,
+                src: Picture,
+                dst: Picture,
+                position: (i16, i16),
+                size: f32,
+                text: &str)
+                -> ReplyFuture<()> {
+        let (uploads, items, new) = self.cache.prepare(text, size, position.0, position.1);
+        if items.is_empty() {
+            return Box::new(futures::finished((client, ())));
+        }
+
+        let glyphset = self.glyphset;
+        let cached = self.cache.glyphs.clone();
+        // The id and metrics of each glyph take 16 bytes.
+        let max_upload = MAX_UPLOAD.min(self.cache.max_image + 16);
+        let mut batches: Vec<Vec<GlyphData>> = Vec::new();
+        let mut batch_size = 0;
+        for glyph in uploads {
+            let size = 16 + glyph.image.len();
+            if batches.is_empty() || batch_size + size > max_upload {
+                batches.push(Vec::new());
+                batch_size = 0;
+            }
+            batch_size += size;
+            batches.last_mut().unwrap().push(glyph);
+        }
+
+        Box::new(futures::stream::iter_ok::<_, io::Error>(batches)
+            .fold(client, move |client, glyphs| {
+                client.perform_ex(RenderAddGlyphs { glyphset, glyphs })
+                    .map(|(client, _)| client)
+            })
+            .and_then(move |client| {
+                cached.lock().unwrap().extend(new.0);
+
+                client.perform_ex(RenderCompositeGlyphs32 {
+                    op: PictOp::Over,
+                    src,
+                    dst,
+                    mask_format: Pictformat::NONE,
+                    glyphset,
+                    src_x: 0,
+                    src_y: 0,
+                    items,
+                })
+            }))
+    }
+
+    /// Frees the glyph set.
+    pub fn free(self, client: Client) -> ReplyFuture<()> {
+        client.perform_ex(RenderFreeGlyphSet { glyphset: self.glyphset })
+    }
+}
@@ -0,0 +1,104 @@
+//! File descriptor passing over the Unix socket of the connection, sent as
+//! `SCM_RIGHTS` ancillary data.
+
+#![allow(unsafe_code)]
+
+use ::std::collections::VecDeque;
+use ::std::io;
+use ::std::mem;
+use ::std::os::unix::io::RawFd;
+use ::std::ptr;
+
+use ::libc;
+
+/// Maximum number of file descriptors received at once.
+const MAX_FDS: usize = 16;
+
+/// Sends `buf` with `fds` attached to its first byte.
+pub fn send_with_fds(socket: RawFd, buf: &[u8], fds: &[RawFd]) -> io::Result<usize> {
+    let mut iov = libc::iovec {
+        iov_base: buf.as_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+
+    let fds_len = size_of_val(fds);
+    let space = unsafe { libc::CMSG_SPACE(fds_len as u32) } as usize;
+    // `u64` keeps the buffer aligned for the `cmsghdr`.
+    let mut control = vec![0u64; space.div_ceil(8)];
+
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    msg.msg_controllen = space;
+
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(fds_len as u32) as _;
+        ptr::copy_nonoverlapping(fds.as_ptr(), libc::CMSG_DATA(cmsg) as *mut RawFd, fds.len());
+    }
+
+    let n = unsafe { libc::sendmsg(socket, &msg, libc::MSG_NOSIGNAL) };
+    if n == -1 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(n as usize)
+    }
+}
+
+/// Reads into `buf`, the file descriptors received are appended to `fds`.
+pub fn recv_with_fds(socket: RawFd,
+                     buf: &mut [u8],
+                     fds: &mut VecDeque<RawFd>)
+                     -> io::Result<usize> {
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+
+    let space = unsafe { libc::CMSG_SPACE(size_of::<[RawFd; MAX_FDS]>() as u32) } as usize;
+    let mut control = vec![0u64; space.div_ceil(8)];
+
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    msg.msg_controllen = space;
+
+    let n = unsafe { libc::recvmsg(socket, &mut msg, libc::MSG_CMSG_CLOEXEC) };
+    if n == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == libc::SOL_SOCKET && (*cmsg).cmsg_type == libc::SCM_RIGHTS {
+                let len = (*cmsg).cmsg_len - libc::CMSG_LEN(0) as usize;
+                let data = libc::CMSG_DATA(cmsg) as *const RawFd;
+                for i in 0..len / size_of::<RawFd>() {
+                    fds.push_back(ptr::read_unaligned(data.add(i)));
+                }
+            }
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+    }
+
+    Ok(n as usize)
+}
+
+/// Duplicates a file descriptor, the copy is closed on exec.
+pub fn dup(fd: RawFd) -> io::Result<RawFd> {
+    let copy = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) };
+    if copy == -1 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(copy)
+    }
+}
+
+pub fn close(fd: RawFd) {
+    unsafe { libc::close(fd) };
+}
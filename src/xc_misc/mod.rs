@@ -1,4 +1,5 @@
-/// XC MISC extension requests.
+//! XC-MISC extension requests, the resource ids no longer used by the
+//! client, used by `Client::reclaim_ids`.
 
 macro_rules! declare_requests {
     ($($request:ident),+) => {
@@ -14,3 +15,6 @@ declare_requests! {
     xc_misc_get_xid_range,
     xc_misc_get_xid_list
 }
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"XC-MISC";
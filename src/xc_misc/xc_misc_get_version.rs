@@ -4,14 +4,20 @@ use ::futures::Future;
 use ::byteorder::NativeEndian;
 use ::byteorder::WriteBytesExt;
 use ::byteorder::ReadBytesExt;
-use ::tokio_io;
 
 use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
 use ::protocol::ExtensionInfo;
+use ::xc_misc::EXTENSION_NAME;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
 use ::Client;
 
 const XC_MISC_OPCODE: u8 = 0;
 
+/// Negotiates the version of the extension, sent by `Client::perform_ex`
+/// before the first request of the extension.
 #[derive(Debug, Clone, Copy)]
 pub struct XCMiscGetVersion {
     /// Indicates what version of the protocol the client wants the server to implement.
@@ -25,7 +31,7 @@ impl ExtensionRequest for XCMiscGetVersion {
     type Reply = XCMiscGetVersionReply;
 
     fn extension_name() -> &'static [u8] {
-        b"XC-MISC"
+        EXTENSION_NAME
     }
 
     fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
@@ -40,9 +46,8 @@ impl ExtensionRequest for XCMiscGetVersion {
         Ok(a.into_inner())
     }
 
-    fn decode(client: Client) -> Box<Future<Item = (Client, Self::Reply), Error = io::Error>> {
-        let buf: [u8; 32] = [0u8; 32];
-        Box::new(tokio_io::io::read_exact(client, buf).and_then(|(client, buf)| {
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
             let mut a = io::Cursor::new(buf);
 
             a.read_u8()?;
@@ -62,7 +67,7 @@ impl ExtensionRequest for XCMiscGetVersion {
     }
 }
 
-/// Reply of `QueryExtension` request.
+/// Reply of `XCMiscGetVersion` request.
 #[derive(Debug, Clone, Copy)]
 pub struct XCMiscGetVersionReply {
     /// Indicates the version that server actually supports.
@@ -71,3 +76,16 @@ pub struct XCMiscGetVersionReply {
     /// Indicates the version that server actually supports.
     pub server_minor_version: u16,
 }
+
+impl VersionRequest for XCMiscGetVersion {
+    fn latest() -> XCMiscGetVersion {
+        XCMiscGetVersion {
+            client_major_version: 1,
+            client_minor_version: 1,
+        }
+    }
+
+    fn version(reply: &XCMiscGetVersionReply) -> Version {
+        Version::new(u32::from(reply.server_major_version), u32::from(reply.server_minor_version))
+    }
+}
@@ -4,15 +4,18 @@ use ::futures::Future;
 use ::byteorder::NativeEndian;
 use ::byteorder::WriteBytesExt;
 use ::byteorder::ReadBytesExt;
-use ::tokio_io;
 
 use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
 use ::protocol::ExtensionInfo;
+use ::xc_misc::EXTENSION_NAME;
 use ::xproto::Xid;
 use ::Client;
 
 const XC_MISC_OPCODE: u8 = 2;
 
+/// Returns scattered ids no longer used by the client, up to `count`.
 #[derive(Debug, Clone, Copy)]
 pub struct XCMiscGetXIDList {
     /// The number of IDs to be requested.
@@ -23,7 +26,7 @@ impl ExtensionRequest for XCMiscGetXIDList {
     type Reply = Vec<Xid>;
 
     fn extension_name() -> &'static [u8] {
-        b"XC-MISC"
+        EXTENSION_NAME
     }
 
     fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
@@ -37,35 +40,23 @@ impl ExtensionRequest for XCMiscGetXIDList {
         Ok(a.into_inner())
     }
 
-    #[cfg_attr(feature = "dev", allow(needless_range_loop))]
-    fn decode(client: Client) -> Box<Future<Item = (Client, Self::Reply), Error = io::Error>> {
-        let buf: [u8; 32] = [0u8; 32];
-        Box::new(tokio_io::io::read_exact(client, buf)
-            .and_then(|(client, buf)| {
-                let mut a = io::Cursor::new(buf);
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let mut a = io::Cursor::new(buf);
 
-                a.read_u8()?;
-                a.read_u8()?;
-                a.read_u16::<NativeEndian>()?;
-                a.read_u32::<NativeEndian>()?;
-                let count = a.read_u32::<NativeEndian>()?;
+            a.read_u8()?;
+            a.read_u8()?;
+            a.read_u16::<NativeEndian>()?;
+            a.read_u32::<NativeEndian>()?;
+            let count = a.read_u32::<NativeEndian>()?;
+            a.set_position(32);
 
-                Ok((client, count as usize))
-            })
-            .and_then(|(client, count)| {
-                let buf = vec![0u8; count * 4];
-                tokio_io::io::read_exact(client, buf)
-                    .map(move |(client, buf)| (client, count, buf))
-            })
-            .and_then(|(client, count, buf)| {
-                let mut reply = vec![0u32; count];
-                let mut a = io::Cursor::new(buf);
+            let mut reply = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                reply.push(a.read_u32::<NativeEndian>()?);
+            }
 
-                for i in 0..count {
-                    reply[i] = a.read_u32::<NativeEndian>()?;
-                }
-
-                Ok((client, reply))
-            }))
+            Ok((client, reply))
+        }))
     }
 }
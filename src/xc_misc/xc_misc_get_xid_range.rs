@@ -4,15 +4,19 @@ use ::futures::Future;
 use ::byteorder::NativeEndian;
 use ::byteorder::WriteBytesExt;
 use ::byteorder::ReadBytesExt;
-use ::tokio_io;
 
 use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
 use ::protocol::ExtensionInfo;
+use ::xc_misc::EXTENSION_NAME;
 use ::xproto::Xid;
 use ::Client;
 
 const XC_MISC_OPCODE: u8 = 1;
 
+/// Returns a range of ids no longer used by the client, empty if there's
+/// no contiguous range left.
 #[derive(Debug, Clone, Copy)]
 pub struct XCMiscGetXIDRange;
 
@@ -20,7 +24,7 @@ impl ExtensionRequest for XCMiscGetXIDRange {
     type Reply = XCMiscGetXIDRangeReply;
 
     fn extension_name() -> &'static [u8] {
-        b"XC-MISC"
+        EXTENSION_NAME
     }
 
     fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
@@ -33,9 +37,8 @@ impl ExtensionRequest for XCMiscGetXIDRange {
         Ok(a.into_inner())
     }
 
-    fn decode(client: Client) -> Box<Future<Item = (Client, Self::Reply), Error = io::Error>> {
-        let buf: [u8; 32] = [0u8; 32];
-        Box::new(tokio_io::io::read_exact(client, buf).and_then(|(client, buf)| {
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
             let mut a = io::Cursor::new(buf);
 
             a.read_u8()?;
@@ -55,7 +58,7 @@ impl ExtensionRequest for XCMiscGetXIDRange {
     }
 }
 
-/// Reply of `QueryExtension` request.
+/// Reply of `XCMiscGetXIDRange` request.
 #[derive(Debug, Clone, Copy)]
 pub struct XCMiscGetXIDRangeReply {
     /// The first ID in the range.
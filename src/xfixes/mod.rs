@@ -0,0 +1,214 @@
+//! XFIXES extension requests and events: selection and cursor tracking,
+//! regions, cursor visibility and pointer barriers.
+
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+
+use ::event::Event;
+use ::protocol::ExtensionInfo;
+use ::xproto::Atom;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    xfixes_query_version,
+    xfixes_select_selection_input,
+    xfixes_select_cursor_input,
+    xfixes_get_cursor_image,
+    xfixes_create_region,
+    xfixes_create_region_from_window,
+    xfixes_destroy_region,
+    xfixes_set_region,
+    xfixes_copy_region,
+    xfixes_union_region,
+    xfixes_intersect_region,
+    xfixes_subtract_region,
+    xfixes_invert_region,
+    xfixes_translate_region,
+    xfixes_region_extents,
+    xfixes_fetch_region,
+    xfixes_set_window_shape_region,
+    xfixes_set_picture_clip_region,
+    xfixes_get_cursor_image_and_name,
+    xfixes_hide_cursor,
+    xfixes_show_cursor,
+    xfixes_create_pointer_barrier,
+    xfixes_delete_pointer_barrier
+}
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"XFIXES";
+
+declare_ids! {
+    /// A region, a set of rectangles.
+    Region,
+
+    /// A pointer barrier.
+    Barrier
+}
+
+/// Changes of a selection reported by `XFixesSelectionNotify` events.
+pub type SelectionEventMask = u32;
+pub const SELECTION_EVENT_MASK_SET_SELECTION_OWNER: SelectionEventMask = 1;
+pub const SELECTION_EVENT_MASK_SELECTION_WINDOW_DESTROY: SelectionEventMask = 2;
+pub const SELECTION_EVENT_MASK_SELECTION_CLIENT_CLOSE: SelectionEventMask = 4;
+
+/// Changes of the cursor reported by `XFixesCursorNotify` events.
+pub type CursorEventMask = u32;
+pub const CURSOR_EVENT_MASK_DISPLAY_CURSOR: CursorEventMask = 1;
+
+/// Directions a pointer barrier can be crossed.
+pub type BarrierDirections = u32;
+pub const BARRIER_POSITIVE_X: BarrierDirections = 1;
+pub const BARRIER_POSITIVE_Y: BarrierDirections = 2;
+pub const BARRIER_NEGATIVE_X: BarrierDirections = 4;
+pub const BARRIER_NEGATIVE_Y: BarrierDirections = 8;
+
+/// The image of a cursor.
+#[derive(Debug, Clone)]
+pub struct CursorImage {
+    /// Position of the pointer.
+    pub x: i16,
+    pub y: i16,
+
+    pub width: u16,
+    pub height: u16,
+
+    /// The hotspot, the position of the pointer in the image.
+    pub xhot: u16,
+    pub yhot: u16,
+
+    /// Changes when the cursor changes, like in `XFixesCursorNotify`.
+    pub cursor_serial: u32,
+
+    /// Premultiplied ARGB pixels, row by row.
+    pub pixels: Vec<u32>,
+}
+
+impl CursorImage {
+    /// Reads the image of a reply, the pixels start at `offset`.
+    fn read(buf: &[u8], offset: usize) -> io::Result<CursorImage> {
+        let width = NativeEndian::read_u16(&buf[12..14]);
+        let height = NativeEndian::read_u16(&buf[14..16]);
+
+        let len = width as usize * height as usize;
+        let data = buf.get(offset..offset + len * 4)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "short cursor image"))?;
+
+        Ok(CursorImage {
+            x: NativeEndian::read_i16(&buf[8..10]),
+            y: NativeEndian::read_i16(&buf[10..12]),
+            width,
+            height,
+            xhot: NativeEndian::read_u16(&buf[16..18]),
+            yhot: NativeEndian::read_u16(&buf[18..20]),
+            cursor_serial: NativeEndian::read_u32(&buf[20..24]),
+            pixels: data.chunks(4).map(NativeEndian::read_u32).collect(),
+        })
+    }
+}
+
+/// Offsets of the XFIXES events from the extension `first_event`.
+const SELECTION_NOTIFY: u8 = 0;
+const CURSOR_NOTIFY: u8 = 1;
+
+/// An event of the XFIXES extension.
+#[derive(Debug, Clone, Copy)]
+pub enum XFixesEvent {
+    SelectionNotify(XFixesSelectionNotifyEvent),
+    CursorNotify(XFixesCursorNotifyEvent),
+}
+
+impl XFixesEvent {
+    /// Decodes `event` if it's a XFIXES event, `info` is the information of
+    /// the extension as returned by `Client::extension_info`.
+    pub fn from_event(info: &ExtensionInfo, event: &Event) -> Option<XFixesEvent> {
+        match *event {
+            Event::Unknown(ref buf) => XFixesEvent::read(info, buf),
+            _ => None,
+        }
+    }
+
+    /// Decodes an event packet.
+    pub fn read(info: &ExtensionInfo, buf: &[u8]) -> Option<XFixesEvent> {
+        if !info.present || info.first_event == 0 {
+            return None;
+        }
+
+        match (buf[0] & 0x7f).wrapping_sub(info.first_event) {
+            SELECTION_NOTIFY => {
+                Some(XFixesEvent::SelectionNotify(XFixesSelectionNotifyEvent {
+                    subtype: SelectionNotifySubtype::from(buf[1]),
+                    window: Window(NativeEndian::read_u32(&buf[4..8])),
+                    owner: Window(NativeEndian::read_u32(&buf[8..12])),
+                    selection: Atom(NativeEndian::read_u32(&buf[12..16])),
+                    timestamp: NativeEndian::read_u32(&buf[16..20]),
+                    selection_timestamp: NativeEndian::read_u32(&buf[20..24]),
+                }))
+            }
+            CURSOR_NOTIFY => {
+                Some(XFixesEvent::CursorNotify(XFixesCursorNotifyEvent {
+                    window: Window(NativeEndian::read_u32(&buf[4..8])),
+                    cursor_serial: NativeEndian::read_u32(&buf[8..12]),
+                    timestamp: NativeEndian::read_u32(&buf[12..16]),
+                    name: Atom(NativeEndian::read_u32(&buf[16..20])),
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Why a `XFixesSelectionNotify` event was sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionNotifySubtype {
+    SetSelectionOwner,
+    SelectionWindowDestroy,
+    SelectionClientClose,
+}
+
+impl From<u8> for SelectionNotifySubtype {
+    fn from(subtype: u8) -> SelectionNotifySubtype {
+        match subtype {
+            1 => SelectionNotifySubtype::SelectionWindowDestroy,
+            2 => SelectionNotifySubtype::SelectionClientClose,
+            _ => SelectionNotifySubtype::SetSelectionOwner,
+        }
+    }
+}
+
+/// Sent when the owner of a selection changes.
+#[derive(Debug, Clone, Copy)]
+pub struct XFixesSelectionNotifyEvent {
+    pub subtype: SelectionNotifySubtype,
+    pub window: Window,
+
+    /// The new owner, `Window::NONE` if the selection has no owner.
+    pub owner: Window,
+
+    pub selection: Atom,
+    pub timestamp: Timestamp,
+    pub selection_timestamp: Timestamp,
+}
+
+/// Sent when the cursor changes.
+#[derive(Debug, Clone, Copy)]
+pub struct XFixesCursorNotifyEvent {
+    pub window: Window,
+    pub cursor_serial: u32,
+    pub timestamp: Timestamp,
+
+    /// The name of the new cursor, `ATOM_NONE` if it has no name.
+    pub name: Atom,
+}
@@ -0,0 +1,46 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xfixes::EXTENSION_NAME;
+use ::xfixes::Region;
+use ::Client;
+
+const XFIXES_OPCODE: u8 = 12;
+
+/// Replaces `destination` with a copy of `source`.
+#[derive(Debug, Clone, Copy)]
+pub struct XFixesCopyRegion {
+    pub source: Region,
+    pub destination: Region,
+}
+
+impl ExtensionRequest for XFixesCopyRegion {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XFIXES_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.source.0)?;
+        a.write_u32::<NativeEndian>(self.destination.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,78 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xfixes::EXTENSION_NAME;
+use ::xfixes::Barrier;
+use ::xfixes::BarrierDirections;
+use ::xproto::Window;
+use ::Client;
+use ::pad;
+
+const XFIXES_OPCODE: u8 = 31;
+
+/// Creates a horizontal or vertical line the pointer can't cross, from
+/// (`x1`, `y1`) to (`x2`, `y2`).
+#[derive(Debug, Clone)]
+pub struct XFixesCreatePointerBarrier {
+    pub barrier: Barrier,
+
+    /// Selects the screen of the barrier.
+    pub window: Window,
+
+    pub x1: u16,
+    pub y1: u16,
+    pub x2: u16,
+    pub y2: u16,
+
+    /// Directions the pointer is allowed to cross the barrier.
+    pub directions: BarrierDirections,
+
+    /// Input devices blocked, all of them if it's empty.
+    pub devices: Vec<u16>,
+}
+
+impl ExtensionRequest for XFixesCreatePointerBarrier {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let devices_len = self.devices.len() * 2;
+        let p = pad(devices_len);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XFIXES_OPCODE)?;
+        a.write_u16::<NativeEndian>((7 + (devices_len + p) / 4) as u16)?;
+        a.write_u32::<NativeEndian>(self.barrier.0)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u16::<NativeEndian>(self.x1)?;
+        a.write_u16::<NativeEndian>(self.y1)?;
+        a.write_u16::<NativeEndian>(self.x2)?;
+        a.write_u16::<NativeEndian>(self.y2)?;
+        a.write_u32::<NativeEndian>(self.directions)?;
+        a.write_u16::<NativeEndian>(0)?;
+        a.write_u16::<NativeEndian>(self.devices.len() as u16)?;
+        for device in &self.devices {
+            a.write_u16::<NativeEndian>(*device)?;
+        }
+        a.write_all(&[0; 3][..p])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,50 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::request_length;
+use ::xfixes::EXTENSION_NAME;
+use ::xfixes::Region;
+use ::xproto::Rectangle;
+use ::Client;
+
+const XFIXES_OPCODE: u8 = 5;
+
+/// Creates a region, the union of `rectangles`.
+#[derive(Debug, Clone)]
+pub struct XFixesCreateRegion {
+    pub region: Region,
+    pub rectangles: Vec<Rectangle>,
+}
+
+impl ExtensionRequest for XFixesCreateRegion {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XFIXES_OPCODE)?;
+        a.write_u16::<NativeEndian>(request_length(2 + 2 * self.rectangles.len())?)?;
+        a.write_u32::<NativeEndian>(self.region.0)?;
+        for rect in &self.rectangles {
+            rect.write(&mut a)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
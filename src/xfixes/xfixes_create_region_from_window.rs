@@ -0,0 +1,52 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xfixes::EXTENSION_NAME;
+use ::xfixes::Region;
+use ::shape::ShapeKind;
+use ::xproto::Window;
+use ::Client;
+
+const XFIXES_OPCODE: u8 = 7;
+
+/// Creates a region from a shape of a window.
+#[derive(Debug, Clone, Copy)]
+pub struct XFixesCreateRegionFromWindow {
+    pub region: Region,
+    pub window: Window,
+    pub kind: ShapeKind,
+}
+
+impl ExtensionRequest for XFixesCreateRegionFromWindow {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XFIXES_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u32::<NativeEndian>(self.region.0)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u8(self.kind as u8)?;
+        a.write_all(&[0; 3])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
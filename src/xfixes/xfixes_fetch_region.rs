@@ -0,0 +1,66 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xfixes::EXTENSION_NAME;
+use ::xfixes::Region;
+use ::xproto::Rectangle;
+use ::Client;
+
+const XFIXES_OPCODE: u8 = 19;
+
+/// Returns the rectangles of a region.
+#[derive(Debug, Clone, Copy)]
+pub struct XFixesFetchRegion {
+    pub region: Region,
+}
+
+impl ExtensionRequest for XFixesFetchRegion {
+    type Reply = XFixesFetchRegionReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XFIXES_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.region.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let mut a = io::Cursor::new(&buf[8..]);
+            let extents = Rectangle::read(&mut a)?;
+
+            let count = (buf.len() - 32) / 8;
+            let mut a = io::Cursor::new(&buf[32..]);
+            let mut rectangles = Vec::with_capacity(count);
+            for _ in 0..count {
+                rectangles.push(Rectangle::read(&mut a)?);
+            }
+
+            Ok((client, XFixesFetchRegionReply { extents, rectangles }))
+        }))
+    }
+}
+
+/// Reply of `XFixesFetchRegion` request.
+#[derive(Debug, Clone)]
+pub struct XFixesFetchRegionReply {
+    /// The bounding box of the region.
+    pub extents: Rectangle,
+
+    pub rectangles: Vec<Rectangle>,
+}
@@ -0,0 +1,44 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xfixes::EXTENSION_NAME;
+use ::xfixes::CursorImage;
+use ::Client;
+
+const XFIXES_OPCODE: u8 = 4;
+
+/// Returns the image of the current cursor.
+#[derive(Debug, Clone, Copy)]
+pub struct XFixesGetCursorImage;
+
+impl ExtensionRequest for XFixesGetCursorImage {
+    type Reply = CursorImage;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XFIXES_OPCODE)?;
+        a.write_u16::<NativeEndian>(1)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let image = CursorImage::read(&buf, 32)?;
+            Ok((client, image))
+        }))
+    }
+}
@@ -0,0 +1,65 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xfixes::EXTENSION_NAME;
+use ::xfixes::CursorImage;
+use ::xproto::Atom;
+use ::Client;
+
+const XFIXES_OPCODE: u8 = 25;
+
+/// Returns the image and the name of the current cursor.
+#[derive(Debug, Clone, Copy)]
+pub struct XFixesGetCursorImageAndName;
+
+impl ExtensionRequest for XFixesGetCursorImageAndName {
+    type Reply = XFixesGetCursorImageAndNameReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XFIXES_OPCODE)?;
+        a.write_u16::<NativeEndian>(1)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let image = CursorImage::read(&buf, 32)?;
+            let atom = Atom(NativeEndian::read_u32(&buf[24..28]));
+            let name_len = NativeEndian::read_u16(&buf[28..30]) as usize;
+
+            let start = 32 + image.pixels.len() * 4;
+            let name = buf.get(start..start + name_len)
+                .map(|name| String::from_utf8_lossy(name).into_owned())
+                .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "short cursor name"))?;
+
+            Ok((client, XFixesGetCursorImageAndNameReply { image, atom, name }))
+        }))
+    }
+}
+
+/// Reply of `XFixesGetCursorImageAndName` request.
+#[derive(Debug, Clone)]
+pub struct XFixesGetCursorImageAndNameReply {
+    pub image: CursorImage,
+
+    /// The name as an atom, `ATOM_NONE` if the cursor has no name.
+    pub atom: Atom,
+
+    pub name: String,
+}
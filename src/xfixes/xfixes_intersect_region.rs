@@ -0,0 +1,48 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xfixes::EXTENSION_NAME;
+use ::xfixes::Region;
+use ::Client;
+
+const XFIXES_OPCODE: u8 = 14;
+
+/// Stores the intersection of `source1` and `source2` in `destination`.
+#[derive(Debug, Clone, Copy)]
+pub struct XFixesIntersectRegion {
+    pub source1: Region,
+    pub source2: Region,
+    pub destination: Region,
+}
+
+impl ExtensionRequest for XFixesIntersectRegion {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XFIXES_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u32::<NativeEndian>(self.source1.0)?;
+        a.write_u32::<NativeEndian>(self.source2.0)?;
+        a.write_u32::<NativeEndian>(self.destination.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,49 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xfixes::EXTENSION_NAME;
+use ::xfixes::Region;
+use ::xproto::Rectangle;
+use ::Client;
+
+const XFIXES_OPCODE: u8 = 16;
+
+/// Stores the part of `bounds` not in `source` in `destination`.
+#[derive(Debug, Clone, Copy)]
+pub struct XFixesInvertRegion {
+    pub source: Region,
+    pub bounds: Rectangle,
+    pub destination: Region,
+}
+
+impl ExtensionRequest for XFixesInvertRegion {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XFIXES_OPCODE)?;
+        a.write_u16::<NativeEndian>(5)?;
+        a.write_u32::<NativeEndian>(self.source.0)?;
+        self.bounds.write(&mut a)?;
+        a.write_u32::<NativeEndian>(self.destination.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
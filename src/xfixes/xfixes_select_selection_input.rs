@@ -0,0 +1,51 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xfixes::EXTENSION_NAME;
+use ::xfixes::SelectionEventMask;
+use ::xproto::Atom;
+use ::xproto::Window;
+use ::Client;
+
+const XFIXES_OPCODE: u8 = 2;
+
+/// Selects the `XFixesSelectionNotify` events reported to `window` about
+/// `selection`.
+#[derive(Debug, Clone, Copy)]
+pub struct XFixesSelectSelectionInput {
+    pub window: Window,
+    pub selection: Atom,
+    pub event_mask: SelectionEventMask,
+}
+
+impl ExtensionRequest for XFixesSelectSelectionInput {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XFIXES_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u32::<NativeEndian>(self.selection.0)?;
+        a.write_u32::<NativeEndian>(self.event_mask)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,54 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xfixes::EXTENSION_NAME;
+use ::render::Picture;
+use ::xfixes::Region;
+use ::Client;
+
+const XFIXES_OPCODE: u8 = 22;
+
+/// Clips a picture to a region.
+#[derive(Debug, Clone, Copy)]
+pub struct XFixesSetPictureClipRegion {
+    pub picture: Picture,
+
+    /// `Region::NONE` removes the clip.
+    pub region: Region,
+
+    pub x_origin: i16,
+    pub y_origin: i16,
+}
+
+impl ExtensionRequest for XFixesSetPictureClipRegion {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XFIXES_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u32::<NativeEndian>(self.picture.0)?;
+        a.write_u32::<NativeEndian>(self.region.0)?;
+        a.write_i16::<NativeEndian>(self.x_origin)?;
+        a.write_i16::<NativeEndian>(self.y_origin)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
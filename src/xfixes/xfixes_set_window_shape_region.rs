@@ -0,0 +1,58 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xfixes::EXTENSION_NAME;
+use ::xfixes::Region;
+use ::shape::ShapeKind;
+use ::xproto::Window;
+use ::Client;
+
+const XFIXES_OPCODE: u8 = 21;
+
+/// Sets a shape of a window from a region.
+#[derive(Debug, Clone, Copy)]
+pub struct XFixesSetWindowShapeRegion {
+    pub dest: Window,
+    pub dest_kind: ShapeKind,
+    pub x_offset: i16,
+    pub y_offset: i16,
+
+    /// `Region::NONE` removes the shape.
+    pub region: Region,
+}
+
+impl ExtensionRequest for XFixesSetWindowShapeRegion {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XFIXES_OPCODE)?;
+        a.write_u16::<NativeEndian>(5)?;
+        a.write_u32::<NativeEndian>(self.dest.0)?;
+        a.write_u8(self.dest_kind as u8)?;
+        a.write_all(&[0; 3])?;
+        a.write_i16::<NativeEndian>(self.x_offset)?;
+        a.write_i16::<NativeEndian>(self.y_offset)?;
+        a.write_u32::<NativeEndian>(self.region.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,126 @@
+//! Resource identifier allocation.
+
+use ::std::sync::Mutex;
+
+use ::xproto::Xid;
+
+/// Allocates the resource ids (XIDs) of a client.
+///
+/// Ids are taken first from the range assigned at connection setup, then
+/// from the ids returned with `free`, and finally from the ranges reclaimed
+/// from the server with the XC-MISC extension and added with `add_range` or
+/// `add_ids`. It can be shared between threads.
+#[derive(Debug)]
+pub struct XidAllocator {
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    /// Next id of the current range.
+    next: u64,
+
+    /// Last id of the current range.
+    last: u64,
+
+    /// Distance between two consecutive ids.
+    inc: u32,
+
+    /// Unused parts of previous ranges, as (next, last), replaced by
+    /// `add_range` before being exhausted.
+    ranges: Vec<(u64, u64)>,
+
+    /// Ids that were freed or reclaimed.
+    free: Vec<Xid>,
+}
+
+impl XidAllocator {
+    /// Creates an allocator for the `resource_id_base` and
+    /// `resource_id_mask` received at connection setup.
+    pub fn new(base: u32, mask: u32) -> XidAllocator {
+        // The lowest set bit of the mask.
+        let inc = mask & mask.wrapping_neg();
+
+        let state = if inc == 0 {
+            State {
+                next: 1,
+                last: 0,
+                inc: 1,
+                ranges: Vec::new(),
+                free: Vec::new(),
+            }
+        } else {
+            State {
+                next: base as u64,
+                last: (base | mask) as u64,
+                inc,
+                ranges: Vec::new(),
+                free: Vec::new(),
+            }
+        };
+
+        XidAllocator { state: Mutex::new(state) }
+    }
+
+    /// Allocates an id, `None` is returned when all the ids are used.
+    pub fn allocate(&self) -> Option<Xid> {
+        let mut state = self.state.lock().unwrap();
+
+        if state.next > state.last {
+            if let Some((next, last)) = state.ranges.pop() {
+                state.next = next;
+                state.last = last;
+            }
+        }
+
+        if state.next <= state.last {
+            let xid = state.next as Xid;
+            state.next += state.inc as u64;
+            Some(xid)
+        } else {
+            state.free.pop()
+        }
+    }
+
+    /// Returns an id to be reused, it must have been freed in the server
+    /// (with a `Free*` or `Destroy*` request) before being returned.
+    pub fn free(&self, xid: Xid) {
+        self.state.lock().unwrap().free.push(xid);
+    }
+
+    /// Adds a range of `count` ids starting at `start_id`, as returned by
+    /// `XCMiscGetXIDRange`. The ids left in the current range are kept and
+    /// allocated once the new range is exhausted.
+    pub fn add_range(&self, start_id: Xid, count: u32) {
+        if count == 0 {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if state.next <= state.last {
+            let current = (state.next, state.last);
+            state.ranges.push(current);
+        }
+        state.next = start_id as u64;
+        state.last = start_id as u64 + (count as u64 - 1) * state.inc as u64;
+    }
+
+    /// Adds a list of unused ids, as returned by `XCMiscGetXIDList`.
+    pub fn add_ids(&self, ids: &[Xid]) {
+        self.state.lock().unwrap().free.extend_from_slice(ids);
+    }
+
+    /// Number of ids that can be allocated without asking the server.
+    pub fn available(&self) -> u64 {
+        let state = self.state.lock().unwrap();
+        let inc = state.inc as u64;
+        let in_range = |(next, last): (u64, u64)| if next <= last {
+            (last - next) / inc + 1
+        } else {
+            0
+        };
+
+        in_range((state.next, state.last)) + state.ranges.iter().map(|&r| in_range(r)).sum::<u64>() +
+        state.free.len() as u64
+    }
+}
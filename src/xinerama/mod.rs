@@ -0,0 +1,23 @@
+//! XINERAMA extension requests, the geometry of the physical screens
+//! joined in a single X screen.
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    xinerama_query_version,
+    xinerama_get_state,
+    xinerama_get_screen_count,
+    xinerama_get_screen_size,
+    xinerama_is_active,
+    xinerama_query_screens
+}
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"XINERAMA";
@@ -0,0 +1,61 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xinerama::EXTENSION_NAME;
+use ::xproto::Window;
+use ::Client;
+
+const XINERAMA_OPCODE: u8 = 3;
+
+/// Returns the size of a physical screen.
+#[derive(Debug, Clone, Copy)]
+pub struct XineramaGetScreenSize {
+    pub window: Window,
+    pub screen: u32,
+}
+
+impl ExtensionRequest for XineramaGetScreenSize {
+    type Reply = XineramaGetScreenSizeReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XINERAMA_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u32::<NativeEndian>(self.screen)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = XineramaGetScreenSizeReply {
+                width: NativeEndian::read_u32(&buf[8..12]),
+                height: NativeEndian::read_u32(&buf[12..16]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `XineramaGetScreenSize` request.
+#[derive(Debug, Clone, Copy)]
+pub struct XineramaGetScreenSizeReply {
+    pub width: u32,
+    pub height: u32,
+}
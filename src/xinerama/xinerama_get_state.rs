@@ -0,0 +1,44 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xinerama::EXTENSION_NAME;
+use ::xproto::Window;
+use ::Client;
+
+const XINERAMA_OPCODE: u8 = 1;
+
+/// Returns whether Xinerama is enabled on the screen of `window`.
+#[derive(Debug, Clone, Copy)]
+pub struct XineramaGetState {
+    pub window: Window,
+}
+
+impl ExtensionRequest for XineramaGetState {
+    type Reply = bool;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XINERAMA_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| (client, buf[1] != 0)))
+    }
+}
@@ -0,0 +1,43 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xinerama::EXTENSION_NAME;
+use ::Client;
+
+const XINERAMA_OPCODE: u8 = 4;
+
+/// Returns whether Xinerama is active.
+#[derive(Debug, Clone, Copy)]
+pub struct XineramaIsActive;
+
+impl ExtensionRequest for XineramaIsActive {
+    type Reply = bool;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XINERAMA_OPCODE)?;
+        a.write_u16::<NativeEndian>(1)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            (client, NativeEndian::read_u32(&buf[8..12]) != 0)
+        }))
+    }
+}
@@ -0,0 +1,53 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xinerama::EXTENSION_NAME;
+use ::xproto::Rectangle;
+use ::Client;
+
+const XINERAMA_OPCODE: u8 = 5;
+
+/// Returns the geometry of the physical screens, empty if Xinerama is
+/// not active.
+#[derive(Debug, Clone, Copy)]
+pub struct XineramaQueryScreens;
+
+impl ExtensionRequest for XineramaQueryScreens {
+    type Reply = Vec<Rectangle>;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XINERAMA_OPCODE)?;
+        a.write_u16::<NativeEndian>(1)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let number = NativeEndian::read_u32(&buf[8..12]) as usize;
+
+            let mut a = &buf[32..];
+            let mut screens = Vec::with_capacity(number);
+            for _ in 0..number {
+                screens.push(Rectangle::read(&mut a)?);
+            }
+
+            Ok((client, screens))
+        }))
+    }
+}
@@ -0,0 +1,78 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::xinerama::EXTENSION_NAME;
+use ::Client;
+
+const XINERAMA_OPCODE: u8 = 0;
+
+/// Returns the version of the extension, `XineramaIsActive` and
+/// `XineramaQueryScreens` need 1.1.
+#[derive(Debug, Clone, Copy)]
+pub struct XineramaQueryVersion {
+    pub major: u8,
+    pub minor: u8,
+}
+
+impl ExtensionRequest for XineramaQueryVersion {
+    type Reply = XineramaQueryVersionReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XINERAMA_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u8(self.major)?;
+        a.write_u8(self.minor)?;
+        a.write_all(&[0; 2])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = XineramaQueryVersionReply {
+                major: NativeEndian::read_u16(&buf[8..10]),
+                minor: NativeEndian::read_u16(&buf[10..12]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `XineramaQueryVersion` request.
+#[derive(Debug, Clone, Copy)]
+pub struct XineramaQueryVersionReply {
+    pub major: u16,
+    pub minor: u16,
+}
+
+impl VersionRequest for XineramaQueryVersion {
+    fn latest() -> XineramaQueryVersion {
+        XineramaQueryVersion {
+            major: 1,
+            minor: 1,
+        }
+    }
+
+    fn version(reply: &XineramaQueryVersionReply) -> Version {
+        Version::new(u32::from(reply.major), u32::from(reply.minor))
+    }
+}
@@ -0,0 +1,225 @@
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+
+use ::event::Event;
+use ::protocol::ExtensionInfo;
+use ::protocol::GENERIC_EVENT;
+use ::xinput::DeviceId;
+use ::xinput::EventFlags;
+use ::xinput::fp1616_to_f64;
+use ::xinput::read_valuators;
+use ::xinput::XI_BUTTON_PRESS;
+use ::xinput::XI_BUTTON_RELEASE;
+use ::xinput::XI_KEY_PRESS;
+use ::xinput::XI_KEY_RELEASE;
+use ::xinput::XI_MOTION;
+use ::xinput::XI_RAW_BUTTON_PRESS;
+use ::xinput::XI_RAW_BUTTON_RELEASE;
+use ::xinput::XI_RAW_KEY_PRESS;
+use ::xinput::XI_RAW_KEY_RELEASE;
+use ::xinput::XI_RAW_MOTION;
+use ::xinput::XI_RAW_TOUCH_BEGIN;
+use ::xinput::XI_RAW_TOUCH_END;
+use ::xinput::XI_RAW_TOUCH_UPDATE;
+use ::xinput::XI_TOUCH_BEGIN;
+use ::xinput::XI_TOUCH_END;
+use ::xinput::XI_TOUCH_UPDATE;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+
+/// An XI2 event, selected with `XISelectEvents`.
+#[derive(Debug, Clone)]
+pub enum XIEvent {
+    KeyPress(XIDeviceEvent),
+    KeyRelease(XIDeviceEvent),
+    ButtonPress(XIDeviceEvent),
+    ButtonRelease(XIDeviceEvent),
+    Motion(XIDeviceEvent),
+    TouchBegin(XIDeviceEvent),
+    TouchUpdate(XIDeviceEvent),
+    TouchEnd(XIDeviceEvent),
+    RawKeyPress(XIRawEvent),
+    RawKeyRelease(XIRawEvent),
+    RawButtonPress(XIRawEvent),
+    RawButtonRelease(XIRawEvent),
+    RawMotion(XIRawEvent),
+    RawTouchBegin(XIRawEvent),
+    RawTouchUpdate(XIRawEvent),
+    RawTouchEnd(XIRawEvent),
+}
+
+impl XIEvent {
+    /// Decodes `event` if it's an XI2 event, `info` is the information of
+    /// the extension as returned by `Client::extension_info`.
+    pub fn from_event(info: &ExtensionInfo, event: &Event) -> Option<XIEvent> {
+        match *event {
+            Event::Unknown(ref buf) => XIEvent::read(info, buf),
+            _ => None,
+        }
+    }
+
+    /// Decodes a GenericEvent packet, `None` is returned for the events of
+    /// other extensions and the XI2 events not decoded by this crate.
+    pub fn read(info: &ExtensionInfo, buf: &[u8]) -> Option<XIEvent> {
+        if !info.present || buf.len() < 32 || buf[0] & 0x7f != GENERIC_EVENT ||
+           buf[1] != info.major_opcode {
+            return None;
+        }
+
+        let event = match NativeEndian::read_u16(&buf[8..10]) {
+            XI_KEY_PRESS => XIEvent::KeyPress(XIDeviceEvent::read(buf)?),
+            XI_KEY_RELEASE => XIEvent::KeyRelease(XIDeviceEvent::read(buf)?),
+            XI_BUTTON_PRESS => XIEvent::ButtonPress(XIDeviceEvent::read(buf)?),
+            XI_BUTTON_RELEASE => XIEvent::ButtonRelease(XIDeviceEvent::read(buf)?),
+            XI_MOTION => XIEvent::Motion(XIDeviceEvent::read(buf)?),
+            XI_TOUCH_BEGIN => XIEvent::TouchBegin(XIDeviceEvent::read(buf)?),
+            XI_TOUCH_UPDATE => XIEvent::TouchUpdate(XIDeviceEvent::read(buf)?),
+            XI_TOUCH_END => XIEvent::TouchEnd(XIDeviceEvent::read(buf)?),
+            XI_RAW_KEY_PRESS => XIEvent::RawKeyPress(XIRawEvent::read(buf)?),
+            XI_RAW_KEY_RELEASE => XIEvent::RawKeyRelease(XIRawEvent::read(buf)?),
+            XI_RAW_BUTTON_PRESS => XIEvent::RawButtonPress(XIRawEvent::read(buf)?),
+            XI_RAW_BUTTON_RELEASE => XIEvent::RawButtonRelease(XIRawEvent::read(buf)?),
+            XI_RAW_MOTION => XIEvent::RawMotion(XIRawEvent::read(buf)?),
+            XI_RAW_TOUCH_BEGIN => XIEvent::RawTouchBegin(XIRawEvent::read(buf)?),
+            XI_RAW_TOUCH_UPDATE => XIEvent::RawTouchUpdate(XIRawEvent::read(buf)?),
+            XI_RAW_TOUCH_END => XIEvent::RawTouchEnd(XIRawEvent::read(buf)?),
+            _ => return None,
+        };
+
+        Some(event)
+    }
+}
+
+/// State of the modifiers or of the group of the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ModifierInfo {
+    pub base: u32,
+    pub latched: u32,
+    pub locked: u32,
+    pub effective: u32,
+}
+
+/// A key, button, motion or touch event of a device.
+#[derive(Debug, Clone)]
+pub struct XIDeviceEvent {
+    pub deviceid: DeviceId,
+    pub timestamp: Timestamp,
+
+    /// The keycode, the button or the touch id.
+    pub detail: u32,
+
+    pub root: Window,
+    pub event: Window,
+    pub child: Window,
+
+    /// Position of the pointer relative to `root`, with subpixel precision.
+    pub root_x: f64,
+    pub root_y: f64,
+
+    /// Position of the pointer relative to `event`.
+    pub event_x: f64,
+    pub event_y: f64,
+
+    /// The slave device which generated the event.
+    pub sourceid: DeviceId,
+
+    pub flags: EventFlags,
+    pub mods: ModifierInfo,
+    pub group: ModifierInfo,
+
+    /// Buttons pressed, bit `n` is set if button `n` is down.
+    pub buttons: Vec<u32>,
+
+    /// Number and value of the valuators changed, in the coordinates of
+    /// the device.
+    pub valuators: Vec<(u16, f64)>,
+}
+
+impl XIDeviceEvent {
+    fn read(buf: &[u8]) -> Option<XIDeviceEvent> {
+        let buttons_len = NativeEndian::read_u16(buf.get(48..50)?) as usize * 4;
+        let valuators_len = NativeEndian::read_u16(&buf[50..52]) as usize * 4;
+        let buttons = buf.get(80..80 + buttons_len)?;
+        let mask = buf.get(80 + buttons_len..80 + buttons_len + valuators_len)?;
+
+        Some(XIDeviceEvent {
+            deviceid: NativeEndian::read_u16(&buf[10..12]),
+            timestamp: NativeEndian::read_u32(&buf[12..16]),
+            detail: NativeEndian::read_u32(&buf[16..20]),
+            root: Window(NativeEndian::read_u32(&buf[20..24])),
+            event: Window(NativeEndian::read_u32(&buf[24..28])),
+            child: Window(NativeEndian::read_u32(&buf[28..32])),
+            root_x: fp1616_to_f64(NativeEndian::read_i32(&buf[32..36])),
+            root_y: fp1616_to_f64(NativeEndian::read_i32(&buf[36..40])),
+            event_x: fp1616_to_f64(NativeEndian::read_i32(&buf[40..44])),
+            event_y: fp1616_to_f64(NativeEndian::read_i32(&buf[44..48])),
+            sourceid: NativeEndian::read_u16(&buf[52..54]),
+            flags: NativeEndian::read_u32(&buf[56..60]),
+            mods: ModifierInfo {
+                base: NativeEndian::read_u32(&buf[60..64]),
+                latched: NativeEndian::read_u32(&buf[64..68]),
+                locked: NativeEndian::read_u32(&buf[68..72]),
+                effective: NativeEndian::read_u32(&buf[72..76]),
+            },
+            group: ModifierInfo {
+                base: buf[76] as u32,
+                latched: buf[77] as u32,
+                locked: buf[78] as u32,
+                effective: buf[79] as u32,
+            },
+            buttons: buttons.chunks(4).map(NativeEndian::read_u32).collect(),
+            valuators: read_valuators(mask, &buf[80 + buttons_len + valuators_len..])?,
+        })
+    }
+
+    /// Returns the value of a valuator if it changed.
+    pub fn valuator(&self, number: u16) -> Option<f64> {
+        self.valuators.iter().find(|&&(n, _)| n == number).map(|&(_, value)| value)
+    }
+
+    pub fn is_button_down(&self, button: u32) -> bool {
+        self.buttons
+            .get(button as usize / 32)
+            .is_some_and(|word| word & (1 << (button % 32)) != 0)
+    }
+}
+
+/// An event of a device before it's processed by the server, sent to the
+/// root window even if the device is grabbed.
+#[derive(Debug, Clone)]
+pub struct XIRawEvent {
+    pub deviceid: DeviceId,
+    pub timestamp: Timestamp,
+
+    /// The keycode, the button or the touch id.
+    pub detail: u32,
+
+    pub sourceid: DeviceId,
+    pub flags: EventFlags,
+
+    /// Number and value of the valuators changed, after the acceleration.
+    pub valuators: Vec<(u16, f64)>,
+
+    /// Values of the same valuators as reported by the device.
+    pub raw_valuators: Vec<(u16, f64)>,
+}
+
+impl XIRawEvent {
+    fn read(buf: &[u8]) -> Option<XIRawEvent> {
+        let valuators_len = NativeEndian::read_u16(&buf[22..24]) as usize * 4;
+        let mask = buf.get(32..32 + valuators_len)?;
+        let values = &buf[32 + valuators_len..];
+        let valuators = read_valuators(mask, values)?;
+        let raw_valuators = read_valuators(mask, values.get(valuators.len() * 8..)?)?;
+
+        Some(XIRawEvent {
+            deviceid: NativeEndian::read_u16(&buf[10..12]),
+            timestamp: NativeEndian::read_u32(&buf[12..16]),
+            detail: NativeEndian::read_u32(&buf[16..20]),
+            sourceid: NativeEndian::read_u16(&buf[20..22]),
+            flags: NativeEndian::read_u32(&buf[24..28]),
+            valuators,
+            raw_valuators,
+        })
+    }
+}
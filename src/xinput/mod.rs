@@ -0,0 +1,253 @@
+//! XInput2 extension: per-device input, raw events, touch events and
+//! smooth scrolling.
+//!
+//! XI2 events are sent as GenericEvents, they are decoded with
+//! `XIEvent::from_event`. `Client::perform_ex` negotiates the
+//! version 2.2 with `XIQueryVersion`, needed to receive touch events.
+
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    xi_get_client_pointer,
+    xi_select_events,
+    xi_query_version,
+    xi_query_device,
+    xi_grab_device,
+    xi_ungrab_device,
+    xi_passive_grab_device,
+    xi_passive_ungrab_device
+}
+
+mod events;
+pub use self::events::*;
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"XInputExtension";
+
+/// Identifies an input device.
+pub type DeviceId = u16;
+
+/// Selects all the devices, including the slave devices.
+pub const ALL_DEVICES: DeviceId = 0;
+
+/// Selects the master pointers and keyboards.
+pub const ALL_MASTER_DEVICES: DeviceId = 1;
+
+/// Types of the XI2 events, also the bits of an `EventMask`.
+pub type EventType = u16;
+pub const XI_DEVICE_CHANGED: EventType = 1;
+pub const XI_KEY_PRESS: EventType = 2;
+pub const XI_KEY_RELEASE: EventType = 3;
+pub const XI_BUTTON_PRESS: EventType = 4;
+pub const XI_BUTTON_RELEASE: EventType = 5;
+pub const XI_MOTION: EventType = 6;
+pub const XI_ENTER: EventType = 7;
+pub const XI_LEAVE: EventType = 8;
+pub const XI_FOCUS_IN: EventType = 9;
+pub const XI_FOCUS_OUT: EventType = 10;
+pub const XI_HIERARCHY: EventType = 11;
+pub const XI_PROPERTY: EventType = 12;
+pub const XI_RAW_KEY_PRESS: EventType = 13;
+pub const XI_RAW_KEY_RELEASE: EventType = 14;
+pub const XI_RAW_BUTTON_PRESS: EventType = 15;
+pub const XI_RAW_BUTTON_RELEASE: EventType = 16;
+pub const XI_RAW_MOTION: EventType = 17;
+pub const XI_TOUCH_BEGIN: EventType = 18;
+pub const XI_TOUCH_UPDATE: EventType = 19;
+pub const XI_TOUCH_END: EventType = 20;
+pub const XI_TOUCH_OWNERSHIP: EventType = 21;
+pub const XI_RAW_TOUCH_BEGIN: EventType = 22;
+pub const XI_RAW_TOUCH_UPDATE: EventType = 23;
+pub const XI_RAW_TOUCH_END: EventType = 24;
+pub const XI_BARRIER_HIT: EventType = 25;
+pub const XI_BARRIER_LEAVE: EventType = 26;
+
+/// Flags of the device and raw events.
+pub type EventFlags = u32;
+
+/// A key press generated by the key repeat.
+pub const KEY_REPEAT: EventFlags = 1 << 16;
+
+/// A pointer event emulated from a touch or a smooth scroll event.
+pub const POINTER_EMULATED: EventFlags = 1 << 16;
+
+/// The touch has ended, but this client doesn't own it yet.
+pub const TOUCH_PENDING_END: EventFlags = 1 << 16;
+
+/// The touch also emulates pointer events.
+pub const TOUCH_EMULATING_POINTER: EventFlags = 1 << 17;
+
+/// The modifiers of a passive grab, in addition to the core ones.
+pub type GrabModifier = u32;
+pub const ANY_MODIFIER: GrabModifier = 1 << 31;
+
+/// A set of XI2 event types, as used by `XISelectEvents` and the grabs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventMask(pub Vec<u32>);
+
+impl EventMask {
+    /// Creates the mask selecting `events`.
+    pub fn new(events: &[EventType]) -> EventMask {
+        let mut mask = EventMask::default();
+        for &event in events {
+            mask.set(event);
+        }
+
+        mask
+    }
+
+    /// Adds `event` to the mask.
+    pub fn set(&mut self, event: EventType) -> &mut Self {
+        let word = event as usize / 32;
+        if self.0.len() <= word {
+            self.0.resize(word + 1, 0);
+        }
+        self.0[word] |= 1 << (event % 32);
+
+        self
+    }
+
+    pub fn contains(&self, event: EventType) -> bool {
+        self.0.get(event as usize / 32).is_some_and(|word| word & (1 << (event % 32)) != 0)
+    }
+
+    /// Length of the mask in 4 bytes units.
+    fn len(&self) -> u16 {
+        self.0.len() as u16
+    }
+
+    fn write<W: io::Write>(&self, a: &mut W) -> io::Result<()> {
+        for word in &self.0 {
+            a.write_u32::<NativeEndian>(*word)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts a FP16.16 fixed point number, used for the positions of the
+/// pointer.
+pub fn fp1616_to_f64(value: i32) -> f64 {
+    value as f64 / 65536.0
+}
+
+/// A FP32.32 fixed point number, used for the values of the valuators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Fp3232 {
+    pub integral: i32,
+    pub frac: u32,
+}
+
+impl Fp3232 {
+    fn read(buf: &[u8]) -> Fp3232 {
+        Fp3232 {
+            integral: NativeEndian::read_i32(&buf[0..4]),
+            frac: NativeEndian::read_u32(&buf[4..8]),
+        }
+    }
+}
+
+impl From<Fp3232> for f64 {
+    fn from(value: Fp3232) -> f64 {
+        value.integral as f64 + value.frac as f64 / 4294967296.0
+    }
+}
+
+/// Reads the values of the valuators set in `mask`, stored as FP32.32
+/// numbers from `buf`.
+fn read_valuators(mask: &[u8], buf: &[u8]) -> Option<Vec<(u16, f64)>> {
+    let mut values = Vec::new();
+    for (i, word) in mask.chunks(4).map(NativeEndian::read_u32).enumerate() {
+        for bit in 0..32 {
+            if word & (1 << bit) != 0 {
+                let offset = values.len() * 8;
+                let value = Fp3232::read(buf.get(offset..offset + 8)?);
+                values.push(((i * 32 + bit) as u16, f64::from(value)));
+            }
+        }
+    }
+
+    Some(values)
+}
+
+/// How a device is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceUse {
+    MasterPointer,
+    MasterKeyboard,
+    SlavePointer,
+    SlaveKeyboard,
+    FloatingSlave,
+}
+
+impl From<u16> for DeviceUse {
+    fn from(device_use: u16) -> DeviceUse {
+        match device_use {
+            1 => DeviceUse::MasterPointer,
+            2 => DeviceUse::MasterKeyboard,
+            3 => DeviceUse::SlavePointer,
+            4 => DeviceUse::SlaveKeyboard,
+            _ => DeviceUse::FloatingSlave,
+        }
+    }
+}
+
+/// How the events of a device are processed during a grab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrabMode {
+    /// The events are frozen until they are allowed.
+    Sync = 0,
+    Async = 1,
+
+    /// Used for touch grabs.
+    Touch = 2,
+}
+
+/// Result of a grab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrabStatus {
+    Success,
+    AlreadyGrabbed,
+    InvalidTime,
+    NotViewable,
+    Frozen,
+}
+
+impl From<u8> for GrabStatus {
+    fn from(status: u8) -> GrabStatus {
+        match status {
+            0 => GrabStatus::Success,
+            1 => GrabStatus::AlreadyGrabbed,
+            2 => GrabStatus::InvalidTime,
+            3 => GrabStatus::NotViewable,
+            _ => GrabStatus::Frozen,
+        }
+    }
+}
+
+/// What activates a passive grab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrabType {
+    /// A button press, `detail` is the button.
+    Button = 0,
+
+    /// A key press, `detail` is the keycode.
+    Keycode = 1,
+
+    Enter = 2,
+    FocusIn = 3,
+    TouchBegin = 4,
+}
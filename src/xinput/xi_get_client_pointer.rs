@@ -0,0 +1,64 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xinput::DeviceId;
+use ::xinput::EXTENSION_NAME;
+use ::xproto::Window;
+use ::Client;
+
+const XINPUT_OPCODE: u8 = 45;
+
+/// Returns the master pointer used by the client owning `window` for the
+/// core requests, the pointer of this client if `window` is
+/// `Window::NONE`.
+#[derive(Debug, Clone, Copy)]
+pub struct XIGetClientPointer {
+    pub window: Window,
+}
+
+impl ExtensionRequest for XIGetClientPointer {
+    type Reply = XIGetClientPointerReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XINPUT_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = XIGetClientPointerReply {
+                set: buf[8] != 0,
+                deviceid: NativeEndian::read_u16(&buf[10..12]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `XIGetClientPointer` request.
+#[derive(Debug, Clone, Copy)]
+pub struct XIGetClientPointerReply {
+    /// Whether the pointer was set explicitly, else the server picked one.
+    pub set: bool,
+
+    pub deviceid: DeviceId,
+}
@@ -0,0 +1,87 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xinput::DeviceId;
+use ::xinput::EventMask;
+use ::xinput::GrabMode;
+use ::xinput::GrabStatus;
+use ::xinput::EXTENSION_NAME;
+use ::xproto::Cursor;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+use ::Client;
+
+const XINPUT_OPCODE: u8 = 51;
+
+/// Actively grabs a device, its events selected in `mask` are only
+/// reported to this client until `XIUngrabDevice`.
+#[derive(Debug, Clone)]
+pub struct XIGrabDevice {
+    pub window: Window,
+    pub time: Timestamp,
+
+    /// Cursor displayed during the grab, `Cursor::NONE` keeps the current
+    /// one.
+    pub cursor: Cursor,
+
+    pub deviceid: DeviceId,
+    pub mode: GrabMode,
+
+    /// Mode of the paired master device, the keyboard of a pointer.
+    pub paired_device_mode: GrabMode,
+
+    /// Whether the events are reported normally to the windows of this
+    /// client, else they are all reported to `window`.
+    pub owner_events: bool,
+
+    pub mask: EventMask,
+}
+
+impl ExtensionRequest for XIGrabDevice {
+    type Reply = XIGrabDeviceReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XINPUT_OPCODE)?;
+        a.write_u16::<NativeEndian>(6 + self.mask.len())?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u32::<NativeEndian>(self.time)?;
+        a.write_u32::<NativeEndian>(self.cursor.0)?;
+        a.write_u16::<NativeEndian>(self.deviceid)?;
+        a.write_u8(self.mode as u8)?;
+        a.write_u8(self.paired_device_mode as u8)?;
+        a.write_u8(self.owner_events as u8)?;
+        a.write_u8(0)?;
+        a.write_u16::<NativeEndian>(self.mask.len())?;
+        self.mask.write(&mut a)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = XIGrabDeviceReply { status: GrabStatus::from(buf[8]) };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `XIGrabDevice` request.
+#[derive(Debug, Clone, Copy)]
+pub struct XIGrabDeviceReply {
+    pub status: GrabStatus,
+}
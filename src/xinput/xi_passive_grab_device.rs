@@ -0,0 +1,101 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xinput::DeviceId;
+use ::xinput::EventMask;
+use ::xinput::GrabModifier;
+use ::xinput::GrabMode;
+use ::xinput::GrabStatus;
+use ::xinput::GrabType;
+use ::xinput::EXTENSION_NAME;
+use ::xproto::Cursor;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+use ::Client;
+
+const XINPUT_OPCODE: u8 = 54;
+
+/// Establishes a passive grab, the device is grabbed when the event of
+/// `grab_type` happens in `grab_window` with one of the `modifiers`.
+#[derive(Debug, Clone)]
+pub struct XIPassiveGrabDevice {
+    pub time: Timestamp,
+    pub grab_window: Window,
+    pub cursor: Cursor,
+
+    /// The button or keycode, 0 for the other grab types.
+    pub detail: u32,
+
+    pub deviceid: DeviceId,
+    pub grab_type: GrabType,
+    pub grab_mode: GrabMode,
+    pub paired_device_mode: GrabMode,
+    pub owner_events: bool,
+    pub mask: EventMask,
+
+    /// Modifiers states the grab is established for, `ANY_MODIFIER`
+    /// matches all of them.
+    pub modifiers: Vec<GrabModifier>,
+}
+
+impl ExtensionRequest for XIPassiveGrabDevice {
+    type Reply = XIPassiveGrabDeviceReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XINPUT_OPCODE)?;
+        a.write_u16::<NativeEndian>(8 + self.mask.len() + self.modifiers.len() as u16)?;
+        a.write_u32::<NativeEndian>(self.time)?;
+        a.write_u32::<NativeEndian>(self.grab_window.0)?;
+        a.write_u32::<NativeEndian>(self.cursor.0)?;
+        a.write_u32::<NativeEndian>(self.detail)?;
+        a.write_u16::<NativeEndian>(self.deviceid)?;
+        a.write_u16::<NativeEndian>(self.modifiers.len() as u16)?;
+        a.write_u16::<NativeEndian>(self.mask.len())?;
+        a.write_u8(self.grab_type as u8)?;
+        a.write_u8(self.grab_mode as u8)?;
+        a.write_u8(self.paired_device_mode as u8)?;
+        a.write_u8(self.owner_events as u8)?;
+        a.write_u16::<NativeEndian>(0)?;
+        self.mask.write(&mut a)?;
+        for modifier in &self.modifiers {
+            a.write_u32::<NativeEndian>(*modifier)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let count = NativeEndian::read_u16(&buf[8..10]) as usize;
+            let failed = buf[32..]
+                .chunks(8)
+                .take(count)
+                .map(|a| (NativeEndian::read_u32(&a[0..4]), GrabStatus::from(a[4])))
+                .collect();
+
+            (client, XIPassiveGrabDeviceReply { failed })
+        }))
+    }
+}
+
+/// Reply of `XIPassiveGrabDevice` request.
+#[derive(Debug, Clone)]
+pub struct XIPassiveGrabDeviceReply {
+    /// The modifiers the grab couldn't be established for, and why.
+    pub failed: Vec<(GrabModifier, GrabStatus)>,
+}
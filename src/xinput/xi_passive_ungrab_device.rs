@@ -0,0 +1,60 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xinput::DeviceId;
+use ::xinput::GrabModifier;
+use ::xinput::GrabType;
+use ::xinput::EXTENSION_NAME;
+use ::xproto::Window;
+use ::Client;
+
+const XINPUT_OPCODE: u8 = 55;
+
+/// Releases passive grabs established with `XIPassiveGrabDevice`.
+#[derive(Debug, Clone)]
+pub struct XIPassiveUngrabDevice {
+    pub grab_window: Window,
+    pub detail: u32,
+    pub deviceid: DeviceId,
+    pub grab_type: GrabType,
+    pub modifiers: Vec<GrabModifier>,
+}
+
+impl ExtensionRequest for XIPassiveUngrabDevice {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XINPUT_OPCODE)?;
+        a.write_u16::<NativeEndian>(5 + self.modifiers.len() as u16)?;
+        a.write_u32::<NativeEndian>(self.grab_window.0)?;
+        a.write_u32::<NativeEndian>(self.detail)?;
+        a.write_u16::<NativeEndian>(self.deviceid)?;
+        a.write_u16::<NativeEndian>(self.modifiers.len() as u16)?;
+        a.write_u8(self.grab_type as u8)?;
+        a.write_all(&[0; 3])?;
+        for modifier in &self.modifiers {
+            a.write_u32::<NativeEndian>(*modifier)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,321 @@
+use ::std::io;
+use ::std::io::Read;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xinput::DeviceId;
+use ::xinput::DeviceUse;
+use ::xinput::Fp3232;
+use ::xinput::EXTENSION_NAME;
+use ::xproto::Atom;
+use ::Client;
+use ::pad;
+
+const XINPUT_OPCODE: u8 = 48;
+
+/// Returns the description of a device, or of all the devices with
+/// `ALL_DEVICES` and `ALL_MASTER_DEVICES`.
+#[derive(Debug, Clone, Copy)]
+pub struct XIQueryDevice {
+    pub deviceid: DeviceId,
+}
+
+impl ExtensionRequest for XIQueryDevice {
+    type Reply = XIQueryDeviceReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XINPUT_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u16::<NativeEndian>(self.deviceid)?;
+        a.write_u16::<NativeEndian>(0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let count = NativeEndian::read_u16(&buf[8..10]);
+            let mut a = io::Cursor::new(&buf[32..]);
+            let mut infos = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                infos.push(XIDeviceInfo::read(&mut a)?);
+            }
+
+            Ok((client, XIQueryDeviceReply { infos }))
+        }))
+    }
+}
+
+/// Reply of `XIQueryDevice` request.
+#[derive(Debug, Clone)]
+pub struct XIQueryDeviceReply {
+    pub infos: Vec<XIDeviceInfo>,
+}
+
+/// Description of an input device.
+#[derive(Debug, Clone)]
+pub struct XIDeviceInfo {
+    pub deviceid: DeviceId,
+    pub device_use: DeviceUse,
+
+    /// The master device of a slave, the paired device of a master.
+    pub attachment: DeviceId,
+
+    pub enabled: bool,
+    pub name: String,
+    pub classes: Vec<DeviceClass>,
+}
+
+impl XIDeviceInfo {
+    fn read<A: Read>(a: &mut A) -> io::Result<XIDeviceInfo> {
+        let deviceid = a.read_u16::<NativeEndian>()?;
+        let device_use = a.read_u16::<NativeEndian>()?;
+        let attachment = a.read_u16::<NativeEndian>()?;
+        let num_classes = a.read_u16::<NativeEndian>()?;
+        let name_len = a.read_u16::<NativeEndian>()? as usize;
+        let enabled = a.read_u8()? != 0;
+        a.read_u8()?;
+
+        let mut name = vec![0u8; name_len + pad(name_len)];
+        a.read_exact(&mut name)?;
+        name.truncate(name_len);
+
+        let mut classes = Vec::with_capacity(num_classes as usize);
+        for _ in 0..num_classes {
+            classes.push(DeviceClass::read(a)?);
+        }
+
+        Ok(XIDeviceInfo {
+            deviceid,
+            device_use: DeviceUse::from(device_use),
+            attachment,
+            enabled,
+            name: String::from_utf8_lossy(&name).into_owned(),
+            classes,
+        })
+    }
+
+    /// Returns the valuator with the given number.
+    pub fn valuator(&self, number: u16) -> Option<&ValuatorClass> {
+        self.classes.iter().filter_map(|class| match *class {
+            DeviceClass::Valuator(ref valuator) if valuator.number == number => Some(valuator),
+            _ => None,
+        }).next()
+    }
+
+    /// Returns the valuators used for smooth scrolling.
+    pub fn scroll_classes(&self) -> Vec<ScrollClass> {
+        self.classes.iter().filter_map(|class| match *class {
+            DeviceClass::Scroll(scroll) => Some(scroll),
+            _ => None,
+        }).collect()
+    }
+}
+
+/// A capability of a device, `sourceid` of the class is the slave device
+/// it comes from.
+#[derive(Debug, Clone)]
+pub enum DeviceClass {
+    /// The keycodes of a keyboard.
+    Key { sourceid: DeviceId, keys: Vec<u32> },
+
+    /// The buttons of a pointer, their labels and the ones pressed.
+    Button {
+        sourceid: DeviceId,
+        state: Vec<u32>,
+        labels: Vec<Atom>,
+    },
+
+    Valuator(ValuatorClass),
+    Scroll(ScrollClass),
+
+    /// A touch device, `num_touches` is the maximum number of touches, 0
+    /// if unknown.
+    Touch {
+        sourceid: DeviceId,
+        mode: TouchMode,
+        num_touches: u8,
+    },
+
+    /// A class this crate doesn't decode.
+    Unknown { class_type: u16, sourceid: DeviceId },
+}
+
+impl DeviceClass {
+    fn read<A: Read>(a: &mut A) -> io::Result<DeviceClass> {
+        let class_type = a.read_u16::<NativeEndian>()?;
+        let len = a.read_u16::<NativeEndian>()? as usize * 4;
+        let sourceid = a.read_u16::<NativeEndian>()?;
+
+        let mut data = vec![0u8; len.saturating_sub(6)];
+        a.read_exact(&mut data)?;
+        let mut a = io::Cursor::new(data);
+
+        let class = match class_type {
+            CLASS_KEY => {
+                let num_keys = a.read_u16::<NativeEndian>()?;
+                let mut keys = Vec::with_capacity(num_keys as usize);
+                for _ in 0..num_keys {
+                    keys.push(a.read_u32::<NativeEndian>()?);
+                }
+
+                DeviceClass::Key { sourceid, keys }
+            }
+            CLASS_BUTTON => {
+                let num_buttons = a.read_u16::<NativeEndian>()? as usize;
+                let mut state = Vec::new();
+                for _ in 0..num_buttons.div_ceil(32) {
+                    state.push(a.read_u32::<NativeEndian>()?);
+                }
+                let mut labels = Vec::with_capacity(num_buttons);
+                for _ in 0..num_buttons {
+                    labels.push(Atom(a.read_u32::<NativeEndian>()?));
+                }
+
+                DeviceClass::Button { sourceid, state, labels }
+            }
+            CLASS_VALUATOR => {
+                let number = a.read_u16::<NativeEndian>()?;
+                let label = Atom(a.read_u32::<NativeEndian>()?);
+                let min = read_fp3232(&mut a)?;
+                let max = read_fp3232(&mut a)?;
+                let value = read_fp3232(&mut a)?;
+                let resolution = a.read_u32::<NativeEndian>()?;
+                let mode = a.read_u8()?;
+
+                DeviceClass::Valuator(ValuatorClass {
+                    sourceid,
+                    number,
+                    label,
+                    min,
+                    max,
+                    value,
+                    resolution,
+                    absolute: mode == 1,
+                })
+            }
+            CLASS_SCROLL => {
+                let number = a.read_u16::<NativeEndian>()?;
+                let scroll_type = a.read_u16::<NativeEndian>()?;
+                a.read_u16::<NativeEndian>()?;
+                let flags = a.read_u32::<NativeEndian>()?;
+                let increment = read_fp3232(&mut a)?;
+
+                DeviceClass::Scroll(ScrollClass {
+                    sourceid,
+                    number,
+                    vertical: scroll_type == 1,
+                    flags,
+                    increment,
+                })
+            }
+            CLASS_TOUCH => {
+                let mode = a.read_u8()?;
+                let num_touches = a.read_u8()?;
+
+                DeviceClass::Touch {
+                    sourceid,
+                    mode: if mode == 1 {
+                        TouchMode::Direct
+                    } else {
+                        TouchMode::Dependent
+                    },
+                    num_touches,
+                }
+            }
+            _ => DeviceClass::Unknown { class_type, sourceid },
+        };
+
+        Ok(class)
+    }
+}
+
+/// Types of the device classes.
+const CLASS_KEY: u16 = 0;
+const CLASS_BUTTON: u16 = 1;
+const CLASS_VALUATOR: u16 = 2;
+const CLASS_SCROLL: u16 = 3;
+const CLASS_TOUCH: u16 = 8;
+
+fn read_fp3232<A: Read>(a: &mut A) -> io::Result<f64> {
+    let integral = a.read_i32::<NativeEndian>()?;
+    let frac = a.read_u32::<NativeEndian>()?;
+    Ok(f64::from(Fp3232 { integral, frac }))
+}
+
+/// An axis of a device, like the position, the pressure or a scroll
+/// wheel.
+#[derive(Debug, Clone, Copy)]
+pub struct ValuatorClass {
+    pub sourceid: DeviceId,
+
+    /// Number of the valuator in the events.
+    pub number: u16,
+
+    /// What the valuator measures, like `Abs Pressure`.
+    pub label: Atom,
+
+    pub min: f64,
+    pub max: f64,
+    pub value: f64,
+
+    /// Units per meter.
+    pub resolution: u32,
+
+    /// Whether the values are absolute, else they are relative to the
+    /// previous ones.
+    pub absolute: bool,
+}
+
+/// A valuator used for smooth scrolling.
+///
+/// The valuator values are accumulated, the scroll distance of an event is
+/// the change of the value divided by `increment`, one unit being one
+/// click of a wheel.
+#[derive(Debug, Clone, Copy)]
+pub struct ScrollClass {
+    pub sourceid: DeviceId,
+
+    /// Number of the valuator.
+    pub number: u16,
+
+    /// Whether the valuator scrolls vertically, else horizontally.
+    pub vertical: bool,
+
+    /// `SCROLL_FLAG_*` flags.
+    pub flags: u32,
+
+    /// Distance of one scroll unit, negative if the direction is inverted.
+    pub increment: f64,
+}
+
+/// The emulated button events are not sent for this scroll valuator.
+pub const SCROLL_FLAG_NO_EMULATION: u32 = 1;
+
+/// The scroll valuator is the preferred one for its direction.
+pub const SCROLL_FLAG_PREFERRED: u32 = 2;
+
+/// How the touches of a device are mapped to the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchMode {
+    /// Touch screens, the touches are at their position on the screen.
+    Direct,
+
+    /// Touchpads, the touches follow the pointer.
+    Dependent,
+}
@@ -0,0 +1,76 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::xinput::EXTENSION_NAME;
+use ::Client;
+
+const XINPUT_OPCODE: u8 = 47;
+
+/// Negotiates the protocol version, it must be sent before any other XI2
+/// request. Touch events need the version 2.2.
+#[derive(Debug, Clone, Copy)]
+pub struct XIQueryVersion {
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl ExtensionRequest for XIQueryVersion {
+    type Reply = XIQueryVersionReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XINPUT_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u16::<NativeEndian>(self.major_version)?;
+        a.write_u16::<NativeEndian>(self.minor_version)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = XIQueryVersionReply {
+                major_version: NativeEndian::read_u16(&buf[8..10]),
+                minor_version: NativeEndian::read_u16(&buf[10..12]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `XIQueryVersion` request.
+#[derive(Debug, Clone, Copy)]
+pub struct XIQueryVersionReply {
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl VersionRequest for XIQueryVersion {
+    fn latest() -> XIQueryVersion {
+        XIQueryVersion {
+            major_version: 2,
+            minor_version: 2,
+        }
+    }
+
+    fn version(reply: &XIQueryVersionReply) -> Version {
+        Version::new(u32::from(reply.major_version), u32::from(reply.minor_version))
+    }
+}
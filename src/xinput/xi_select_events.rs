@@ -0,0 +1,60 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xinput::DeviceId;
+use ::xinput::EventMask;
+use ::xinput::EXTENSION_NAME;
+use ::xproto::Window;
+use ::Client;
+
+const XINPUT_OPCODE: u8 = 46;
+
+/// Selects the XI2 events reported to `window`, per device. A mask
+/// replaces the previous one of the same device, an empty mask clears it.
+#[derive(Debug, Clone)]
+pub struct XISelectEvents {
+    pub window: Window,
+
+    /// The events selected for each device, `ALL_DEVICES` and
+    /// `ALL_MASTER_DEVICES` can be used.
+    pub masks: Vec<(DeviceId, EventMask)>,
+}
+
+impl ExtensionRequest for XISelectEvents {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let masks_len: u16 = self.masks.iter().map(|(_, mask)| 1 + mask.len()).sum();
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XINPUT_OPCODE)?;
+        a.write_u16::<NativeEndian>(3 + masks_len)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u16::<NativeEndian>(self.masks.len() as u16)?;
+        a.write_u16::<NativeEndian>(0)?;
+        for &(deviceid, ref mask) in &self.masks {
+            a.write_u16::<NativeEndian>(deviceid)?;
+            a.write_u16::<NativeEndian>(mask.len())?;
+            mask.write(&mut a)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,48 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xinput::DeviceId;
+use ::xinput::EXTENSION_NAME;
+use ::xproto::Timestamp;
+use ::Client;
+
+const XINPUT_OPCODE: u8 = 52;
+
+/// Releases an active grab of a device.
+#[derive(Debug, Clone, Copy)]
+pub struct XIUngrabDevice {
+    pub time: Timestamp,
+    pub deviceid: DeviceId,
+}
+
+impl ExtensionRequest for XIUngrabDevice {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XINPUT_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.time)?;
+        a.write_u16::<NativeEndian>(self.deviceid)?;
+        a.write_u16::<NativeEndian>(0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,254 @@
+use ::std::collections::HashMap;
+use ::std::collections::HashSet;
+use ::std::env;
+use ::std::fs;
+use ::std::io;
+use ::std::path::Path;
+
+use ::xkb::Keysym;
+use ::xkb::is_modifier_keysym;
+use ::xkb::keysym_from_name;
+
+/// Maximum depth of the `include` statements of Compose files.
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Directory of the system Compose files, `%S` in the includes.
+const SYSTEM_DIR: &str = "/usr/share/X11/locale";
+
+/// What a compose sequence produces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComposeOutput {
+    pub string: String,
+
+    /// The keysym of the result, if one is given.
+    pub keysym: Option<Keysym>,
+}
+
+/// The sequences of keysyms of a Compose file, like
+/// `<dead_acute> <e> : "é" eacute`.
+///
+/// Sequences using keysyms unknown to `keysym_from_name` and the ones
+/// with modifiers are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct ComposeTable {
+    sequences: HashMap<Vec<Keysym>, ComposeOutput>,
+    prefixes: HashSet<Vec<Keysym>>,
+}
+
+impl ComposeTable {
+    /// Parses a Compose file, the `include` statements are ignored.
+    pub fn parse(text: &str) -> ComposeTable {
+        let mut table = ComposeTable::default();
+        table.add(text, false, 0);
+        table
+    }
+
+    /// Reads a Compose file with its includes, `%H` being the home directory
+    /// and `%S` the directory of the system files. The includes of the
+    /// locale file (`%L`) are skipped.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<ComposeTable> {
+        let mut table = ComposeTable::default();
+        let text = fs::read_to_string(path)?;
+        table.add(&text, true, 0);
+        Ok(table)
+    }
+
+    /// Number of sequences.
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+
+    /// Returns the output of a complete sequence.
+    pub fn get(&self, sequence: &[Keysym]) -> Option<&ComposeOutput> {
+        self.sequences.get(sequence)
+    }
+
+    /// Adds a sequence, replacing the ones it is a prefix of.
+    pub fn insert(&mut self, sequence: Vec<Keysym>, output: ComposeOutput) {
+        if sequence.is_empty() {
+            return;
+        }
+        if self.prefixes.contains(&sequence) {
+            self.sequences.retain(|other, _| !other.starts_with(&sequence));
+        }
+        for i in 1..sequence.len() {
+            self.sequences.remove(&sequence[..i]);
+            self.prefixes.insert(sequence[..i].to_vec());
+        }
+        self.sequences.insert(sequence, output);
+    }
+
+    /// Adds the sequences of a file, reading its includes if `includes` is
+    /// set. A missing include is skipped.
+    fn add(&mut self, text: &str, includes: bool, depth: usize) {
+        for line in text.lines() {
+            let line = line.trim();
+            if let Some(include) = line.strip_prefix("include") {
+                if !includes || depth >= MAX_INCLUDE_DEPTH {
+                    continue;
+                }
+                if let Some(text) = parse_include(include)
+                    .and_then(|path| fs::read_to_string(path).ok()) {
+                    self.add(&text, true, depth + 1);
+                }
+            } else if let Some((sequence, output)) = parse_line(line) {
+                self.insert(sequence, output);
+            }
+        }
+    }
+}
+
+/// Returns the path of an include statement, `None` if it can't be
+/// resolved.
+fn parse_include(include: &str) -> Option<String> {
+    let (path, _) = parse_string(include.trim())?;
+    if path.contains("%L") {
+        return None;
+    }
+
+    let home = env::var("HOME").unwrap_or_default();
+    Some(path.replace("%H", &home).replace("%S", SYSTEM_DIR).replace("%%", "%"))
+}
+
+/// Parses a line like `<Multi_key> <o> <c> : "©" copyright # comment`.
+fn parse_line(line: &str) -> Option<(Vec<Keysym>, ComposeOutput)> {
+    let colon = line.find(':')?;
+    let (keys, rest) = (line[..colon].trim(), line[colon + 1..].trim());
+    if !keys.starts_with('<') {
+        return None;
+    }
+
+    let mut sequence = Vec::new();
+    for key in keys.split_whitespace() {
+        let name = key.strip_prefix('<')?.strip_suffix('>')?;
+        sequence.push(keysym_from_name(name)?);
+    }
+
+    let (string, rest) = parse_string(rest)?;
+    let keysym = rest.split_whitespace()
+        .next()
+        .filter(|name| !name.starts_with('#'))
+        .and_then(keysym_from_name);
+
+    Some((sequence, ComposeOutput { string, keysym }))
+}
+
+/// Parses a quoted string with its escapes, returns it with the text
+/// following it. The octal and hexadecimal escapes are UTF-8 bytes.
+fn parse_string(text: &str) -> Option<(String, &str)> {
+    let text = text.strip_prefix('"')?;
+    let mut string = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let c = match c {
+            '"' => {
+                let string = String::from_utf8_lossy(&string).into_owned();
+                return Some((string, &text[i + 1..]));
+            }
+            '\\' => {
+                let (_, escaped) = chars.next()?;
+                match escaped {
+                    'x' | 'X' | '0'..='7' => {
+                        // Up to 3 octal or 2 hexadecimal digits.
+                        let (radix, max) = if escaped.is_digit(8) { (8, 3) } else { (16, 2) };
+                        let mut digits = String::new();
+                        if radix == 8 {
+                            digits.push(escaped);
+                        }
+                        while digits.len() < max {
+                            match chars.peek() {
+                                Some(&(_, d)) if d.is_digit(radix) => {
+                                    digits.push(d);
+                                    chars.next();
+                                }
+                                _ => break,
+                            }
+                        }
+                        string.push(u8::from_str_radix(&digits, radix).ok()?);
+                        continue;
+                    }
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    other => other,
+                }
+            }
+            c => c,
+        };
+        let mut buf = [0; 4];
+        string.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    }
+
+    None
+}
+
+/// The result of a keysym fed to a `ComposeState`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComposeStatus {
+    /// The keysym isn't part of a sequence and should be handled normally.
+    Nothing,
+
+    /// A sequence is being typed, the keysym should not be handled.
+    Composing,
+
+    /// A sequence was completed.
+    Composed(ComposeOutput),
+
+    /// The keysyms typed don't form a sequence, they are discarded.
+    Cancelled,
+}
+
+/// Tracks the compose sequence being typed, for example after a dead key.
+#[derive(Debug, Clone, Default)]
+pub struct ComposeState {
+    typed: Vec<Keysym>,
+}
+
+impl ComposeState {
+    pub fn new() -> ComposeState {
+        ComposeState::default()
+    }
+
+    /// Whether a sequence is being typed.
+    pub fn is_composing(&self) -> bool {
+        !self.typed.is_empty()
+    }
+
+    /// Cancels the sequence being typed.
+    pub fn reset(&mut self) {
+        self.typed.clear();
+    }
+
+    /// Feeds the keysym of a key press, the modifier keys are ignored.
+    pub fn feed(&mut self, table: &ComposeTable, keysym: Keysym) -> ComposeStatus {
+        if is_modifier_keysym(keysym) {
+            return if self.is_composing() {
+                ComposeStatus::Composing
+            } else {
+                ComposeStatus::Nothing
+            };
+        }
+
+        self.typed.push(keysym);
+        if let Some(output) = table.sequences.get(&self.typed) {
+            self.typed.clear();
+            return ComposeStatus::Composed(output.clone());
+        }
+        if table.prefixes.contains(&self.typed) {
+            return ComposeStatus::Composing;
+        }
+
+        let started = self.typed.len() > 1;
+        self.typed.clear();
+        if started {
+            ComposeStatus::Cancelled
+        } else {
+            ComposeStatus::Nothing
+        }
+    }
+}
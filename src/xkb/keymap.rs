@@ -0,0 +1,158 @@
+use ::std::collections::HashMap;
+
+use ::futures::Future;
+
+use ::protocol::ReplyFuture;
+use ::xkb::DeviceSpec;
+use ::xkb::KeySymMap;
+use ::xkb::KeyType;
+use ::xkb::KeyboardState;
+use ::xkb::Keysym;
+use ::xkb::XkbGetMap;
+use ::xkb::XkbGetMapReply;
+use ::xkb::MAP_PART_KEY_SYMS;
+use ::xkb::MAP_PART_KEY_TYPES;
+use ::xkb::MAP_PART_MODIFIER_MAP;
+use ::xkb::NO_SYMBOL;
+use ::Client;
+
+/// How a group out of the range of a key is brought into range, in the
+/// `group_info` of the key.
+const GROUP_INFO_CLAMP: u8 = 0x40;
+const GROUP_INFO_REDIRECT: u8 = 0x80;
+
+/// The result of the lookup of a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyLookup {
+    pub keysym: Keysym,
+
+    /// The group and shift level used.
+    pub group: u8,
+    pub level: u8,
+
+    /// The modifiers used to select the level, which should not be used
+    /// further, like Shift for an uppercase letter.
+    pub consumed_mods: u8,
+}
+
+/// The keysyms of a keyboard, resolving a keycode and a state to a keysym
+/// like the XKB rules do, with the group (the layout) and the shift level.
+#[derive(Debug, Clone, Default)]
+pub struct Keymap {
+    pub min_key_code: u8,
+    pub max_key_code: u8,
+    types: Vec<KeyType>,
+    keys: HashMap<u8, KeySymMap>,
+    modmap: HashMap<u8, u8>,
+}
+
+impl Keymap {
+    /// Fetches the key types, keysyms and modifier map of a keyboard.
+    pub fn fetch(client: Client, device_spec: DeviceSpec) -> ReplyFuture<Keymap> {
+        Box::new(client.perform_ex(XkbGetMap {
+                device_spec,
+                full: MAP_PART_KEY_TYPES | MAP_PART_KEY_SYMS | MAP_PART_MODIFIER_MAP,
+                ..XkbGetMap::default()
+            })
+            .map(|(client, reply)| (client, Keymap::new(&reply))))
+    }
+
+    /// Creates a keymap from a reply containing all the key types.
+    pub fn new(reply: &XkbGetMapReply) -> Keymap {
+        let mut keymap = Keymap {
+            min_key_code: reply.min_key_code,
+            max_key_code: reply.max_key_code,
+            ..Keymap::default()
+        };
+        keymap.update(reply);
+
+        keymap
+    }
+
+    /// Updates the parts of the keymap present in `reply`, for example
+    /// after a `MapNotify` event.
+    pub fn update(&mut self, reply: &XkbGetMapReply) {
+        self.min_key_code = reply.min_key_code;
+        self.max_key_code = reply.max_key_code;
+
+        if !reply.types.is_empty() {
+            let first = reply.first_type as usize;
+            let end = first + reply.types.len();
+            if self.types.len() < end {
+                self.types.resize(end, KeyType::default());
+            }
+            self.types[first..end].clone_from_slice(&reply.types);
+        }
+
+        for (i, key) in reply.syms.iter().enumerate() {
+            let keycode = reply.first_key_sym as usize + i;
+            self.keys.insert(keycode as u8, key.clone());
+        }
+
+        if !reply.modmap.is_empty() {
+            self.modmap = reply.modmap.iter().cloned().collect();
+        }
+    }
+
+    /// Number of groups of a key.
+    pub fn num_groups(&self, keycode: u8) -> u8 {
+        self.keys.get(&keycode).map_or(0, |key| key.group_info & 0x0f)
+    }
+
+    /// The real modifiers bound to a key.
+    pub fn modifiers(&self, keycode: u8) -> u8 {
+        self.modmap.get(&keycode).cloned().unwrap_or(0)
+    }
+
+    /// Returns the keysym of a key with the modifiers `mods` and the group
+    /// `group`, the lookup modifiers and the group of a `KeyboardState`.
+    pub fn lookup(&self, keycode: u8, mods: u8, group: u8) -> Option<KeyLookup> {
+        let key = self.keys.get(&keycode)?;
+        let num_groups = key.group_info & 0x0f;
+        if num_groups == 0 {
+            return None;
+        }
+
+        let group = if group < num_groups {
+            group
+        } else if key.group_info & GROUP_INFO_REDIRECT != 0 {
+            let target = (key.group_info >> 4) & 0x03;
+            if target < num_groups { target } else { 0 }
+        } else if key.group_info & GROUP_INFO_CLAMP != 0 {
+            num_groups - 1
+        } else {
+            group % num_groups
+        };
+
+        let mut level = 0;
+        let mut consumed_mods = 0;
+        if let Some(key_type) = self.types.get(key.kt_index[group as usize] as usize) {
+            let mods = mods & key_type.mods_mask;
+            consumed_mods = key_type.mods_mask;
+            if let Some(entry) = key_type.map
+                .iter()
+                .find(|entry| entry.active && entry.mods_mask == mods) {
+                level = entry.level;
+                consumed_mods &= !entry.preserve;
+            }
+        }
+
+        let index = group as usize * key.width as usize + level as usize;
+        match key.syms.get(index).cloned() {
+            Some(NO_SYMBOL) | None => None,
+            Some(keysym) => {
+                Some(KeyLookup {
+                    keysym,
+                    group,
+                    level,
+                    consumed_mods,
+                })
+            }
+        }
+    }
+
+    /// Returns the keysym of a key in the given state.
+    pub fn keysym(&self, keycode: u8, state: &KeyboardState) -> Option<Keysym> {
+        self.lookup(keycode, state.lookup_mods, state.group).map(|lookup| lookup.keysym)
+    }
+}
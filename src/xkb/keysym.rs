@@ -0,0 +1,193 @@
+/// A symbol printed on a key.
+pub type Keysym = u32;
+
+/// The key has no symbol.
+pub const NO_SYMBOL: Keysym = 0;
+
+/// Keysyms of the Unicode characters out of Latin-1 are this offset plus
+/// the code point.
+const UNICODE_OFFSET: Keysym = 0x0100_0000;
+
+/// Names of the keysyms from 0x20 to 0x7e, the ASCII characters.
+const ASCII_NAMES: [&str; 95] = [
+    "space", "exclam", "quotedbl", "numbersign", "dollar", "percent", "ampersand",
+    "apostrophe", "parenleft", "parenright", "asterisk", "plus", "comma", "minus",
+    "period", "slash", "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "colon",
+    "semicolon", "less", "equal", "greater", "question", "at", "A", "B", "C", "D", "E",
+    "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R", "S", "T", "U", "V",
+    "W", "X", "Y", "Z", "bracketleft", "backslash", "bracketright", "asciicircum",
+    "underscore", "grave", "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l",
+    "m", "n", "o", "p", "q", "r", "s", "t", "u", "v", "w", "x", "y", "z", "braceleft",
+    "bar", "braceright", "asciitilde",
+];
+
+/// Names of the keysyms from 0xa0 to 0xff, the Latin-1 characters.
+const LATIN1_NAMES: [&str; 96] = [
+    "nobreakspace", "exclamdown", "cent", "sterling", "currency", "yen", "brokenbar",
+    "section", "diaeresis", "copyright", "ordfeminine", "guillemotleft", "notsign",
+    "hyphen", "registered", "macron", "degree", "plusminus", "twosuperior",
+    "threesuperior", "acute", "mu", "paragraph", "periodcentered", "cedilla",
+    "onesuperior", "masculine", "guillemotright", "onequarter", "onehalf",
+    "threequarters", "questiondown", "Agrave", "Aacute", "Acircumflex", "Atilde",
+    "Adiaeresis", "Aring", "AE", "Ccedilla", "Egrave", "Eacute", "Ecircumflex",
+    "Ediaeresis", "Igrave", "Iacute", "Icircumflex", "Idiaeresis", "ETH", "Ntilde",
+    "Ograve", "Oacute", "Ocircumflex", "Otilde", "Odiaeresis", "multiply", "Oslash",
+    "Ugrave", "Uacute", "Ucircumflex", "Udiaeresis", "Yacute", "THORN", "ssharp",
+    "agrave", "aacute", "acircumflex", "atilde", "adiaeresis", "aring", "ae", "ccedilla",
+    "egrave", "eacute", "ecircumflex", "ediaeresis", "igrave", "iacute", "icircumflex",
+    "idiaeresis", "eth", "ntilde", "ograve", "oacute", "ocircumflex", "otilde",
+    "odiaeresis", "division", "oslash", "ugrave", "uacute", "ucircumflex", "udiaeresis",
+    "yacute", "thorn", "ydiaeresis",
+];
+
+/// Names of the function, modifier and dead keys.
+const OTHER_NAMES: &[(&str, Keysym)] = &[
+    ("BackSpace", 0xff08),
+    ("Tab", 0xff09),
+    ("Linefeed", 0xff0a),
+    ("Clear", 0xff0b),
+    ("Return", 0xff0d),
+    ("Pause", 0xff13),
+    ("Scroll_Lock", 0xff14),
+    ("Sys_Req", 0xff15),
+    ("Escape", 0xff1b),
+    ("Multi_key", 0xff20),
+    ("Home", 0xff50),
+    ("Left", 0xff51),
+    ("Up", 0xff52),
+    ("Right", 0xff53),
+    ("Down", 0xff54),
+    ("Prior", 0xff55),
+    ("Page_Up", 0xff55),
+    ("Next", 0xff56),
+    ("Page_Down", 0xff56),
+    ("End", 0xff57),
+    ("Begin", 0xff58),
+    ("Print", 0xff61),
+    ("Insert", 0xff63),
+    ("Menu", 0xff67),
+    ("Mode_switch", 0xff7e),
+    ("Num_Lock", 0xff7f),
+    ("KP_Enter", 0xff8d),
+    ("F1", 0xffbe),
+    ("F2", 0xffbf),
+    ("F3", 0xffc0),
+    ("F4", 0xffc1),
+    ("F5", 0xffc2),
+    ("F6", 0xffc3),
+    ("F7", 0xffc4),
+    ("F8", 0xffc5),
+    ("F9", 0xffc6),
+    ("F10", 0xffc7),
+    ("F11", 0xffc8),
+    ("F12", 0xffc9),
+    ("Shift_L", 0xffe1),
+    ("Shift_R", 0xffe2),
+    ("Control_L", 0xffe3),
+    ("Control_R", 0xffe4),
+    ("Caps_Lock", 0xffe5),
+    ("Shift_Lock", 0xffe6),
+    ("Meta_L", 0xffe7),
+    ("Meta_R", 0xffe8),
+    ("Alt_L", 0xffe9),
+    ("Alt_R", 0xffea),
+    ("Super_L", 0xffeb),
+    ("Super_R", 0xffec),
+    ("Hyper_L", 0xffed),
+    ("Hyper_R", 0xffee),
+    ("Delete", 0xffff),
+    ("ISO_Level3_Shift", 0xfe03),
+    ("ISO_Next_Group", 0xfe08),
+    ("ISO_Prev_Group", 0xfe0a),
+    ("dead_grave", 0xfe50),
+    ("dead_acute", 0xfe51),
+    ("dead_circumflex", 0xfe52),
+    ("dead_tilde", 0xfe53),
+    ("dead_macron", 0xfe54),
+    ("dead_breve", 0xfe55),
+    ("dead_abovedot", 0xfe56),
+    ("dead_diaeresis", 0xfe57),
+    ("dead_abovering", 0xfe58),
+    ("dead_doubleacute", 0xfe59),
+    ("dead_caron", 0xfe5a),
+    ("dead_cedilla", 0xfe5b),
+    ("dead_ogonek", 0xfe5c),
+    ("dead_iota", 0xfe5d),
+    ("dead_belowdot", 0xfe60),
+    ("dead_hook", 0xfe61),
+    ("dead_horn", 0xfe62),
+    ("dead_stroke", 0xfe63),
+    ("EuroSign", 0x20ac),
+    ("VoidSymbol", 0xffffff),
+];
+
+/// Returns the keysym of a name as used in keymaps and Compose files: the
+/// names of the ASCII, Latin-1, function, modifier and dead keys, the
+/// `U<hex>` names of the Unicode keysyms and hexadecimal numbers.
+pub fn keysym_from_name(name: &str) -> Option<Keysym> {
+    if let Some(i) = ASCII_NAMES.iter().position(|&n| n == name) {
+        return Some(0x20 + i as Keysym);
+    }
+    if let Some(i) = LATIN1_NAMES.iter().position(|&n| n == name) {
+        return Some(0xa0 + i as Keysym);
+    }
+    if let Some(&(_, keysym)) = OTHER_NAMES.iter().find(|&&(n, _)| n == name) {
+        return Some(keysym);
+    }
+
+    if let Some(hex) = name.strip_prefix("0x") {
+        return Keysym::from_str_radix(hex, 16).ok();
+    }
+    if let Some(hex) = name.strip_prefix('U') {
+        let code = u32::from_str_radix(hex, 16).ok()?;
+        return keysym_from_char(::std::char::from_u32(code)?);
+    }
+
+    None
+}
+
+/// Returns the name of a keysym, `None` if it's unknown.
+pub fn keysym_name(keysym: Keysym) -> Option<&'static str> {
+    match keysym {
+        0x20..=0x7e => Some(ASCII_NAMES[keysym as usize - 0x20]),
+        0xa0..=0xff => Some(LATIN1_NAMES[keysym as usize - 0xa0]),
+        _ => OTHER_NAMES.iter().find(|&&(_, k)| k == keysym).map(|&(name, _)| name),
+    }
+}
+
+/// Returns the keysym typing `c`, a Latin-1 keysym if there is one.
+pub fn keysym_from_char(c: char) -> Option<Keysym> {
+    match c as u32 {
+        code @ 0x20..=0x7e | code @ 0xa0..=0xff => Some(code),
+        0x20ac => Some(0x20ac),
+        code @ 0x100..=0x10ffff => Some(UNICODE_OFFSET + code),
+        _ => None,
+    }
+}
+
+/// Returns the character typed by a keysym, `None` for the function,
+/// modifier and dead keys and for the legacy keysyms out of Latin-1.
+pub fn keysym_to_char(keysym: Keysym) -> Option<char> {
+    let code = match keysym {
+        0x20..=0x7e | 0xa0..=0xff => keysym,
+        0x20ac => 0x20ac,
+        0xff08 | 0xff09 | 0xff0a | 0xff0d | 0xff1b => keysym & 0x7f,
+        0xff8d => 0x0d,
+        0xffff => 0x7f,
+        0x0100_0100..=0x0110_ffff => keysym - UNICODE_OFFSET,
+        _ => return None,
+    };
+
+    ::std::char::from_u32(code)
+}
+
+/// Whether the keysym is a modifier or a group switch, ignored while a
+/// compose sequence is typed.
+pub fn is_modifier_keysym(keysym: Keysym) -> bool {
+    matches!(keysym, 0xffe1..=0xffee | 0xfe01..=0xfe13 | 0xff7e | 0xff7f)
+}
+
+/// Whether the keysym is a dead key, which starts a compose sequence.
+pub fn is_dead_keysym(keysym: Keysym) -> bool {
+    (0xfe50..=0xfe8f).contains(&keysym)
+}
@@ -0,0 +1,294 @@
+//! XKB extension: keyboard state, keymaps with several groups (layouts),
+//! and the client side lookup of keysyms.
+//!
+//! `XkbUseExtension` must be sent before any other XKB request. The keymap
+//! of a keyboard is fetched with `Keymap::fetch` and kept up to date with
+//! the `MapNotify` and `NewKeyboardNotify` events, the state used for the
+//! lookups comes from `XkbGetState` and the `StateNotify` events.
+
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+
+use ::event::Event;
+use ::protocol::ExtensionInfo;
+use ::xproto::Timestamp;
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    xkb_use_extension,
+    xkb_select_events,
+    xkb_bell,
+    xkb_get_state,
+    xkb_latch_lock_state,
+    xkb_get_map,
+    xkb_get_names,
+    xkb_per_client_flags
+}
+
+mod keysym;
+pub use self::keysym::*;
+
+mod keymap;
+pub use self::keymap::*;
+
+mod compose;
+pub use self::compose::*;
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"XKEYBOARD";
+
+/// Selects a keyboard, an input device id or one of the `USE_CORE_*`
+/// values.
+pub type DeviceSpec = u16;
+pub const USE_CORE_KBD: DeviceSpec = 0x100;
+pub const USE_CORE_PTR: DeviceSpec = 0x200;
+
+/// The XKB events, selected with `XkbSelectEvents`.
+pub type EventType = u16;
+pub const EVENT_TYPE_NEW_KEYBOARD_NOTIFY: EventType = 0x0001;
+pub const EVENT_TYPE_MAP_NOTIFY: EventType = 0x0002;
+pub const EVENT_TYPE_STATE_NOTIFY: EventType = 0x0004;
+pub const EVENT_TYPE_CONTROLS_NOTIFY: EventType = 0x0008;
+pub const EVENT_TYPE_INDICATOR_STATE_NOTIFY: EventType = 0x0010;
+pub const EVENT_TYPE_INDICATOR_MAP_NOTIFY: EventType = 0x0020;
+pub const EVENT_TYPE_NAMES_NOTIFY: EventType = 0x0040;
+pub const EVENT_TYPE_COMPAT_MAP_NOTIFY: EventType = 0x0080;
+pub const EVENT_TYPE_BELL_NOTIFY: EventType = 0x0100;
+pub const EVENT_TYPE_ACTION_MESSAGE: EventType = 0x0200;
+pub const EVENT_TYPE_ACCESS_X_NOTIFY: EventType = 0x0400;
+pub const EVENT_TYPE_EXTENSION_DEVICE_NOTIFY: EventType = 0x0800;
+
+/// Parts of a keymap, requested with `XkbGetMap` and reported by
+/// `MapNotify` events.
+pub type MapPart = u16;
+pub const MAP_PART_KEY_TYPES: MapPart = 0x0001;
+pub const MAP_PART_KEY_SYMS: MapPart = 0x0002;
+pub const MAP_PART_MODIFIER_MAP: MapPart = 0x0004;
+pub const MAP_PART_EXPLICIT_COMPONENTS: MapPart = 0x0008;
+pub const MAP_PART_KEY_ACTIONS: MapPart = 0x0010;
+pub const MAP_PART_KEY_BEHAVIORS: MapPart = 0x0020;
+pub const MAP_PART_VIRTUAL_MODS: MapPart = 0x0040;
+pub const MAP_PART_VIRTUAL_MOD_MAP: MapPart = 0x0080;
+
+/// Parts of the keyboard state reported by `StateNotify` events.
+pub type StatePart = u16;
+pub const STATE_PART_MODIFIER_STATE: StatePart = 0x0001;
+pub const STATE_PART_MODIFIER_BASE: StatePart = 0x0002;
+pub const STATE_PART_MODIFIER_LATCH: StatePart = 0x0004;
+pub const STATE_PART_MODIFIER_LOCK: StatePart = 0x0008;
+pub const STATE_PART_GROUP_STATE: StatePart = 0x0010;
+pub const STATE_PART_GROUP_BASE: StatePart = 0x0020;
+pub const STATE_PART_GROUP_LATCH: StatePart = 0x0040;
+pub const STATE_PART_GROUP_LOCK: StatePart = 0x0080;
+pub const STATE_PART_COMPAT_STATE: StatePart = 0x0100;
+pub const STATE_PART_GRAB_MODS: StatePart = 0x0200;
+pub const STATE_PART_COMPAT_GRAB_MODS: StatePart = 0x0400;
+pub const STATE_PART_LOOKUP_MODS: StatePart = 0x0800;
+pub const STATE_PART_COMPAT_LOOKUP_MODS: StatePart = 0x1000;
+pub const STATE_PART_POINTER_BUTTONS: StatePart = 0x2000;
+
+/// Changes reported by `NewKeyboardNotify` events.
+pub type NknDetail = u16;
+pub const NKN_DETAIL_KEYCODES: NknDetail = 0x01;
+pub const NKN_DETAIL_GEOMETRY: NknDetail = 0x02;
+pub const NKN_DETAIL_DEVICE_ID: NknDetail = 0x04;
+
+/// Names requested with `XkbGetNames`.
+pub type NameDetail = u32;
+pub const NAME_DETAIL_KEYCODES: NameDetail = 1 << 0;
+pub const NAME_DETAIL_GEOMETRY: NameDetail = 1 << 1;
+pub const NAME_DETAIL_SYMBOLS: NameDetail = 1 << 2;
+pub const NAME_DETAIL_PHYS_SYMBOLS: NameDetail = 1 << 3;
+pub const NAME_DETAIL_TYPES: NameDetail = 1 << 4;
+pub const NAME_DETAIL_COMPAT: NameDetail = 1 << 5;
+pub const NAME_DETAIL_KEY_TYPE_NAMES: NameDetail = 1 << 6;
+pub const NAME_DETAIL_KT_LEVEL_NAMES: NameDetail = 1 << 7;
+pub const NAME_DETAIL_INDICATOR_NAMES: NameDetail = 1 << 8;
+pub const NAME_DETAIL_KEY_NAMES: NameDetail = 1 << 9;
+pub const NAME_DETAIL_KEY_ALIASES: NameDetail = 1 << 10;
+pub const NAME_DETAIL_VIRTUAL_MOD_NAMES: NameDetail = 1 << 11;
+pub const NAME_DETAIL_GROUP_NAMES: NameDetail = 1 << 12;
+pub const NAME_DETAIL_RG_NAMES: NameDetail = 1 << 13;
+
+/// Flags changing how the server handles the keyboard for this client.
+pub type PerClientFlag = u32;
+
+/// The auto-repeat sends only `KeyPress` events, without the `KeyRelease`
+/// ones in between.
+pub const PER_CLIENT_FLAG_DETECTABLE_AUTO_REPEAT: PerClientFlag = 0x01;
+pub const PER_CLIENT_FLAG_GRABS_USE_XKB_STATE: PerClientFlag = 0x02;
+pub const PER_CLIENT_FLAG_AUTO_RESET_CONTROLS: PerClientFlag = 0x04;
+pub const PER_CLIENT_FLAG_LOOKUP_STATE_WHEN_GRABBED: PerClientFlag = 0x08;
+pub const PER_CLIENT_FLAG_SEND_EVENT_USES_XKB_STATE: PerClientFlag = 0x10;
+
+/// Sub-codes of the XKB event, all the XKB events use the same event code.
+const NEW_KEYBOARD_NOTIFY: u8 = 0;
+const MAP_NOTIFY: u8 = 1;
+const STATE_NOTIFY: u8 = 2;
+
+/// An event of the XKB extension.
+#[derive(Debug, Clone, Copy)]
+pub enum XkbEvent {
+    NewKeyboardNotify(XkbNewKeyboardNotifyEvent),
+    MapNotify(XkbMapNotifyEvent),
+    StateNotify(XkbStateNotifyEvent),
+}
+
+impl XkbEvent {
+    /// Decodes `event` if it's a XKB event, `info` is the information of
+    /// the extension as returned by `Client::extension_info`.
+    pub fn from_event(info: &ExtensionInfo, event: &Event) -> Option<XkbEvent> {
+        match *event {
+            Event::Unknown(ref buf) => XkbEvent::read(info, buf),
+            _ => None,
+        }
+    }
+
+    /// Decodes an event packet, `None` is returned for the XKB events not
+    /// decoded by this crate.
+    pub fn read(info: &ExtensionInfo, buf: &[u8]) -> Option<XkbEvent> {
+        if !info.present || info.first_event == 0 || buf[0] & 0x7f != info.first_event {
+            return None;
+        }
+
+        let timestamp = NativeEndian::read_u32(&buf[4..8]);
+        let device_id = buf[8];
+
+        match buf[1] {
+            NEW_KEYBOARD_NOTIFY => {
+                Some(XkbEvent::NewKeyboardNotify(XkbNewKeyboardNotifyEvent {
+                    timestamp,
+                    device_id,
+                    old_device_id: buf[9],
+                    min_key_code: buf[10],
+                    max_key_code: buf[11],
+                    old_min_key_code: buf[12],
+                    old_max_key_code: buf[13],
+                    changed: NativeEndian::read_u16(&buf[16..18]),
+                }))
+            }
+            MAP_NOTIFY => {
+                Some(XkbEvent::MapNotify(XkbMapNotifyEvent {
+                    timestamp,
+                    device_id,
+                    changed: NativeEndian::read_u16(&buf[10..12]),
+                    min_key_code: buf[12],
+                    max_key_code: buf[13],
+                    first_key_sym: buf[16],
+                    n_key_syms: buf[17],
+                }))
+            }
+            STATE_NOTIFY => {
+                Some(XkbEvent::StateNotify(XkbStateNotifyEvent {
+                    timestamp,
+                    device_id,
+                    state: KeyboardState {
+                        mods: buf[9],
+                        base_mods: buf[10],
+                        latched_mods: buf[11],
+                        locked_mods: buf[12],
+                        group: buf[13],
+                        base_group: NativeEndian::read_i16(&buf[14..16]),
+                        latched_group: NativeEndian::read_i16(&buf[16..18]),
+                        locked_group: buf[18],
+                        compat_state: buf[19],
+                        grab_mods: buf[20],
+                        compat_grab_mods: buf[21],
+                        lookup_mods: buf[22],
+                        compat_lookup_mods: buf[23],
+                        ptr_btn_state: NativeEndian::read_u16(&buf[24..26]),
+                    },
+                    changed: NativeEndian::read_u16(&buf[26..28]),
+                    keycode: buf[28],
+                    event_type: buf[29],
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Sent when the keyboard device or its range of keycodes changes, the
+/// keymap must be fetched again.
+#[derive(Debug, Clone, Copy)]
+pub struct XkbNewKeyboardNotifyEvent {
+    pub timestamp: Timestamp,
+    pub device_id: u8,
+    pub old_device_id: u8,
+    pub min_key_code: u8,
+    pub max_key_code: u8,
+    pub old_min_key_code: u8,
+    pub old_max_key_code: u8,
+
+    /// `NKN_DETAIL_*` flags.
+    pub changed: NknDetail,
+}
+
+/// Sent when the keymap changes, for example when the layout is switched
+/// with `setxkbmap`.
+#[derive(Debug, Clone, Copy)]
+pub struct XkbMapNotifyEvent {
+    pub timestamp: Timestamp,
+    pub device_id: u8,
+
+    /// The parts of the keymap changed.
+    pub changed: MapPart,
+
+    pub min_key_code: u8,
+    pub max_key_code: u8,
+
+    /// The keys whose keysyms changed.
+    pub first_key_sym: u8,
+    pub n_key_syms: u8,
+}
+
+/// The state of the modifiers and groups of a keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KeyboardState {
+    /// The effective modifiers.
+    pub mods: u8,
+
+    pub base_mods: u8,
+    pub latched_mods: u8,
+    pub locked_mods: u8,
+
+    /// The effective group, the active layout.
+    pub group: u8,
+
+    pub base_group: i16,
+    pub latched_group: i16,
+    pub locked_group: u8,
+    pub compat_state: u8,
+    pub grab_mods: u8,
+    pub compat_grab_mods: u8,
+
+    /// The modifiers used to look up the keysyms.
+    pub lookup_mods: u8,
+
+    pub compat_lookup_mods: u8,
+    pub ptr_btn_state: u16,
+}
+
+/// Sent when the state of the keyboard changes.
+#[derive(Debug, Clone, Copy)]
+pub struct XkbStateNotifyEvent {
+    pub timestamp: Timestamp,
+    pub device_id: u8,
+    pub state: KeyboardState,
+
+    /// The parts of the state changed, `STATE_PART_*` flags.
+    pub changed: StatePart,
+
+    /// The key and the core event type which changed the state, 0 if it was
+    /// changed by a request.
+    pub keycode: u8,
+    pub event_type: u8,
+}
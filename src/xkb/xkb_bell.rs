@@ -0,0 +1,81 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xkb::DeviceSpec;
+use ::xkb::EXTENSION_NAME;
+use ::xproto::Atom;
+use ::xproto::Window;
+use ::Client;
+
+const XKB_OPCODE: u8 = 3;
+
+/// The default bell class and id of a keyboard.
+pub const DFLT_XI_CLASS: u16 = 0x300;
+pub const DFLT_XI_ID: u16 = 0x400;
+
+/// Rings the bell of a keyboard, or only generates a `BellNotify` event
+/// with `event_only`.
+#[derive(Debug, Clone, Copy)]
+pub struct XkbBell {
+    pub device_spec: DeviceSpec,
+    pub bell_class: u16,
+    pub bell_id: u16,
+
+    /// Volume from -100 to 100, relative to the volume of the keyboard.
+    pub percent: i8,
+
+    /// Rings the bell even if the `AudibleBell` control is disabled.
+    pub force_sound: bool,
+
+    pub event_only: bool,
+
+    /// Pitch in Hz and duration in milliseconds, -1 for the default ones.
+    pub pitch: i16,
+    pub duration: i16,
+
+    /// Name of the bell, reported in the `BellNotify` event.
+    pub name: Atom,
+
+    pub window: Window,
+}
+
+impl ExtensionRequest for XkbBell {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XKB_OPCODE)?;
+        a.write_u16::<NativeEndian>(7)?;
+        a.write_u16::<NativeEndian>(self.device_spec)?;
+        a.write_u16::<NativeEndian>(self.bell_class)?;
+        a.write_u16::<NativeEndian>(self.bell_id)?;
+        a.write_i8(self.percent)?;
+        a.write_u8(self.force_sound as u8)?;
+        a.write_u8(self.event_only as u8)?;
+        a.write_u8(0)?;
+        a.write_i16::<NativeEndian>(self.pitch)?;
+        a.write_i16::<NativeEndian>(self.duration)?;
+        a.write_u16::<NativeEndian>(0)?;
+        a.write_u32::<NativeEndian>(self.name.0)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,302 @@
+use ::std::io;
+use ::std::io::Read;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xkb::DeviceSpec;
+use ::xkb::Keysym;
+use ::xkb::MapPart;
+use ::xkb::EXTENSION_NAME;
+use ::xkb::MAP_PART_EXPLICIT_COMPONENTS;
+use ::xkb::MAP_PART_KEY_ACTIONS;
+use ::xkb::MAP_PART_KEY_BEHAVIORS;
+use ::xkb::MAP_PART_KEY_SYMS;
+use ::xkb::MAP_PART_KEY_TYPES;
+use ::xkb::MAP_PART_MODIFIER_MAP;
+use ::xkb::MAP_PART_VIRTUAL_MODS;
+use ::Client;
+use ::pad;
+
+const XKB_OPCODE: u8 = 8;
+
+/// Returns parts of the keymap of a keyboard.
+///
+/// The parts in `full` are returned for all the keys, the ranges of the
+/// other fields are used for the parts in `partial`. Only the key types,
+/// the keysyms and the modifier map are decoded.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XkbGetMap {
+    pub device_spec: DeviceSpec,
+    pub full: MapPart,
+    pub partial: MapPart,
+    pub first_type: u8,
+    pub n_types: u8,
+    pub first_key_sym: u8,
+    pub n_key_syms: u8,
+    pub first_mod_map_key: u8,
+    pub n_mod_map_keys: u8,
+}
+
+impl ExtensionRequest for XkbGetMap {
+    type Reply = XkbGetMapReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XKB_OPCODE)?;
+        a.write_u16::<NativeEndian>(7)?;
+        a.write_u16::<NativeEndian>(self.device_spec)?;
+        a.write_u16::<NativeEndian>(self.full)?;
+        a.write_u16::<NativeEndian>(self.partial)?;
+        a.write_u8(self.first_type)?;
+        a.write_u8(self.n_types)?;
+        a.write_u8(self.first_key_sym)?;
+        a.write_u8(self.n_key_syms)?;
+        // Key actions, behaviors, virtual mods, explicit components.
+        a.write_u32::<NativeEndian>(0)?;
+        a.write_u32::<NativeEndian>(0)?;
+        a.write_u8(self.first_mod_map_key)?;
+        a.write_u8(self.n_mod_map_keys)?;
+        // Virtual modifier map and padding.
+        a.write_u32::<NativeEndian>(0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let reply = XkbGetMapReply::read(&buf)?;
+            Ok((client, reply))
+        }))
+    }
+}
+
+/// Reply of `XkbGetMap` request.
+#[derive(Debug, Clone, Default)]
+pub struct XkbGetMapReply {
+    pub device_id: u8,
+    pub min_key_code: u8,
+    pub max_key_code: u8,
+
+    /// The parts of the keymap returned.
+    pub present: MapPart,
+
+    /// Index of the first type of `types`.
+    pub first_type: u8,
+    pub types: Vec<KeyType>,
+
+    /// Keycode of the first key of `syms`.
+    pub first_key_sym: u8,
+    pub syms: Vec<KeySymMap>,
+
+    /// The real modifiers bound to the keys, as (keycode, modifiers).
+    pub modmap: Vec<(u8, u8)>,
+}
+
+impl XkbGetMapReply {
+    pub(crate) fn read(buf: &[u8]) -> io::Result<XkbGetMapReply> {
+        let mut a = io::Cursor::new(buf);
+
+        a.read_u8()?;
+        let device_id = a.read_u8()?;
+        a.set_position(10);
+        let min_key_code = a.read_u8()?;
+        let max_key_code = a.read_u8()?;
+        let present = a.read_u16::<NativeEndian>()?;
+        let first_type = a.read_u8()?;
+        let n_types = a.read_u8()?;
+        a.read_u8()?;
+        let first_key_sym = a.read_u8()?;
+        a.read_u16::<NativeEndian>()?;
+        let n_key_syms = a.read_u8()?;
+        a.read_u8()?;
+        let total_actions = a.read_u16::<NativeEndian>()?;
+        let n_key_actions = a.read_u8()?;
+        a.read_u8()?;
+        a.read_u8()?;
+        let total_key_behaviors = a.read_u8()?;
+        a.read_u16::<NativeEndian>()?;
+        let total_key_explicit = a.read_u8()?;
+        a.read_u8()?;
+        a.read_u8()?;
+        let total_mod_map_keys = a.read_u8()?;
+        a.set_position(38);
+        let virtual_mods = a.read_u16::<NativeEndian>()?;
+
+        let mut reply = XkbGetMapReply {
+            device_id,
+            min_key_code,
+            max_key_code,
+            present,
+            first_type,
+            first_key_sym,
+            ..XkbGetMapReply::default()
+        };
+
+        if present & MAP_PART_KEY_TYPES != 0 {
+            for _ in 0..n_types {
+                reply.types.push(KeyType::read(&mut a)?);
+            }
+        }
+        if present & MAP_PART_KEY_SYMS != 0 {
+            for _ in 0..n_key_syms {
+                reply.syms.push(KeySymMap::read(&mut a)?);
+            }
+        }
+
+        // The parts before the modifier map are skipped.
+        let mut skip = 0;
+        if present & MAP_PART_KEY_ACTIONS != 0 {
+            skip += n_key_actions as usize + pad(n_key_actions as usize);
+            skip += total_actions as usize * 8;
+        }
+        if present & MAP_PART_KEY_BEHAVIORS != 0 {
+            skip += total_key_behaviors as usize * 4;
+        }
+        if present & MAP_PART_VIRTUAL_MODS != 0 {
+            let count = virtual_mods.count_ones() as usize;
+            skip += count + pad(count);
+        }
+        if present & MAP_PART_EXPLICIT_COMPONENTS != 0 {
+            let len = total_key_explicit as usize * 2;
+            skip += len + pad(len);
+        }
+        a.set_position(a.position() + skip as u64);
+
+        if present & MAP_PART_MODIFIER_MAP != 0 {
+            for _ in 0..total_mod_map_keys {
+                let keycode = a.read_u8()?;
+                let mods = a.read_u8()?;
+                reply.modmap.push((keycode, mods));
+            }
+        }
+
+        Ok(reply)
+    }
+}
+
+/// Maps a combination of modifiers to a shift level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KTMapEntry {
+    pub active: bool,
+
+    /// The modifiers matched, with the virtual modifiers resolved.
+    pub mods_mask: u8,
+
+    pub level: u8,
+    pub mods_mods: u8,
+    pub mods_vmods: u16,
+
+    /// The modifiers not consumed by the level.
+    pub preserve: u8,
+}
+
+/// A key type, how the modifiers select the shift level of a key.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KeyType {
+    /// The modifiers used by the type, with the virtual modifiers resolved.
+    pub mods_mask: u8,
+
+    pub mods_mods: u8,
+    pub mods_vmods: u16,
+    pub num_levels: u8,
+    pub map: Vec<KTMapEntry>,
+}
+
+impl KeyType {
+    fn read<A: Read>(a: &mut A) -> io::Result<KeyType> {
+        let mods_mask = a.read_u8()?;
+        let mods_mods = a.read_u8()?;
+        let mods_vmods = a.read_u16::<NativeEndian>()?;
+        let num_levels = a.read_u8()?;
+        let n_map_entries = a.read_u8()?;
+        let has_preserve = a.read_u8()? != 0;
+        a.read_u8()?;
+
+        let mut map = Vec::with_capacity(n_map_entries as usize);
+        for _ in 0..n_map_entries {
+            let active = a.read_u8()? != 0;
+            let mods_mask = a.read_u8()?;
+            let level = a.read_u8()?;
+            let mods_mods = a.read_u8()?;
+            let mods_vmods = a.read_u16::<NativeEndian>()?;
+            a.read_u16::<NativeEndian>()?;
+
+            map.push(KTMapEntry {
+                active,
+                mods_mask,
+                level,
+                mods_mods,
+                mods_vmods,
+                preserve: 0,
+            });
+        }
+
+        if has_preserve {
+            for entry in &mut map {
+                entry.preserve = a.read_u8()?;
+                a.read_u8()?;
+                a.read_u16::<NativeEndian>()?;
+            }
+        }
+
+        Ok(KeyType {
+            mods_mask,
+            mods_mods,
+            mods_vmods,
+            num_levels,
+            map,
+        })
+    }
+}
+
+/// The keysyms of a key.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KeySymMap {
+    /// The index of the key type of each group.
+    pub kt_index: [u8; 4],
+
+    /// The number of groups in the low 4 bits, and how a group out of range
+    /// is brought into range in the high ones.
+    pub group_info: u8,
+
+    /// Number of keysyms per group.
+    pub width: u8,
+
+    /// The keysyms, group by group.
+    pub syms: Vec<Keysym>,
+}
+
+impl KeySymMap {
+    fn read<A: Read>(a: &mut A) -> io::Result<KeySymMap> {
+        let mut kt_index = [0u8; 4];
+        a.read_exact(&mut kt_index)?;
+        let group_info = a.read_u8()?;
+        let width = a.read_u8()?;
+        let n_syms = a.read_u16::<NativeEndian>()?;
+
+        let mut syms = Vec::with_capacity(n_syms as usize);
+        for _ in 0..n_syms {
+            syms.push(a.read_u32::<NativeEndian>()?);
+        }
+
+        Ok(KeySymMap {
+            kt_index,
+            group_info,
+            width,
+            syms,
+        })
+    }
+}
@@ -0,0 +1,208 @@
+use ::std::io;
+use ::std::io::Read;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xkb::DeviceSpec;
+use ::xkb::NameDetail;
+use ::xkb::EXTENSION_NAME;
+use ::xkb::NAME_DETAIL_COMPAT;
+use ::xkb::NAME_DETAIL_GEOMETRY;
+use ::xkb::NAME_DETAIL_GROUP_NAMES;
+use ::xkb::NAME_DETAIL_INDICATOR_NAMES;
+use ::xkb::NAME_DETAIL_KEYCODES;
+use ::xkb::NAME_DETAIL_KEY_ALIASES;
+use ::xkb::NAME_DETAIL_KEY_NAMES;
+use ::xkb::NAME_DETAIL_KEY_TYPE_NAMES;
+use ::xkb::NAME_DETAIL_KT_LEVEL_NAMES;
+use ::xkb::NAME_DETAIL_PHYS_SYMBOLS;
+use ::xkb::NAME_DETAIL_RG_NAMES;
+use ::xkb::NAME_DETAIL_SYMBOLS;
+use ::xkb::NAME_DETAIL_TYPES;
+use ::xkb::NAME_DETAIL_VIRTUAL_MOD_NAMES;
+use ::xproto::Atom;
+use ::Client;
+use ::pad;
+
+const XKB_OPCODE: u8 = 17;
+
+/// Returns the names of the components of a keymap, `which` selects them.
+/// The group names are the names of the layouts.
+#[derive(Debug, Clone, Copy)]
+pub struct XkbGetNames {
+    pub device_spec: DeviceSpec,
+    pub which: NameDetail,
+}
+
+impl ExtensionRequest for XkbGetNames {
+    type Reply = XkbGetNamesReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XKB_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u16::<NativeEndian>(self.device_spec)?;
+        a.write_u16::<NativeEndian>(0)?;
+        a.write_u32::<NativeEndian>(self.which)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let reply = XkbGetNamesReply::read(&buf)?;
+            Ok((client, reply))
+        }))
+    }
+}
+
+/// Reply of `XkbGetNames` request, the names not requested are
+/// `Atom::NONE` or empty.
+#[derive(Debug, Clone, Default)]
+pub struct XkbGetNamesReply {
+    pub device_id: u8,
+    pub which: NameDetail,
+    pub min_key_code: u8,
+    pub max_key_code: u8,
+    pub keycodes: Atom,
+    pub geometry: Atom,
+
+    /// Name of the symbols component, like `pc+us+de:2+inet(evdev)`.
+    pub symbols: Atom,
+
+    pub phys_symbols: Atom,
+    pub types: Atom,
+    pub compat: Atom,
+    pub type_names: Vec<Atom>,
+
+    /// The names of the levels of each type.
+    pub level_names: Vec<Vec<Atom>>,
+
+    pub indicator_names: Vec<Atom>,
+    pub virtual_mod_names: Vec<Atom>,
+
+    /// The names of the groups set, like `English (US)`.
+    pub group_names: Vec<Atom>,
+
+    /// Names of the keys from `first_key`, like `AE01`.
+    pub first_key: u8,
+    pub key_names: Vec<[u8; 4]>,
+
+    /// Aliases of the key names, as (alias, real name).
+    pub key_aliases: Vec<([u8; 4], [u8; 4])>,
+
+    pub radio_group_names: Vec<Atom>,
+}
+
+impl XkbGetNamesReply {
+    fn read(buf: &[u8]) -> io::Result<XkbGetNamesReply> {
+        let mut a = io::Cursor::new(buf);
+
+        a.read_u8()?;
+        let device_id = a.read_u8()?;
+        a.set_position(8);
+        let which = a.read_u32::<NativeEndian>()?;
+        let min_key_code = a.read_u8()?;
+        let max_key_code = a.read_u8()?;
+        let n_types = a.read_u8()?;
+        let group_names = a.read_u8()?;
+        let virtual_mods = a.read_u16::<NativeEndian>()?;
+        let first_key = a.read_u8()?;
+        let n_keys = a.read_u8()?;
+        let indicators = a.read_u32::<NativeEndian>()?;
+        let n_radio_groups = a.read_u8()?;
+        let n_key_aliases = a.read_u8()?;
+        a.set_position(32);
+
+        let mut reply = XkbGetNamesReply {
+            device_id,
+            which,
+            min_key_code,
+            max_key_code,
+            first_key,
+            ..XkbGetNamesReply::default()
+        };
+
+        // The names are in this order, not in the order of the bits.
+        if which & NAME_DETAIL_KEYCODES != 0 {
+            reply.keycodes = Atom(a.read_u32::<NativeEndian>()?);
+        }
+        if which & NAME_DETAIL_GEOMETRY != 0 {
+            reply.geometry = Atom(a.read_u32::<NativeEndian>()?);
+        }
+        if which & NAME_DETAIL_SYMBOLS != 0 {
+            reply.symbols = Atom(a.read_u32::<NativeEndian>()?);
+        }
+        if which & NAME_DETAIL_PHYS_SYMBOLS != 0 {
+            reply.phys_symbols = Atom(a.read_u32::<NativeEndian>()?);
+        }
+        if which & NAME_DETAIL_TYPES != 0 {
+            reply.types = Atom(a.read_u32::<NativeEndian>()?);
+        }
+        if which & NAME_DETAIL_COMPAT != 0 {
+            reply.compat = Atom(a.read_u32::<NativeEndian>()?);
+        }
+        if which & NAME_DETAIL_KEY_TYPE_NAMES != 0 {
+            reply.type_names = read_atoms(&mut a, n_types as usize)?;
+        }
+        if which & NAME_DETAIL_KT_LEVEL_NAMES != 0 {
+            let mut levels = vec![0u8; n_types as usize + pad(n_types as usize)];
+            a.read_exact(&mut levels)?;
+            for &count in &levels[..n_types as usize] {
+                reply.level_names.push(read_atoms(&mut a, count as usize)?);
+            }
+        }
+        if which & NAME_DETAIL_INDICATOR_NAMES != 0 {
+            reply.indicator_names = read_atoms(&mut a, indicators.count_ones() as usize)?;
+        }
+        if which & NAME_DETAIL_VIRTUAL_MOD_NAMES != 0 {
+            reply.virtual_mod_names = read_atoms(&mut a, virtual_mods.count_ones() as usize)?;
+        }
+        if which & NAME_DETAIL_GROUP_NAMES != 0 {
+            reply.group_names = read_atoms(&mut a, group_names.count_ones() as usize)?;
+        }
+        if which & NAME_DETAIL_KEY_NAMES != 0 {
+            for _ in 0..n_keys {
+                let mut name = [0u8; 4];
+                a.read_exact(&mut name)?;
+                reply.key_names.push(name);
+            }
+        }
+        if which & NAME_DETAIL_KEY_ALIASES != 0 {
+            for _ in 0..n_key_aliases {
+                let mut real = [0u8; 4];
+                let mut alias = [0u8; 4];
+                a.read_exact(&mut real)?;
+                a.read_exact(&mut alias)?;
+                reply.key_aliases.push((alias, real));
+            }
+        }
+        if which & NAME_DETAIL_RG_NAMES != 0 {
+            reply.radio_group_names = read_atoms(&mut a, n_radio_groups as usize)?;
+        }
+
+        Ok(reply)
+    }
+}
+
+fn read_atoms<A: Read>(a: &mut A, count: usize) -> io::Result<Vec<Atom>> {
+    let mut atoms = Vec::with_capacity(count);
+    for _ in 0..count {
+        atoms.push(Atom(a.read_u32::<NativeEndian>()?));
+    }
+
+    Ok(atoms)
+}
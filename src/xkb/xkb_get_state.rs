@@ -0,0 +1,76 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xkb::DeviceSpec;
+use ::xkb::KeyboardState;
+use ::xkb::EXTENSION_NAME;
+use ::Client;
+
+const XKB_OPCODE: u8 = 4;
+
+/// Returns the state of the modifiers and groups of a keyboard.
+#[derive(Debug, Clone, Copy)]
+pub struct XkbGetState {
+    pub device_spec: DeviceSpec,
+}
+
+impl ExtensionRequest for XkbGetState {
+    type Reply = XkbGetStateReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XKB_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u16::<NativeEndian>(self.device_spec)?;
+        a.write_u16::<NativeEndian>(0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = XkbGetStateReply {
+                device_id: buf[1],
+                state: KeyboardState {
+                    mods: buf[8],
+                    base_mods: buf[9],
+                    latched_mods: buf[10],
+                    locked_mods: buf[11],
+                    group: buf[12],
+                    locked_group: buf[13],
+                    base_group: NativeEndian::read_i16(&buf[14..16]),
+                    latched_group: NativeEndian::read_i16(&buf[16..18]),
+                    compat_state: buf[18],
+                    grab_mods: buf[19],
+                    compat_grab_mods: buf[20],
+                    lookup_mods: buf[21],
+                    compat_lookup_mods: buf[22],
+                    ptr_btn_state: NativeEndian::read_u16(&buf[24..26]),
+                },
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `XkbGetState` request.
+#[derive(Debug, Clone, Copy)]
+pub struct XkbGetStateReply {
+    pub device_id: u8,
+    pub state: KeyboardState,
+}
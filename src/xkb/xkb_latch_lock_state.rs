@@ -0,0 +1,65 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xkb::DeviceSpec;
+use ::xkb::EXTENSION_NAME;
+use ::Client;
+
+const XKB_OPCODE: u8 = 5;
+
+/// Changes the locked and latched modifiers and groups of a keyboard, like
+/// switching the layout or turning Caps Lock on.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XkbLatchLockState {
+    pub device_spec: DeviceSpec,
+
+    /// The modifiers locked are set to `mod_locks`, among `affect_mod_locks`.
+    pub affect_mod_locks: u8,
+    pub mod_locks: u8,
+
+    /// Whether the locked group is set to `group_lock`.
+    pub lock_group: bool,
+    pub group_lock: u8,
+
+    pub affect_mod_latches: u8,
+    pub latch_group: bool,
+    pub group_latch: u16,
+}
+
+impl ExtensionRequest for XkbLatchLockState {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XKB_OPCODE)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u16::<NativeEndian>(self.device_spec)?;
+        a.write_u8(self.affect_mod_locks)?;
+        a.write_u8(self.mod_locks)?;
+        a.write_u8(self.lock_group as u8)?;
+        a.write_u8(self.group_lock)?;
+        a.write_u8(self.affect_mod_latches)?;
+        a.write_u16::<NativeEndian>(0)?;
+        a.write_u8(self.latch_group as u8)?;
+        a.write_u16::<NativeEndian>(self.group_latch)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
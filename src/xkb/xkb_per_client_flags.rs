@@ -0,0 +1,81 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xkb::DeviceSpec;
+use ::xkb::PerClientFlag;
+use ::xkb::EXTENSION_NAME;
+use ::Client;
+
+const XKB_OPCODE: u8 = 21;
+
+/// Changes the `PER_CLIENT_FLAG_*` flags of this client among `change`,
+/// and returns the new ones.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XkbPerClientFlags {
+    pub device_spec: DeviceSpec,
+    pub change: PerClientFlag,
+    pub value: PerClientFlag,
+
+    /// The controls reset when this client exits, with
+    /// `PER_CLIENT_FLAG_AUTO_RESET_CONTROLS`.
+    pub ctrls_to_change: u32,
+    pub auto_ctrls: u32,
+    pub auto_ctrls_values: u32,
+}
+
+impl ExtensionRequest for XkbPerClientFlags {
+    type Reply = XkbPerClientFlagsReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XKB_OPCODE)?;
+        a.write_u16::<NativeEndian>(7)?;
+        a.write_u16::<NativeEndian>(self.device_spec)?;
+        a.write_u16::<NativeEndian>(0)?;
+        a.write_u32::<NativeEndian>(self.change)?;
+        a.write_u32::<NativeEndian>(self.value)?;
+        a.write_u32::<NativeEndian>(self.ctrls_to_change)?;
+        a.write_u32::<NativeEndian>(self.auto_ctrls)?;
+        a.write_u32::<NativeEndian>(self.auto_ctrls_values)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = XkbPerClientFlagsReply {
+                supported: NativeEndian::read_u32(&buf[8..12]),
+                value: NativeEndian::read_u32(&buf[12..16]),
+                auto_ctrls: NativeEndian::read_u32(&buf[16..20]),
+                auto_ctrls_values: NativeEndian::read_u32(&buf[20..24]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `XkbPerClientFlags` request.
+#[derive(Debug, Clone, Copy)]
+pub struct XkbPerClientFlagsReply {
+    /// The flags supported by the server.
+    pub supported: PerClientFlag,
+
+    pub value: PerClientFlag,
+    pub auto_ctrls: u32,
+    pub auto_ctrls_values: u32,
+}
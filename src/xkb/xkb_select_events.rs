@@ -0,0 +1,101 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xkb::DeviceSpec;
+use ::xkb::EventType;
+use ::xkb::MapPart;
+use ::xkb::NknDetail;
+use ::xkb::StatePart;
+use ::xkb::EVENT_TYPE_MAP_NOTIFY;
+use ::xkb::EVENT_TYPE_NEW_KEYBOARD_NOTIFY;
+use ::xkb::EVENT_TYPE_STATE_NOTIFY;
+use ::xkb::EXTENSION_NAME;
+use ::Client;
+
+const XKB_OPCODE: u8 = 1;
+
+/// Selects the XKB events reported to this client.
+///
+/// The selection of the events in `affect_which` is changed: the ones in
+/// `clear` are deselected, the ones in `select_all` are selected with all
+/// their details, and the details of the others are given by the
+/// `affect_*` and `*_details` fields. Only the `MapNotify`, `StateNotify`
+/// and `NewKeyboardNotify` events can be selected by details.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XkbSelectEvents {
+    pub device_spec: DeviceSpec,
+    pub affect_which: EventType,
+    pub clear: EventType,
+    pub select_all: EventType,
+
+    /// The parts of the keymap whose changes are reported, among
+    /// `affect_map`.
+    pub affect_map: MapPart,
+    pub map: MapPart,
+
+    pub affect_new_keyboard: NknDetail,
+    pub new_keyboard_details: NknDetail,
+
+    pub affect_state: StatePart,
+    pub state_details: StatePart,
+}
+
+impl ExtensionRequest for XkbSelectEvents {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        // The details of the MapNotify event are in the fixed part.
+        let detailed = self.affect_which & !self.clear & !self.select_all;
+        if detailed & !(EVENT_TYPE_NEW_KEYBOARD_NOTIFY | EVENT_TYPE_MAP_NOTIFY |
+                        EVENT_TYPE_STATE_NOTIFY) != 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      "events selected by details must be MapNotify, \
+                                       StateNotify or NewKeyboardNotify"));
+        }
+
+        let mut length = 4;
+        if detailed & EVENT_TYPE_NEW_KEYBOARD_NOTIFY != 0 {
+            length += 1;
+        }
+        if detailed & EVENT_TYPE_STATE_NOTIFY != 0 {
+            length += 1;
+        }
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XKB_OPCODE)?;
+        a.write_u16::<NativeEndian>(length)?;
+        a.write_u16::<NativeEndian>(self.device_spec)?;
+        a.write_u16::<NativeEndian>(self.affect_which)?;
+        a.write_u16::<NativeEndian>(self.clear)?;
+        a.write_u16::<NativeEndian>(self.select_all)?;
+        a.write_u16::<NativeEndian>(self.affect_map)?;
+        a.write_u16::<NativeEndian>(self.map)?;
+        if detailed & EVENT_TYPE_NEW_KEYBOARD_NOTIFY != 0 {
+            a.write_u16::<NativeEndian>(self.affect_new_keyboard)?;
+            a.write_u16::<NativeEndian>(self.new_keyboard_details)?;
+        }
+        if detailed & EVENT_TYPE_STATE_NOTIFY != 0 {
+            a.write_u16::<NativeEndian>(self.affect_state)?;
+            a.write_u16::<NativeEndian>(self.state_details)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,81 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::xkb::EXTENSION_NAME;
+use ::Client;
+
+const XKB_OPCODE: u8 = 0;
+
+/// Enables XKB for this client, it must be sent before any other XKB
+/// request.
+#[derive(Debug, Clone, Copy)]
+pub struct XkbUseExtension {
+    pub wanted_major: u16,
+    pub wanted_minor: u16,
+}
+
+impl ExtensionRequest for XkbUseExtension {
+    type Reply = XkbUseExtensionReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XKB_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u16::<NativeEndian>(self.wanted_major)?;
+        a.write_u16::<NativeEndian>(self.wanted_minor)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = XkbUseExtensionReply {
+                supported: buf[1] != 0,
+                server_major: NativeEndian::read_u16(&buf[8..10]),
+                server_minor: NativeEndian::read_u16(&buf[10..12]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `XkbUseExtension` request.
+#[derive(Debug, Clone, Copy)]
+pub struct XkbUseExtensionReply {
+    /// Whether the server supports the wanted version, XKB isn't enabled
+    /// otherwise.
+    pub supported: bool,
+
+    pub server_major: u16,
+    pub server_minor: u16,
+}
+
+impl VersionRequest for XkbUseExtension {
+    fn latest() -> XkbUseExtension {
+        XkbUseExtension {
+            wanted_major: 1,
+            wanted_minor: 0,
+        }
+    }
+
+    fn version(reply: &XkbUseExtensionReply) -> Version {
+        Version::new(u32::from(reply.server_major), u32::from(reply.server_minor))
+    }
+}
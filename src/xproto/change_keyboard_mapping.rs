@@ -0,0 +1,49 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::Client;
+
+const OPCODE: u8 = 100;
+
+/// Changes the keysyms of the keys from `first_keycode`, a
+/// `MappingNotify` event is sent to all the clients.
+#[derive(Debug, Clone)]
+pub struct ChangeKeyboardMapping {
+    pub first_keycode: u8,
+    pub keysyms_per_keycode: u8,
+
+    /// The keysyms of the keys, `keysyms_per_keycode` for each one.
+    pub keysyms: Vec<u32>,
+}
+
+impl Request for ChangeKeyboardMapping {
+    type Reply = VoidReply;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let count = self.keysyms.len() / self.keysyms_per_keycode.max(1) as usize;
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(count as u8)?;
+        a.write_u16::<NativeEndian>(2 + self.keysyms.len() as u16)?;
+        a.write_u8(self.first_keycode)?;
+        a.write_u8(self.keysyms_per_keycode)?;
+        a.write_u16::<NativeEndian>(0)?;
+        for keysym in &self.keysyms {
+            a.write_u32::<NativeEndian>(*keysym)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,75 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::request_length;
+use ::protocol::void_reply;
+use ::xproto::Atom;
+use ::xproto::Window;
+use ::Client;
+use ::pad;
+
+const OPCODE: u8 = 18;
+
+/// How the new data is combined with the current value of the property.
+#[derive(Debug, Clone, Copy)]
+pub enum PropMode {
+    Replace = 0,
+    Prepend = 1,
+    Append = 2,
+}
+
+#[derive(Debug)]
+pub struct ChangeProperty {
+    pub mode: PropMode,
+
+    /// The window owning the property.
+    pub window: Window,
+
+    /// The property name.
+    pub property: Atom,
+
+    /// The property type, it's not interpreted by the server.
+    pub type_: Atom,
+
+    /// Whether the data is a list of 8, 16 or 32 bit quantities.
+    pub format: u8,
+
+    /// The property data, in native byte order.
+    pub data: Vec<u8>,
+}
+
+impl Request for ChangeProperty {
+    type Reply = VoidReply;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let n = self.data.len();
+        let p = pad(n);
+        let length = request_length(6 + (n + p) / 4)?;
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(self.mode as u8)?;
+        a.write_u16::<NativeEndian>(length)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u32::<NativeEndian>(self.property.0)?;
+        a.write_u32::<NativeEndian>(self.type_.0)?;
+        a.write_u8(self.format)?;
+        a.write_all(&[0; 3])?;
+        a.write_u32::<NativeEndian>((n / (self.format as usize / 8)) as u32)?;
+        a.write_all(&self.data)?;
+        a.write_all(&[0; 3][..p])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,45 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::xproto::Window;
+use ::xproto::WindowAttributes;
+use ::Client;
+
+const OPCODE: u8 = 2;
+
+pub struct ChangeWindowAttributes {
+    /// The window to be changed.
+    pub window: Window,
+
+    /// Only the attributes that were set are changed.
+    pub attrs: WindowAttributes,
+}
+
+impl Request for ChangeWindowAttributes {
+    type Reply = VoidReply;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let (buf, n) = self.attrs.encode()?;
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(0)?;
+        a.write_u16::<NativeEndian>(3 + n)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_all(buf.as_slice())?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,37 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::xproto::Font;
+use ::Client;
+
+const OPCODE: u8 = 46;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CloseFont {
+    pub font: Font,
+}
+
+impl Request for CloseFont {
+    type Reply = VoidReply;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(0)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.font.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
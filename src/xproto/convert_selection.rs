@@ -0,0 +1,55 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::xproto::Atom;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+use ::Client;
+
+const OPCODE: u8 = 24;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ConvertSelection {
+    /// The window that receives the `SelectionNotify` event and where the
+    /// converted data is stored.
+    pub requestor: Window,
+
+    pub selection: Atom,
+
+    /// The type the data should be converted to.
+    pub target: Atom,
+
+    /// The property of `requestor` where the owner stores the data.
+    pub property: Atom,
+
+    pub time: Timestamp,
+}
+
+impl Request for ConvertSelection {
+    type Reply = VoidReply;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(0)?;
+        a.write_u16::<NativeEndian>(6)?;
+        a.write_u32::<NativeEndian>(self.requestor.0)?;
+        a.write_u32::<NativeEndian>(self.selection.0)?;
+        a.write_u32::<NativeEndian>(self.target.0)?;
+        a.write_u32::<NativeEndian>(self.property.0)?;
+        a.write_u32::<NativeEndian>(self.time)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -8,6 +8,7 @@ use ::byteorder::WriteBytesExt;
 
 use ::protocol::Request;
 use ::protocol::VoidReply;
+use ::xproto::Visualid;
 use ::xproto::Window;
 use ::xproto::WindowAttributes;
 use ::Client;
@@ -19,7 +20,7 @@ pub struct CreateWindow {
     pub wid: Window,
 
     /// The window parent.
-    pub parent: u32,
+    pub parent: Window,
 
     /// The class
     pub class: u16,
@@ -28,7 +29,7 @@ pub struct CreateWindow {
     pub depth: u8,
 
     /// Window visual
-    pub visual: u32,
+    pub visual: Visualid,
 
     /// Window x coordinate on parent.
     pub x: u16,
@@ -59,16 +60,16 @@ impl Request for CreateWindow {
         a.write_u8(OPCODE)?;
         a.write_u8(self.depth)?;
         a.write_u16::<NativeEndian>(8 + n)?;
-        a.write_u32::<NativeEndian>(self.wid)?;
-        a.write_u32::<NativeEndian>(self.parent)?;
+        a.write_u32::<NativeEndian>(self.wid.0)?;
+        a.write_u32::<NativeEndian>(self.parent.0)?;
         a.write_u16::<NativeEndian>(self.x)?;
         a.write_u16::<NativeEndian>(self.y)?;
         a.write_u16::<NativeEndian>(self.width)?;
         a.write_u16::<NativeEndian>(self.height)?;
         a.write_u16::<NativeEndian>(self.border_width)?;
         a.write_u16::<NativeEndian>(self.class)?;
-        a.write_u32::<NativeEndian>(self.visual)?;
-        a.write(buf.as_slice())?;
+        a.write_u32::<NativeEndian>(self.visual.0)?;
+        a.write_all(buf.as_slice())?;
 
         Ok(a.into_inner())
     }
@@ -0,0 +1,46 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::Client;
+
+const OPCODE: u8 = 115;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenSaverMode {
+    /// Stops the screen saver if it's on and resets its timer, like user
+    /// input does.
+    Reset = 0,
+
+    /// Starts the screen saver, even if it's disabled.
+    Activate = 1,
+}
+
+/// Starts or stops the screen saver.
+#[derive(Debug, Clone, Copy)]
+pub struct ForceScreenSaver {
+    pub mode: ScreenSaverMode,
+}
+
+impl Request for ForceScreenSaver {
+    type Reply = VoidReply;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(self.mode as u8)?;
+        a.write_u16::<NativeEndian>(1)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
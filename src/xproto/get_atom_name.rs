@@ -0,0 +1,56 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+use ::byteorder::ByteOrder;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::pipeline;
+use ::xproto::Atom;
+use ::Client;
+
+const OPCODE: u8 = 17;
+
+#[derive(Debug, Clone, Copy)]
+pub struct GetAtomName {
+    /// The atom whose name is requested.
+    pub atom: Atom,
+}
+
+impl Request for GetAtomName {
+    type Reply = String;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(0)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.atom.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let name = read_name(&buf)?;
+            Ok((client, name))
+        }))
+    }
+}
+
+/// Returns the names of all of `atoms` with a single round trip, in the
+/// same order.
+pub fn get_atom_names(client: Client, atoms: &[Atom]) -> ReplyFuture<Vec<String>> {
+    let requests: Vec<GetAtomName> = atoms.iter().map(|&atom| GetAtomName { atom }).collect();
+    pipeline(client, requests, read_name)
+}
+
+fn read_name(buf: &[u8]) -> io::Result<String> {
+    let n = NativeEndian::read_u16(&buf[8..10]) as usize;
+    String::from_utf8(buf[32..32 + n].to_vec())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
@@ -0,0 +1,73 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+use ::byteorder::ByteOrder;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::xproto::Drawable;
+use ::xproto::ImageFormat;
+use ::xproto::Visualid;
+use ::Client;
+
+const OPCODE: u8 = 73;
+
+/// Reads the contents of a rectangle of a drawable.
+#[derive(Debug, Clone, Copy)]
+pub struct GetImage {
+    pub format: ImageFormat,
+    pub drawable: Drawable,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+
+    /// Planes to read, `u32::MAX` reads all of them.
+    pub plane_mask: u32,
+}
+
+impl Request for GetImage {
+    type Reply = GetImageReply;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(self.format)?;
+        a.write_u16::<NativeEndian>(5)?;
+        a.write_u32::<NativeEndian>(self.drawable.0)?;
+        a.write_i16::<NativeEndian>(self.x)?;
+        a.write_i16::<NativeEndian>(self.y)?;
+        a.write_u16::<NativeEndian>(self.width)?;
+        a.write_u16::<NativeEndian>(self.height)?;
+        a.write_u32::<NativeEndian>(self.plane_mask)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, mut buf)| {
+            let reply = GetImageReply {
+                depth: buf[1],
+                visual: Visualid(NativeEndian::read_u32(&buf[8..12])),
+                data: buf.split_off(32),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `GetImage` request.
+#[derive(Debug, Clone)]
+pub struct GetImageReply {
+    pub depth: u8,
+
+    /// The visual of the window, `Visualid::NONE` for pixmaps.
+    pub visual: Visualid,
+
+    pub data: Vec<u8>,
+}
@@ -0,0 +1,57 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+use ::byteorder::ByteOrder;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::Client;
+
+const OPCODE: u8 = 101;
+
+/// Returns the keysyms of `count` keys from `first_keycode`.
+#[derive(Debug, Clone, Copy)]
+pub struct GetKeyboardMapping {
+    pub first_keycode: u8,
+    pub count: u8,
+}
+
+impl Request for GetKeyboardMapping {
+    type Reply = GetKeyboardMappingReply;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(0)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u8(self.first_keycode)?;
+        a.write_u8(self.count)?;
+        a.write_u16::<NativeEndian>(0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = GetKeyboardMappingReply {
+                keysyms_per_keycode: buf[1],
+                keysyms: buf[32..].chunks(4).map(NativeEndian::read_u32).collect(),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `GetKeyboardMapping` request.
+#[derive(Debug, Clone)]
+pub struct GetKeyboardMappingReply {
+    pub keysyms_per_keycode: u8,
+
+    /// The keysyms of the keys, `keysyms_per_keycode` for each one.
+    pub keysyms: Vec<u32>,
+}
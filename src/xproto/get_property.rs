@@ -0,0 +1,93 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+use ::byteorder::ByteOrder;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::xproto::Atom;
+use ::xproto::Window;
+use ::Client;
+
+const OPCODE: u8 = 20;
+
+#[derive(Debug, Clone, Copy)]
+pub struct GetProperty {
+    /// Delete the property after reading it, only done if all of the data
+    /// was returned.
+    pub delete: bool,
+
+    pub window: Window,
+    pub property: Atom,
+
+    /// The expected type, `ATOM_ANY` (zero) matches any type.
+    pub type_: Atom,
+
+    /// Offset, in 32-bit units, of the data to return.
+    pub long_offset: u32,
+
+    /// Maximum length, in 32-bit units, of the data to return.
+    pub long_length: u32,
+}
+
+impl Request for GetProperty {
+    type Reply = GetPropertyReply;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(self.delete as u8)?;
+        a.write_u16::<NativeEndian>(6)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u32::<NativeEndian>(self.property.0)?;
+        a.write_u32::<NativeEndian>(self.type_.0)?;
+        a.write_u32::<NativeEndian>(self.long_offset)?;
+        a.write_u32::<NativeEndian>(self.long_length)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let format = buf[1];
+            let len = NativeEndian::read_u32(&buf[16..20]) as usize * (format as usize / 8);
+
+            let reply = GetPropertyReply {
+                format,
+                type_: Atom(NativeEndian::read_u32(&buf[8..12])),
+                bytes_after: NativeEndian::read_u32(&buf[12..16]),
+                value: buf[32..32 + len].to_vec(),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `GetProperty` request.
+#[derive(Debug, Clone)]
+pub struct GetPropertyReply {
+    /// The format of the data: 8, 16 or 32. Zero if the property doesn't
+    /// exist.
+    pub format: u8,
+
+    /// The actual type of the property, `ATOM_NONE` if it doesn't exist.
+    pub type_: Atom,
+
+    /// The number of bytes remaining after the returned data.
+    pub bytes_after: u32,
+
+    /// The property data, in native byte order.
+    pub value: Vec<u8>,
+}
+
+impl GetPropertyReply {
+    /// Interprets the value as a list of 32 bit quantities.
+    pub fn value32(&self) -> Vec<u32> {
+        self.value.chunks(4).filter(|c| c.len() == 4).map(NativeEndian::read_u32).collect()
+    }
+}
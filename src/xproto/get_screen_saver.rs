@@ -0,0 +1,58 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+use ::byteorder::ByteOrder;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::Client;
+
+const OPCODE: u8 = 108;
+
+/// Returns the parameters of the screen saver.
+#[derive(Debug, Clone, Copy)]
+pub struct GetScreenSaver;
+
+impl Request for GetScreenSaver {
+    type Reply = GetScreenSaverReply;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(0)?;
+        a.write_u16::<NativeEndian>(1)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = GetScreenSaverReply {
+                timeout: NativeEndian::read_u16(&buf[8..10]),
+                interval: NativeEndian::read_u16(&buf[10..12]),
+                prefer_blanking: buf[12] != 0,
+                allow_exposures: buf[13] != 0,
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `GetScreenSaver` request.
+#[derive(Debug, Clone, Copy)]
+pub struct GetScreenSaverReply {
+    /// Seconds of inactivity before the screen saver starts, zero if it's
+    /// disabled.
+    pub timeout: u16,
+
+    /// Seconds between changes of the pattern, zero if they are disabled.
+    pub interval: u16,
+
+    pub prefer_blanking: bool,
+    pub allow_exposures: bool,
+}
@@ -0,0 +1,41 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+use ::byteorder::ByteOrder;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::xproto::Atom;
+use ::xproto::Window;
+use ::Client;
+
+const OPCODE: u8 = 23;
+
+#[derive(Debug, Clone, Copy)]
+pub struct GetSelectionOwner {
+    pub selection: Atom,
+}
+
+impl Request for GetSelectionOwner {
+    /// The owner window, zero if the selection has no owner.
+    type Reply = Window;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(0)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.selection.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client)
+            .map(|(client, buf)| (client, Window(NativeEndian::read_u32(&buf[8..12])))))
+    }
+}
@@ -0,0 +1,67 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+use ::byteorder::ByteOrder;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::pipeline;
+use ::xproto::Atom;
+use ::Client;
+use ::pad;
+
+const OPCODE: u8 = 16;
+
+#[derive(Debug)]
+pub struct InternAtom {
+    /// If the atom doesn't exist and this is true `ATOM_NONE` is returned
+    /// instead of creating it.
+    pub only_if_exists: bool,
+
+    /// The atom name.
+    pub name: Vec<u8>,
+}
+
+impl Request for InternAtom {
+    type Reply = Atom;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let n = self.name.len();
+        let p = pad(n);
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(self.only_if_exists as u8)?;
+        a.write_u16::<NativeEndian>((2 + (n + p) / 4) as u16)?;
+        a.write_u16::<NativeEndian>(n as u16)?;
+        a.write_u16::<NativeEndian>(0)?;
+        a.write_all(&self.name)?;
+        a.write_all(&[0; 3][..p])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client)
+            .map(|(client, buf)| (client, Atom(NativeEndian::read_u32(&buf[8..12])))))
+    }
+}
+
+/// Interns all of `names` with a single round trip, the atoms are returned
+/// in the same order.
+pub fn intern_atoms(client: Client, names: &[&[u8]]) -> ReplyFuture<Vec<Atom>> {
+    let requests: Vec<InternAtom> = names.iter()
+        .map(|name| {
+            InternAtom {
+                only_if_exists: false,
+                name: name.to_vec(),
+            }
+        })
+        .collect();
+    pipeline(client, requests, |buf| Ok(Atom(NativeEndian::read_u32(&buf[8..12]))))
+}
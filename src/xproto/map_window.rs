@@ -27,7 +27,7 @@ impl Request for MapWindow {
         a.write_u8(OPCODE)?;
         a.write_u8(0)?;
         a.write_u16::<NativeEndian>(request_size)?;
-        a.write_u32::<NativeEndian>(self.wid)?;
+        a.write_u32::<NativeEndian>(self.wid.0)?;
 
         Ok(a.into_inner())
     }
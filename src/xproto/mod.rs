@@ -1,8 +1,11 @@
 //! This module contains all X11 standard requests.
 
 use std::io;
+use std::io::Read;
+use std::io::Write;
 
 use byteorder::NativeEndian;
+use byteorder::ReadBytesExt;
 use byteorder::WriteBytesExt;
 
 macro_rules! declare_requests {
@@ -16,7 +19,30 @@ macro_rules! declare_requests {
 
 declare_requests! {
     create_window,
+    change_window_attributes,
+    destroy_window,
     map_window,
+    intern_atom,
+    get_atom_name,
+    change_property,
+    delete_property,
+    get_property,
+    set_selection_owner,
+    get_selection_owner,
+    convert_selection,
+    send_event,
+    close_font,
+    free_pixmap,
+    free_gc,
+    put_image,
+    get_image,
+    free_colormap,
+    free_cursor,
+    change_keyboard_mapping,
+    get_keyboard_mapping,
+    set_screen_saver,
+    get_screen_saver,
+    force_screen_saver,
     query_extension,
     list_extensions
 }
@@ -25,17 +51,175 @@ declare_requests! {
 
 pub type Xid = u32;
 
-/// A window identifier.
-pub type Window = Xid;
+declare_ids! {
+    /// A window identifier.
+    Window,
 
-/// A Pixmap identifier.
-pub type Pixmap = u32;
+    /// A Pixmap identifier.
+    Pixmap,
 
-/// A Colormap identifier.
-pub type Colormap = u32;
+    /// A graphics context identifier.
+    Gcontext,
 
-/// A Cursor identifier.
-pub type Cursor = u32;
+    /// A font identifier.
+    Font,
+
+    /// A Colormap identifier.
+    Colormap,
+
+    /// A Cursor identifier.
+    Cursor,
+
+    /// An atom identifier.
+    Atom,
+
+    /// A visual identifier.
+    Visualid,
+
+    /// Either a `Window` or a `Pixmap`.
+    Drawable,
+
+    /// Either a `Font` or a `Gcontext`.
+    Fontable
+}
+
+macro_rules! declare_unions {
+    ($($union:ident: $($member:ident),+;)+) => {
+        $($(
+            impl From<$member> for $union {
+                fn from(member: $member) -> $union {
+                    $union(member.0)
+                }
+            }
+        )+)+
+    }
+}
+
+declare_unions! {
+    Drawable: Window, Pixmap;
+    Fontable: Font, Gcontext;
+}
+
+impl Visualid {
+    /// Used by `CreateWindow` to take the visual of the parent.
+    pub const COPY_FROM_PARENT: Visualid = Visualid(0);
+}
+
+/// A server time in milliseconds.
+pub type Timestamp = u32;
+
+/// Used instead of a timestamp to mean the current server time.
+pub const CURRENT_TIME: Timestamp = 0;
+
+/// Window classes used by `CreateWindow`.
+pub const WINDOW_CLASS_COPY_FROM_PARENT: u16 = 0;
+pub const WINDOW_CLASS_INPUT_OUTPUT: u16 = 1;
+pub const WINDOW_CLASS_INPUT_ONLY: u16 = 2;
+
+/// Formats of the images of `PutImage` and `GetImage`.
+pub type ImageFormat = u8;
+pub const IMAGE_FORMAT_XY_BITMAP: ImageFormat = 0;
+pub const IMAGE_FORMAT_XY_PIXMAP: ImageFormat = 1;
+pub const IMAGE_FORMAT_Z_PIXMAP: ImageFormat = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rectangle {
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Rectangle {
+    pub(crate) fn read<A: Read>(a: &mut A) -> io::Result<Rectangle> {
+        Ok(Rectangle {
+            x: a.read_i16::<NativeEndian>()?,
+            y: a.read_i16::<NativeEndian>()?,
+            width: a.read_u16::<NativeEndian>()?,
+            height: a.read_u16::<NativeEndian>()?,
+        })
+    }
+
+    pub(crate) fn write<A: Write>(&self, a: &mut A) -> io::Result<()> {
+        a.write_i16::<NativeEndian>(self.x)?;
+        a.write_i16::<NativeEndian>(self.y)?;
+        a.write_u16::<NativeEndian>(self.width)?;
+        a.write_u16::<NativeEndian>(self.height)
+    }
+}
+
+/// Predefined atoms, `ATOM_NONE` also matches any property type on
+/// `GetProperty`.
+pub const ATOM_NONE: Atom = Atom(0);
+pub const ATOM_ANY: Atom = Atom(0);
+pub const ATOM_PRIMARY: Atom = Atom(1);
+pub const ATOM_SECONDARY: Atom = Atom(2);
+pub const ATOM_ARC: Atom = Atom(3);
+pub const ATOM_ATOM: Atom = Atom(4);
+pub const ATOM_BITMAP: Atom = Atom(5);
+pub const ATOM_CARDINAL: Atom = Atom(6);
+pub const ATOM_COLORMAP: Atom = Atom(7);
+pub const ATOM_CURSOR: Atom = Atom(8);
+pub const ATOM_CUT_BUFFER0: Atom = Atom(9);
+pub const ATOM_CUT_BUFFER1: Atom = Atom(10);
+pub const ATOM_CUT_BUFFER2: Atom = Atom(11);
+pub const ATOM_CUT_BUFFER3: Atom = Atom(12);
+pub const ATOM_CUT_BUFFER4: Atom = Atom(13);
+pub const ATOM_CUT_BUFFER5: Atom = Atom(14);
+pub const ATOM_CUT_BUFFER6: Atom = Atom(15);
+pub const ATOM_CUT_BUFFER7: Atom = Atom(16);
+pub const ATOM_DRAWABLE: Atom = Atom(17);
+pub const ATOM_FONT: Atom = Atom(18);
+pub const ATOM_INTEGER: Atom = Atom(19);
+pub const ATOM_PIXMAP: Atom = Atom(20);
+pub const ATOM_POINT: Atom = Atom(21);
+pub const ATOM_RECTANGLE: Atom = Atom(22);
+pub const ATOM_RESOURCE_MANAGER: Atom = Atom(23);
+pub const ATOM_RGB_COLOR_MAP: Atom = Atom(24);
+pub const ATOM_RGB_BEST_MAP: Atom = Atom(25);
+pub const ATOM_RGB_BLUE_MAP: Atom = Atom(26);
+pub const ATOM_RGB_DEFAULT_MAP: Atom = Atom(27);
+pub const ATOM_RGB_GRAY_MAP: Atom = Atom(28);
+pub const ATOM_RGB_GREEN_MAP: Atom = Atom(29);
+pub const ATOM_RGB_RED_MAP: Atom = Atom(30);
+pub const ATOM_STRING: Atom = Atom(31);
+pub const ATOM_VISUALID: Atom = Atom(32);
+pub const ATOM_WINDOW: Atom = Atom(33);
+pub const ATOM_WM_COMMAND: Atom = Atom(34);
+pub const ATOM_WM_HINTS: Atom = Atom(35);
+pub const ATOM_WM_CLIENT_MACHINE: Atom = Atom(36);
+pub const ATOM_WM_ICON_NAME: Atom = Atom(37);
+pub const ATOM_WM_ICON_SIZE: Atom = Atom(38);
+pub const ATOM_WM_NAME: Atom = Atom(39);
+pub const ATOM_WM_NORMAL_HINTS: Atom = Atom(40);
+pub const ATOM_WM_SIZE_HINTS: Atom = Atom(41);
+pub const ATOM_WM_ZOOM_HINTS: Atom = Atom(42);
+pub const ATOM_MIN_SPACE: Atom = Atom(43);
+pub const ATOM_NORM_SPACE: Atom = Atom(44);
+pub const ATOM_MAX_SPACE: Atom = Atom(45);
+pub const ATOM_END_SPACE: Atom = Atom(46);
+pub const ATOM_SUPERSCRIPT_X: Atom = Atom(47);
+pub const ATOM_SUPERSCRIPT_Y: Atom = Atom(48);
+pub const ATOM_SUBSCRIPT_X: Atom = Atom(49);
+pub const ATOM_SUBSCRIPT_Y: Atom = Atom(50);
+pub const ATOM_UNDERLINE_POSITION: Atom = Atom(51);
+pub const ATOM_UNDERLINE_THICKNESS: Atom = Atom(52);
+pub const ATOM_STRIKEOUT_ASCENT: Atom = Atom(53);
+pub const ATOM_STRIKEOUT_DESCENT: Atom = Atom(54);
+pub const ATOM_ITALIC_ANGLE: Atom = Atom(55);
+pub const ATOM_X_HEIGHT: Atom = Atom(56);
+pub const ATOM_QUAD_WIDTH: Atom = Atom(57);
+pub const ATOM_WEIGHT: Atom = Atom(58);
+pub const ATOM_POINT_SIZE: Atom = Atom(59);
+pub const ATOM_RESOLUTION: Atom = Atom(60);
+pub const ATOM_COPYRIGHT: Atom = Atom(61);
+pub const ATOM_NOTICE: Atom = Atom(62);
+pub const ATOM_FONT_NAME: Atom = Atom(63);
+pub const ATOM_FAMILY_NAME: Atom = Atom(64);
+pub const ATOM_FULL_NAME: Atom = Atom(65);
+pub const ATOM_CAP_HEIGHT: Atom = Atom(66);
+pub const ATOM_WM_CLASS: Atom = Atom(67);
+pub const ATOM_WM_TRANSIENT_FOR: Atom = Atom(68);
 
 /// Bit gravity.
 #[derive(Debug, Clone, Copy)]
@@ -54,7 +238,7 @@ pub enum BitGravity {
 }
 
 /// Window gravity.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WinGravity {
     Unmap = 0,
     NorthWest = 1,
@@ -144,9 +328,9 @@ pub struct WindowAttributes {
 impl WindowAttributes {
     pub fn new() -> WindowAttributes {
         WindowAttributes {
-            background_pixmap: 0,
+            background_pixmap: Pixmap::NONE,
             background_pixel: 0,
-            border_pixmap: 0,
+            border_pixmap: Pixmap::NONE,
             border_pixel: 0,
             bit_gravity: BitGravity::Forget,
             win_gravity: WinGravity::Unmap,
@@ -157,15 +341,15 @@ impl WindowAttributes {
             save_under: false,
             event_mask: 0,
             do_not_propagate_mask: 0,
-            colormap: 0,
-            cursor: 0,
+            colormap: Colormap::NONE,
+            cursor: Cursor::NONE,
 
             value_mask: 0,
         }
     }
 
     pub fn background_pixmap(&mut self, value: Pixmap) -> &mut Self {
-        self.background_pixel = value;
+        self.background_pixmap = value;
         self.value_mask |= 0x00000001;
         self
     }
@@ -243,7 +427,7 @@ impl WindowAttributes {
     }
 
     pub fn colormap(&mut self, value: Colormap) -> &mut Self {
-        self.do_not_propagate_mask = value;
+        self.colormap = value;
         self.value_mask |= 0x00002000;
         self
     }
@@ -258,14 +442,14 @@ impl WindowAttributes {
         self
     }
 
-    fn encode(&self) -> io::Result<(Vec<u8>, u16)> {
+    pub(crate) fn encode(&self) -> io::Result<(Vec<u8>, u16)> {
         let mut count = 0u16;
         let mut a = io::Cursor::new(vec![]);
 
         a.write_u32::<NativeEndian>(self.value_mask)?;
 
         if (self.value_mask & 0x00000001) == 0x00000001 {
-            a.write_u32::<NativeEndian>(self.background_pixmap)?;
+            a.write_u32::<NativeEndian>(self.background_pixmap.0)?;
             count += 1;
         }
 
@@ -275,7 +459,7 @@ impl WindowAttributes {
         }
 
         if (self.value_mask & 0x00000004) == 0x00000004 {
-            a.write_u32::<NativeEndian>(self.border_pixmap)?;
+            a.write_u32::<NativeEndian>(self.border_pixmap.0)?;
             count += 1;
         }
 
@@ -338,12 +522,12 @@ impl WindowAttributes {
         }
 
         if (self.value_mask & 0x00002000) == 0x00002000 {
-            a.write_u32::<NativeEndian>(self.colormap)?;
+            a.write_u32::<NativeEndian>(self.colormap.0)?;
             count += 1;
         }
 
         if (self.value_mask & 0x00004000) == 0x00004000 {
-            a.write_u32::<NativeEndian>(self.cursor)?;
+            a.write_u32::<NativeEndian>(self.cursor.0)?;
             count += 1;
         }
 
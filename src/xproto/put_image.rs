@@ -0,0 +1,69 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::xproto::Drawable;
+use ::xproto::Gcontext;
+use ::xproto::ImageFormat;
+use ::Client;
+use ::pad;
+
+const OPCODE: u8 = 72;
+
+/// Draws an image, `data` must fit in the maximum request length.
+#[derive(Debug, Clone)]
+pub struct PutImage {
+    pub format: ImageFormat,
+    pub drawable: Drawable,
+    pub gc: Gcontext,
+    pub width: u16,
+    pub height: u16,
+    pub dst_x: i16,
+    pub dst_y: i16,
+
+    /// Bits to skip at the start of each row, only for the bitmap formats.
+    pub left_pad: u8,
+
+    pub depth: u8,
+
+    /// The rows of the image, padded to the scanline pad of the pixmap
+    /// format of `depth`.
+    pub data: Vec<u8>,
+}
+
+impl Request for PutImage {
+    type Reply = VoidReply;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let p = pad(self.data.len());
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(self.format)?;
+        a.write_u16::<NativeEndian>((6 + (self.data.len() + p) / 4) as u16)?;
+        a.write_u32::<NativeEndian>(self.drawable.0)?;
+        a.write_u32::<NativeEndian>(self.gc.0)?;
+        a.write_u16::<NativeEndian>(self.width)?;
+        a.write_u16::<NativeEndian>(self.height)?;
+        a.write_i16::<NativeEndian>(self.dst_x)?;
+        a.write_i16::<NativeEndian>(self.dst_y)?;
+        a.write_u8(self.left_pad)?;
+        a.write_u8(self.depth)?;
+        a.write_u16::<NativeEndian>(0)?;
+        a.write_all(&self.data)?;
+        a.write_all(&[0; 3][..p])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
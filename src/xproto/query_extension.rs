@@ -4,10 +4,11 @@ use ::std::io::Write;
 use ::futures::Future;
 use ::byteorder::NativeEndian;
 use ::byteorder::WriteBytesExt;
-use ::byteorder::ReadBytesExt;
-use ::tokio_io;
 
 use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::pipeline;
 use ::Client;
 use ::pad;
 
@@ -36,7 +37,7 @@ impl Request for QueryExtension {
         a.write_u16::<NativeEndian>(n as u16)?;
         a.write_u16::<NativeEndian>(0)?;
 
-        a.write(self.name.as_slice())?;
+        a.write_all(self.name.as_slice())?;
 
         for _ in 0..p {
             a.write_u8(0)?;
@@ -45,33 +46,21 @@ impl Request for QueryExtension {
         Ok(a.into_inner())
     }
 
-    fn decode(client: Client) -> Box<Future<Item = (Client, Self::Reply), Error = io::Error>> {
-        let buf: [u8; 32] = [0u8; 32];
-        tokio_io::io::read_exact(client, buf)
-            .and_then(|(client, buf)| {
-                let mut a = io::Cursor::new(buf);
-
-                a.read_u8()?;
-                a.read_u8()?;
-                a.read_u16::<NativeEndian>()?;
-                a.read_u32::<NativeEndian>()?;
-                let present = a.read_u8()? == 1;
-                let major_opcode = a.read_u8()?;
-                let first_event = a.read_u8()?;
-                let first_error = a.read_u8()?;
-
-                Ok((client,
-                    QueryExtensionReply {
-                    present: present,
-                    major_opcode: major_opcode,
-                    first_event: first_event,
-                    first_error: first_error,
-                }))
-            })
-            .boxed()
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| (client, QueryExtensionReply::read(&buf))))
     }
 }
 
+/// Queries all of `names` with a single round trip, the replies are
+/// returned in the same order.
+pub fn query_extensions(client: Client, names: &[&[u8]]) -> ReplyFuture<Vec<QueryExtensionReply>> {
+    let requests: Vec<QueryExtension> = names.iter()
+        .map(|name| QueryExtension { name: name.to_vec() })
+        .collect();
+
+    pipeline(client, requests, |buf| Ok(QueryExtensionReply::read(buf)))
+}
+
 /// Reply of `QueryExtension` request.
 #[derive(Debug, Clone, Copy)]
 pub struct QueryExtensionReply {
@@ -90,3 +79,15 @@ pub struct QueryExtensionReply {
     /// code is returned. Otherwise, zero is returned.
     pub first_error: u8,
 }
+
+impl QueryExtensionReply {
+    /// Decodes a reply packet.
+    pub fn read(buf: &[u8]) -> QueryExtensionReply {
+        QueryExtensionReply {
+            present: buf[8] == 1,
+            major_opcode: buf[9],
+            first_event: buf[10],
+            first_error: buf[11],
+        }
+    }
+}
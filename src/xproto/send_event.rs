@@ -0,0 +1,58 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::xproto::Event;
+use ::xproto::Window;
+use ::Client;
+
+const OPCODE: u8 = 25;
+
+#[derive(Debug)]
+pub struct SendEvent {
+    /// Propagate the event to the ancestors of `destination` if no client
+    /// selected `event_mask` on it.
+    pub propagate: bool,
+
+    /// The destination window, `POINTER_WINDOW` or `INPUT_FOCUS` can also
+    /// be used.
+    pub destination: Window,
+
+    /// Clients selecting any of these events on `destination` receive the
+    /// event, if empty the event is sent to the creator of `destination`.
+    pub event_mask: Event,
+
+    /// The encoded event, 32 bytes long.
+    pub event: Vec<u8>,
+}
+
+impl Request for SendEvent {
+    type Reply = VoidReply;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        if self.event.len() != 32 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "events must be 32 bytes long"));
+        }
+
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(self.propagate as u8)?;
+        a.write_u16::<NativeEndian>(11)?;
+        a.write_u32::<NativeEndian>(self.destination.0)?;
+        a.write_u32::<NativeEndian>(self.event_mask)?;
+        a.write_all(&self.event)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
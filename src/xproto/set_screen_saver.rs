@@ -0,0 +1,69 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::Client;
+
+const OPCODE: u8 = 107;
+
+/// Whether the screen saver should blank the screen instead of drawing a
+/// pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Blanking {
+    NotPreferred = 0,
+    Preferred = 1,
+    Default = 2,
+}
+
+/// Whether the screen saver may draw patterns that need `Expose` events
+/// when it stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exposures {
+    NotAllowed = 0,
+    Allowed = 1,
+    Default = 2,
+}
+
+/// Sets the parameters of the screen saver.
+#[derive(Debug, Clone, Copy)]
+pub struct SetScreenSaver {
+    /// Seconds of inactivity before the screen saver starts, zero to
+    /// disable it and -1 for the default.
+    pub timeout: i16,
+
+    /// Seconds between changes of the pattern, zero to disable them and -1
+    /// for the default.
+    pub interval: i16,
+
+    pub prefer_blanking: Blanking,
+    pub allow_exposures: Exposures,
+}
+
+impl Request for SetScreenSaver {
+    type Reply = VoidReply;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(0)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_i16::<NativeEndian>(self.timeout)?;
+        a.write_i16::<NativeEndian>(self.interval)?;
+        a.write_u8(self.prefer_blanking as u8)?;
+        a.write_u8(self.allow_exposures as u8)?;
+        a.write_all(&[0; 2])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,47 @@
+use ::std::io;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::Request;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::xproto::Atom;
+use ::xproto::Timestamp;
+use ::xproto::Window;
+use ::Client;
+
+const OPCODE: u8 = 22;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SetSelectionOwner {
+    /// The new owner, zero (`None`) makes the selection have no owner.
+    pub owner: Window,
+
+    pub selection: Atom,
+
+    /// The time of the event that triggered the change, or `CURRENT_TIME`.
+    pub time: Timestamp,
+}
+
+impl Request for SetSelectionOwner {
+    type Reply = VoidReply;
+
+    fn encode(&mut self) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(OPCODE)?;
+        a.write_u8(0)?;
+        a.write_u16::<NativeEndian>(4)?;
+        a.write_u32::<NativeEndian>(self.owner.0)?;
+        a.write_u32::<NativeEndian>(self.selection.0)?;
+        a.write_u32::<NativeEndian>(self.time)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
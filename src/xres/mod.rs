@@ -0,0 +1,232 @@
+//! X-Resource extension requests, the resources allocated by each client
+//! of the server.
+
+use ::std::io;
+use ::std::io::Read;
+use ::std::io::Write;
+
+use ::futures;
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ReplyFuture;
+use ::xproto;
+use ::xproto::Atom;
+use ::xproto::Window;
+use ::Client;
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    xres_query_version,
+    xres_query_clients,
+    xres_query_client_resources,
+    xres_query_client_pixmap_bytes,
+    xres_query_client_ids,
+    xres_query_resource_bytes
+}
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"X-Resource";
+
+/// A client connected to the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceClient {
+    /// The first id allocated by the client.
+    pub resource_base: u32,
+
+    /// The bits of the ids the client can allocate.
+    pub resource_mask: u32,
+}
+
+impl ResourceClient {
+    /// Whether `xid` was allocated by the client.
+    pub fn owns(&self, xid: u32) -> bool {
+        xid & !self.resource_mask == self.resource_base
+    }
+}
+
+/// The number of resources of a type allocated by a client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceType {
+    /// The type, an atom such as `WINDOW` or `PIXMAP`.
+    pub resource_type: Atom,
+
+    pub count: u32,
+}
+
+/// `ResourceType` with the name of its atom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedResourceType {
+    pub name: String,
+    pub resource_type: Atom,
+    pub count: u32,
+}
+
+/// The ids asked for with `XResQueryClientIds`.
+pub type ClientIdMask = u32;
+
+/// The resource base of the client.
+pub const CLIENT_ID_MASK_CLIENT_XID: ClientIdMask = 1;
+
+/// The process id of a local client.
+pub const CLIENT_ID_MASK_LOCAL_CLIENT_PID: ClientIdMask = 2;
+
+/// Selects the ids of a client, `client` is any id it allocated or zero
+/// for all clients, `mask` zero for all ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIdSpec {
+    pub client: u32,
+    pub mask: ClientIdMask,
+}
+
+impl ClientIdSpec {
+    fn read<A: Read>(a: &mut A) -> io::Result<ClientIdSpec> {
+        Ok(ClientIdSpec {
+            client: a.read_u32::<NativeEndian>()?,
+            mask: a.read_u32::<NativeEndian>()?,
+        })
+    }
+}
+
+/// An id of a client, the kind of id is the single bit of `spec.mask`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientIdValue {
+    pub spec: ClientIdSpec,
+    pub value: Vec<u32>,
+}
+
+impl ClientIdValue {
+    /// The process id, if the value is one.
+    pub fn pid(&self) -> Option<u32> {
+        if self.spec.mask & CLIENT_ID_MASK_LOCAL_CLIENT_PID == 0 {
+            return None;
+        }
+
+        self.value.first().cloned()
+    }
+}
+
+/// A resource, `resource_type` zero matches any type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceIdSpec {
+    pub resource: u32,
+    pub resource_type: Atom,
+}
+
+impl ResourceIdSpec {
+    fn read<A: Read>(a: &mut A) -> io::Result<ResourceIdSpec> {
+        Ok(ResourceIdSpec {
+            resource: a.read_u32::<NativeEndian>()?,
+            resource_type: Atom(a.read_u32::<NativeEndian>()?),
+        })
+    }
+
+    fn write<A: Write>(&self, a: &mut A) -> io::Result<()> {
+        a.write_u32::<NativeEndian>(self.resource)?;
+        a.write_u32::<NativeEndian>(self.resource_type.0)
+    }
+}
+
+/// The memory used by a resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceSizeSpec {
+    pub spec: ResourceIdSpec,
+
+    /// Bytes used, divided by the references to the resource.
+    pub bytes: u32,
+
+    pub ref_count: u32,
+    pub use_count: u32,
+}
+
+impl ResourceSizeSpec {
+    fn read<A: Read>(a: &mut A) -> io::Result<ResourceSizeSpec> {
+        Ok(ResourceSizeSpec {
+            spec: ResourceIdSpec::read(a)?,
+            bytes: a.read_u32::<NativeEndian>()?,
+            ref_count: a.read_u32::<NativeEndian>()?,
+            use_count: a.read_u32::<NativeEndian>()?,
+        })
+    }
+}
+
+/// The memory used by a resource and by the resources it references, such
+/// as the pixmap of a picture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceSizeValue {
+    pub size: ResourceSizeSpec,
+    pub cross_references: Vec<ResourceSizeSpec>,
+}
+
+/// Returns the resources of the client owning `xid` with the names of
+/// their types.
+pub fn client_resources(client: Client, xid: u32) -> ReplyFuture<Vec<NamedResourceType>> {
+    Box::new(client.perform_ex(XResQueryClientResources { xid })
+        .and_then(|(client, types)| {
+            let atoms: Vec<Atom> = types.iter().map(|t| t.resource_type).collect();
+            xproto::get_atom_names(client, &atoms).map(move |(client, names)| {
+                let types = types.into_iter()
+                    .zip(names)
+                    .map(|(t, name)| {
+                        NamedResourceType {
+                            name,
+                            resource_type: t.resource_type,
+                            count: t.count,
+                        }
+                    })
+                    .collect();
+                (client, types)
+            })
+        }))
+}
+
+/// Returns the process id of the client owning `window`, `None` if it's
+/// not a local client.
+pub fn window_pid(client: Client, window: Window) -> ReplyFuture<Option<u32>> {
+    let specs = vec![ClientIdSpec {
+                         client: window.0,
+                         mask: CLIENT_ID_MASK_LOCAL_CLIENT_PID,
+                     }];
+
+    Box::new(client.perform_ex(XResQueryClientIds { specs })
+        .map(|(client, ids)| (client, ids.iter().filter_map(ClientIdValue::pid).next())))
+}
+
+/// Returns the process ids of all the local clients, `None` for the
+/// others, in the order of `clients`.
+pub fn client_pids(client: Client, clients: &[ResourceClient]) -> ReplyFuture<Vec<Option<u32>>> {
+    if clients.is_empty() {
+        return Box::new(futures::finished((client, Vec::new())));
+    }
+
+    let specs = clients.iter()
+        .map(|c| {
+            ClientIdSpec {
+                client: c.resource_base,
+                mask: CLIENT_ID_MASK_LOCAL_CLIENT_PID,
+            }
+        })
+        .collect();
+    let clients = clients.to_vec();
+
+    Box::new(client.perform_ex(XResQueryClientIds { specs }).map(move |(client, ids)| {
+        let pids = clients.iter()
+            .map(|c| {
+                ids.iter()
+                    .find(|id| c.owns(id.spec.client))
+                    .and_then(ClientIdValue::pid)
+            })
+            .collect();
+        (client, pids)
+    }))
+}
@@ -0,0 +1,70 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xres::EXTENSION_NAME;
+use ::xres::ClientIdSpec;
+use ::xres::ClientIdValue;
+use ::Client;
+
+const XRES_OPCODE: u8 = 4;
+
+/// Returns the ids of the clients matching `specs`, such as the process
+/// id of the client owning a window.
+#[derive(Debug, Clone)]
+pub struct XResQueryClientIds {
+    pub specs: Vec<ClientIdSpec>,
+}
+
+impl ExtensionRequest for XResQueryClientIds {
+    type Reply = Vec<ClientIdValue>;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XRES_OPCODE)?;
+        a.write_u16::<NativeEndian>(2 + 2 * self.specs.len() as u16)?;
+        a.write_u32::<NativeEndian>(self.specs.len() as u32)?;
+        for spec in &self.specs {
+            a.write_u32::<NativeEndian>(spec.client)?;
+            a.write_u32::<NativeEndian>(spec.mask)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let number = NativeEndian::read_u32(&buf[8..12]) as usize;
+
+            let mut a = &buf[32..];
+            let mut ids = Vec::with_capacity(number);
+            for _ in 0..number {
+                let spec = ClientIdSpec::read(&mut a)?;
+                let length = a.read_u32::<NativeEndian>()? as usize / 4;
+
+                let mut value = Vec::with_capacity(length);
+                for _ in 0..length {
+                    value.push(a.read_u32::<NativeEndian>()?);
+                }
+
+                ids.push(ClientIdValue { spec, value });
+            }
+
+            Ok((client, ids))
+        }))
+    }
+}
@@ -0,0 +1,50 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xres::EXTENSION_NAME;
+use ::Client;
+
+const XRES_OPCODE: u8 = 3;
+
+/// Returns the bytes of the pixmaps allocated by the client owning `xid`,
+/// a pixmap shared by several clients is divided among them.
+#[derive(Debug, Clone, Copy)]
+pub struct XResQueryClientPixmapBytes {
+    pub xid: u32,
+}
+
+impl ExtensionRequest for XResQueryClientPixmapBytes {
+    type Reply = u64;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XRES_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.xid)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let bytes = NativeEndian::read_u32(&buf[8..12]) as u64;
+            let overflow = NativeEndian::read_u32(&buf[12..16]) as u64;
+
+            (client, overflow << 32 | bytes)
+        }))
+    }
+}
@@ -0,0 +1,61 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xres::EXTENSION_NAME;
+use ::xres::ResourceType;
+use ::xproto::Atom;
+use ::Client;
+
+const XRES_OPCODE: u8 = 2;
+
+/// Returns the number of resources of each type allocated by the client
+/// owning `xid`, see `client_resources` for the names of the types.
+#[derive(Debug, Clone, Copy)]
+pub struct XResQueryClientResources {
+    pub xid: u32,
+}
+
+impl ExtensionRequest for XResQueryClientResources {
+    type Reply = Vec<ResourceType>;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XRES_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u32::<NativeEndian>(self.xid)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let number = NativeEndian::read_u32(&buf[8..12]) as usize;
+
+            let mut a = &buf[32..];
+            let mut types = Vec::with_capacity(number);
+            for _ in 0..number {
+                types.push(ResourceType {
+                    resource_type: Atom(a.read_u32::<NativeEndian>()?),
+                    count: a.read_u32::<NativeEndian>()?,
+                });
+            }
+
+            Ok((client, types))
+        }))
+    }
+}
@@ -0,0 +1,56 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xres::EXTENSION_NAME;
+use ::xres::ResourceClient;
+use ::Client;
+
+const XRES_OPCODE: u8 = 1;
+
+/// Returns the clients connected to the server.
+#[derive(Debug, Clone, Copy)]
+pub struct XResQueryClients;
+
+impl ExtensionRequest for XResQueryClients {
+    type Reply = Vec<ResourceClient>;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XRES_OPCODE)?;
+        a.write_u16::<NativeEndian>(1)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let number = NativeEndian::read_u32(&buf[8..12]) as usize;
+
+            let mut a = &buf[32..];
+            let mut clients = Vec::with_capacity(number);
+            for _ in 0..number {
+                clients.push(ResourceClient {
+                    resource_base: a.read_u32::<NativeEndian>()?,
+                    resource_mask: a.read_u32::<NativeEndian>()?,
+                });
+            }
+
+            Ok((client, clients))
+        }))
+    }
+}
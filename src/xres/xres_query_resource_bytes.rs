@@ -0,0 +1,75 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::ReadBytesExt;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xres::EXTENSION_NAME;
+use ::xres::ResourceIdSpec;
+use ::xres::ResourceSizeSpec;
+use ::xres::ResourceSizeValue;
+use ::Client;
+
+const XRES_OPCODE: u8 = 5;
+
+/// Returns the memory used by the resources matching `specs`, allocated by
+/// the client owning `client` or by any client if it's zero.
+#[derive(Debug, Clone)]
+pub struct XResQueryResourceBytes {
+    pub client: u32,
+    pub specs: Vec<ResourceIdSpec>,
+}
+
+impl ExtensionRequest for XResQueryResourceBytes {
+    type Reply = Vec<ResourceSizeValue>;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XRES_OPCODE)?;
+        a.write_u16::<NativeEndian>(3 + 2 * self.specs.len() as u16)?;
+        a.write_u32::<NativeEndian>(self.client)?;
+        a.write_u32::<NativeEndian>(self.specs.len() as u32)?;
+        for spec in &self.specs {
+            spec.write(&mut a)?;
+        }
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).and_then(|(client, buf)| {
+            let number = NativeEndian::read_u32(&buf[8..12]) as usize;
+
+            let mut a = &buf[32..];
+            let mut sizes = Vec::with_capacity(number);
+            for _ in 0..number {
+                let size = ResourceSizeSpec::read(&mut a)?;
+                let count = a.read_u32::<NativeEndian>()? as usize;
+
+                let mut cross_references = Vec::with_capacity(count);
+                for _ in 0..count {
+                    cross_references.push(ResourceSizeSpec::read(&mut a)?);
+                }
+
+                sizes.push(ResourceSizeValue {
+                    size,
+                    cross_references,
+                });
+            }
+
+            Ok((client, sizes))
+        }))
+    }
+}
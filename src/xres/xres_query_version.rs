@@ -0,0 +1,78 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::xres::EXTENSION_NAME;
+use ::Client;
+
+const XRES_OPCODE: u8 = 0;
+
+/// Returns the version of the extension, `XResQueryClientIds` and
+/// `XResQueryResourceBytes` need 1.2.
+#[derive(Debug, Clone, Copy)]
+pub struct XResQueryVersion {
+    pub client_major: u8,
+    pub client_minor: u8,
+}
+
+impl ExtensionRequest for XResQueryVersion {
+    type Reply = XResQueryVersionReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XRES_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u8(self.client_major)?;
+        a.write_u8(self.client_minor)?;
+        a.write_all(&[0; 2])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = XResQueryVersionReply {
+                server_major: NativeEndian::read_u16(&buf[8..10]),
+                server_minor: NativeEndian::read_u16(&buf[10..12]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `XResQueryVersion` request.
+#[derive(Debug, Clone, Copy)]
+pub struct XResQueryVersionReply {
+    pub server_major: u16,
+    pub server_minor: u16,
+}
+
+impl VersionRequest for XResQueryVersion {
+    fn latest() -> XResQueryVersion {
+        XResQueryVersion {
+            client_major: 1,
+            client_minor: 2,
+        }
+    }
+
+    fn version(reply: &XResQueryVersionReply) -> Version {
+        Version::new(u32::from(reply.server_major), u32::from(reply.server_minor))
+    }
+}
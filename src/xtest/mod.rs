@@ -0,0 +1,23 @@
+//! XTEST extension, generates synthetic input events for automated tests.
+
+macro_rules! declare_requests {
+    ($($request:ident),+) => {
+        $(
+            mod $request;
+            pub use self::$request::*;
+        )+
+    }
+}
+
+declare_requests! {
+    xtest_get_version,
+    xtest_compare_cursor,
+    xtest_fake_input,
+    xtest_grab_control
+}
+
+mod typist;
+pub use self::typist::*;
+
+/// Name of the extension.
+pub const EXTENSION_NAME: &[u8] = b"XTEST";
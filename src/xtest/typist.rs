@@ -0,0 +1,183 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::futures::Stream;
+
+use ::protocol::ReplyFuture;
+use ::xkb::Keysym;
+use ::xkb::NO_SYMBOL;
+use ::xkb::keysym_from_char;
+use ::xproto::ChangeKeyboardMapping;
+use ::xproto::GetKeyboardMapping;
+use ::xproto::GetKeyboardMappingReply;
+use ::xtest::FakeEvent;
+use ::xtest::XTestFakeInput;
+use ::xtest::XTestGetVersion;
+use ::Client;
+
+const SHIFT_L: Keysym = 0xffe1;
+
+/// Delay in milliseconds before a key press following a remapping, so the
+/// clients have time to fetch the new keyboard mapping.
+const REMAP_DELAY: u32 = 20;
+
+/// A step of the typing of a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Fake(FakeEvent, u32),
+    Remap(u8, Keysym),
+}
+
+/// Types strings with fake key events, like `xdotool type`.
+///
+/// The characters are mapped to keycodes with the core keyboard mapping,
+/// pressing Shift for the second keysym of a key. A character without a
+/// key is typed by remapping a keycode without keysyms, which is restored
+/// once the string is typed.
+#[derive(Debug, Clone)]
+pub struct Typist {
+    first_keycode: u8,
+    keysyms_per_keycode: u8,
+    keysyms: Vec<Keysym>,
+    shift: Option<u8>,
+    spare: Option<u8>,
+}
+
+impl Typist {
+    /// Fetches the keyboard mapping.
+    pub fn new(client: Client) -> ReplyFuture<Typist> {
+        let first_keycode = client.get_server_info().min_keycode;
+        let count = client.get_server_info().max_keycode - first_keycode + 1;
+
+        Box::new(client.perform(GetKeyboardMapping { first_keycode, count })
+            .map(move |(client, reply)| (client, Typist::from_mapping(first_keycode, &reply))))
+    }
+
+    /// Creates a typist from the mapping of the keys from `first_keycode`.
+    pub fn from_mapping(first_keycode: u8, mapping: &GetKeyboardMappingReply) -> Typist {
+        let width = mapping.keysyms_per_keycode.max(1) as usize;
+        let keys = mapping.keysyms.chunks(width);
+
+        let shift = keys.clone().position(|syms| syms[0] == SHIFT_L);
+        let spare = keys.clone().rposition(|syms| syms.iter().all(|&sym| sym == NO_SYMBOL));
+
+        Typist {
+            first_keycode,
+            keysyms_per_keycode: width as u8,
+            keysyms: mapping.keysyms.clone(),
+            shift: shift.map(|i| first_keycode + i as u8),
+            spare: spare.map(|i| first_keycode + i as u8),
+        }
+    }
+
+    /// Returns the key typing `keysym` and whether Shift must be pressed.
+    pub fn key_of(&self, keysym: Keysym) -> Option<(u8, bool)> {
+        let width = self.keysyms_per_keycode as usize;
+        let mut shifted = None;
+
+        for (i, syms) in self.keysyms.chunks(width).enumerate() {
+            let keycode = self.first_keycode + i as u8;
+            if syms[0] == keysym {
+                return Some((keycode, false));
+            }
+            if shifted.is_none() && self.shift.is_some() && syms.get(1) == Some(&keysym) {
+                shifted = Some((keycode, true));
+            }
+        }
+
+        shifted
+    }
+
+    /// Types `text`.
+    pub fn type_text(self, client: Client, text: &str) -> ReplyFuture<Typist> {
+        let steps = match self.plan(text) {
+            Ok(steps) => steps,
+            Err(e) => return Box::new(::futures::failed(e)),
+        };
+
+        let width = self.keysyms_per_keycode;
+        Box::new(::futures::stream::iter_ok::<_, io::Error>(steps)
+            .fold(client, move |client, step| {
+                let sent: ReplyFuture<()> = match step {
+                    Step::Fake(event, delay) => {
+                        client.perform_ex(XTestFakeInput { delay, ..XTestFakeInput::new(event) })
+                    }
+                    Step::Remap(keycode, keysym) => {
+                        let mut keysyms = vec![NO_SYMBOL; width as usize];
+                        keysyms[0] = keysym;
+                        if width > 1 {
+                            keysyms[1] = keysym;
+                        }
+                        client.perform(ChangeKeyboardMapping {
+                            first_keycode: keycode,
+                            keysyms_per_keycode: width,
+                            keysyms,
+                        })
+                    }
+                };
+                sent.map(|(client, _)| client)
+            })
+            // Waits for the events to be processed.
+            .and_then(|client| {
+                client.perform_ex(XTestGetVersion {
+                    major_version: 2,
+                    minor_version: 2,
+                })
+            })
+            .map(move |(client, _)| (client, self)))
+    }
+
+    /// Returns the steps typing `text`.
+    fn plan(&self, text: &str) -> io::Result<Vec<Step>> {
+        let mut steps = Vec::new();
+        let mut remapped = NO_SYMBOL;
+
+        for c in text.chars() {
+            let keysym = match c {
+                '\n' => 0xff0d,
+                '\t' => 0xff09,
+                '\u{8}' => 0xff08,
+                c => {
+                    keysym_from_char(c).ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidInput,
+                                       format!("no keysym for {:?}", c))
+                    })?
+                }
+            };
+
+            let mut delay = 0;
+            let (keycode, shift) = match (self.key_of(keysym), self.spare) {
+                (Some(key), _) => key,
+                (None, Some(spare)) => {
+                    if remapped != keysym {
+                        steps.push(Step::Remap(spare, keysym));
+                        remapped = keysym;
+                        delay = REMAP_DELAY;
+                    }
+                    (spare, false)
+                }
+                (None, None) => {
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                              format!("no key to type {:?}", c)));
+                }
+            };
+
+            let shift = if shift { self.shift } else { None };
+            if let Some(shift) = shift {
+                steps.push(Step::Fake(FakeEvent::KeyPress(shift), delay));
+                delay = 0;
+            }
+            steps.push(Step::Fake(FakeEvent::KeyPress(keycode), delay));
+            steps.push(Step::Fake(FakeEvent::KeyRelease(keycode), 0));
+            if let Some(shift) = shift {
+                steps.push(Step::Fake(FakeEvent::KeyRelease(shift), 0));
+            }
+        }
+
+        if let (Some(spare), true) = (self.spare, remapped != NO_SYMBOL) {
+            steps.push(Step::Remap(spare, NO_SYMBOL));
+        }
+
+        Ok(steps)
+    }
+}
@@ -0,0 +1,52 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::xtest::EXTENSION_NAME;
+use ::xproto::Cursor;
+use ::xproto::Window;
+use ::Client;
+
+const XTEST_OPCODE: u8 = 1;
+
+/// The cursor `CompareCursor` compares to when the window has the cursor of
+/// the screen.
+pub const CURRENT_CURSOR: Cursor = Cursor(1);
+
+/// Returns whether `cursor` is the cursor of `window`, `Cursor::NONE`
+/// checks that the window has no cursor of its own.
+#[derive(Debug, Clone, Copy)]
+pub struct XTestCompareCursor {
+    pub window: Window,
+    pub cursor: Cursor,
+}
+
+impl ExtensionRequest for XTestCompareCursor {
+    type Reply = bool;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XTEST_OPCODE)?;
+        a.write_u16::<NativeEndian>(3)?;
+        a.write_u32::<NativeEndian>(self.window.0)?;
+        a.write_u32::<NativeEndian>(self.cursor.0)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| (client, buf[1] != 0)))
+    }
+}
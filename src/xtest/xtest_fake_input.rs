@@ -0,0 +1,115 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::event::BUTTON_PRESS;
+use ::event::BUTTON_RELEASE;
+use ::event::KEY_PRESS;
+use ::event::KEY_RELEASE;
+use ::event::MOTION_NOTIFY;
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xtest::EXTENSION_NAME;
+use ::xproto::Window;
+use ::Client;
+
+const XTEST_OPCODE: u8 = 2;
+
+/// An input event generated by `XTestFakeInput`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FakeEvent {
+    KeyPress(u8),
+    KeyRelease(u8),
+    ButtonPress(u8),
+    ButtonRelease(u8),
+
+    /// Moves the pointer to (`x`, `y`) on the screen of `root`, or by
+    /// (`x`, `y`) if `relative`. `Window::NONE` is the screen of the
+    /// pointer.
+    Motion {
+        relative: bool,
+        root: Window,
+        x: i16,
+        y: i16,
+    },
+
+    /// An event with the given core type and detail, like the events of the
+    /// XInput devices.
+    Raw {
+        event_type: u8,
+        detail: u8,
+        root: Window,
+        x: i16,
+        y: i16,
+    },
+}
+
+/// Generates an input event as if it came from a device.
+#[derive(Debug, Clone, Copy)]
+pub struct XTestFakeInput {
+    pub event: FakeEvent,
+
+    /// Delay in milliseconds before the event is processed.
+    pub delay: u32,
+
+    /// The XInput device, 0 for the core devices.
+    pub deviceid: u8,
+}
+
+impl XTestFakeInput {
+    pub fn new(event: FakeEvent) -> XTestFakeInput {
+        XTestFakeInput {
+            event,
+            delay: 0,
+            deviceid: 0,
+        }
+    }
+}
+
+impl ExtensionRequest for XTestFakeInput {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        let (event_type, detail, root, x, y) = match self.event {
+            FakeEvent::KeyPress(key) => (KEY_PRESS, key, Window::NONE, 0, 0),
+            FakeEvent::KeyRelease(key) => (KEY_RELEASE, key, Window::NONE, 0, 0),
+            FakeEvent::ButtonPress(button) => (BUTTON_PRESS, button, Window::NONE, 0, 0),
+            FakeEvent::ButtonRelease(button) => (BUTTON_RELEASE, button, Window::NONE, 0, 0),
+            FakeEvent::Motion { relative, root, x, y } => {
+                (MOTION_NOTIFY, relative as u8, root, x, y)
+            }
+            FakeEvent::Raw { event_type, detail, root, x, y } => (event_type, detail, root, x, y),
+        };
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XTEST_OPCODE)?;
+        a.write_u16::<NativeEndian>(9)?;
+        a.write_u8(event_type)?;
+        a.write_u8(detail)?;
+        a.write_u16::<NativeEndian>(0)?;
+        a.write_u32::<NativeEndian>(self.delay)?;
+        a.write_u32::<NativeEndian>(root.0)?;
+        a.write_all(&[0; 8])?;
+        a.write_i16::<NativeEndian>(x)?;
+        a.write_i16::<NativeEndian>(y)?;
+        a.write_all(&[0; 7])?;
+        a.write_u8(self.deviceid)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
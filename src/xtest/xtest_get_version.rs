@@ -0,0 +1,76 @@
+use ::std::io;
+
+use ::futures::Future;
+use ::byteorder::NativeEndian;
+use ::byteorder::ByteOrder;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::read_reply;
+use ::protocol::ExtensionInfo;
+use ::protocol::Version;
+use ::protocol::VersionRequest;
+use ::xtest::EXTENSION_NAME;
+use ::Client;
+
+const XTEST_OPCODE: u8 = 0;
+
+/// Returns the version of the extension.
+#[derive(Debug, Clone, Copy)]
+pub struct XTestGetVersion {
+    pub major_version: u8,
+    pub minor_version: u16,
+}
+
+impl ExtensionRequest for XTestGetVersion {
+    type Reply = XTestGetVersionReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XTEST_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u8(self.major_version)?;
+        a.write_u8(0)?;
+        a.write_u16::<NativeEndian>(self.minor_version)?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        Box::new(read_reply(client).map(|(client, buf)| {
+            let reply = XTestGetVersionReply {
+                major_version: buf[1],
+                minor_version: NativeEndian::read_u16(&buf[8..10]),
+            };
+
+            (client, reply)
+        }))
+    }
+}
+
+/// Reply of `XTestGetVersion` request.
+#[derive(Debug, Clone, Copy)]
+pub struct XTestGetVersionReply {
+    pub major_version: u8,
+    pub minor_version: u16,
+}
+
+impl VersionRequest for XTestGetVersion {
+    fn latest() -> XTestGetVersion {
+        XTestGetVersion {
+            major_version: 2,
+            minor_version: 2,
+        }
+    }
+
+    fn version(reply: &XTestGetVersionReply) -> Version {
+        Version::new(u32::from(reply.major_version), u32::from(reply.minor_version))
+    }
+}
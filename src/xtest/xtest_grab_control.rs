@@ -0,0 +1,46 @@
+use ::std::io;
+use ::std::io::Write;
+
+use ::byteorder::NativeEndian;
+use ::byteorder::WriteBytesExt;
+
+use ::protocol::ExtensionRequest;
+use ::protocol::ReplyFuture;
+use ::protocol::VoidReply;
+use ::protocol::void_reply;
+use ::protocol::ExtensionInfo;
+use ::xtest::EXTENSION_NAME;
+use ::Client;
+
+const XTEST_OPCODE: u8 = 3;
+
+/// Makes this client impervious to the server grabs of other clients, so a
+/// test can't be blocked by the application it drives.
+#[derive(Debug, Clone, Copy)]
+pub struct XTestGrabControl {
+    pub impervious: bool,
+}
+
+impl ExtensionRequest for XTestGrabControl {
+    type Reply = VoidReply;
+
+    fn extension_name() -> &'static [u8] {
+        EXTENSION_NAME
+    }
+
+    fn encode(&mut self, info: &ExtensionInfo) -> io::Result<Vec<u8>> {
+        let mut a = io::Cursor::new(vec![]);
+
+        a.write_u8(info.major_opcode)?;
+        a.write_u8(XTEST_OPCODE)?;
+        a.write_u16::<NativeEndian>(2)?;
+        a.write_u8(self.impervious as u8)?;
+        a.write_all(&[0; 3])?;
+
+        Ok(a.into_inner())
+    }
+
+    fn decode(client: Client) -> ReplyFuture<Self::Reply> {
+        void_reply(client)
+    }
+}
@@ -0,0 +1,320 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use std::collections::VecDeque;
+
+use common::FakeServer;
+use xrb::clipboard::Clipboard;
+use xrb::xproto;
+use xrb::xproto::Atom;
+
+const WINDOW: u32 = 0x400000;
+const REQUESTOR: u32 = 0x600001;
+const PROPERTY: u32 = 400;
+
+// The fake server interns the atoms in order from 301, see `setup`.
+const CLIPBOARD: Atom = Atom(301);
+const TARGETS: u32 = 302;
+const TIMESTAMP: u32 = 303;
+const INCR: u32 = 304;
+const UTF8_STRING: u32 = 305;
+const TEXT_PLAIN: u32 = 306;
+const TEXT_PLAIN_UTF8: u32 = 307;
+const IMAGE_PNG: Atom = Atom(308);
+const XRB_SELECTION: u32 = 309;
+
+fn read_u32(buf: &[u8], offset: usize) -> u32 {
+    u32::from_ne_bytes([buf[offset], buf[offset + 1], buf[offset + 2], buf[offset + 3]])
+}
+
+fn put_u32(buf: &mut [u8], offset: usize, value: u32) {
+    buf[offset..offset + 4].copy_from_slice(&value.to_ne_bytes());
+}
+
+fn u32_bytes(values: &[u32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_ne_bytes().to_vec()).collect()
+}
+
+/// Answers the requests made by `Clipboard::new`, returns `None` for the
+/// other requests.
+fn setup(sequence: u16, request: &[u8]) -> Option<Vec<Vec<u8>>> {
+    match request[0] {
+        // InternAtom
+        16 => Some(vec![common::reply(sequence, 0, &(300 + sequence as u32).to_ne_bytes())]),
+        // CreateWindow
+        1 => Some(vec![]),
+        _ => None,
+    }
+}
+
+fn selection_request(target: u32, property: u32) -> Vec<u8> {
+    let mut packet = common::event(30, 0);
+    put_u32(&mut packet, 4, 10);
+    put_u32(&mut packet, 8, WINDOW);
+    put_u32(&mut packet, 12, REQUESTOR);
+    put_u32(&mut packet, 16, CLIPBOARD.0);
+    put_u32(&mut packet, 20, target);
+    put_u32(&mut packet, 24, property);
+    packet
+}
+
+fn property_notify(window: u32, atom: u32, deleted: bool) -> Vec<u8> {
+    let mut packet = common::event(28, 0);
+    put_u32(&mut packet, 4, window);
+    put_u32(&mut packet, 8, atom);
+    packet[16] = deleted as u8;
+    packet
+}
+
+fn selection_notify(target: u32, property: u32) -> Vec<u8> {
+    let mut packet = common::event(31, 0);
+    put_u32(&mut packet, 8, WINDOW);
+    put_u32(&mut packet, 12, CLIPBOARD.0);
+    put_u32(&mut packet, 16, target);
+    put_u32(&mut packet, 20, property);
+    packet
+}
+
+fn get_property_reply(sequence: u16, type_: u32, format: u8, value: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&type_.to_ne_bytes());
+    body.extend_from_slice(&0u32.to_ne_bytes());
+    let len = if format == 0 { 0 } else { value.len() as u32 * 8 / format as u32 };
+    body.extend_from_slice(&len.to_ne_bytes());
+    body.extend_from_slice(&[0; 12]);
+    body.extend_from_slice(value);
+    common::reply(sequence, format, &body)
+}
+
+/// A `ChangeProperty` request as (window, property, type, format, data).
+fn change_property(request: &[u8]) -> (u32, u32, u32, u8, Vec<u8>) {
+    assert_eq!(request[0], 18);
+    let format = request[16];
+    let len = read_u32(request, 20) as usize * format as usize / 8;
+    (read_u32(request, 4),
+     read_u32(request, 8),
+     read_u32(request, 12),
+     format,
+     request[24..24 + len].to_vec())
+}
+
+/// The `SelectionNotify` event of a `SendEvent` request as (destination,
+/// target, property).
+fn sent_notify(request: &[u8]) -> (u32, u32, u32) {
+    assert_eq!(request[0], 25);
+    let event = &request[12..44];
+    assert_eq!(event[0], 31);
+    (read_u32(request, 4), read_u32(event, 16), read_u32(event, 20))
+}
+
+#[test]
+fn owned_text_is_converted_locally() {
+    let server = FakeServer::start(|sequence, request| {
+        setup(sequence, request).unwrap_or_else(|| match request[0] {
+            // GetSelectionOwner
+            23 => vec![common::reply(sequence, 0, &WINDOW.to_ne_bytes())],
+            _ => vec![],
+        })
+    });
+
+    let (mut lp, client) = server.connect();
+    let (client, mut clipboard) = lp.run(Clipboard::new(client)).unwrap();
+    assert_eq!(clipboard.window(), xproto::Window(WINDOW));
+    assert_eq!(clipboard.atoms().xrb_selection, Atom(XRB_SELECTION));
+
+    let (client, owner) = lp.run(clipboard.set_text(client, CLIPBOARD, "Café", 5)).unwrap();
+    assert!(owner);
+    assert!(clipboard.owns(CLIPBOARD));
+
+    let (client, targets) = lp.run(clipboard.get_targets(client, CLIPBOARD, 0)).unwrap();
+    let expected: Vec<Atom> = [TARGETS, TIMESTAMP, UTF8_STRING, TEXT_PLAIN_UTF8, TEXT_PLAIN]
+        .iter()
+        .map(|&atom| Atom(atom))
+        .chain(Some(xproto::ATOM_STRING))
+        .collect();
+    assert_eq!(targets, expected);
+
+    let (client, time) = lp.run(clipboard.get(client, CLIPBOARD, Atom(TIMESTAMP), 0)).unwrap();
+    assert_eq!(time, Some((xproto::ATOM_INTEGER, u32_bytes(&[5]))));
+
+    let (client, latin1) = lp.run(clipboard.get(client, CLIPBOARD, xproto::ATOM_STRING, 0)).unwrap();
+    assert_eq!(latin1, Some((xproto::ATOM_STRING, vec![b'C', b'a', b'f', 0xe9])));
+
+    let (client, text) = lp.run(clipboard.get_text(client, CLIPBOARD, 0)).unwrap();
+    assert_eq!(text, Some("Café".to_owned()));
+
+    drop(client);
+    let requests = server.finish();
+    // 9 InternAtom, CreateWindow, SetSelectionOwner and GetSelectionOwner.
+    assert_eq!(requests.len(), 12);
+}
+
+#[test]
+fn selection_requests_are_served() {
+    let server = FakeServer::start(|sequence, request| {
+        setup(sequence, request).unwrap_or_else(|| match request[0] {
+            23 => {
+                vec![common::reply(sequence, 0, &WINDOW.to_ne_bytes()),
+                     selection_request(TARGETS, PROPERTY),
+                     selection_request(UTF8_STRING, 0),
+                     selection_request(IMAGE_PNG.0, PROPERTY)]
+            }
+            _ => vec![],
+        })
+    });
+
+    let (mut lp, client) = server.connect();
+    let (client, mut clipboard) = lp.run(Clipboard::new(client)).unwrap();
+    let (mut client, _) = lp.run(clipboard.set_text(client, CLIPBOARD, "hi", 5)).unwrap();
+
+    for _ in 0..3 {
+        let (c, event) = lp.run(client.next_event()).unwrap();
+        let (c, handled) = lp.run(clipboard.handle_event(c, &event)).unwrap();
+        assert!(handled);
+        client = c;
+    }
+
+    drop(client);
+    let requests = server.finish();
+    let requests = &requests[12..];
+    assert_eq!(requests.len(), 5);
+
+    let targets = u32_bytes(&[TARGETS, TIMESTAMP, UTF8_STRING, TEXT_PLAIN_UTF8, TEXT_PLAIN,
+                              xproto::ATOM_STRING.0]);
+    assert_eq!(change_property(&requests[0]),
+               (REQUESTOR, PROPERTY, xproto::ATOM_ATOM.0, 32, targets));
+    assert_eq!(sent_notify(&requests[1]), (REQUESTOR, TARGETS, PROPERTY));
+
+    // Obsolete clients without a property get the target as property.
+    assert_eq!(change_property(&requests[2]),
+               (REQUESTOR, UTF8_STRING, UTF8_STRING, 8, b"hi".to_vec()));
+    assert_eq!(sent_notify(&requests[3]), (REQUESTOR, UTF8_STRING, UTF8_STRING));
+
+    // Unavailable targets are refused.
+    assert_eq!(sent_notify(&requests[4]), (REQUESTOR, IMAGE_PNG.0, 0));
+}
+
+#[test]
+fn large_selections_are_sent_in_chunks() {
+    let server = FakeServer::start(|sequence, request| {
+        setup(sequence, request).unwrap_or_else(|| match request[0] {
+            23 => {
+                vec![common::reply(sequence, 0, &WINDOW.to_ne_bytes()),
+                     selection_request(IMAGE_PNG.0, PROPERTY)]
+            }
+            // The requestor deletes the property after the notification and
+            // after each chunk but the last, empty, one.
+            25 => vec![property_notify(REQUESTOR, PROPERTY, true)],
+            18 if request[16] == 8 && read_u32(request, 20) > 0 => {
+                vec![property_notify(REQUESTOR, PROPERTY, true)]
+            }
+            _ => vec![],
+        })
+    });
+
+    let data: Vec<u8> = (0..300000u32).map(|i| i as u8).collect();
+
+    let (mut lp, client) = server.connect();
+    let (client, mut clipboard) = lp.run(Clipboard::new(client)).unwrap();
+    let contents = vec![(IMAGE_PNG, data.clone())];
+    let (client, _) = lp.run(clipboard.set(client, CLIPBOARD, contents, 5)).unwrap();
+    let (client, event) = lp.run(client.next_event()).unwrap();
+    let (client, handled) = lp.run(clipboard.handle_event(client, &event)).unwrap();
+    assert!(handled);
+
+    drop(client);
+    let requests = server.finish();
+    let requests = &requests[12..];
+
+    // ChangeWindowAttributes selecting PropertyNotify on the requestor.
+    assert_eq!(requests[0][0], 2);
+    assert_eq!(read_u32(&requests[0], 4), REQUESTOR);
+    assert_eq!(change_property(&requests[1]),
+               (REQUESTOR, PROPERTY, INCR, 32, u32_bytes(&[300000])));
+    assert_eq!(sent_notify(&requests[2]), (REQUESTOR, IMAGE_PNG.0, PROPERTY));
+
+    // The maximum request length of the fake server is 0xffff words.
+    let max_size = 0xffff * 4 - 24;
+    let chunks: Vec<_> = requests[3..6].iter().map(|request| change_property(request)).collect();
+    assert_eq!(chunks.iter().map(|chunk| chunk.4.len()).collect::<Vec<_>>(),
+               vec![max_size, 300000 - max_size, 0]);
+    assert!(chunks.iter().all(|chunk| chunk.2 == IMAGE_PNG.0 && chunk.3 == 8));
+    let sent: Vec<u8> = chunks.into_iter().flat_map(|chunk| chunk.4).collect();
+    assert_eq!(sent, data);
+
+    // The event mask of the requestor is restored.
+    assert_eq!(requests[6][0], 2);
+    assert_eq!(requests.len(), 7);
+}
+
+#[test]
+fn large_selections_are_received_in_chunks() {
+    // The GetProperty replies as (type, value, format, PropertyNotify events
+    // sent after the reply).
+    let mut replies: VecDeque<(u32, Vec<u8>, u8, usize)> = vec![(INCR, u32_bytes(&[9]), 32, 1),
+                                                                 (IMAGE_PNG.0, b"abc".to_vec(), 8, 1),
+                                                                 (IMAGE_PNG.0, b"def".to_vec(), 8, 2),
+                                                                 (0, vec![], 0, 0),
+                                                                 (IMAGE_PNG.0, b"ghi".to_vec(), 8, 1),
+                                                                 (IMAGE_PNG.0, vec![], 8, 0)]
+        .into_iter()
+        .collect();
+
+    let server = FakeServer::start(move |sequence, request| {
+        setup(sequence, request).unwrap_or_else(|| match request[0] {
+            // ConvertSelection
+            24 => vec![selection_notify(IMAGE_PNG.0, XRB_SELECTION)],
+            // GetProperty
+            20 => {
+                let (type_, value, format, notifies) = replies.pop_front().unwrap();
+                let mut packets = vec![get_property_reply(sequence, type_, format, &value)];
+                for _ in 0..notifies {
+                    packets.push(property_notify(WINDOW, XRB_SELECTION, false));
+                }
+                packets
+            }
+            _ => vec![],
+        })
+    });
+
+    let (mut lp, client) = server.connect();
+    let (client, clipboard) = lp.run(Clipboard::new(client)).unwrap();
+    let (client, data) = lp.run(clipboard.get(client, CLIPBOARD, IMAGE_PNG, 0)).unwrap();
+    assert_eq!(data, Some((IMAGE_PNG, b"abcdefghi".to_vec())));
+
+    drop(client);
+    let requests = server.finish();
+    let requests = &requests[10..];
+
+    assert_eq!(requests[0][0], 24);
+    assert_eq!(read_u32(&requests[0], 4), WINDOW);
+    assert_eq!(read_u32(&requests[0], 12), IMAGE_PNG.0);
+
+    // The INCR property, the three chunks, the stale notification and the
+    // final empty chunk are read and deleted.
+    assert_eq!(requests.len(), 7);
+    assert!(requests[1..].iter().all(|request| request[0] == 20 && request[1] == 1));
+}
+
+#[test]
+fn refused_conversions_resolve_to_none() {
+    let server = FakeServer::start(|sequence, request| {
+        setup(sequence, request).unwrap_or_else(|| match request[0] {
+            24 => vec![selection_notify(read_u32(request, 12), 0)],
+            _ => vec![],
+        })
+    });
+
+    let (mut lp, client) = server.connect();
+    let (client, clipboard) = lp.run(Clipboard::new(client)).unwrap();
+    let (client, text) = lp.run(clipboard.get_text(client, CLIPBOARD, 0)).unwrap();
+    assert_eq!(text, None);
+
+    drop(client);
+    let requests = server.finish();
+    let targets: Vec<u32> = requests[10..].iter().map(|request| read_u32(request, 12)).collect();
+    assert_eq!(targets, vec![UTF8_STRING, xproto::ATOM_STRING.0]);
+}
@@ -0,0 +1,189 @@
+//! Helpers shared by the integration tests, a scripted X server listening
+//! on a local display.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::io::Read;
+use std::io::Write;
+use std::os::unix::net::UnixListener;
+use std::process;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+
+use tokio_core::reactor::Core;
+use xauth::Family;
+use xauth::Xauth;
+use xrb::Client;
+use xrb::xproto::QueryExtensionReply;
+
+static NEXT_DISPLAY: AtomicUsize = AtomicUsize::new(0);
+
+/// The major opcode of the extension described by `info`.
+pub const MAJOR_OPCODE: u8 = 130;
+
+/// The first event code of the extension described by `info`.
+pub const FIRST_EVENT: u8 = 90;
+
+/// The first error code of the extension described by `info`.
+pub const FIRST_ERROR: u8 = 140;
+
+/// The information of an extension present on the server, used to encode
+/// its requests and decode its events without a connection.
+pub fn info() -> QueryExtensionReply {
+    QueryExtensionReply {
+        present: true,
+        major_opcode: MAJOR_OPCODE,
+        first_event: FIRST_EVENT,
+        first_error: FIRST_ERROR,
+    }
+}
+
+/// A server answering the requests of a single client with a script, the
+/// requests received are returned by `finish`.
+pub struct FakeServer {
+    pub display: u16,
+    thread: thread::JoinHandle<Vec<Vec<u8>>>,
+}
+
+impl FakeServer {
+    /// Listens on a free display, `script` is called with the sequence
+    /// number and the packet of each request and returns the packets sent
+    /// back.
+    pub fn start<F>(mut script: F) -> FakeServer
+        where F: FnMut(u16, &[u8]) -> Vec<Vec<u8>> + Send + 'static
+    {
+        let n = NEXT_DISPLAY.fetch_add(1, Ordering::SeqCst);
+        let display = (1000 + (process::id() as usize % 500) * 100 + n % 100) as u16;
+        let path = format!("/tmp/.X11-unix/X{}", display);
+        let _ = fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+
+        let thread = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = fs::remove_file(&path);
+
+            let mut setup = [0u8; 12];
+            stream.read_exact(&mut setup).unwrap();
+            let name_len = u16::from_ne_bytes([setup[6], setup[7]]) as usize;
+            let data_len = u16::from_ne_bytes([setup[8], setup[9]]) as usize;
+            let mut auth = vec![0u8; padded(name_len) + padded(data_len)];
+            stream.read_exact(&mut auth).unwrap();
+            stream.write_all(&setup_reply()).unwrap();
+
+            let mut requests = Vec::new();
+            let mut sequence = 0u16;
+            loop {
+                let mut header = [0u8; 4];
+                if stream.read_exact(&mut header).is_err() {
+                    break;
+                }
+
+                let length = u16::from_ne_bytes([header[2], header[3]]) as usize * 4;
+                let mut request = header.to_vec();
+                request.resize(length.max(4), 0);
+                stream.read_exact(&mut request[4..]).unwrap();
+                sequence = sequence.wrapping_add(1);
+
+                for packet in script(sequence, &request) {
+                    stream.write_all(&packet).unwrap();
+                }
+                requests.push(request);
+            }
+
+            requests
+        });
+
+        FakeServer { display, thread }
+    }
+
+    /// Connects a client to the server.
+    pub fn connect(&self) -> (Core, Client) {
+        let auth_info = Xauth {
+            family: Family::Local,
+            address: Vec::new(),
+            number: Vec::new(),
+            name: Vec::new(),
+            data: Vec::new(),
+        };
+
+        let mut lp = Core::new().unwrap();
+        let client = lp.run(Client::connect(self.display, &auth_info, lp.handle())).unwrap();
+        (lp, client)
+    }
+
+    /// Waits for the client to disconnect and returns its requests.
+    pub fn finish(self) -> Vec<Vec<u8>> {
+        self.thread.join().unwrap()
+    }
+}
+
+fn padded(n: usize) -> usize {
+    n.div_ceil(4) * 4
+}
+
+/// A successful setup with a single screen without depths.
+fn setup_reply() -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(&1u32.to_ne_bytes());
+    data.extend_from_slice(&0x400000u32.to_ne_bytes());
+    data.extend_from_slice(&0x1fffffu32.to_ne_bytes());
+    data.extend_from_slice(&0u32.to_ne_bytes());
+    data.extend_from_slice(&4u16.to_ne_bytes());
+    data.extend_from_slice(&0xffffu16.to_ne_bytes());
+    data.extend_from_slice(&[1, 0, 0, 0, 32, 32, 8, 255, 0, 0, 0, 0]);
+    data.extend_from_slice(b"fake");
+
+    data.extend_from_slice(&0x100u32.to_ne_bytes());
+    data.extend_from_slice(&0x20u32.to_ne_bytes());
+    data.extend_from_slice(&0xffffffu32.to_ne_bytes());
+    data.extend_from_slice(&0u32.to_ne_bytes());
+    data.extend_from_slice(&0u32.to_ne_bytes());
+    for &value in &[1920u16, 1080, 500, 280, 1, 1] {
+        data.extend_from_slice(&value.to_ne_bytes());
+    }
+    data.extend_from_slice(&0x21u32.to_ne_bytes());
+    data.extend_from_slice(&[0, 0, 24, 0]);
+
+    let mut reply = vec![1, 0];
+    reply.extend_from_slice(&11u16.to_ne_bytes());
+    reply.extend_from_slice(&0u16.to_ne_bytes());
+    reply.extend_from_slice(&(data.len() as u16 / 4).to_ne_bytes());
+    reply.extend_from_slice(&data);
+    reply
+}
+
+/// A reply packet, `body` is written from byte 8 and padded to 32 bytes at
+/// least.
+pub fn reply(sequence: u16, detail: u8, body: &[u8]) -> Vec<u8> {
+    let mut packet = vec![1, detail];
+    packet.extend_from_slice(&sequence.to_ne_bytes());
+    let total = padded((8 + body.len()).max(32));
+    packet.extend_from_slice(&((total - 32) as u32 / 4).to_ne_bytes());
+    packet.extend_from_slice(body);
+    packet.resize(total, 0);
+    packet
+}
+
+/// An error packet for the request with the given sequence number.
+pub fn error(sequence: u16, code: u8, major_opcode: u8) -> Vec<u8> {
+    let mut packet = vec![0, code];
+    packet.extend_from_slice(&sequence.to_ne_bytes());
+    packet.resize(32, 0);
+    packet[10] = major_opcode;
+    packet
+}
+
+/// An event packet with the given code.
+pub fn event(code: u8, sequence: u16) -> Vec<u8> {
+    let mut packet = vec![code, 0];
+    packet.extend_from_slice(&sequence.to_ne_bytes());
+    packet.resize(32, 0);
+    packet
+}
+
+/// The reply of a `QueryExtension` request.
+pub fn query_extension_reply(sequence: u16, present: bool, major_opcode: u8) -> Vec<u8> {
+    reply(sequence, 0, &[present as u8, major_opcode, 0, 0])
+}
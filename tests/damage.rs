@@ -0,0 +1,69 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use common::FIRST_EVENT;
+use common::info;
+use xrb::damage::*;
+use xrb::event::Event;
+use xrb::xproto::Drawable;
+use xrb::xproto::Rectangle;
+use xrb::xproto::Window;
+
+fn notify(damage: u32, area: Rectangle, more: bool) -> DamageNotifyEvent {
+    DamageNotifyEvent {
+        level: ReportLevel::RawRectangles,
+        more,
+        drawable: Drawable(damage),
+        damage: Damage(damage),
+        timestamp: 0,
+        area,
+        geometry: Rectangle { x: 100, y: 50, width: 640, height: 480 },
+    }
+}
+
+fn rect(x: i16, y: i16, width: u16, height: u16) -> Rectangle {
+    Rectangle { x, y, width, height }
+}
+
+#[test]
+fn decode() {
+    let mut buf = vec![0u8; 32];
+    buf[0] = FIRST_EVENT;
+    buf[1] = 0x80 | 1;
+    buf[8..12].copy_from_slice(&5u32.to_ne_bytes());
+    buf[20..22].copy_from_slice(&10u16.to_ne_bytes());
+
+    let event = DamageNotifyEvent::from_event(&info(), &Event::Unknown(buf)).unwrap();
+    assert_eq!(event.level, ReportLevel::DeltaRectangles);
+    assert!(event.more);
+    assert_eq!(event.damage, Damage(5));
+    assert_eq!(event.area.width, 10);
+}
+
+#[test]
+fn batches() {
+    let mut tracker = DamageTracker::new();
+    tracker.watch(Damage(1), Window(10));
+    tracker.watch(Damage(2), Window(20));
+
+    assert_eq!(tracker.handle(&notify(1, rect(0, 0, 10, 10), true)), None);
+    // Contained in the previous rectangle.
+    assert_eq!(tracker.handle(&notify(1, rect(2, 2, 4, 4), true)), None);
+    // Not watched.
+    assert_eq!(tracker.handle(&notify(3, rect(0, 0, 10, 10), true)), None);
+
+    let batch = tracker.handle(&notify(2, rect(10, 10, 5, 5), false)).unwrap();
+    assert_eq!(batch.windows,
+               vec![(Window(10), vec![rect(100, 50, 10, 10)]),
+                    (Window(20), vec![rect(110, 60, 5, 5)])]);
+    assert_eq!(batch.damages, vec![Damage(1), Damage(2)]);
+    assert_eq!(batch.bounds(), Some(rect(100, 50, 15, 15)));
+    assert_eq!(batch.subtract_requests().len(), 2);
+
+    assert!(tracker.take_batch().is_empty());
+    assert_eq!(tracker.unwatch(Window(10)), Some(Damage(1)));
+    assert_eq!(tracker.handle(&notify(1, rect(0, 0, 1, 1), false)), None);
+}
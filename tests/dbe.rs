@@ -0,0 +1,109 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use common::MAJOR_OPCODE;
+use common::info;
+use xrb::dbe::*;
+use xrb::protocol::ExtensionRequest;
+use xrb::xproto::Colormap;
+use xrb::xproto::Visualid;
+use xrb::xproto::Window;
+use xrb::BackingStores;
+use xrb::Depth;
+use xrb::Screen;
+use xrb::Visual;
+use xrb::VisualClass;
+
+fn visual(id: u32) -> Visual {
+    Visual {
+        id: Visualid(id),
+        class: VisualClass::TrueColor,
+        bits_per_rgb_value: 8,
+        colormap_entries: 256,
+        red_mask: 0xff0000,
+        green_mask: 0xff00,
+        blue_mask: 0xff,
+    }
+}
+
+#[test]
+fn swap_encoding() {
+    let buf = DbeAllocateBackBuffer {
+            window: Window(1),
+            buffer: BackBuffer(2),
+            swap_action: SwapAction::Background,
+        }
+        .encode(&info())
+        .unwrap();
+    assert_eq!(buf.len(), 16);
+    assert_eq!(&buf[..4], &[MAJOR_OPCODE, 1, 4, 0]);
+    assert_eq!(buf[12], 1);
+
+    let mut request = DbeSwapBuffers {
+        actions: vec![SwapInfo {
+                          window: Window(1),
+                          swap_action: SwapAction::Copied,
+                      },
+                      SwapInfo {
+                          window: Window(3),
+                          swap_action: SwapAction::Undefined,
+                      }],
+    };
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(buf.len(), 24);
+    assert_eq!(&buf[..4], &[MAJOR_OPCODE, 3, 6, 0]);
+    assert_eq!(&buf[4..8], &2u32.to_ne_bytes());
+    assert_eq!(buf[12], 3);
+    assert_eq!(&buf[16..20], &3u32.to_ne_bytes());
+}
+
+#[test]
+fn visuals_of_screen() {
+    let screen = Screen {
+        root: Window(0x100),
+        default_colormap: Colormap(0x20),
+        white_pixel: 0xffffff,
+        black_pixel: 0,
+        current_input_masks: 0,
+        width_pixels: 1920,
+        height_pixels: 1080,
+        width_millimeters: 500,
+        height_millimeters: 280,
+        min_installed_maps: 1,
+        max_installed_maps: 1,
+        root_visual: Visualid(0x21),
+        backing_stores: BackingStores::Never,
+        save_unders: false,
+        root_depth: 24,
+        allowed_depths: vec![Depth {
+                                 depth: 24,
+                                 visuals: vec![visual(0x21), visual(0x22)],
+                             },
+                             Depth {
+                                 depth: 32,
+                                 visuals: vec![visual(0x40)],
+                             }],
+    };
+
+    let infos = ScreenVisualInfo {
+        infos: vec![VisualInfo {
+                        visual: Visualid(0x21),
+                        depth: 24,
+                        perf_level: 1,
+                    },
+                    VisualInfo {
+                        visual: Visualid(0x40),
+                        depth: 32,
+                        perf_level: 2,
+                    }],
+    };
+
+    let visuals = infos.visuals(&screen);
+    assert_eq!(visuals.len(), 2);
+    assert_eq!(visuals[0].0.id, Visualid(0x40));
+    assert_eq!(visuals[1].0.id, Visualid(0x21));
+    assert!(infos.find(Visualid(0x22)).is_none());
+}
@@ -0,0 +1,382 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use common::FakeServer;
+use xrb::event::ClientMessageEvent;
+use xrb::event::Event;
+use xrb::ewmh::*;
+use xrb::sync::Counter;
+use xrb::xproto;
+use xrb::xproto::Atom;
+use xrb::xproto::GetPropertyReply;
+use xrb::xproto::Window;
+
+/// Interns the atoms with a fake server giving them consecutive values
+/// from 301.
+fn atoms() -> Atoms {
+    let server = FakeServer::start(|sequence, _| {
+        vec![common::reply(sequence, 0, &(300 + sequence as u32).to_ne_bytes())]
+    });
+
+    let (mut lp, client) = server.connect();
+    let (client, atoms) = lp.run(Atoms::intern(client)).unwrap();
+    drop(client);
+    server.finish();
+
+    atoms
+}
+
+fn reply<P: NetProperty>(atoms: &Atoms, value: &P) -> GetPropertyReply {
+    let data = value.encode(atoms);
+    GetPropertyReply {
+        format: data.format,
+        type_: data.type_,
+        bytes_after: 0,
+        value: data.data,
+    }
+}
+
+fn roundtrip<P: NetProperty>(atoms: &Atoms, value: &P) -> Option<P> {
+    P::decode(atoms, &reply(atoms, value))
+}
+
+fn cardinals(values: &[u32]) -> GetPropertyReply {
+    GetPropertyReply {
+        format: 32,
+        type_: xproto::ATOM_CARDINAL,
+        bytes_after: 0,
+        value: values.iter().flat_map(|v| v.to_ne_bytes().to_vec()).collect(),
+    }
+}
+
+#[test]
+fn atoms_are_interned_in_order() {
+    let atoms = atoms();
+
+    assert_eq!(atoms.utf8_string, Atom(301));
+    assert_eq!(atoms.net_supported, Atom(302));
+    // The last of the 82 names.
+    assert_eq!(atoms.net_wm_action_below, Atom(382));
+}
+
+#[test]
+fn lists_and_values() {
+    let atoms = atoms();
+
+    let supported = NetSupported(vec![atoms.net_wm_state, atoms.net_wm_name]);
+    assert_eq!(reply(&atoms, &supported).type_, xproto::ATOM_ATOM);
+    assert_eq!(roundtrip(&atoms, &supported), Some(supported));
+
+    let clients = NetClientList(vec![Window(0x400001), Window(0x600002)]);
+    assert_eq!(reply(&atoms, &clients).type_, xproto::ATOM_WINDOW);
+    assert_eq!(roundtrip(&atoms, &clients), Some(clients));
+    assert_eq!(roundtrip(&atoms, &NetClientListStacking(vec![])),
+               Some(NetClientListStacking(vec![])));
+
+    let desktops = NetNumberOfDesktops(4);
+    assert_eq!(reply(&atoms, &desktops).value.len(), 4);
+    assert_eq!(roundtrip(&atoms, &desktops), Some(desktops));
+
+    let all = NetWmDesktop(ALL_DESKTOPS);
+    assert_eq!(roundtrip(&atoms, &all), Some(all));
+
+    let counter = NetWmSyncRequestCounter(Counter(0x400010));
+    assert_eq!(roundtrip(&atoms, &counter), Some(counter));
+
+    assert_eq!(roundtrip(&atoms, &NetShowingDesktop(true)), Some(NetShowingDesktop(true)));
+    assert_eq!(roundtrip(&atoms, &NetWmUserTime(1234)), Some(NetWmUserTime(1234)));
+
+    // Values need at least one item of format 32.
+    assert_eq!(NetNumberOfDesktops::decode(&atoms, &cardinals(&[])), None);
+    let mut bytes = cardinals(&[4]);
+    bytes.format = 8;
+    assert_eq!(NetNumberOfDesktops::decode(&atoms, &bytes), None);
+}
+
+#[test]
+fn desktop_geometry_and_layout() {
+    let atoms = atoms();
+
+    let geometry = NetDesktopGeometry {
+        width: 3840,
+        height: 1080,
+    };
+    assert_eq!(roundtrip(&atoms, &geometry), Some(geometry));
+
+    let viewport = NetDesktopViewport(vec![(0, 0), (1920, 0)]);
+    assert_eq!(reply(&atoms, &viewport).value.len(), 4 * 4);
+    assert_eq!(roundtrip(&atoms, &viewport), Some(viewport));
+    assert_eq!(NetDesktopViewport::decode(&atoms, &cardinals(&[0, 0, 1920])), None);
+
+    let layout = NetDesktopLayout {
+        orientation: Orientation::Vertical,
+        columns: 2,
+        rows: 3,
+        starting_corner: Corner::BottomLeft,
+    };
+    assert_eq!(roundtrip(&atoms, &layout), Some(layout));
+
+    // The starting corner may be omitted.
+    assert_eq!(NetDesktopLayout::decode(&atoms, &cardinals(&[0, 4, 1])),
+               Some(NetDesktopLayout {
+                   orientation: Orientation::Horizontal,
+                   columns: 4,
+                   rows: 1,
+                   starting_corner: Corner::TopLeft,
+               }));
+}
+
+#[test]
+fn texts() {
+    let atoms = atoms();
+
+    let name = NetWmName("Résumé – 日本語".to_owned());
+    let data = reply(&atoms, &name);
+    assert_eq!((data.type_, data.format), (atoms.utf8_string, 8));
+    assert_eq!(roundtrip(&atoms, &name), Some(name));
+
+    let icon_name = NetWmVisibleIconName("term".to_owned());
+    assert_eq!(roundtrip(&atoms, &icon_name), Some(icon_name));
+
+    // Only UTF8_STRING is accepted.
+    let mut latin1 = reply(&atoms, &NetWmName("abc".to_owned()));
+    latin1.type_ = xproto::ATOM_STRING;
+    assert_eq!(NetWmName::decode(&atoms, &latin1), None);
+
+    let names = NetDesktopNames(vec!["web".to_owned(), "".to_owned(), "mail".to_owned()]);
+    assert_eq!(reply(&atoms, &names).value, b"web\0\0mail\0".to_vec());
+    assert_eq!(roundtrip(&atoms, &names), Some(names));
+    assert_eq!(roundtrip(&atoms, &NetDesktopNames(vec![])), Some(NetDesktopNames(vec![])));
+
+    // The last terminator may be missing.
+    let mut unterminated = reply(&atoms, &NetDesktopNames(vec!["a".to_owned(), "b".to_owned()]));
+    unterminated.value.pop();
+    assert_eq!(NetDesktopNames::decode(&atoms, &unterminated),
+               Some(NetDesktopNames(vec!["a".to_owned(), "b".to_owned()])));
+}
+
+#[test]
+fn rectangles_and_struts() {
+    let atoms = atoms();
+
+    let rect = Rect {
+        x: -10,
+        y: 20,
+        width: 300,
+        height: 200,
+    };
+
+    let workarea = NetWorkarea(vec![rect, Rect::default()]);
+    assert_eq!(reply(&atoms, &workarea).value.len(), 8 * 4);
+    assert_eq!(roundtrip(&atoms, &workarea), Some(workarea));
+    assert_eq!(NetWorkarea::decode(&atoms, &cardinals(&[0, 0, 10])), None);
+
+    let region = NetWmOpaqueRegion(vec![rect]);
+    assert_eq!(roundtrip(&atoms, &region), Some(region));
+
+    let geometry = NetWmIconGeometry(rect);
+    assert_eq!(roundtrip(&atoms, &geometry), Some(geometry));
+
+    let strut = NetWmStrut {
+        left: 0,
+        right: 0,
+        top: 24,
+        bottom: 0,
+    };
+    assert_eq!(reply(&atoms, &strut).value.len(), 4 * 4);
+    assert_eq!(roundtrip(&atoms, &strut), Some(strut));
+
+    let extents = NetFrameExtents {
+        left: 1,
+        right: 2,
+        top: 20,
+        bottom: 3,
+    };
+    assert_eq!(roundtrip(&atoms, &extents), Some(extents));
+
+    let partial = NetWmStrutPartial {
+        top: 24,
+        top_start_x: 0,
+        top_end_x: 1919,
+        ..NetWmStrutPartial::default()
+    };
+    assert_eq!(reply(&atoms, &partial).value.len(), 12 * 4);
+    assert_eq!(roundtrip(&atoms, &partial), Some(partial));
+    assert_eq!(NetWmStrutPartial::decode(&atoms, &cardinals(&[0; 4])), None);
+}
+
+#[test]
+fn icons() {
+    let atoms = atoms();
+
+    let icons = NetWmIcon(vec![Icon {
+                                   width: 2,
+                                   height: 1,
+                                   data: vec![0xff000000, 0xffffffff],
+                               },
+                               Icon {
+                                   width: 1,
+                                   height: 1,
+                                   data: vec![0x80ff0000],
+                               }]);
+    assert_eq!(reply(&atoms, &icons).value.len(), (4 + 3) * 4);
+    assert_eq!(roundtrip(&atoms, &icons), Some(icons));
+
+    // A truncated icon is dropped, the complete ones are kept.
+    let truncated = cardinals(&[1, 1, 0xffffffff, 16, 16, 0]);
+    assert_eq!(NetWmIcon::decode(&atoms, &truncated),
+               Some(NetWmIcon(vec![Icon {
+                                       width: 1,
+                                       height: 1,
+                                       data: vec![0xffffffff],
+                                   }])));
+}
+
+#[test]
+fn large_icons_are_sent_in_chunks() {
+    let atoms = atoms();
+    let server = FakeServer::start(|_, _| vec![]);
+
+    // 262152 bytes, over the 262116 bytes of data a request can carry.
+    let icon = NetWmIcon(vec![Icon {
+                                  width: 256,
+                                  height: 256,
+                                  data: vec![0xff336699; 256 * 256],
+                              }]);
+    let (mut lp, client) = server.connect();
+    let (client, _) = lp.run(set(client, &atoms, Window(0x400001), &icon)).unwrap();
+    drop(client);
+
+    let requests = server.finish();
+    // ChangeProperty
+    assert!(requests.iter().all(|r| r[0] == 18));
+    let modes: Vec<u8> = requests.iter().map(|r| r[1]).collect();
+    assert_eq!(modes, vec![0, 2]);
+
+    let mut data = Vec::new();
+    for r in &requests {
+        assert_eq!(u32::from_ne_bytes([r[8], r[9], r[10], r[11]]), atoms.net_wm_icon.0);
+        let length = u16::from_ne_bytes([r[2], r[3]]) as usize * 4;
+        assert_eq!(r.len(), length);
+        let items = u32::from_ne_bytes([r[20], r[21], r[22], r[23]]) as usize;
+        data.extend_from_slice(&r[24..24 + items * 4]);
+    }
+    assert_eq!(data, reply(&atoms, &icon).value);
+}
+
+fn message_roundtrip(atoms: &Atoms, message: &NetMessage) -> Option<NetMessage> {
+    let event = message.to_event(atoms);
+    assert_eq!(event.format, 32);
+
+    NetMessage::from_event(atoms, &Event::ClientMessage(event))
+}
+
+#[test]
+fn messages() {
+    let atoms = atoms();
+    let window = Window(0x400001);
+
+    let messages = [NetMessage::ActiveWindow {
+                        window,
+                        source: Source::Application,
+                        time: 42,
+                        current_active: Window(0x600001),
+                    },
+                    NetMessage::WmState {
+                        window,
+                        action: StateAction::Toggle,
+                        first: atoms.net_wm_state_maximized_horz,
+                        second: atoms.net_wm_state_maximized_vert,
+                        source: Source::Pager,
+                    },
+                    NetMessage::CloseWindow {
+                        window,
+                        time: 7,
+                        source: Source::Legacy,
+                    },
+                    NetMessage::MoveResizeWindow {
+                        window,
+                        geometry: MoveResize {
+                            gravity: 10,
+                            x: Some(-5),
+                            height: Some(300),
+                            ..MoveResize::default()
+                        },
+                        source: Source::Pager,
+                    },
+                    NetMessage::CurrentDesktop {
+                        root: Window(0x100),
+                        desktop: 2,
+                        time: 9,
+                    },
+                    NetMessage::WmDesktop {
+                        window,
+                        desktop: ALL_DESKTOPS,
+                        source: Source::Application,
+                    }];
+
+    for message in &messages {
+        assert_eq!(message_roundtrip(&atoms, message), Some(*message));
+    }
+}
+
+#[test]
+fn message_layout() {
+    let atoms = atoms();
+
+    let event = NetMessage::MoveResizeWindow {
+            window: Window(0x400001),
+            geometry: MoveResize {
+                gravity: 1,
+                y: Some(20),
+                width: Some(640),
+                ..MoveResize::default()
+            },
+            source: Source::Pager,
+        }
+        .to_event(&atoms);
+
+    assert_eq!(event.window, Window(0x400001));
+    assert_eq!(event.type_, atoms.net_moveresize_window);
+    assert_eq!(event.data32(), [1 | 1 << 9 | 1 << 10 | 2 << 12, 0, 20, 640, 0]);
+
+    let event = NetMessage::WmState {
+            window: Window(0x400001),
+            action: StateAction::Add,
+            first: atoms.net_wm_state_fullscreen,
+            second: Atom::NONE,
+            source: Source::Application,
+        }
+        .to_event(&atoms);
+    assert_eq!(event.data32(), [1, atoms.net_wm_state_fullscreen.0, 0, 1, 0]);
+
+    // `_NET_CURRENT_DESKTOP` is addressed to the root window.
+    let event = NetMessage::CurrentDesktop {
+            root: Window(0x100),
+            desktop: 1,
+            time: 5,
+        }
+        .to_event(&atoms);
+    assert_eq!(event.window, Window(0x100));
+    assert_eq!(event.data32(), [1, 5, 0, 0, 0]);
+}
+
+#[test]
+fn unknown_messages_are_ignored() {
+    let atoms = atoms();
+    let window = Window(0x400001);
+
+    let other = ClientMessageEvent::new32(window, atoms.net_wm_ping, [0; 5]);
+    assert_eq!(NetMessage::from_event(&atoms, &Event::ClientMessage(other)), None);
+
+    // A `_NET_WM_STATE` message with an invalid action.
+    let invalid = ClientMessageEvent::new32(window, atoms.net_wm_state, [3, 0, 0, 0, 0]);
+    assert_eq!(NetMessage::from_event(&atoms, &Event::ClientMessage(invalid)), None);
+
+    let mut bytes = ClientMessageEvent::new32(window, atoms.net_close_window, [0; 5]);
+    bytes.format = 8;
+    assert_eq!(NetMessage::from_event(&atoms, &Event::ClientMessage(bytes)), None);
+}
@@ -0,0 +1,24 @@
+extern crate xrb;
+
+use xrb::xproto::QueryExtensionReply;
+
+#[test]
+fn decode_query_extension() {
+    let mut buf = vec![0u8; 32];
+    buf[0] = 1;
+    buf[2..4].copy_from_slice(&7u16.to_ne_bytes());
+    buf[8] = 1;
+    buf[9] = 140;
+    buf[10] = 90;
+    buf[11] = 150;
+
+    let info = QueryExtensionReply::read(&buf);
+    assert!(info.present);
+    assert_eq!(info.major_opcode, 140);
+    assert_eq!(info.first_event, 90);
+    assert_eq!(info.first_error, 150);
+
+    buf[8] = 0;
+    buf[9] = 0;
+    assert!(!QueryExtensionReply::read(&buf).present);
+}
@@ -0,0 +1,89 @@
+extern crate xrb;
+
+use xrb::icccm::*;
+use xrb::xproto::Atom;
+use xrb::xproto::GetPropertyReply;
+use xrb::xproto::WinGravity;
+use xrb::xproto::Window;
+
+fn atoms() -> Atoms {
+    Atoms {
+        wm_protocols: Atom(300),
+        wm_delete_window: Atom(301),
+        wm_take_focus: Atom(302),
+        utf8_string: Atom(303),
+        compound_text: Atom(304),
+    }
+}
+
+fn roundtrip<P: WmProperty>(value: &P) -> Option<P> {
+    let atoms = atoms();
+    let data = value.encode(&atoms);
+    let reply = GetPropertyReply {
+        format: data.format,
+        type_: data.type_,
+        bytes_after: 0,
+        value: data.data,
+    };
+
+    P::decode(&atoms, &reply)
+}
+
+#[test]
+fn wm_name() {
+    let latin1 = WmName("Café".to_owned());
+    assert_eq!(latin1.encode(&atoms()).type_, xrb::xproto::ATOM_STRING);
+    assert_eq!(roundtrip(&latin1), Some(latin1));
+
+    let utf8 = WmName("日本語".to_owned());
+    assert_eq!(utf8.encode(&atoms()).type_, atoms().utf8_string);
+    assert_eq!(roundtrip(&utf8), Some(utf8));
+}
+
+#[test]
+fn wm_class() {
+    let class = WmClass {
+        instance: "xterm".to_owned(),
+        class: "XTerm".to_owned(),
+    };
+
+    assert_eq!(class.encode(&atoms()).data, b"xterm\0XTerm\0".to_vec());
+    assert_eq!(roundtrip(&class), Some(class));
+}
+
+#[test]
+fn wm_size_hints() {
+    let hints = WmSizeHints {
+        position: Some((HintSource::User, -10, 20)),
+        min_size: Some((100, 50)),
+        resize_inc: Some((8, 16)),
+        aspect: Some(((1, 2), (2, 1))),
+        win_gravity: Some(WinGravity::Static),
+        ..WmSizeHints::default()
+    };
+
+    assert_eq!(hints.encode(&atoms()).data.len(), 18 * 4);
+    assert_eq!(roundtrip(&hints), Some(hints));
+}
+
+#[test]
+fn wm_hints() {
+    let hints = WmHints {
+        input: Some(true),
+        initial_state: Some(WmState::Iconic),
+        window_group: Some(Window(0x200001)),
+        urgent: true,
+        ..WmHints::default()
+    };
+
+    assert_eq!(roundtrip(&hints), Some(hints));
+}
+
+#[test]
+fn wm_protocols_and_transient_for() {
+    let protocols = WmProtocols(vec![atoms().wm_delete_window, atoms().wm_take_focus]);
+    assert_eq!(roundtrip(&protocols), Some(protocols));
+
+    let transient = WmTransientFor(Window(0x400003));
+    assert_eq!(roundtrip(&transient), Some(transient));
+}
@@ -0,0 +1,60 @@
+extern crate xrb;
+
+use xrb::monitors::*;
+use xrb::protocol::ExtensionRequest;
+use xrb::randr::MonitorInfo;
+use xrb::randr::Output;
+use xrb::xinerama::XineramaGetScreenSize;
+use xrb::xproto::Atom;
+use xrb::xproto::QueryExtensionReply;
+use xrb::xproto::Rectangle;
+use xrb::xproto::Window;
+
+#[test]
+fn xinerama_encoding() {
+    let info = QueryExtensionReply {
+        present: true,
+        major_opcode: 135,
+        first_event: 0,
+        first_error: 0,
+    };
+
+    let buf = XineramaGetScreenSize { window: Window(1), screen: 2 }.encode(&info).unwrap();
+    assert_eq!(buf.len(), 12);
+    assert_eq!(&buf[..4], &[135, 3, 3, 0]);
+    assert_eq!(&buf[8..12], &2u32.to_ne_bytes());
+}
+
+#[test]
+fn monitor_lookup() {
+    let left = Monitor::from_xinerama(0,
+                                      &Rectangle {
+                                          x: 0,
+                                          y: 0,
+                                          width: 1920,
+                                          height: 1080,
+                                      });
+    let right = Monitor::from_randr(&MonitorInfo {
+        name: Atom(400),
+        primary: false,
+        automatic: true,
+        x: 1920,
+        y: 0,
+        width: 1280,
+        height: 1024,
+        width_mm: 340,
+        height_mm: 270,
+        outputs: vec![Output(1)],
+    });
+
+    assert!(left.primary);
+    assert_eq!(left.source, MonitorSource::Xinerama);
+    assert_eq!(right.source, MonitorSource::RandR);
+    assert!(!right.contains(1919, 10));
+    assert!(right.contains(1920, 1023));
+
+    let monitors = vec![left, right];
+    assert_eq!(monitor_at(&monitors, 2000, 500).unwrap().name, Atom(400));
+    assert!(monitor_at(&monitors, 2000, 1050).unwrap().primary);
+    assert!(monitor_at(&[], 0, 0).is_none());
+}
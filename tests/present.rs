@@ -0,0 +1,100 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use common::MAJOR_OPCODE;
+use common::info;
+use xrb::event::Event;
+use xrb::present::*;
+use xrb::protocol::ExtensionRequest;
+use xrb::xproto::Pixmap;
+use xrb::xproto::Window;
+
+fn generic(evtype: u16, eid: u32) -> Vec<u8> {
+    let mut buf = vec![0u8; 40];
+    buf[0] = 35;
+    buf[1] = MAJOR_OPCODE;
+    buf[4..8].copy_from_slice(&2u32.to_ne_bytes());
+    buf[8..10].copy_from_slice(&evtype.to_ne_bytes());
+    buf[12..16].copy_from_slice(&eid.to_ne_bytes());
+    buf[16..20].copy_from_slice(&9u32.to_ne_bytes());
+    buf
+}
+
+fn complete(serial: u32, msc: u64, ust: u64) -> Event {
+    let mut buf = generic(1, 5);
+    buf[11] = 1;
+    buf[20..24].copy_from_slice(&serial.to_ne_bytes());
+    buf[24..32].copy_from_slice(&ust.to_ne_bytes());
+    buf[32..40].copy_from_slice(&msc.to_ne_bytes());
+    Event::Unknown(buf)
+}
+
+fn idle(serial: u32, pixmap: u32) -> Event {
+    let mut buf = generic(2, 5);
+    buf[20..24].copy_from_slice(&serial.to_ne_bytes());
+    buf[24..28].copy_from_slice(&pixmap.to_ne_bytes());
+    Event::Unknown(buf)
+}
+
+#[test]
+fn pixmap_length() {
+    let mut request = PresentPixmap::new(Window(9), Pixmap(1), 3);
+    request.notifies.push(Notify { window: Window(2), serial: 4 });
+    request.target_msc = 100;
+
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(buf.len(), 80);
+    assert_eq!(&buf[..2], &[MAJOR_OPCODE, 1]);
+    assert_eq!(u16::from_ne_bytes([buf[2], buf[3]]) as usize * 4, buf.len());
+    assert_eq!(&buf[48..56], &100u64.to_ne_bytes());
+}
+
+#[test]
+fn decode_complete_notify() {
+    match PresentEvent::from_event(&info(), &complete(7, 60, 1_000_000)).unwrap() {
+        PresentEvent::CompleteNotify(ev) => {
+            assert_eq!(ev.kind, CompleteKind::Pixmap);
+            assert_eq!(ev.mode, CompleteMode::Flip);
+            assert_eq!(ev.eid, EventId(5));
+            assert_eq!(ev.window, Window(9));
+            assert_eq!(ev.serial, 7);
+            assert_eq!(ev.msc, 60);
+            assert_eq!(ev.ust, 1_000_000);
+        }
+        ev => panic!("unexpected event {:?}", ev),
+    }
+
+    let mut other = generic(1, 5);
+    other[1] = 149;
+    assert!(PresentEvent::read(&info(), &other).is_none());
+}
+
+#[test]
+fn scheduler_tracks_frames() {
+    let mut scheduler = FrameScheduler::with_selection(info(), Window(9), EventId(5));
+    scheduler.add_pixmap(Pixmap(1));
+    scheduler.add_pixmap(Pixmap(2));
+    assert_eq!(scheduler.next_msc(), 0);
+
+    let ev = scheduler.handle(&complete(1, 100, 1_000_000)).unwrap();
+    assert_eq!(ev.serial, 1);
+    assert_eq!(scheduler.next_msc(), 101);
+    assert_eq!(scheduler.frame_interval(), None);
+
+    scheduler.handle(&complete(2, 102, 1_033_332));
+    assert_eq!(scheduler.frame_interval(), Some(16_666));
+    assert_eq!(scheduler.ust_at(104), Some(1_066_664));
+
+    // Events of other selections are ignored.
+    let mut buf = generic(1, 6);
+    buf[32..40].copy_from_slice(&500u64.to_ne_bytes());
+    assert!(scheduler.handle(&Event::Unknown(buf)).is_none());
+    assert_eq!(scheduler.msc(), 102);
+
+    assert_eq!(scheduler.idle_pixmap(), Some(Pixmap(1)));
+    assert!(scheduler.handle(&idle(3, 1)).is_none());
+    assert!(!scheduler.is_busy(Pixmap(1)));
+}
@@ -0,0 +1,128 @@
+extern crate xrb;
+
+use xrb::randr::*;
+use xrb::xproto::QueryExtensionReply;
+use xrb::xproto::Timestamp;
+use xrb::xproto::Window;
+
+fn mode(id: u32, width: u16, height: u16) -> ModeInfo {
+    ModeInfo {
+        id: Mode(id),
+        width,
+        height,
+        dot_clock: 148500000,
+        hsync_start: 0,
+        hsync_end: 0,
+        htotal: 2200,
+        hskew: 0,
+        vsync_start: 0,
+        vsync_end: 0,
+        vtotal: 1125,
+        mode_flags: 0,
+        name: format!("{}x{}", width, height),
+    }
+}
+
+fn output(id: u32, name: &str, crtcs: &[u32]) -> OutputState {
+    OutputState {
+        output: Output(id),
+        name: name.to_owned(),
+        connection: Connection::Connected,
+        crtc: Crtc::NONE,
+        crtcs: crtcs.iter().map(|&c| Crtc(c)).collect(),
+        modes: vec![Mode(1), Mode(2)],
+        preferred_mode: Some(Mode(1)),
+        mm_width: 520,
+        mm_height: 290,
+    }
+}
+
+fn layout() -> Layout {
+    Layout {
+        root: Window(0x100),
+        config_timestamp: 1000 as Timestamp,
+        modes: vec![mode(1, 1920, 1080), mode(2, 1280, 1024)],
+        outputs: vec![output(10, "DP-1", &[20, 21]), output(11, "HDMI-1", &[20, 21])],
+        crtcs: vec![CrtcConfig {
+                        crtc: Crtc(20),
+                        x: 0,
+                        y: 0,
+                        mode: Mode(1),
+                        rotation: ROTATE_0,
+                        outputs: vec![Output(10)],
+                    },
+                    CrtcConfig {
+                        crtc: Crtc(21),
+                        x: 0,
+                        y: 0,
+                        mode: Mode::NONE,
+                        rotation: ROTATE_0,
+                        outputs: vec![],
+                    }],
+    }
+}
+
+#[test]
+fn refresh_rate() {
+    let rate = mode(1, 1920, 1080).refresh_rate();
+    assert!((rate - 60.0).abs() < 0.01);
+}
+
+#[test]
+fn layout_diff() {
+    let current = layout();
+    assert!(current.diff(&current).is_empty());
+    assert_eq!(current.screen_size(), (1920, 1080));
+
+    let mut target = current.clone();
+    let hdmi = target.output_by_name("HDMI-1").unwrap().output;
+    assert!(target.enable_output(hdmi, Mode(2), 1920, 0, ROTATE_90));
+    assert_eq!(target.crtc_of(hdmi).unwrap().crtc, Crtc(21));
+    assert_eq!(target.screen_size(), (1920 + 1024, 1280));
+
+    let changes = current.diff(&target);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].crtc, Crtc(21));
+    assert_eq!(changes[0].outputs, vec![hdmi]);
+
+    // No free CRTC left.
+    target.outputs.push(output(12, "VGA-1", &[20, 21]));
+    assert!(!target.enable_output(Output(12), Mode(1), 0, 0, ROTATE_0));
+
+    target.disable_output(Output(10));
+    assert!(!target.crtc(Crtc(20)).unwrap().is_enabled());
+    assert_eq!(current.diff(&target).len(), 2);
+}
+
+#[test]
+fn events() {
+    let info = QueryExtensionReply {
+        present: true,
+        major_opcode: 140,
+        first_event: 89,
+        first_error: 147,
+    };
+
+    let mut buf = [0u8; 32];
+    buf[0] = 90;
+    buf[1] = 1;
+    buf[16..20].copy_from_slice(&7u32.to_ne_bytes());
+    buf[30] = 1;
+    match RandrEvent::read(&info, &buf) {
+        Some(RandrEvent::OutputChangeNotify(ev)) => {
+            assert_eq!(ev.output, Output(7));
+            assert_eq!(ev.connection, Connection::Disconnected);
+        }
+        ev => panic!("unexpected event {:?}", ev),
+    }
+
+    buf[0] = 89 | 0x80;
+    buf[24..26].copy_from_slice(&1920u16.to_ne_bytes());
+    match RandrEvent::read(&info, &buf) {
+        Some(RandrEvent::ScreenChangeNotify(ev)) => assert_eq!(ev.width, 1920),
+        ev => panic!("unexpected event {:?}", ev),
+    }
+
+    buf[0] = 33;
+    assert!(RandrEvent::read(&info, &buf).is_none());
+}
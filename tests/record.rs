@@ -0,0 +1,91 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use common::MAJOR_OPCODE;
+use common::info;
+use xrb::event::Event;
+use xrb::protocol::ExtensionRequest;
+use xrb::record::*;
+
+fn reply(category: u8, element_header: u8, data: &[u8]) -> Vec<u8> {
+    let mut buf = vec![0u8; 32];
+    buf[0] = 1;
+    buf[1] = category;
+    buf[4..8].copy_from_slice(&(data.len() as u32 / 4).to_ne_bytes());
+    buf[8] = element_header;
+    buf[12..16].copy_from_slice(&0x400000u32.to_ne_bytes());
+    buf.extend_from_slice(data);
+    buf
+}
+
+#[test]
+fn create_context_encoding() {
+    let mut request = RecordCreateContext {
+        context: Context(7),
+        element_header: ELEMENT_HEADER_FROM_SERVER_TIME,
+        client_specs: vec![CLIENT_SPEC_ALL_CLIENTS],
+        ranges: vec![Range::input()],
+    };
+
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(buf.len(), 20 + 4 + 24);
+    assert_eq!(&buf[..4], &[MAJOR_OPCODE, 1, 12, 0]);
+    assert_eq!(buf[8], ELEMENT_HEADER_FROM_SERVER_TIME);
+    assert_eq!(&buf[20..24], &3u32.to_ne_bytes());
+
+    let range = &buf[24..];
+    assert_eq!(&range[16..20], &[0, 0, 2, 6]);
+    assert!(range.iter().enumerate().all(|(i, &b)| b == 0 || i == 18 || i == 19));
+}
+
+#[test]
+fn decode_intercepted_events() {
+    let mut event = vec![0u8; 32];
+    event[0] = 2;
+    event[1] = 38;
+    event[4..8].copy_from_slice(&1234u32.to_ne_bytes());
+    event[20..22].copy_from_slice(&100i16.to_ne_bytes());
+
+    let mut data = 5000u32.to_ne_bytes().to_vec();
+    data.extend_from_slice(&event);
+
+    let record = RecordData::read(&reply(0, ELEMENT_HEADER_FROM_SERVER_TIME, &data));
+    assert_eq!(record.category, Category::FromServer);
+    assert_eq!(record.id_base, 0x400000);
+
+    let elements = record.elements();
+    assert_eq!(elements.len(), 1);
+    assert_eq!(elements[0].time, Some(5000));
+    match elements[0].data {
+        Intercepted::Event(Event::KeyPress(ref input)) => {
+            assert_eq!(input.detail, 38);
+            assert_eq!(input.time, 1234);
+            assert_eq!(input.root_x, 100);
+        }
+        ref other => panic!("unexpected element {:?}", other),
+    }
+}
+
+#[test]
+fn decode_intercepted_requests() {
+    let request = [8u8, 0, 2, 0, 1, 0, 0, 0];
+    let mut data = 42u32.to_ne_bytes().to_vec();
+    data.extend_from_slice(&request);
+    data.extend_from_slice(&[0; 4]);
+
+    let record = RecordData::read(&reply(1, ELEMENT_HEADER_FROM_CLIENT_SEQUENCE, &data));
+    let elements = record.elements();
+    assert_eq!(elements.len(), 1);
+    assert_eq!(elements[0].sequence, Some(42));
+    match elements[0].data {
+        Intercepted::Request(ref buf) => assert_eq!(&buf[..], &request),
+        ref other => panic!("unexpected element {:?}", other),
+    }
+
+    let end = RecordData::read(&reply(5, 0, &[]));
+    assert_eq!(end.category, Category::EndOfData);
+    assert!(end.elements().is_empty());
+}
@@ -0,0 +1,154 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use std::io;
+
+use common::info;
+use xrb::protocol::ExtensionRequest;
+use xrb::render::*;
+use xrb::xproto::Colormap;
+use xrb::xproto::Rectangle;
+
+fn format(depth: u8, direct: DirectFormat) -> PictFormInfo {
+    PictFormInfo {
+        id: Pictformat(1),
+        type_: PictType::Direct,
+        depth,
+        direct,
+        colormap: Colormap::NONE,
+    }
+}
+
+#[test]
+fn fixed() {
+    assert_eq!(Fixed::from_int(1), Fixed::ONE);
+    assert_eq!(Fixed::from_f64(-1.5), Fixed(-0x18000));
+    assert_eq!(Fixed(0x8000).to_f64(), 0.5);
+}
+
+#[test]
+fn color_premultiplied() {
+    assert_eq!(Color::from_rgba8(255, 0, 255, 255),
+               Color { red: 0xffff, green: 0, blue: 0xffff, alpha: 0xffff });
+
+    let half = Color::from_rgba8(255, 255, 255, 0x80);
+    assert_eq!(half.alpha, 0x8080);
+    assert_eq!(half.red, 0x8080);
+}
+
+#[test]
+fn standard_formats() {
+    let argb32 = format(32,
+                        DirectFormat {
+                            red_shift: 16,
+                            red_mask: 0xff,
+                            green_shift: 8,
+                            green_mask: 0xff,
+                            blue_shift: 0,
+                            blue_mask: 0xff,
+                            alpha_shift: 24,
+                            alpha_mask: 0xff,
+                        });
+    assert!(StandardFormat::Argb32.matches(&argb32));
+    assert!(!StandardFormat::Rgb24.matches(&argb32));
+
+    let a8 = format(8, DirectFormat { alpha_mask: 0xff, ..DirectFormat::default() });
+    assert!(StandardFormat::A8.matches(&a8));
+    assert_eq!(StandardFormat::from_name("A8"), Some(StandardFormat::A8));
+}
+
+#[test]
+fn add_glyphs_padding() {
+    let mut request = RenderAddGlyphs {
+        glyphset: Glyphset(1),
+        glyphs: vec![GlyphData {
+                         id: 65,
+                         info: GlyphInfo { width: 3, height: 2, ..GlyphInfo::default() },
+                         image: vec![0xff; 2 * 4],
+                     },
+                     GlyphData {
+                         id: 66,
+                         info: GlyphInfo { width: 1, height: 1, ..GlyphInfo::default() },
+                         image: vec![0xff; 2],
+                     }],
+    };
+
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(buf.len(), 12 + 2 * 16 + 12);
+    assert_eq!(u16::from_ne_bytes([buf[2], buf[3]]) as usize * 4, buf.len());
+}
+
+#[test]
+fn add_glyphs_over_the_request_length() {
+    let mut request = RenderAddGlyphs {
+        glyphset: Glyphset(1),
+        glyphs: vec![GlyphData {
+                         id: 65,
+                         info: GlyphInfo { width: 600, height: 600, ..GlyphInfo::default() },
+                         image: vec![0xff; 600 * 600],
+                     }],
+    };
+
+    let e = request.encode(&info()).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn lists_over_the_request_length() {
+    let mut request = RenderFillRectangles {
+        op: PictOp::Src,
+        dst: Picture(1),
+        color: Color::default(),
+        rects: vec![Rectangle::default(); 40000],
+    };
+    let e = request.encode(&info()).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+
+    let stop = Stop {
+        offset: Fixed::ONE,
+        color: Color::default(),
+    };
+    let mut request = RenderCreateLinearGradient {
+        picture: Picture(1),
+        p1: PointFix::default(),
+        p2: PointFix::default(),
+        stops: vec![stop; 30000],
+    };
+    let e = request.encode(&info()).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn composite_glyphs_splits_long_runs() {
+    let mut request = RenderCompositeGlyphs8 {
+        op: PictOp::Over,
+        src: Picture(1),
+        dst: Picture(2),
+        mask_format: Pictformat::NONE,
+        glyphset: Glyphset(3),
+        src_x: 0,
+        src_y: 0,
+        items: vec![GlyphItem::Glyphs { dx: 10, dy: 20, glyphs: vec![b'a' as Glyph; 300] },
+                    GlyphItem::Glyphset(Glyphset(4))],
+    };
+
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(u16::from_ne_bytes([buf[2], buf[3]]) as usize * 4, buf.len());
+
+    // 254 glyphs, then 46 more continuing from the previous element.
+    assert_eq!(buf[28], 254);
+    assert_eq!(i16::from_ne_bytes([buf[32], buf[33]]), 10);
+    let second = 28 + 8 + 256;
+    assert_eq!(buf[second], 46);
+    assert_eq!(i16::from_ne_bytes([buf[second + 4], buf[second + 5]]), 0);
+
+    let switch = second + 8 + 48;
+    assert_eq!(buf[switch], 255);
+    assert_eq!(u32::from_ne_bytes([buf[switch + 8], buf[switch + 9], buf[switch + 10],
+                                   buf[switch + 11]]),
+               4);
+    assert_eq!(buf.len(), switch + 12);
+}
@@ -0,0 +1,197 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+extern crate futures;
+
+mod common;
+
+use std::io;
+
+use futures::Stream;
+
+use common::FakeServer;
+use common::MAJOR_OPCODE;
+use xrb::protocol;
+use xrb::protocol::Request;
+use xrb::xproto::ATOM_CARDINAL;
+use xrb::xproto::Atom;
+use xrb::xproto::ChangeProperty;
+use xrb::xproto::MapWindow;
+use xrb::xproto::PropMode;
+use xrb::xproto::QueryExtension;
+use xrb::record::Category;
+use xrb::record::Context;
+use xrb::record::RecordStream;
+use xrb::xproto::Window;
+use xrb::xproto;
+
+fn query(name: &[u8]) -> QueryExtension {
+    QueryExtension { name: name.to_vec() }
+}
+
+#[test]
+fn events_before_a_reply_are_skipped() {
+    let server = FakeServer::start(|sequence, _| {
+        vec![common::event(28, sequence - 1),
+             common::error(sequence - 1, 3, 18),
+             common::query_extension_reply(sequence, true, 130)]
+    });
+
+    let (mut lp, client) = server.connect();
+    let (client, info) = lp.run(client.perform(query(b"RANDR"))).unwrap();
+    assert!(info.present);
+    assert_eq!(info.major_opcode, 130);
+
+    drop(client);
+    assert_eq!(server.finish().len(), 1);
+}
+
+#[test]
+fn error_of_the_request_fails_it() {
+    let server = FakeServer::start(|sequence, _| {
+        if sequence == 1 {
+            vec![common::query_extension_reply(sequence, false, 0)]
+        } else {
+            vec![common::error(sequence, 2, 98)]
+        }
+    });
+
+    let (mut lp, client) = server.connect();
+    let (client, _) = lp.run(client.perform(query(b"A"))).unwrap();
+    let e = match lp.run(client.perform(query(b"B"))) {
+        Err(e) => e,
+        Ok(_) => panic!("the request should fail"),
+    };
+
+    match e.get_ref().and_then(|e| e.downcast_ref::<protocol::Error>()) {
+        Some(&protocol::Error::Value { sequence_number, major_opcode, .. }) => {
+            assert_eq!(sequence_number, 2);
+            assert_eq!(major_opcode, 98);
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+}
+
+#[test]
+fn replies_out_of_order() {
+    // The second request is answered before the first one.
+    let server = FakeServer::start(|sequence, _| {
+        match sequence {
+            1 => vec![],
+            _ => {
+                vec![common::query_extension_reply(sequence, true, 131),
+                     common::query_extension_reply(sequence - 1, true, 130)]
+            }
+        }
+    });
+
+    let (mut lp, client) = server.connect();
+    let (client, infos) = lp.run(xproto::query_extensions(client, &[b"A", b"B"])).unwrap();
+    let opcodes: Vec<u8> = infos.iter().map(|info| info.major_opcode).collect();
+    assert_eq!(opcodes, vec![130, 131]);
+
+    drop(client);
+    assert_eq!(server.finish().len(), 2);
+}
+
+#[test]
+fn replies_nobody_read_are_dropped() {
+    // The first request gets a second reply, read while waiting for the
+    // next one. Its sequence number comes back after 65536 requests.
+    let server = FakeServer::start(|sequence, request| {
+        match request[0] {
+            // QueryExtension of "A"
+            98 if request[8] == b'A' => {
+                vec![common::query_extension_reply(sequence, true, 130),
+                     common::query_extension_reply(sequence, true, 140)]
+            }
+            98 => vec![common::query_extension_reply(sequence, true, 131)],
+            _ => vec![],
+        }
+    });
+
+    let (mut lp, client) = server.connect();
+    let (client, _) = lp.run(client.perform(query(b"A"))).unwrap();
+    let (client, _) = lp.run(client.perform(query(b"B"))).unwrap();
+
+    let maps = (0..65534).map(|_| MapWindow { wid: Window(0x400001) }).collect();
+    let (client, _) = lp.run(client.send_all(maps)).unwrap();
+    let (client, info) = lp.run(client.perform(query(b"C"))).unwrap();
+    assert_eq!(info.major_opcode, 131);
+
+    drop(client);
+    assert_eq!(server.finish().len(), 65537);
+}
+
+/// A reply of `RecordEnableContext` with the given category and server time.
+fn record_data(sequence: u16, category: u8, time: u32) -> Vec<u8> {
+    let mut body = vec![0; 8];
+    body.extend_from_slice(&time.to_ne_bytes());
+    common::reply(sequence, category, &body)
+}
+
+#[test]
+fn several_replies_of_a_request() {
+    let server = FakeServer::start(|sequence, request| {
+        match (request[0], request[1]) {
+            // QueryExtension
+            (98, _) => vec![common::query_extension_reply(sequence, true, MAJOR_OPCODE)],
+            // RecordQueryVersion
+            (MAJOR_OPCODE, 0) => {
+                let mut version = 1u16.to_ne_bytes().to_vec();
+                version.extend_from_slice(&13u16.to_ne_bytes());
+                vec![common::reply(sequence, 0, &version)]
+            }
+            // RecordEnableContext, with an event in between its replies.
+            (MAJOR_OPCODE, 5) => {
+                vec![record_data(sequence, 4, 0),
+                     record_data(sequence, 0, 10),
+                     common::event(28, sequence),
+                     record_data(sequence, 1, 20),
+                     record_data(sequence, 5, 30)]
+            }
+            _ => vec![],
+        }
+    });
+
+    let (mut lp, client) = server.connect();
+    let stream = lp.run(RecordStream::enable(client, Context(0x400000))).unwrap();
+    let replies = lp.run(stream.collect()).unwrap();
+
+    let categories: Vec<(Category, u32)> =
+        replies.iter().map(|data| (data.category, data.server_time)).collect();
+    assert_eq!(categories,
+               vec![(Category::StartOfData, 0),
+                    (Category::FromServer, 10),
+                    (Category::FromClient, 20),
+                    (Category::EndOfData, 30)]);
+
+    assert_eq!(server.finish().len(), 3);
+}
+
+fn icon_property() -> ChangeProperty {
+    // A 256x256 `_NET_WM_ICON`, over the 16-bit length of a request.
+    ChangeProperty {
+        mode: PropMode::Replace,
+        window: Window(0x400001),
+        property: Atom(300),
+        type_: ATOM_CARDINAL,
+        format: 32,
+        data: vec![0; (2 + 256 * 256) * 4],
+    }
+}
+
+#[test]
+fn too_long_requests_are_rejected() {
+    let e = icon_property().encode().unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+
+    let server = FakeServer::start(|_, _| vec![]);
+    let (mut lp, client) = server.connect();
+    match lp.run(client.perform(icon_property())) {
+        Err(e) => assert_eq!(e.kind(), io::ErrorKind::InvalidInput),
+        Ok(_) => panic!("the request should fail"),
+    }
+
+    assert!(server.finish().is_empty());
+}
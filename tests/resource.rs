@@ -0,0 +1,29 @@
+extern crate xrb;
+
+use xrb::resource::Resource;
+use xrb::xproto::*;
+
+#[test]
+fn unions() {
+    assert_eq!(Drawable::from(Window(0x200001)), Drawable(0x200001));
+    assert_eq!(Drawable::from(Pixmap(0x200002)), Drawable(0x200002));
+    assert_eq!(Fontable::from(Font(0x200003)), Fontable(0x200003));
+    assert_eq!(Fontable::from(Gcontext(0x200004)), Fontable(0x200004));
+    assert_eq!(Xid::from(Atom::NONE), 0);
+}
+
+#[test]
+fn free_requests() {
+    let data = Window(0x200001).free_request().unwrap();
+    assert_eq!(data.len(), 8);
+    assert_eq!(data[0], 4);
+    assert_eq!(data[2..4], 2u16.to_ne_bytes());
+    assert_eq!(data[4..], 0x200001u32.to_ne_bytes());
+
+    let opcodes: Vec<u8> = vec![Pixmap(1).free_request().unwrap()[0],
+                                Gcontext(1).free_request().unwrap()[0],
+                                Font(1).free_request().unwrap()[0],
+                                Colormap(1).free_request().unwrap()[0],
+                                Cursor(1).free_request().unwrap()[0]];
+    assert_eq!(opcodes, vec![54, 60, 46, 79, 95]);
+}
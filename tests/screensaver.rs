@@ -0,0 +1,101 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use common::FIRST_EVENT;
+use common::MAJOR_OPCODE;
+use common::info;
+use xrb::dpms::*;
+use xrb::event::Event;
+use xrb::protocol::ExtensionRequest;
+use xrb::protocol::Request;
+use xrb::screensaver::*;
+use xrb::xproto::Blanking;
+use xrb::xproto::Exposures;
+use xrb::xproto::ForceScreenSaver;
+use xrb::xproto::ScreenSaverMode;
+use xrb::xproto::SetScreenSaver;
+use xrb::xproto::Visualid;
+use xrb::xproto::Window;
+use xrb::xproto::WindowAttributes;
+use xrb::xproto::WINDOW_CLASS_INPUT_OUTPUT;
+
+#[test]
+fn core_requests() {
+    let buf = SetScreenSaver {
+            timeout: -1,
+            interval: 0,
+            prefer_blanking: Blanking::Preferred,
+            allow_exposures: Exposures::Default,
+        }
+        .encode()
+        .unwrap();
+    assert_eq!(buf.len(), 12);
+    assert_eq!(&buf[..4], &[107, 0, 3, 0]);
+    assert_eq!(&buf[4..10], &[0xff, 0xff, 0, 0, 1, 2]);
+
+    let buf = ForceScreenSaver { mode: ScreenSaverMode::Activate }.encode().unwrap();
+    assert_eq!(buf, vec![115, 1, 1, 0]);
+}
+
+#[test]
+fn decode_notify() {
+    let mut buf = vec![0u8; 32];
+    buf[0] = FIRST_EVENT;
+    buf[1] = 1;
+    buf[4..8].copy_from_slice(&1000u32.to_ne_bytes());
+    buf[8..12].copy_from_slice(&0x100u32.to_ne_bytes());
+    buf[12..16].copy_from_slice(&0x200u32.to_ne_bytes());
+    buf[16] = 2;
+    buf[17] = 1;
+
+    let event = ScreenSaverNotifyEvent::from_event(&info(), &Event::Unknown(buf)).unwrap();
+    assert_eq!(event.state, State::On);
+    assert_eq!(event.time, 1000);
+    assert_eq!(event.root, Window(0x100));
+    assert_eq!(event.window, Window(0x200));
+    assert_eq!(event.kind, Kind::External);
+    assert!(event.forced);
+}
+
+#[test]
+fn set_attributes_length() {
+    let mut attrs = WindowAttributes::new();
+    attrs.background_pixel(0);
+
+    let mut request = ScreenSaverSetAttributes {
+        drawable: Window(1).into(),
+        x: 0,
+        y: 0,
+        width: 640,
+        height: 480,
+        border_width: 0,
+        class: WINDOW_CLASS_INPUT_OUTPUT as u8,
+        depth: 24,
+        visual: Visualid::COPY_FROM_PARENT,
+        attrs: attrs.build(),
+    };
+
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(buf.len(), 32);
+    assert_eq!(u16::from_ne_bytes([buf[2], buf[3]]) as usize * 4, buf.len());
+}
+
+#[test]
+fn dpms_requests() {
+    let buf = DPMSForceLevel { power_level: DPMSMode::Off }.encode(&info()).unwrap();
+    assert_eq!(&buf[..4], &[MAJOR_OPCODE, 6, 2, 0]);
+    assert_eq!(&buf[4..6], &3u16.to_ne_bytes());
+
+    let buf = DPMSSetTimeouts {
+            standby_timeout: 600,
+            suspend_timeout: 0,
+            off_timeout: 900,
+        }
+        .encode(&info())
+        .unwrap();
+    assert_eq!(buf.len(), 12);
+    assert_eq!(&buf[8..10], &900u16.to_ne_bytes());
+}
@@ -0,0 +1,73 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use std::io;
+
+use common::FIRST_EVENT;
+use common::MAJOR_OPCODE;
+use common::info;
+use xrb::event::Event;
+use xrb::protocol::ExtensionRequest;
+use xrb::shape::*;
+use xrb::xproto::Rectangle;
+use xrb::xproto::Window;
+
+#[test]
+fn decode_notify() {
+    let mut buf = vec![0u8; 32];
+    buf[0] = FIRST_EVENT;
+    buf[1] = 2;
+    buf[4..8].copy_from_slice(&7u32.to_ne_bytes());
+    buf[8..10].copy_from_slice(&(-3i16).to_ne_bytes());
+    buf[12..14].copy_from_slice(&20u16.to_ne_bytes());
+    buf[20] = 1;
+
+    let event = ShapeNotifyEvent::from_event(&info(), &Event::Unknown(buf.clone())).unwrap();
+    assert_eq!(event.kind, ShapeKind::Input);
+    assert_eq!(event.window, Window(7));
+    assert_eq!(event.extents.x, -3);
+    assert_eq!(event.extents.width, 20);
+    assert!(event.shaped);
+
+    buf[0] = FIRST_EVENT + 1;
+    assert!(ShapeNotifyEvent::read(&info(), &buf).is_none());
+}
+
+#[test]
+fn rectangles_length() {
+    let rect = Rectangle { x: 0, y: 0, width: 10, height: 10 };
+    let mut request = ShapeRectangles {
+        op: ShapeOp::Union,
+        dest_kind: ShapeKind::Bounding,
+        ordering: Ordering::YXBanded,
+        dest: Window(1),
+        x_offset: 0,
+        y_offset: 0,
+        rectangles: vec![rect; 3],
+    };
+
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(buf.len(), 40);
+    assert_eq!(&buf[..2], &[MAJOR_OPCODE, 1]);
+    assert_eq!(u16::from_ne_bytes([buf[2], buf[3]]) as usize * 4, buf.len());
+    assert_eq!(&buf[4..7], &[1, 0, 3]);
+}
+
+#[test]
+fn rectangles_over_the_request_length() {
+    let mut request = ShapeRectangles {
+        op: ShapeOp::Set,
+        dest_kind: ShapeKind::Input,
+        ordering: Ordering::Unsorted,
+        dest: Window(1),
+        x_offset: 0,
+        y_offset: 0,
+        rectangles: vec![Rectangle::default(); 40000],
+    };
+
+    let e = request.encode(&info()).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+}
@@ -0,0 +1,94 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use common::MAJOR_OPCODE;
+use common::info;
+use xrb::protocol::ExtensionRequest;
+use xrb::shm::*;
+use xrb::xproto::Drawable;
+use xrb::xproto::Gcontext;
+use xrb::xproto::IMAGE_FORMAT_Z_PIXMAP;
+
+fn length(buf: &[u8]) -> usize {
+    u16::from_ne_bytes([buf[2], buf[3]]) as usize * 4
+}
+
+#[test]
+fn sysv_segment() {
+    let mut segment = match Segment::sysv(4096) {
+        Ok(segment) => segment,
+        // System V IPC may be disabled.
+        Err(_) => return,
+    };
+
+    assert!(segment.shmid().is_some());
+    assert_eq!(segment.fd(), None);
+    segment.as_mut_slice()[4095] = 42;
+    segment.remove_id();
+    assert_eq!(segment.as_slice()[4095], 42);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn memfd_segment() {
+    let mut segment = Segment::memfd(8192).unwrap();
+
+    assert!(segment.fd().is_some());
+    assert_eq!(segment.len(), 8192);
+    segment.as_mut_slice()[0] = 7;
+    assert_eq!(segment.as_slice()[0], 7);
+}
+
+#[test]
+fn request_lengths() {
+    let put = ShmPutImage {
+        drawable: Drawable(1),
+        gc: Gcontext(2),
+        total_width: 640,
+        total_height: 480,
+        src_x: 0,
+        src_y: 0,
+        src_width: 640,
+        src_height: 480,
+        dst_x: 0,
+        dst_y: 0,
+        depth: 24,
+        format: IMAGE_FORMAT_Z_PIXMAP,
+        send_event: true,
+        shmseg: Seg(3),
+        offset: 0,
+    };
+    let buf = { put }.encode(&info()).unwrap();
+    assert_eq!(length(&buf), buf.len());
+    assert_eq!(buf[0], MAJOR_OPCODE);
+    assert_eq!(buf[1], 3);
+
+    let get = ShmGetImage {
+        drawable: Drawable(1),
+        x: 0,
+        y: 0,
+        width: 640,
+        height: 480,
+        plane_mask: u32::MAX,
+        format: IMAGE_FORMAT_Z_PIXMAP,
+        shmseg: Seg(3),
+        offset: 0,
+    };
+    let buf = { get }.encode(&info()).unwrap();
+    assert_eq!(length(&buf), buf.len());
+}
+
+#[test]
+fn attach_fd_sends_its_fd() {
+    let request = ShmAttachFd {
+        shmseg: Seg(3),
+        fd: 42,
+        read_only: true,
+    };
+
+    assert_eq!(request.fds(), vec![42]);
+    assert!(ShmDetach { shmseg: Seg(3) }.fds().is_empty());
+}
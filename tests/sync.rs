@@ -0,0 +1,110 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use common::FIRST_EVENT;
+use common::MAJOR_OPCODE;
+use common::info;
+use xrb::event::ClientMessageEvent;
+use xrb::event::Event;
+use xrb::protocol::ExtensionRequest;
+use xrb::sync::*;
+use xrb::xproto::Atom;
+use xrb::xproto::Window;
+
+fn int64(buf: &[u8]) -> (i32, u32) {
+    (i32::from_ne_bytes([buf[0], buf[1], buf[2], buf[3]]),
+     u32::from_ne_bytes([buf[4], buf[5], buf[6], buf[7]]))
+}
+
+#[test]
+fn set_counter_splits_int64() {
+    let mut request = SyncSetCounter {
+        counter: Counter(5),
+        value: -(3 << 32) - 1,
+    };
+
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(buf.len(), 16);
+    assert_eq!(&buf[..4], &[MAJOR_OPCODE, 3, 4, 0]);
+    assert_eq!(int64(&buf[8..16]), (-4, u32::MAX));
+}
+
+#[test]
+fn alarm_attributes() {
+    let mut attrs = AlarmAttributes::new();
+    attrs.trigger(Trigger {
+            counter: Counter(9),
+            wait_type: ValueType::Relative,
+            wait_value: 1 << 33,
+            test_type: TestType::PositiveComparison,
+        })
+        .events(false);
+
+    let mut request = SyncCreateAlarm {
+        id: Alarm(1),
+        attrs: attrs.build(),
+    };
+
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(u16::from_ne_bytes([buf[2], buf[3]]), 9);
+    assert_eq!(buf.len(), 36);
+    assert_eq!(u32::from_ne_bytes([buf[8], buf[9], buf[10], buf[11]]), 0x2f);
+    assert_eq!(int64(&buf[20..28]), (2, 0));
+    assert_eq!(&buf[32..36], &[0; 4]);
+}
+
+#[test]
+fn await_length() {
+    let condition = WaitCondition {
+        trigger: Trigger {
+            counter: Counter(2),
+            wait_type: ValueType::Absolute,
+            wait_value: 10,
+            test_type: TestType::PositiveTransition,
+        },
+        event_threshold: 0,
+    };
+
+    let mut request = SyncAwait { wait_list: vec![condition; 2] };
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(buf.len(), 60);
+    assert_eq!(u16::from_ne_bytes([buf[2], buf[3]]) as usize * 4, buf.len());
+}
+
+#[test]
+fn decode_alarm_notify() {
+    let mut buf = vec![0u8; 32];
+    buf[0] = FIRST_EVENT + 1;
+    buf[4..8].copy_from_slice(&3u32.to_ne_bytes());
+    buf[8..12].copy_from_slice(&(-1i32).to_ne_bytes());
+    buf[12..16].copy_from_slice(&u32::MAX.to_ne_bytes());
+    buf[16..20].copy_from_slice(&1i32.to_ne_bytes());
+    buf[20..24].copy_from_slice(&2u32.to_ne_bytes());
+    buf[28] = 1;
+
+    match SyncEvent::from_event(&info(), &Event::Unknown(buf)).unwrap() {
+        SyncEvent::AlarmNotify(ev) => {
+            assert_eq!(ev.alarm, Alarm(3));
+            assert_eq!(ev.counter_value, -1);
+            assert_eq!(ev.alarm_value, (1 << 32) + 2);
+            assert_eq!(ev.state, AlarmState::Inactive);
+        }
+        ev => panic!("unexpected event {:?}", ev),
+    }
+}
+
+#[test]
+fn sync_request_message() {
+    let mut sync_request = SyncRequest::from_counter(Counter(7), Atom(300), Atom(400));
+
+    let other = ClientMessageEvent::new32(Window(1), Atom(300), [401, 0, 0, 0, 0]);
+    assert!(!sync_request.handle(&Event::ClientMessage(other)));
+
+    let message = ClientMessageEvent::new32(Window(1), Atom(300), [400, 1234, 5, 1, 0]);
+    assert!(sync_request.handle(&Event::ClientMessage(message)));
+    assert_eq!(sync_request.pending(), Some((1 << 32) + 5));
+    assert_eq!(sync_request.time(), 1234);
+}
@@ -0,0 +1,218 @@
+#![cfg(feature = "text")]
+
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use common::FakeServer;
+use xrb::render::GlyphItem;
+use xrb::render::Glyphset;
+use xrb::render::Picture;
+use xrb::text::*;
+
+// Fonts made for the tests, with 1000 units per em and an ascent of 800
+// units so a size of 20 pixels is 1/50 of the units. `latin.ttf` has the
+// glyphs of "AVWehlo " with advances of 600, 600, 900, 500, 550, 250, 500
+// and 250 units, and kerns the pairs "AV" and "VA" by -100 units.
+// `accent.ttf` only has "é".
+static LATIN: &[u8] = include_bytes!("fonts/latin.ttf");
+static ACCENT: &[u8] = include_bytes!("fonts/accent.ttf");
+
+fn font(data: &'static [u8]) -> FontArc {
+    FontArc::try_from_slice(data).unwrap()
+}
+
+fn cache() -> GlyphCache {
+    GlyphCache::new(vec![font(LATIN), font(ACCENT)]).unwrap()
+}
+
+#[test]
+fn fonts_are_needed() {
+    assert!(GlyphCache::new(vec![]).is_err());
+}
+
+#[test]
+fn metrics() {
+    let cache = cache();
+
+    assert_eq!(cache.ascent(20.0), 16.0);
+    assert_eq!(cache.descent(20.0), -4.0);
+    assert_eq!(cache.measure("hello", 20.0), 11.0 + 10.0 + 5.0 + 5.0 + 10.0);
+    assert_eq!(cache.measure("", 20.0), 0.0);
+}
+
+#[test]
+fn kerning() {
+    let cache = cache();
+
+    let glyphs = cache.layout("AVA", 20.0);
+    let positions: Vec<f32> = glyphs.iter().map(|glyph| glyph.x).collect();
+    assert_eq!(positions, vec![0.0, 10.0, 20.0]);
+    assert_eq!(cache.measure("AV", 20.0), 22.0);
+    assert_eq!(cache.measure("A", 20.0) + cache.measure("V", 20.0), 24.0);
+}
+
+#[test]
+fn missing_characters_use_the_next_font() {
+    let cache = cache();
+
+    let fonts: Vec<usize> = cache.layout("hé\nz", 20.0).iter().map(|glyph| glyph.font).collect();
+    // Control characters are skipped and "z" is the missing glyph of the
+    // first font.
+    assert_eq!(fonts, vec![0, 1, 0]);
+    assert_eq!(cache.layout("z", 20.0)[0].glyph.0, 0);
+}
+
+#[test]
+fn glyphs_are_uploaded_once() {
+    let mut cache = cache();
+
+    let (uploads, items, new) = cache.prepare("hello", 20.0, 10, 20);
+    // "l" is rasterised once.
+    assert_eq!(uploads.len(), 4);
+    assert_eq!(new.len(), 4);
+    // The glyphs are cached once uploaded.
+    assert!(cache.is_empty());
+    let (again, _, _) = cache.prepare("hello", 20.0, 10, 20);
+    assert_eq!(again.len(), 4);
+    cache.insert(new);
+    assert_eq!(cache.len(), 4);
+    let advances: Vec<i16> = uploads.iter().map(|glyph| glyph.info.x_off).collect();
+    assert_eq!(advances, vec![11, 10, 5, 10]);
+
+    // The advances are whole pixels, a single element draws the string.
+    match items[..] {
+        [GlyphItem::Glyphs { dx, dy, ref glyphs }] => {
+            assert_eq!((dx, dy), (10, 20));
+            assert_eq!(*glyphs, vec![1, 2, 3, 3, 4]);
+        }
+        _ => panic!("unexpected items {:?}", items),
+    }
+
+    let (uploads, _, _) = cache.prepare("hell", 20.0, 0, 0);
+    assert!(uploads.is_empty());
+
+    // Another size is another glyph.
+    let (uploads, _, _) = cache.prepare("h", 12.0, 0, 0);
+    assert_eq!(uploads.len(), 1);
+}
+
+#[test]
+fn kerned_glyphs_start_new_elements() {
+    let mut cache = cache();
+
+    let (_, items, _) = cache.prepare("AVe", 20.0, 5, 30);
+    match items[..] {
+        [GlyphItem::Glyphs { dx: 5, dy: 30, glyphs: ref first },
+         GlyphItem::Glyphs { dx: -2, dy: 0, glyphs: ref second }] => {
+            assert_eq!(*first, vec![1]);
+            assert_eq!(*second, vec![2, 3]);
+        }
+        _ => panic!("unexpected items {:?}", items),
+    }
+}
+
+#[test]
+fn images_are_padded() {
+    let mut cache = cache();
+
+    let (uploads, _, _) = cache.prepare("W é", 13.0, 0, 0);
+    assert_eq!(uploads.len(), 3);
+    for glyph in &uploads {
+        let stride = (glyph.info.width as usize + 3) & !3;
+        assert_eq!(glyph.image.len(), stride * glyph.info.height as usize);
+    }
+
+    // The space has no image.
+    assert_eq!((uploads[1].info.width, uploads[1].image.len()), (0, 0));
+    assert_eq!(uploads[1].info.x_off, 3);
+}
+
+#[test]
+fn glyphs_over_the_request_length_have_no_image() {
+    let mut cache = cache();
+
+    // 900x700 pixels, over the 256 KiB of a request.
+    let (uploads, _, _) = cache.prepare("W", 1000.0, 0, 0);
+    assert_eq!((uploads[0].info.width, uploads[0].image.len()), (0, 0));
+    assert_eq!(uploads[0].info.x_off, 900);
+
+    // 90x70 pixels with a server accepting 4000 bytes.
+    cache.set_maximum_request_length(1000);
+    let (uploads, _, _) = cache.prepare("Wl", 100.0, 0, 0);
+    assert_eq!(uploads[0].image.len(), 0);
+    assert_eq!(uploads[1].info.width, 15);
+    assert!(!uploads[1].image.is_empty());
+}
+
+#[test]
+fn uploads_are_batched() {
+    let server = FakeServer::start(|sequence, request| {
+        match request[0] {
+            // QueryExtension
+            98 => vec![common::query_extension_reply(sequence, true, common::MAJOR_OPCODE)],
+            // RenderQueryVersion
+            common::MAJOR_OPCODE if request[1] == 0 => {
+                let mut version = Vec::new();
+                version.extend_from_slice(&0u32.to_ne_bytes());
+                version.extend_from_slice(&11u32.to_ne_bytes());
+                vec![common::reply(sequence, 0, &version)]
+            }
+            _ => vec![],
+        }
+    });
+
+    let (mut lp, client) = server.connect();
+    let mut renderer = TextRenderer {
+        glyphset: Glyphset(0x400001),
+        cache: cache(),
+    };
+
+    // The images of the glyphs take 10 to 25 KiB each at this size.
+    let text = "Whole VA";
+    let (client, _) = lp.run(renderer.draw(client,
+                                             Picture(0x400002),
+                                             Picture(0x400003),
+                                             (0, 200),
+                                             200.0,
+                                             text))
+        .unwrap();
+    drop(client);
+    assert_eq!(renderer.cache.len(), 8);
+
+    let requests = server.finish();
+    let render: Vec<&Vec<u8>> = requests.iter().filter(|r| r[0] == common::MAJOR_OPCODE).collect();
+
+    // RenderAddGlyphs
+    let batches: Vec<&&Vec<u8>> = render.iter().filter(|r| r[1] == 20).collect();
+    assert!(batches.len() > 1);
+    let glyphs: u32 = batches.iter()
+        .map(|r| u32::from_ne_bytes([r[8], r[9], r[10], r[11]]))
+        .sum();
+    assert_eq!(glyphs, 8);
+    assert!(batches.iter().all(|r| r.len() <= 64 * 1024 + 12 + 16 * 8));
+
+    // RenderCompositeGlyphs32 comes last.
+    assert_eq!(render.last().unwrap()[1], 25);
+}
+
+#[test]
+fn glyphs_of_a_dropped_draw_are_not_cached() {
+    let server = FakeServer::start(|_, _| vec![]);
+
+    let (_lp, client) = server.connect();
+    let mut renderer = TextRenderer {
+        glyphset: Glyphset(0x400001),
+        cache: cache(),
+    };
+
+    let draw = renderer.draw(client, Picture(0x400002), Picture(0x400003), (0, 20), 20.0, "hello");
+    drop(draw);
+    server.finish();
+
+    assert!(renderer.cache.is_empty());
+    let (uploads, _, _) = renderer.cache.prepare("hello", 20.0, 0, 20);
+    assert_eq!(uploads.len(), 4);
+}
@@ -0,0 +1,116 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use std::io;
+
+use common::FakeServer;
+use common::MAJOR_OPCODE;
+use common::info;
+use xrb::protocol::Error;
+use xrb::protocol::ExtensionRequest;
+use xrb::protocol::Version;
+use xrb::protocol::VersionRequest;
+use xrb::randr::RRQueryVersion;
+use xrb::randr::RRQueryVersionReply;
+use xrb::xc_misc::*;
+
+#[test]
+fn requests_encoding() {
+    let buf = XCMiscGetVersion::latest().encode(&info()).unwrap();
+    assert_eq!(&buf[..4], &[MAJOR_OPCODE, 0, 2, 0]);
+    assert_eq!(&buf[4..6], &1u16.to_ne_bytes());
+    assert_eq!(&buf[6..8], &1u16.to_ne_bytes());
+
+    let buf = XCMiscGetXIDRange.encode(&info()).unwrap();
+    assert_eq!(buf, vec![MAJOR_OPCODE, 1, 1, 0]);
+
+    let buf = XCMiscGetXIDList { count: 32 }.encode(&info()).unwrap();
+    assert_eq!(&buf[..4], &[MAJOR_OPCODE, 2, 2, 0]);
+    assert_eq!(&buf[4..8], &32u32.to_ne_bytes());
+}
+
+#[test]
+fn negotiated_versions() {
+    let reply = XCMiscGetVersionReply {
+        server_major_version: 1,
+        server_minor_version: 1,
+    };
+    assert_eq!(XCMiscGetVersion::version(&reply), Version::new(1, 1));
+
+    let reply = RRQueryVersionReply {
+        major_version: 1,
+        minor_version: 4,
+    };
+    let version = RRQueryVersion::version(&reply);
+    assert!(version < Version::new(1, 5));
+    assert!(version > Version::new(0, 10));
+}
+
+#[test]
+fn version_is_negotiated_before_the_first_request() {
+    let server = FakeServer::start(|sequence, request| {
+        match (request[0], request[1]) {
+            // QueryExtension
+            (98, _) => vec![common::query_extension_reply(sequence, true, MAJOR_OPCODE)],
+            // XCMiscGetVersion
+            (MAJOR_OPCODE, 0) => {
+                let mut version = Vec::new();
+                version.extend_from_slice(&1u16.to_ne_bytes());
+                version.extend_from_slice(&1u16.to_ne_bytes());
+                vec![common::reply(sequence, 0, &version)]
+            }
+            // XCMiscGetXIDRange
+            (MAJOR_OPCODE, 1) => {
+                let mut range = Vec::new();
+                range.extend_from_slice(&0x600000u32.to_ne_bytes());
+                range.extend_from_slice(&16u32.to_ne_bytes());
+                vec![common::reply(sequence, 0, &range)]
+            }
+            _ => vec![],
+        }
+    });
+
+    let (mut lp, client) = server.connect();
+    assert_eq!(client.extension_version(EXTENSION_NAME), None);
+    let (client, range) = lp.run(client.perform_ex(XCMiscGetXIDRange)).unwrap();
+    assert_eq!((range.start_id, range.count), (0x600000, 16));
+    assert_eq!(client.extension_version(EXTENSION_NAME), Some(Version::new(1, 1)));
+    let (client, _) = lp.run(client.perform_ex(XCMiscGetXIDRange)).unwrap();
+    drop(client);
+
+    let requests: Vec<(u8, u8)> = server.finish().iter().map(|r| (r[0], r[1])).collect();
+    assert_eq!(requests,
+               vec![(98, 0), (MAJOR_OPCODE, 0), (MAJOR_OPCODE, 1), (MAJOR_OPCODE, 1)]);
+}
+
+#[test]
+fn extension_not_present() {
+    let server = FakeServer::start(|sequence, request| {
+        match request[0] {
+            // QueryExtension
+            98 => vec![common::query_extension_reply(sequence, false, 0)],
+            _ => vec![],
+        }
+    });
+
+    let (mut lp, client) = server.connect();
+    let (client, infos) = lp.run(client.prefetch_extensions(&[EXTENSION_NAME])).unwrap();
+    assert!(!infos[0].present);
+
+    // The cached entry fails the request without sending it.
+    let e = match lp.run(client.perform_ex(XCMiscGetXIDRange)) {
+        Ok(_) => panic!("the extension isn't present"),
+        Err(e) => e,
+    };
+    assert_eq!(e.kind(), io::ErrorKind::Unsupported);
+    assert_eq!(e.to_string(), "extension XC-MISC not present");
+    match e.get_ref().and_then(|e| e.downcast_ref::<Error>()) {
+        Some(&Error::ExtensionNotPresent(name)) => assert_eq!(name, b"XC-MISC"),
+        other => panic!("unexpected error {:?}", other),
+    }
+
+    assert_eq!(server.finish().len(), 1);
+}
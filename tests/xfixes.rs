@@ -0,0 +1,96 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use std::io;
+
+use common::FIRST_EVENT;
+use common::info;
+use xrb::event::Event;
+use xrb::protocol::ExtensionRequest;
+use xrb::xfixes::*;
+use xrb::xproto::Atom;
+use xrb::xproto::Rectangle;
+use xrb::xproto::Window;
+
+fn length(buf: &[u8]) -> usize {
+    u16::from_ne_bytes([buf[2], buf[3]]) as usize * 4
+}
+
+#[test]
+fn selection_notify() {
+    let mut buf = vec![0u8; 32];
+    buf[0] = FIRST_EVENT;
+    buf[1] = 2;
+    buf[4..8].copy_from_slice(&1u32.to_ne_bytes());
+    buf[12..16].copy_from_slice(&1u32.to_ne_bytes());
+
+    match XFixesEvent::from_event(&info(), &Event::Unknown(buf)) {
+        Some(XFixesEvent::SelectionNotify(event)) => {
+            assert_eq!(event.subtype, SelectionNotifySubtype::SelectionClientClose);
+            assert_eq!(event.window, Window(1));
+            assert_eq!(event.owner, Window::NONE);
+            assert_eq!(event.selection, Atom(1));
+        }
+        event => panic!("unexpected {:?}", event),
+    }
+}
+
+#[test]
+fn cursor_notify() {
+    let mut buf = vec![0u8; 32];
+    // Sent with `SendEvent`.
+    buf[0] = (FIRST_EVENT + 1) | 0x80;
+    buf[8..12].copy_from_slice(&7u32.to_ne_bytes());
+
+    match XFixesEvent::from_event(&info(), &Event::Unknown(buf)) {
+        Some(XFixesEvent::CursorNotify(event)) => assert_eq!(event.cursor_serial, 7),
+        event => panic!("unexpected {:?}", event),
+    }
+}
+
+#[test]
+fn request_lengths() {
+    let mut region = XFixesCreateRegion {
+        region: Region(1),
+        rectangles: vec![Rectangle { x: 0, y: 0, width: 10, height: 10 }; 3],
+    };
+    let buf = region.encode(&info()).unwrap();
+    assert_eq!(length(&buf), buf.len());
+    assert_eq!(buf.len(), 8 + 3 * 8);
+
+    let mut barrier = XFixesCreatePointerBarrier {
+        barrier: Barrier(2),
+        window: Window(1),
+        x1: 0,
+        y1: 0,
+        x2: 0,
+        y2: 1080,
+        directions: BARRIER_POSITIVE_X,
+        devices: vec![2, 3, 4],
+    };
+    let buf = barrier.encode(&info()).unwrap();
+    assert_eq!(length(&buf), buf.len());
+    assert_eq!(buf.len(), 28 + 8);
+}
+
+#[test]
+fn regions_over_the_request_length() {
+    let rectangles = vec![Rectangle::default(); 40000];
+
+    let mut request = XFixesCreateRegion {
+        region: Region(1),
+        rectangles: rectangles.clone(),
+    };
+    let e = request.encode(&info()).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+
+    let mut request = XFixesSetRegion {
+        region: Region(1),
+        rectangles,
+    };
+    let e = request.encode(&info()).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+}
@@ -0,0 +1,73 @@
+extern crate xrb;
+
+use xrb::XidAllocator;
+
+#[test]
+fn allocates_distinct_ids() {
+    let xid = XidAllocator::new(0x04000000, 0x001FFFFF);
+
+    let first = xid.allocate().unwrap();
+    let second = xid.allocate().unwrap();
+
+    assert_eq!(first, 0x04000000);
+    assert_eq!(second, 0x04000001);
+}
+
+#[test]
+fn increment_is_lowest_mask_bit() {
+    let xid = XidAllocator::new(0x00200000, 0x0000FFF0);
+
+    assert_eq!(xid.allocate(), Some(0x00200000));
+    assert_eq!(xid.allocate(), Some(0x00200010));
+    assert_eq!(xid.available(), 0xFFF - 1);
+}
+
+#[test]
+fn range_exhaustion() {
+    let xid = XidAllocator::new(0x00400000, 0x3);
+
+    let ids: Vec<u32> = (0..4).map(|_| xid.allocate().unwrap()).collect();
+    assert_eq!(ids, vec![0x00400000, 0x00400001, 0x00400002, 0x00400003]);
+    assert_eq!(xid.allocate(), None);
+    assert_eq!(xid.available(), 0);
+
+    // Freed ids are reused once the range is exhausted.
+    xid.free(0x00400001);
+    assert_eq!(xid.allocate(), Some(0x00400001));
+    assert_eq!(xid.allocate(), None);
+}
+
+#[test]
+fn reclaimed_ids() {
+    let xid = XidAllocator::new(0x00400000, 0x1);
+    xid.allocate().unwrap();
+    xid.allocate().unwrap();
+    assert_eq!(xid.allocate(), None);
+
+    // As returned by XCMiscGetXIDRange.
+    xid.add_range(0x00400000, 2);
+    assert_eq!(xid.available(), 2);
+    assert_eq!(xid.allocate(), Some(0x00400000));
+    assert_eq!(xid.allocate(), Some(0x00400001));
+    assert_eq!(xid.allocate(), None);
+
+    // As returned by XCMiscGetXIDList.
+    xid.add_ids(&[0x00400001]);
+    assert_eq!(xid.allocate(), Some(0x00400001));
+    assert_eq!(xid.allocate(), None);
+}
+
+#[test]
+fn early_range_keeps_current_ids() {
+    let xid = XidAllocator::new(0x00400000, 0x3);
+    assert_eq!(xid.allocate(), Some(0x00400000));
+
+    // A range reclaimed before the current one is exhausted.
+    xid.add_range(0x00400010, 2);
+    assert_eq!(xid.available(), 5);
+
+    let ids: Vec<u32> = (0..5).map(|_| xid.allocate().unwrap()).collect();
+    assert_eq!(ids, vec![0x00400010, 0x00400011, 0x00400001, 0x00400002, 0x00400003]);
+    assert_eq!(xid.allocate(), None);
+    assert_eq!(xid.available(), 0);
+}
@@ -0,0 +1,119 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use common::MAJOR_OPCODE;
+use common::info;
+use xrb::event::Event;
+use xrb::protocol::ExtensionRequest;
+use xrb::xinput::*;
+use xrb::xproto::Window;
+
+fn generic_event(evtype: EventType, len: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; len];
+    buf[0] = 35;
+    buf[1] = MAJOR_OPCODE;
+    buf[4..8].copy_from_slice(&((len as u32 - 32) / 4).to_ne_bytes());
+    buf[8..10].copy_from_slice(&evtype.to_ne_bytes());
+    buf[10..12].copy_from_slice(&2u16.to_ne_bytes());
+    buf
+}
+
+fn fp3232(value: f64) -> Vec<u8> {
+    let integral = value.floor();
+    let frac = ((value - integral) * 4294967296.0) as u32;
+    let mut buf = (integral as i32).to_ne_bytes().to_vec();
+    buf.extend_from_slice(&frac.to_ne_bytes());
+    buf
+}
+
+#[test]
+fn event_mask() {
+    let mask = EventMask::new(&[XI_MOTION, XI_TOUCH_BEGIN, XI_BARRIER_HIT]);
+    assert_eq!(mask.0, vec![(1 << 6) | (1 << 18) | (1 << 25)]);
+    assert!(mask.contains(XI_TOUCH_BEGIN));
+    assert!(!mask.contains(XI_TOUCH_END));
+    assert!(!mask.contains(40));
+
+    let mut request = XISelectEvents {
+        window: Window(1),
+        masks: vec![(ALL_MASTER_DEVICES, mask), (5, EventMask::default())],
+    };
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(buf.len(), 24);
+    assert_eq!(u16::from_ne_bytes([buf[2], buf[3]]) as usize * 4, buf.len());
+    assert_eq!(u16::from_ne_bytes([buf[8], buf[9]]), 2);
+}
+
+#[test]
+fn passive_grab_length() {
+    let mut request = XIPassiveGrabDevice {
+        time: 0,
+        grab_window: Window(1),
+        cursor: xrb::xproto::Cursor::NONE,
+        detail: 1,
+        deviceid: ALL_MASTER_DEVICES,
+        grab_type: GrabType::Button,
+        grab_mode: GrabMode::Async,
+        paired_device_mode: GrabMode::Async,
+        owner_events: false,
+        mask: EventMask::new(&[XI_BUTTON_PRESS]),
+        modifiers: vec![ANY_MODIFIER],
+    };
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(buf.len(), 40);
+    assert_eq!(u16::from_ne_bytes([buf[2], buf[3]]) as usize * 4, buf.len());
+}
+
+#[test]
+fn decode_device_event() {
+    let mut buf = generic_event(XI_MOTION, 80 + 4 + 4 + 16);
+    buf[32..36].copy_from_slice(&(10 * 65536 + 32768i32).to_ne_bytes());
+    buf[48..50].copy_from_slice(&1u16.to_ne_bytes());
+    buf[50..52].copy_from_slice(&1u16.to_ne_bytes());
+    buf[52..54].copy_from_slice(&9u16.to_ne_bytes());
+    buf[80..84].copy_from_slice(&(1u32 << 1).to_ne_bytes());
+    buf[84..88].copy_from_slice(&((1u32 << 0) | (1 << 3)).to_ne_bytes());
+    buf[88..96].copy_from_slice(&fp3232(1.5));
+    buf[96..104].copy_from_slice(&fp3232(-2.25));
+
+    let event = XIEvent::from_event(&info(), &Event::Unknown(buf)).unwrap();
+    let event = match event {
+        XIEvent::Motion(event) => event,
+        event => panic!("unexpected event {:?}", event),
+    };
+    assert_eq!(event.deviceid, 2);
+    assert_eq!(event.sourceid, 9);
+    assert_eq!(event.root_x, 10.5);
+    assert!(event.is_button_down(1));
+    assert!(!event.is_button_down(2));
+    assert_eq!(event.valuators, vec![(0, 1.5), (3, -2.25)]);
+    assert_eq!(event.valuator(3), Some(-2.25));
+    assert_eq!(event.valuator(1), None);
+}
+
+#[test]
+fn decode_raw_event() {
+    let mut buf = generic_event(XI_RAW_MOTION, 32 + 4 + 16);
+    buf[22..24].copy_from_slice(&1u16.to_ne_bytes());
+    buf[32..36].copy_from_slice(&(1u32 << 2).to_ne_bytes());
+    buf[36..44].copy_from_slice(&fp3232(4.0));
+    buf[44..52].copy_from_slice(&fp3232(2.0));
+
+    match XIEvent::read(&info(), &buf) {
+        Some(XIEvent::RawMotion(event)) => {
+            assert_eq!(event.valuators, vec![(2, 4.0)]);
+            assert_eq!(event.raw_valuators, vec![(2, 2.0)]);
+        }
+        event => panic!("unexpected event {:?}", event),
+    }
+
+    // Truncated values.
+    assert!(XIEvent::read(&info(), &buf[..44]).is_none());
+
+    // An event of another extension.
+    buf[1] = 140;
+    assert!(XIEvent::read(&info(), &buf).is_none());
+}
@@ -0,0 +1,215 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use common::FIRST_EVENT;
+use common::info;
+use xrb::event::Event;
+use xrb::protocol::ExtensionRequest;
+use xrb::xkb::*;
+
+const SHIFT: u8 = 0x01;
+const LOCK: u8 = 0x02;
+const MOD5: u8 = 0x80;
+
+fn entry(mods: u8, level: u8) -> KTMapEntry {
+    KTMapEntry {
+        active: true,
+        mods_mask: mods,
+        level,
+        mods_mods: mods,
+        ..KTMapEntry::default()
+    }
+}
+
+/// A keymap with an `us` and a `de` group, `ONE_LEVEL`, `TWO_LEVEL`,
+/// `ALPHABETIC` and `FOUR_LEVEL` types.
+fn keymap() -> Keymap {
+    let types = vec![
+        KeyType { num_levels: 1, ..KeyType::default() },
+        KeyType {
+            mods_mask: SHIFT,
+            num_levels: 2,
+            map: vec![entry(SHIFT, 1)],
+            ..KeyType::default()
+        },
+        KeyType {
+            mods_mask: SHIFT | LOCK,
+            num_levels: 2,
+            map: vec![entry(SHIFT, 1), entry(LOCK, 1)],
+            ..KeyType::default()
+        },
+        KeyType {
+            mods_mask: SHIFT | MOD5,
+            num_levels: 4,
+            map: vec![entry(SHIFT, 1), entry(MOD5, 2), entry(SHIFT | MOD5, 3)],
+            ..KeyType::default()
+        },
+    ];
+
+    let syms = vec![
+        // Keycode 29: y / z.
+        KeySymMap {
+            kt_index: [2, 2, 0, 0],
+            group_info: 2,
+            width: 2,
+            syms: vec![0x79, 0x59, 0x7a, 0x5a],
+        },
+        // Keycode 30: escape, a single group clamped.
+        KeySymMap {
+            kt_index: [0, 0, 0, 0],
+            group_info: 1 | 0x40,
+            width: 1,
+            syms: vec![0xff1b],
+        },
+        // Keycode 31: e with a third level in the second group.
+        KeySymMap {
+            kt_index: [2, 3, 0, 0],
+            group_info: 2,
+            width: 4,
+            syms: vec![0x65, 0x45, 0, 0, 0x65, 0x45, 0x20ac, 0],
+        },
+    ];
+
+    Keymap::new(&XkbGetMapReply {
+        min_key_code: 8,
+        max_key_code: 255,
+        present: MAP_PART_KEY_TYPES | MAP_PART_KEY_SYMS,
+        types,
+        first_key_sym: 29,
+        syms,
+        ..XkbGetMapReply::default()
+    })
+}
+
+#[test]
+fn lookup() {
+    let keymap = keymap();
+
+    assert_eq!(keymap.num_groups(29), 2);
+    assert_eq!(keymap.lookup(29, 0, 0).unwrap().keysym, 0x79);
+    assert_eq!(keymap.lookup(29, 0, 1).unwrap().keysym, 0x7a);
+    assert_eq!(keymap.lookup(29, SHIFT, 1).unwrap().keysym, 0x5a);
+    assert_eq!(keymap.lookup(29, LOCK, 0).unwrap().keysym, 0x59);
+    // Shift and Lock cancel each other in ALPHABETIC.
+    assert_eq!(keymap.lookup(29, SHIFT | LOCK, 0).unwrap().keysym, 0x79);
+    // The group wraps.
+    assert_eq!(keymap.lookup(29, 0, 3).unwrap().keysym, 0x7a);
+
+    // Clamped to the only group.
+    let lookup = keymap.lookup(30, SHIFT, 2).unwrap();
+    assert_eq!((lookup.keysym, lookup.group, lookup.consumed_mods), (0xff1b, 0, 0));
+
+    let lookup = keymap.lookup(31, MOD5 | 0x04, 1).unwrap();
+    assert_eq!((lookup.keysym, lookup.level), (0x20ac, 2));
+    assert_eq!(lookup.consumed_mods, SHIFT | MOD5);
+    // No symbol at this level.
+    assert_eq!(keymap.lookup(31, MOD5 | SHIFT, 1), None);
+    assert_eq!(keymap.lookup(32, 0, 0), None);
+
+    let state = KeyboardState { lookup_mods: SHIFT, group: 1, ..KeyboardState::default() };
+    assert_eq!(keymap.keysym(31, &state), Some(0x45));
+}
+
+#[test]
+fn keysyms() {
+    assert_eq!(keysym_from_name("a"), Some(0x61));
+    assert_eq!(keysym_from_name("multiply"), Some(0xd7));
+    assert_eq!(keysym_from_name("dead_acute"), Some(0xfe51));
+    assert_eq!(keysym_from_name("U20AC"), Some(0x20ac));
+    assert_eq!(keysym_from_name("U0416"), Some(0x01000416));
+    assert_eq!(keysym_from_name("Cyrillic_zhe"), None);
+    assert_eq!(keysym_name(0xe9), Some("eacute"));
+    assert_eq!(keysym_to_char(0x01000416), Some('Ж'));
+    assert_eq!(keysym_to_char(0xff0d), Some('\r'));
+    assert_eq!(keysym_to_char(0xfe51), None);
+    assert!(is_dead_keysym(0xfe51));
+}
+
+#[test]
+fn compose() {
+    let table = ComposeTable::parse(r#"
+# Comment
+include "%L"
+<dead_acute> <e>            : "é"   eacute # LATIN SMALL LETTER E WITH ACUTE
+<Multi_key> <o> <c>         : "©"   copyright
+<Multi_key> <minus> <minus> : "\xe2\x80\x93"
+<Multi_key> <quotedbl> <a>  : "\"a\""
+<Multi_key> <Cyrillic_a>    : "?"
+<dead_acute> <dead_acute>   : "\302\264" acute
+"#);
+
+    assert_eq!(table.len(), 5);
+    assert_eq!(table.get(&[0xfe51, 0x65]).unwrap().keysym, Some(0xe9));
+    assert_eq!(table.get(&[0xff20, 0x22, 0x61]).unwrap().string, "\"a\"");
+    assert_eq!(table.get(&[0xfe51, 0xfe51]).unwrap().string, "\u{b4}");
+    assert_eq!(table.get(&[0xff20, 0x2d, 0x2d]).unwrap().string, "\u{2013}");
+
+    let mut state = ComposeState::new();
+    assert_eq!(state.feed(&table, 0x61), ComposeStatus::Nothing);
+    assert_eq!(state.feed(&table, 0xfe51), ComposeStatus::Composing);
+    // Shift is ignored.
+    assert_eq!(state.feed(&table, 0xffe1), ComposeStatus::Composing);
+    match state.feed(&table, 0x65) {
+        ComposeStatus::Composed(output) => assert_eq!(output.string, "é"),
+        status => panic!("unexpected status {:?}", status),
+    }
+    assert!(!state.is_composing());
+
+    assert_eq!(state.feed(&table, 0xff20), ComposeStatus::Composing);
+    assert_eq!(state.feed(&table, 0x6f), ComposeStatus::Composing);
+    assert_eq!(state.feed(&table, 0x78), ComposeStatus::Cancelled);
+}
+
+#[test]
+fn select_events() {
+    let mut request = XkbSelectEvents {
+        device_spec: USE_CORE_KBD,
+        affect_which: EVENT_TYPE_STATE_NOTIFY | EVENT_TYPE_MAP_NOTIFY |
+                      EVENT_TYPE_NEW_KEYBOARD_NOTIFY,
+        select_all: EVENT_TYPE_NEW_KEYBOARD_NOTIFY,
+        affect_map: MAP_PART_KEY_SYMS,
+        map: MAP_PART_KEY_SYMS,
+        affect_state: STATE_PART_GROUP_STATE,
+        state_details: STATE_PART_GROUP_STATE,
+        ..XkbSelectEvents::default()
+    };
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(buf.len(), 20);
+    assert_eq!(u16::from_ne_bytes([buf[2], buf[3]]) as usize * 4, buf.len());
+
+    request.affect_which |= EVENT_TYPE_BELL_NOTIFY;
+    assert!(request.encode(&info()).is_err());
+}
+
+#[test]
+fn decode_state_notify() {
+    let mut buf = vec![0u8; 32];
+    buf[0] = FIRST_EVENT;
+    buf[1] = 2;
+    buf[8] = 3;
+    buf[13] = 1;
+    buf[22] = SHIFT;
+    buf[26..28].copy_from_slice(&STATE_PART_GROUP_STATE.to_ne_bytes());
+    buf[28] = 50;
+
+    match XkbEvent::from_event(&info(), &Event::Unknown(buf.clone())) {
+        Some(XkbEvent::StateNotify(event)) => {
+            assert_eq!(event.device_id, 3);
+            assert_eq!(event.state.group, 1);
+            assert_eq!(event.state.lookup_mods, SHIFT);
+            assert_eq!(event.changed, STATE_PART_GROUP_STATE);
+            assert_eq!(event.keycode, 50);
+        }
+        event => panic!("unexpected event {:?}", event),
+    }
+
+    buf[1] = 1;
+    buf[10..12].copy_from_slice(&MAP_PART_KEY_SYMS.to_ne_bytes());
+    match XkbEvent::read(&info(), &buf) {
+        Some(XkbEvent::MapNotify(event)) => assert_eq!(event.changed, MAP_PART_KEY_SYMS),
+        event => panic!("unexpected event {:?}", event),
+    }
+}
@@ -0,0 +1,69 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use common::MAJOR_OPCODE;
+use common::info;
+use xrb::protocol::ExtensionRequest;
+use xrb::xproto::Atom;
+use xrb::xres::*;
+
+#[test]
+fn query_encoding() {
+    let buf = XResQueryClientPixmapBytes { xid: 0x400000 }.encode(&info()).unwrap();
+    assert_eq!(&buf[..4], &[MAJOR_OPCODE, 3, 2, 0]);
+    assert_eq!(&buf[4..8], &0x400000u32.to_ne_bytes());
+
+    let mut request = XResQueryClientIds {
+        specs: vec![ClientIdSpec {
+                        client: 0x600001,
+                        mask: CLIENT_ID_MASK_LOCAL_CLIENT_PID,
+                    }],
+    };
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(buf.len(), 16);
+    assert_eq!(&buf[..4], &[MAJOR_OPCODE, 4, 4, 0]);
+    assert_eq!(&buf[4..8], &1u32.to_ne_bytes());
+    assert_eq!(&buf[12..16], &2u32.to_ne_bytes());
+
+    let mut request = XResQueryResourceBytes {
+        client: 0,
+        specs: vec![ResourceIdSpec {
+                        resource: 0,
+                        resource_type: Atom::NONE,
+                    }],
+    };
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(buf.len(), 20);
+    assert_eq!(u16::from_ne_bytes([buf[2], buf[3]]) as usize * 4, buf.len());
+}
+
+#[test]
+fn client_ids() {
+    let client = ResourceClient {
+        resource_base: 0x600000,
+        resource_mask: 0x1fffff,
+    };
+    assert!(client.owns(0x600001));
+    assert!(!client.owns(0x800001));
+
+    let pid = ClientIdValue {
+        spec: ClientIdSpec {
+            client: 0x600000,
+            mask: CLIENT_ID_MASK_LOCAL_CLIENT_PID,
+        },
+        value: vec![4242],
+    };
+    assert_eq!(pid.pid(), Some(4242));
+
+    let xid = ClientIdValue {
+        spec: ClientIdSpec {
+            client: 0x600000,
+            mask: CLIENT_ID_MASK_CLIENT_XID,
+        },
+        value: vec![0x600000],
+    };
+    assert_eq!(xid.pid(), None);
+}
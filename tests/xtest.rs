@@ -0,0 +1,140 @@
+extern crate xrb;
+extern crate xauth;
+extern crate tokio_core;
+
+mod common;
+
+use common::FakeServer;
+use common::MAJOR_OPCODE;
+use common::info;
+use xrb::protocol::ExtensionRequest;
+use xrb::protocol::Request;
+use xrb::xproto::ChangeKeyboardMapping;
+use xrb::xproto::GetKeyboardMappingReply;
+use xrb::xproto::Window;
+use xrb::xtest::*;
+
+#[test]
+fn fake_input() {
+    let mut request = XTestFakeInput::new(FakeEvent::Motion {
+        relative: true,
+        root: Window::NONE,
+        x: -5,
+        y: 7,
+    });
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(buf.len(), 36);
+    assert_eq!(&buf[..6], &[MAJOR_OPCODE, 2, 9, 0, 6, 1]);
+    assert_eq!(i16::from_ne_bytes([buf[24], buf[25]]), -5);
+    assert_eq!(i16::from_ne_bytes([buf[26], buf[27]]), 7);
+
+    let mut request = XTestFakeInput { delay: 20, ..XTestFakeInput::new(FakeEvent::KeyPress(38)) };
+    let buf = request.encode(&info()).unwrap();
+    assert_eq!(&buf[4..6], &[2, 38]);
+    assert_eq!(u32::from_ne_bytes([buf[8], buf[9], buf[10], buf[11]]), 20);
+}
+
+#[test]
+fn keys() {
+    // Keycodes 8 to 11: Shift_L, a A, 1 exclam, nothing.
+    let mapping = GetKeyboardMappingReply {
+        keysyms_per_keycode: 2,
+        keysyms: vec![0xffe1, 0, 0x61, 0x41, 0x31, 0x21, 0, 0],
+    };
+    let typist = Typist::from_mapping(8, &mapping);
+
+    assert_eq!(typist.key_of(0x61), Some((9, false)));
+    assert_eq!(typist.key_of(0x41), Some((9, true)));
+    assert_eq!(typist.key_of(0x21), Some((10, true)));
+    assert_eq!(typist.key_of(0xe9), None);
+
+    let mut request = ChangeKeyboardMapping {
+        first_keycode: 11,
+        keysyms_per_keycode: 2,
+        keysyms: vec![0xe9, 0xe9],
+    };
+    let buf = request.encode().unwrap();
+    assert_eq!(buf.len(), 16);
+    assert_eq!(&buf[..6], &[100, 1, 4, 0, 11, 2]);
+}
+
+/// A request sent by `Typist::type_text`.
+#[derive(Debug, PartialEq, Eq)]
+enum Sent {
+    Press(u8, u32),
+    Release(u8),
+    Remap(u8, u32),
+    GetVersion,
+}
+
+#[test]
+fn typing_remaps_a_spare_key() {
+    let server = FakeServer::start(|sequence, request| {
+        match request[0] {
+            // QueryExtension
+            98 => vec![common::query_extension_reply(sequence, true, MAJOR_OPCODE)],
+            // XTestGetVersion
+            MAJOR_OPCODE if request[1] == 0 => vec![common::reply(sequence, 2, &2u16.to_ne_bytes())],
+            _ => vec![],
+        }
+    });
+
+    // Keycodes 8 to 11: Shift_L, a A, 1 exclam, nothing.
+    let mapping = GetKeyboardMappingReply {
+        keysyms_per_keycode: 2,
+        keysyms: vec![0xffe1, 0, 0x61, 0x41, 0x31, 0x21, 0, 0],
+    };
+    let typist = Typist::from_mapping(8, &mapping);
+
+    let (mut lp, client) = server.connect();
+    let (client, _) = lp.run(typist.type_text(client, "aAéé!ü")).unwrap();
+    drop(client);
+
+    let sent: Vec<Sent> = server.finish()
+        .iter()
+        .filter_map(|r| {
+            let word = |i: usize| u32::from_ne_bytes([r[i], r[i + 1], r[i + 2], r[i + 3]]);
+            match (r[0], r[1]) {
+                (MAJOR_OPCODE, 0) => Some(Sent::GetVersion),
+                (MAJOR_OPCODE, 2) if r[4] == 2 => Some(Sent::Press(r[5], word(8))),
+                (MAJOR_OPCODE, 2) if r[4] == 3 => Some(Sent::Release(r[5])),
+                // ChangeKeyboardMapping
+                (100, 1) => {
+                    assert_eq!(word(8), word(12));
+                    Some(Sent::Remap(r[4], word(8)))
+                }
+                _ => None,
+            }
+        })
+        .collect();
+
+    assert_eq!(sent,
+               vec![Sent::GetVersion,
+                    // a
+                    Sent::Press(9, 0),
+                    Sent::Release(9),
+                    // A
+                    Sent::Press(8, 0),
+                    Sent::Press(9, 0),
+                    Sent::Release(9),
+                    Sent::Release(8),
+                    // é, the next key press waits for the new mapping.
+                    Sent::Remap(11, 0xe9),
+                    Sent::Press(11, 20),
+                    Sent::Release(11),
+                    // é again, the remapping is kept.
+                    Sent::Press(11, 0),
+                    Sent::Release(11),
+                    // !
+                    Sent::Press(8, 0),
+                    Sent::Press(10, 0),
+                    Sent::Release(10),
+                    Sent::Release(8),
+                    // ü
+                    Sent::Remap(11, 0xfc),
+                    Sent::Press(11, 20),
+                    Sent::Release(11),
+                    // The spare key is restored.
+                    Sent::Remap(11, 0),
+                    Sent::GetVersion]);
+}